@@ -1,15 +1,28 @@
 from ._core import (
     NODEFAULT,
     Meta,
+    SchemaCoverage,
     Struct,
     StructConfig,
     StructMeta,
+    Tag,
     TarsDict,
     TraceNode,
     ValidationError,
+    compile_module,
+    decode_checked,
+    decode_concatenated,
+    decode_many,
+    decode_stream,
     decode_trace,
+    decode_value,
+    detect_prefix,
+    encode_into,
+    encode_many,
     field,
+    guess_layout,
     inspect,
+    probe_schema,
     probe_struct,
 )
 from .api import decode, encode
@@ -19,16 +32,29 @@ __version__ = "0.5.1"
 __all__ = [
     "NODEFAULT",
     "Meta",
+    "SchemaCoverage",
     "Struct",
     "StructConfig",
     "StructMeta",
+    "Tag",
     "TarsDict",
     "TraceNode",
     "ValidationError",
+    "compile_module",
     "decode",
+    "decode_checked",
+    "decode_concatenated",
+    "decode_many",
+    "decode_stream",
     "decode_trace",
+    "decode_value",
+    "detect_prefix",
     "encode",
+    "encode_into",
+    "encode_many",
     "field",
+    "guess_layout",
     "inspect",
+    "probe_schema",
     "probe_struct",
 ]
@@ -4,9 +4,10 @@
 `StructMeta`、配置对象 `StructConfig` 以及编码/解码函数。
 """
 
-from collections.abc import Callable
+from collections.abc import Callable, Iterable, Iterator, Sequence
 from inspect import Signature
-from typing import Any, ClassVar, Final, TypeVar, overload
+from types import ModuleType
+from typing import Any, ClassVar, Final, Generic, Literal, Protocol, TypeVar, overload
 
 from typing_extensions import dataclass_transform
 
@@ -16,23 +17,40 @@ _StructT = TypeVar("_StructT")
 _SM = TypeVar("_SM", bound="StructMeta")
 _FieldDefaultT = TypeVar("_FieldDefaultT")
 _BytesLike = bytes | bytearray | memoryview
+_WireInt = Literal["int8", "int16", "int32", "int64", "uint8", "uint16", "uint32"]
+
+class _Readable(Protocol):
+    def read(self, size: int, /) -> _BytesLike: ...
 
 __all__ = [
     "NODEFAULT",
     "Meta",
+    "SchemaCoverage",
     "Struct",
     "StructConfig",
     "StructMeta",
+    "Tag",
     "TarsDict",
     "TraceNode",
     "ValidationError",
+    "compile_module",
     "decode",
+    "decode_checked",
+    "decode_concatenated",
+    "decode_many",
+    "decode_stream",
     "decode_raw",
     "decode_trace",
+    "decode_value",
+    "detect_prefix",
     "encode",
+    "encode_into",
+    "encode_many",
     "encode_raw",
     "field",
+    "guess_layout",
     "inspect",
+    "probe_schema",
     "probe_struct",
 ]
 
@@ -43,6 +61,7 @@ def field(
     *,
     tag: int | None = None,
     wrap_simplelist: bool = ...,
+    alias_tags: Sequence[int] = ...,
 ) -> Any: ...
 @overload
 def field(
@@ -50,12 +69,14 @@ def field(
     tag: int | None = None,
     default: Any,
     wrap_simplelist: bool = ...,
+    alias_tags: Sequence[int] = ...,
 ) -> Any: ...
 @overload
 def field(
     *,
     tag: int | None = None,
     wrap_simplelist: bool = ...,
+    alias_tags: Sequence[int] = ...,
     default_factory: Callable[[], _FieldDefaultT],
 ) -> _FieldDefaultT: ...
 def field(
@@ -63,6 +84,7 @@ def field(
     tag: int | None = None,
     default: Any = NODEFAULT,
     wrap_simplelist: bool = False,
+    alias_tags: Sequence[int] = (),
     default_factory: Any = NODEFAULT,
 ) -> Any:
     """声明字段默认值或默认值工厂.
@@ -72,6 +94,8 @@ def field(
         default: 字段默认值。
         wrap_simplelist: 是否将 Struct/TarsDict 字段包装为 SimpleList(bytes)。
             仅在字段注解为 Struct 或 TarsDict 时有效。
+        alias_tags: 解码时额外接受的旧 Tag，读入同一字段；编码始终使用 `tag`。
+            与其他字段的 Tag 或别名冲突时在类定义阶段报错。
         default_factory: 字段默认值工厂（可调用对象）。
 
     Returns:
@@ -79,7 +103,7 @@ def field(
 
     Raises:
         TypeError: 同时提供 default 与 default_factory，default_factory 不可调用，
-            或 wrap_simplelist 非 bool 时抛出。
+            wrap_simplelist 非 bool，或 alias_tags 不是 0-255 的整数序列时抛出。
     """
     ...
 
@@ -125,6 +149,36 @@ class ValidationError(ValueError):
 
     ...
 
+class Tag:
+    """显式字段 Tag 标记.
+
+    用于 `Annotated[T, Tag(n)]`，与裸整数 Tag（`Annotated[T, n]`）等价，
+    但不会与其他整数型元数据混淆。
+
+    Examples:
+        ```python
+        from typing import Annotated
+        from tarsio import Meta, Struct, Tag
+
+        class User(Struct):
+            uid: Annotated[int, Tag(0)]
+            age: Annotated[int, Tag(1), Meta(ge=0)]
+        ```
+    """
+
+    def __init__(self, value: int) -> None:
+        """初始化 Tag 标记.
+
+        Args:
+            value: 字段 Tag，范围 0..=255。
+
+        Raises:
+            TypeError: value 超出 0..=255。
+        """
+        ...
+    @property
+    def value(self) -> int: ...
+
 class Meta:
     """字段元数据与约束定义.
 
@@ -153,6 +207,22 @@ class Meta:
         min_len: int | None = ...,
         max_len: int | None = ...,
         pattern: str | None = ...,
+        endian: Literal["big", "little"] | None = ...,
+        coerce_enum: bool = ...,
+        enum_mode: Literal["value", "name"] | None = ...,
+        compress: Literal["zlib", "gzip"] | None = ...,
+        accept_bytes: bool = ...,
+        fixed_len: int | None = ...,
+        choices: list[int] | list[str] | None = ...,
+        wire: _WireInt | None = ...,
+        multiple_of: float | None = ...,
+        chunk_size: int | None = ...,
+        as_bytes: bool = ...,
+        any_preserve_bool: bool = ...,
+        alias: str | None = ...,
+        normalize: Literal["NFC", "NFD", "NFKC", "NFKD"] | None = ...,
+        encoder: Callable[[Any], Any] | None = ...,
+        decoder: Callable[[Any], Any] | None = ...,
     ) -> None:
         """初始化字段元数据.
 
@@ -164,6 +234,54 @@ class Meta:
             min_len: 长度下限。
             max_len: 长度上限。
             pattern: 正则表达式约束。
+            endian: 数值字段 payload 的字节序覆盖，仅适用于 int/float 字段，
+                优先于编解码时的 `little_endian`；None 表示沿用报文字节序。
+            coerce_enum: 为 True 时枚举字段接受成员的原始值，构造与编码时按
+                `enum_cls(value)` 转换为成员，仅适用于 Enum 字段。
+            enum_mode: 覆盖 Struct 级 `enum_mode`，"value" 按成员值编码，"name"
+                按成员名称编码为字符串；None 表示沿用 Struct 配置。
+            compress: bytes 字段的压缩算法，编码时压缩后写入 SimpleList，
+                解码时解压；仅适用于 bytes 字段。
+            accept_bytes: 为 True 时 str 字段在构造与编码时接受 bytes，按 UTF-8
+                解码为 str，非法 UTF-8 抛 `ValidationError`；仅适用于 str 字段。
+            fixed_len: bytes 的精确长度，构造、编码与解码时长度不符抛
+                `ValidationError`；仅适用于 bytes 字段。
+            choices: 允许的取值列表，构造、编码与解码时取值不在列表内抛
+                `ValidationError`；int 列表仅适用于 int 字段（不含 bool），str
+                列表仅适用于 str 字段，不能为空。
+            wire: int 字段的固定线上宽度，编码时始终写出对应宽度的整数类型
+                （uint 与同宽度有符号类型共用类型码），超出范围抛 `ValidationError`；
+                None 表示按数值大小自动选择。仅适用于 int 字段。
+            multiple_of: 数值必须为该值的整数倍（浮点按 1e-9 容差比较），
+                构造与解码时不满足抛 `ValidationError`；须为正数。
+            chunk_size: bytes 字段的分块大小，编码时拆分为每块至多 chunk_size
+                字节的 SimpleList 并以 List 写出，解码时按顺序拼接；仅适用于
+                bytes 字段，不能与 compress 同时使用。
+            as_bytes: 为 True 时 str 字段解码为原始 bytes，不做 UTF-8 校验，
+                构造与编码时也接受 bytes；长度约束按字节数计算。仅适用于 str 字段，
+                不能与 accept_bytes、pattern 或 choices 同时使用。
+            any_preserve_bool: 为 True 时 Any 字段的 bool 值编码为 tarsio 专有的
+                标记结构体，解码时还原为 bool（默认编码为整数 0/1）；仅适用于 Any
+                字段，对端需同样使用 tarsio 才能识别。
+            alias: Python 侧 dict 表示使用的公开键名。用于 Struct 字段时作为
+                `inspect.to_builtins` 输出的键；用于 TypedDict 成员时解码结果以别名为键，
+                编码时先按别名、再按字段名取值。线上编码不受影响。
+            normalize: str 字段编码前按 `unicodedata.normalize` 执行的 Unicode
+                规范化形式，使等价字符串得到相同的字节；解码结果不做转换。
+                仅适用于 str 字段。
+            encoder: 编码前对字段值调用的转换函数，返回值须符合字段注解类型；
+                设置后构造时不再校验原始值类型。抛出的异常包装为 `ValueError`。
+            decoder: 解码并校验约束后对字段值调用的转换函数，返回值直接作为
+                字段值；抛出的异常包装为 `ValidationError`。
+
+        Raises:
+            TypeError: choices 不是全 int 或全 str 的列表（含 bool），或 encoder/decoder
+                不可调用。
+            ValueError: endian 不是 "big" 或 "little"，enum_mode 不是
+                "value" 或 "name"，compress 不是 "zlib" 或 "gzip"，
+                wire 不是受支持的宽度名称，choices 为空，multiple_of 不是正数，chunk_size
+                不是正整数，alias 为空字符串，或 normalize 不是
+                "NFC"/"NFD"/"NFKC"/"NFKD"。
         """
         ...
 
@@ -171,9 +289,39 @@ class Meta:
     lt: float | None
     ge: float | None
     le: float | None
+    multiple_of: float | None
     min_len: int | None
     max_len: int | None
     pattern: str | None
+    fixed_len: int | None
+    @property
+    def choices(self) -> list[int] | list[str] | None: ...
+    @property
+    def endian(self) -> str | None: ...
+    @property
+    def coerce_enum(self) -> bool: ...
+    @property
+    def enum_mode(self) -> str | None: ...
+    @property
+    def compress(self) -> str | None: ...
+    @property
+    def accept_bytes(self) -> bool: ...
+    @property
+    def wire(self) -> str | None: ...
+    @property
+    def chunk_size(self) -> int | None: ...
+    @property
+    def as_bytes(self) -> bool: ...
+    @property
+    def any_preserve_bool(self) -> bool: ...
+    @property
+    def alias(self) -> str | None: ...
+    @property
+    def normalize(self) -> str | None: ...
+    @property
+    def encoder(self) -> Callable[[Any], Any] | None: ...
+    @property
+    def decoder(self) -> Callable[[Any], Any] | None: ...
 
 @dataclass_transform(
     eq_default=True,
@@ -206,10 +354,15 @@ class StructMeta(type):
         forbid_unknown_tags: bool = ...,
         eq: bool = ...,
         omit_defaults: bool = ...,
+        omit_none: bool = ...,
         repr_omit_defaults: bool = ...,
         kw_only: bool = ...,
         dict: bool = ...,
         weakref: bool = ...,
+        emit_empty_marker: bool = ...,
+        forbid_extra_bytes: bool = ...,
+        enum_mode: Literal["value", "name"] = ...,
+        str_fields: list[str] | tuple[str, ...] | None = ...,
         **kwargs: Any,
     ) -> _SM:
         """创建 Struct 子类并编译 Schema.
@@ -224,10 +377,15 @@ class StructMeta(type):
             forbid_unknown_tags: 是否禁止未知 Tag。
             eq: 是否生成相等比较。
             omit_defaults: 编码时是否省略默认值字段。
+            omit_none: 编码时是否跳过值为 None 的字段；False 时报错。
             repr_omit_defaults: repr 是否省略默认值字段。
             kw_only: 是否只允许关键字参数构造。
             dict: 是否为实例保留 `__dict__`。
             weakref: 是否支持弱引用。
+            emit_empty_marker: 空结构体编码时是否写出 `StructEnd` 标记。
+            forbid_extra_bytes: 解码后存在未消费的字节时是否报错。
+            enum_mode: 枚举字段默认按成员值（"value"）还是名称（"name"）编码。
+            str_fields: `str()` 展示的字段名列表；None 时 `str()` 与 `repr()` 相同。
             **kwargs: 预留扩展配置。
 
         Returns:
@@ -248,8 +406,13 @@ class StructConfig:
         kw_only: 构造函数是否仅接受关键字参数。
         repr_omit_defaults: `repr` 是否省略默认值字段。
         omit_defaults: 编码时是否省略默认值字段。
+        omit_none: 编码时是否跳过值为 None 的字段。
         weakref: 是否支持弱引用。
         dict: 是否保留 `__dict__`（允许动态属性）。
+        emit_empty_marker: 空结构体编码时是否写出 `StructEnd` 标记。
+        forbid_extra_bytes: 解码后存在未消费的字节时是否报错。
+        enum_mode: 枚举字段的默认编码方式（"value" 或 "name"）。
+        str_fields: `str()` 展示的字段名列表；未设置时为 None。
         rename: 预留字段（当前默认未启用）。
     """
 
@@ -259,8 +422,13 @@ class StructConfig:
     kw_only: bool
     repr_omit_defaults: bool
     omit_defaults: bool
+    omit_none: bool
     weakref: bool
     dict: bool
+    emit_empty_marker: bool
+    forbid_extra_bytes: bool
+    enum_mode: Literal["value", "name"]
+    str_fields: list[str] | None
     rename: Any | None
 
 class Struct(metaclass=StructMeta):
@@ -283,9 +451,14 @@ class Struct(metaclass=StructMeta):
     - `__init__`：支持按 Tag 顺序的 positional 参数，以及按字段名的 keyword 参数。
     - `__eq__`：当 `eq=True` 时生成相等比较。
     - `__repr__`：生成可读的 repr；当 `repr_omit_defaults=True` 时省略默认值字段。
-    - `__copy__`：生成浅拷贝。
+    - `__str__`：配置 `str_fields` 时只展示所列字段，否则与 `__repr__` 相同。
+    - `__copy__`：生成浅拷贝，保留实例级只读标记。
+    - `__deepcopy__`：按 `copy.deepcopy` 递归复制字段值，frozen 实例同样适用。
+    - `__reduce__`/`__getstate__`/`__setstate__`：支持 pickle，frozen 实例
+      （含 `decode(..., freeze=True)` 的只读标记）与 `__dict__` 中的额外属性
+      同样可往返。
     - `__post_init__`：若定义则在实例初始化完成后调用（包括解码路径）。
-    - `__replace__`：返回替换指定字段后的新实例。
+    - `__replace__` / `replace`：返回替换指定字段后的新实例。
     - `__match_args__`：用于模式匹配的位置参数顺序。
     - `__rich_repr__`：为 rich pretty-print 提供字段迭代项。
     - 排序比较：当 `order=True` 时生成 `__lt__/__le__/__gt__/__ge__`。
@@ -295,6 +468,8 @@ class Struct(metaclass=StructMeta):
 
     - `__struct_fields__`：字段名元组，按 Tag 升序排列。
     - `__struct_config__`：配置对象（见 `StructConfig`）。
+    - `__tarsio_present__`：`decode(..., track_presence=True)` 时记录的线上出现字段名，
+      其他情况下为 None。
 
     Configuration:
         可在定义 `Struct` 子类时传入关键字参数控制行为：
@@ -304,10 +479,24 @@ class Struct(metaclass=StructMeta):
         - eq (bool, default True): 是否生成 `__eq__`。
         - kw_only (bool, default False): 是否将所有字段设为仅关键字参数。
         - omit_defaults (bool, default False): 编码时是否省略值等于默认值的字段。
+        - omit_none (bool, default True): 编码时是否跳过值为 None 的字段。设为 False 时
+          None 字段抛出 `ValueError`，用于发现线上必须存在的字段被置空；默认值为 None 的
+          字段在 `omit_defaults=True` 时仍按默认值省略。
         - repr_omit_defaults (bool, default False): repr 是否省略值等于默认值的字段。
         - forbid_unknown_tags (bool, default False): 解码时是否禁止出现未知 Tag.
         - dict (bool, default False): 是否为实例保留 `__dict__`（允许附加额外属性）。
         - weakref (bool, default False): 是否支持弱引用。
+        - emit_empty_marker (bool, default False): 未写出任何字段时（如全部为默认值且
+          `omit_defaults=True`）写出单个 `StructEnd` 标记，使“空但存在”的 payload 与
+          “无数据”可区分；解码时该标记会被忽略。
+        - forbid_extra_bytes (bool, default True): 解码结束后仍有未消费的字节时抛出
+          `ValueError`。设为 False 时忽略尾部字节，适用于结构体之后带填充的外层封包；
+          也可通过 `decode(..., allow_trailing=True)` 按调用放宽。
+        - enum_mode ("value" | "name", default "value"): 枚举字段的默认编码方式。
+          "name" 按成员名称编码为字符串，成员值调整后仍可互通；可通过
+          `Meta(enum_mode=...)` 按字段覆盖。
+        - str_fields (list[str] | None, default None): `str()` 只展示所列字段，
+          用于面向用户的简短展示；未设置时 `str()` 回退到 `repr()`。
 
     Examples:
         基本用法：
@@ -341,6 +530,7 @@ class Struct(metaclass=StructMeta):
     __struct_fields__: ClassVar[tuple[str, ...]]
     __struct_config__: ClassVar[StructConfig]
     __match_args__: ClassVar[tuple[str, ...]]
+    __tarsio_present__: frozenset[str] | None
 
     def __init_subclass__(
         cls,
@@ -350,10 +540,15 @@ class Struct(metaclass=StructMeta):
         forbid_unknown_tags: bool = False,
         eq: bool = True,
         omit_defaults: bool = False,
+        omit_none: bool = True,
         repr_omit_defaults: bool = False,
         kw_only: bool = False,
         dict: bool = False,
         weakref: bool = False,
+        emit_empty_marker: bool = False,
+        forbid_extra_bytes: bool = True,
+        enum_mode: Literal["value", "name"] = "value",
+        str_fields: list[str] | tuple[str, ...] | None = None,
         **kwargs: Any,
     ) -> None:
         """配置 Struct 子类行为."""
@@ -377,12 +572,61 @@ class Struct(metaclass=StructMeta):
             ValueError: 缺少必填字段或类型不匹配。
         """
         ...
+    def __bytes__(self) -> bytes:
+        """支持 `bytes(obj)`，结果与 `encode()` 相同."""
+        ...
+    def __getstate__(self) -> tuple[dict[str, Any], bool]:
+        """返回 pickle 使用的状态：`(属性 dict, 实例级只读标记)`.
+
+        属性 dict 包含已赋值的字段与 `__dict__` 中的额外属性。
+        """
+        ...
+    def __setstate__(self, state: tuple[dict[str, Any], bool]) -> None:
+        """从 `__getstate__` 的结果恢复属性与只读标记，绕过 frozen 检查与 `__post_init__`."""
+        ...
+    def __reduce__(self) -> tuple[Any, tuple[type], tuple[dict[str, Any], bool]]:
+        """支持 pickle：以 `cls.__new__(cls)` 创建实例后调用 `__setstate__`."""
+        ...
     @classmethod
-    def decode(cls: type[_StructT], data: _BytesLike) -> _StructT:
+    def decode(
+        cls: type[_StructT],
+        data: _BytesLike,
+        *,
+        freeze: bool = False,
+        track_presence: bool = False,
+        coverage: SchemaCoverage | None = None,
+        on_unknown: Callable[[int, int, int], object] | None = None,
+        max_bytes: int | None = None,
+        float_as_decimal: bool = False,
+        widen_float: bool = False,
+        allow_trailing: bool = False,
+        accept_wrapped_structs: bool = False,
+        strict: bool = False,
+        context: Any = None,
+        max_depth: int | None = None,
+    ) -> _StructT:
         """将 Tars 二进制数据解码为当前类实例.
 
         Args:
             data: 待解码的 bytes。
+            freeze: 为 True 时返回的实例拒绝属性赋值，即使类未声明 `frozen`。
+            track_presence: 为 True 时在 `__tarsio_present__` 记录线上出现的字段名。
+            coverage: 可选的 `SchemaCoverage`，累计本次解码中出现的字段与未知 Tag。
+            on_unknown: 可选回调，任意深度的 Struct 每遇到一个未知 Tag 时以
+                `(tag, type_code, offset)` 调用；返回值被忽略，抛出的异常原样传播。
+            max_bytes: 输入长度上限，解析前检查，同时限制压缩字段解压后的长度；
+                None 表示不限制输入长度，解压上限为 64 MiB。
+            float_as_decimal: 为 True 时 float/double 字段解码为 `decimal.Decimal`，
+                字段声明类型仍为 float。
+            widen_float: 为 True 时线上的单精度 Float 按最短十进制表示转换为 float，
+                避免 f32 提升为 f64 时暴露的二进制误差。
+            allow_trailing: 为 True 时忽略解码结束后剩余的字节。
+            accept_wrapped_structs: 为 True 时 Struct 字段也接受 SimpleList 包装的
+                嵌套序列化。
+            strict: 为 True 时要求线上类型与字段声明精确一致，double 字段拒绝单精度 Float。
+            context: 非 None 时以关键字参数 `context` 传给字段 decoder 钩子与
+                `__post_init__`。
+            max_depth: 嵌套深度上限；None 表示默认上限。
 
         Returns:
             解码得到的实例。
@@ -390,6 +634,22 @@ class Struct(metaclass=StructMeta):
         Raises:
             TypeError: 目标类未注册 Schema。
             ValueError: 数据格式不正确或缺少必填字段。
+            ValidationError: 解码后 `__post_init__` 抛出 TypeError/ValueError，
+                或输入长度超过 max_bytes。
+        """
+        ...
+    def decode_into(self, data: _BytesLike) -> None:
+        """将 Tars 二进制数据解码并就地覆盖当前实例的字段.
+
+        Tag 查找、默认值填充、尾随字节检查及 `__post_init__` 行为与 `decode` 一致，
+        但不分配新对象，适合热循环中复用实例池。
+
+        Args:
+            data: 待解码的 bytes。
+
+        Raises:
+            AttributeError: 实例或其类为 frozen。
+            ValueError: 数据格式不正确或缺少必填字段；失败时实例可能已被部分覆盖。
             ValidationError: 解码后 `__post_init__` 抛出 TypeError/ValueError。
         """
         ...
@@ -407,63 +667,459 @@ class Struct(metaclass=StructMeta):
             ValidationError: 替换值不满足类型或约束时抛出。
         """
         ...
-    def __rich_repr__(self) -> list[tuple[str, Any]]:
+    def replace(self: _StructT, **changes: Any) -> _StructT:
+        """返回替换部分字段后的新实例，与 `dataclasses.replace` 对应.
+
+        未替换字段沿用原实例值；新实例按构造流程校验并调用 `__post_init__`，
+        frozen 实例同样适用，`decode(..., freeze=True)` 的只读标记会保留。
+
+        Args:
+            **changes: 需要替换的字段名和值。
+
+        Returns:
+            同类的新实例。
+
+        Raises:
+            TypeError: 包含未知字段名时抛出。
+            ValidationError: 替换值不满足类型或约束时抛出。
+        """
+        ...
+    def __rich_repr__(self) -> list[tuple[str, Any] | tuple[str, Any, Any]]:
         """返回 rich pretty-print 使用的字段序列.
 
         Returns:
             形如 ``[(field_name, value), ...]`` 的字段序列，顺序按 tag。
+            值为 None 的 Optional 字段以 ``(field_name, None, None)`` 给出，
+            rich 会将其视为默认值而省略。
         """
         ...
 
-def encode(obj: Any) -> bytes:
+def encode(
+    obj: Any,
+    *,
+    little_endian: bool = False,
+    context: Any = None,
+    max_depth: int | None = None,
+    include: Iterable[str] | None = None,
+    exclude: Iterable[str] | None = None,
+) -> bytes:
     """将 Tars Struct 对象序列化为 Tars 二进制格式.
 
     Args:
         obj: `Struct`、dataclass、NamedTuple、TypedDict 的实例。
+        little_endian: 为 True 时多字节整数、浮点数与长字符串长度按小端写出，
+            兼容部分旧客户端的 JCE 报文。
+        context: 任意对象，非 None 时以关键字参数 `context` 传给任意深度的
+            `Meta(encoder=...)` 钩子；None 时钩子按 `encoder(value)` 调用。
+        max_depth: 嵌套深度上限，超过时抛出 `ValueError`；None 表示默认上限。
+        include: 顶层字段名集合，非 None 时只写出其中的字段。
+        exclude: 顶层字段名集合，其中的字段不写出；与 include 同时给出时取差集。
+            被筛掉的必填字段不报错，保留的字段仍受 `omit_defaults` 等配置约束。
 
     Returns:
         包含序列化数据的 bytes 对象。
 
     Raises:
-        TypeError: 如果对象不是有效的 Tars Struct。
+        TypeError: 如果对象不是有效的 Tars Struct，或 include/exclude 不是字段名集合。
+        ValueError: 如果 include/exclude 含未知字段名。
     """
     ...
 
-def decode(cls: type[_StructT], data: _BytesLike) -> _StructT:
+def encode_into(
+    obj: Any,
+    buffer: bytearray,
+    *,
+    little_endian: bool = False,
+    context: Any = None,
+    max_depth: int | None = None,
+) -> int:
+    """将 Tars Struct 对象序列化并追加到调用方提供的 bytearray.
+
+    输出与 `encode` 相同，但直接扩展 `buffer`，省去中间 bytes 对象的分配与拷贝，
+    适合在同一缓冲区中连续写入多条消息。
+
+    Args:
+        obj: `Struct`、dataclass、NamedTuple、TypedDict 的实例。
+        buffer: 目标 bytearray，编码结果追加在已有内容之后。
+        little_endian: 为 True 时多字节数值按小端写出。
+        context: 非 None 时以关键字参数 `context` 传给字段 encoder 钩子。
+        max_depth: 嵌套深度上限；None 表示默认上限。
+
+    Returns:
+        本次写入的字节数。
+
+    Raises:
+        TypeError: 如果对象不是有效的 Tars Struct，或 buffer 不是 bytearray。
+        BufferError: buffer 正被 memoryview 等对象导出，无法扩容。
+        RuntimeError: 编码过程中（如字段的 `__eq__`）再次触发编码。
+    """
+    ...
+
+@overload
+def encode_many(
+    objs: Iterable[Any],
+    *,
+    framed: Literal[False] = False,
+    little_endian: bool = False,
+    context: Any = None,
+    max_depth: int | None = None,
+) -> list[bytes]: ...
+@overload
+def encode_many(
+    objs: Iterable[Any],
+    *,
+    framed: Literal[True],
+    little_endian: bool = False,
+    context: Any = None,
+    max_depth: int | None = None,
+) -> bytes: ...
+def encode_many(
+    objs: Iterable[Any],
+    *,
+    framed: bool = False,
+    little_endian: bool = False,
+    context: Any = None,
+    max_depth: int | None = None,
+) -> list[bytes] | bytes:
+    """批量序列化同一类的多个 Tars Struct 对象.
+
+    Schema 只解析一次，所有对象复用同一块编码缓冲区，比在 Python 中逐个调用
+    `encode` 开销更低。每条记录的字节与单独 `encode` 的结果一致。
+
+    `framed=True` 时返回单个 bytes，每条记录前写 4 字节大端无符号长度（不含长度头
+    本身）；长度头始终为大端，不受 `little_endian` 影响。
+
+    Args:
+        objs: 对象的可迭代对象，所有元素必须是同一个类（不接受子类混入）。
+        framed: 为 True 时返回带长度前缀的拼接结果。
+        little_endian: 为 True 时多字节数值按小端写出。
+        context: 非 None 时以关键字参数 `context` 传给字段 encoder 钩子。
+        max_depth: 嵌套深度上限；None 表示默认上限。
+
+    Returns:
+        `framed=False` 时为与 `objs` 一一对应的 `list[bytes]`，否则为单个 bytes。
+
+    Raises:
+        TypeError: 元素不是有效的 Tars Struct，或与第一个元素的类不同。
+        ValueError: 缺少必填字段、类型不匹配、递归深度超过限制，或单条记录超过 4 GiB。
+    """
+    ...
+
+@overload
+def decode(
+    cls: type[_StructT],
+    data: _BytesLike,
+    *,
+    freeze: bool = False,
+    skip_prefix: int = 0,
+    track_presence: bool = False,
+    coverage: SchemaCoverage | None = None,
+    on_unknown: Callable[[int, int, int], object] | None = None,
+    max_bytes: int | None = None,
+    float_as_decimal: bool = False,
+    widen_float: bool = False,
+    little_endian: bool = False,
+    allow_trailing: bool = False,
+    accept_wrapped_structs: bool = False,
+    strict: bool = False,
+    keep_raw: None = None,
+    context: Any = None,
+    max_depth: int | None = None,
+) -> _StructT: ...
+@overload
+def decode(
+    cls: type[_StructT],
+    data: _BytesLike,
+    *,
+    freeze: bool = False,
+    skip_prefix: int = 0,
+    track_presence: bool = False,
+    coverage: SchemaCoverage | None = None,
+    on_unknown: Callable[[int, int, int], object] | None = None,
+    max_bytes: int | None = None,
+    float_as_decimal: bool = False,
+    widen_float: bool = False,
+    little_endian: bool = False,
+    allow_trailing: bool = False,
+    accept_wrapped_structs: bool = False,
+    strict: bool = False,
+    keep_raw: Iterable[int],
+    context: Any = None,
+    max_depth: int | None = None,
+) -> tuple[_StructT, dict[int, bytes]]: ...
+def decode(
+    cls: type[_StructT],
+    data: _BytesLike,
+    *,
+    freeze: bool = False,
+    skip_prefix: int = 0,
+    track_presence: bool = False,
+    coverage: SchemaCoverage | None = None,
+    on_unknown: Callable[[int, int, int], object] | None = None,
+    max_bytes: int | None = None,
+    float_as_decimal: bool = False,
+    widen_float: bool = False,
+    little_endian: bool = False,
+    allow_trailing: bool = False,
+    accept_wrapped_structs: bool = False,
+    strict: bool = False,
+    keep_raw: Iterable[int] | None = None,
+    context: Any = None,
+    max_depth: int | None = None,
+) -> Any:
     """从 Tars 二进制数据反序列化为类实例.
 
     Args:
         cls: 目标类（`Struct`、dataclass、NamedTuple、TypedDict）。
         data: 包含 Tars 编码数据的 bytes 对象。
+        freeze: 为 True 时返回只读实例（拒绝属性赋值），即使类未声明 `frozen`；
+            仅冻结顶层实例，要求 cls 为 `Struct` 子类。
+        skip_prefix: 解析前跳过的前缀字节数，用于带填充或哨兵字节的报文。
+        track_presence: 为 True 时在实例的 `__tarsio_present__` 记录线上出现的
+            字段名（frozenset），仅记录顶层实例，要求 cls 为 `Struct` 子类。
+        coverage: 可选的 `SchemaCoverage`，累计顶层实例中出现的字段与未知 Tag，
+            cls 不能是 TarsDict。
+        on_unknown: 可选回调，任意深度的 Struct 每遇到一个未知 Tag 时以
+            `(tag, type_code, offset)` 调用，在跳过该字段（或按
+            `forbid_unknown_tags` 报错）之前执行；offset 为字段头在 skip_prefix
+            之后数据中的偏移。返回值被忽略，回调抛出的异常原样传播，可用于中止解码。
+        max_bytes: 输入总长度上限（含 skip_prefix 跳过的字节），解析前检查，
+            作为消息入口的第一道防线；同时限制 `Meta(compress=...)` 字段解压后的
+            长度。None 表示不限制输入长度，解压上限为 64 MiB。
+        float_as_decimal: 为 True 时 float/double 字段（含嵌套）解码为
+            `decimal.Decimal`，按最短往返文本构造；字段声明类型仍为 float。
+            Any 字段与 TarsDict 中的浮点数同样转换。
+        widen_float: 为 True 时线上的单精度 Float 按最短十进制表示转换为 float，
+            如 `1.1f32` 得到 `1.1` 而非 `1.100000023841858`。Any 字段与
+            TarsDict 中的单精度 Float 同样转换。
+        little_endian: 为 True 时多字节数值按小端解释，需与编码端一致。
+        allow_trailing: 为 True 时忽略解码结束后剩余的字节（如外层封包的填充），
+            覆盖类配置 `forbid_extra_bytes=True`。
+        accept_wrapped_structs: 为 True 时 Struct 字段（含嵌套与容器元素）遇到
+            SimpleList 时按其中的字节解码嵌套 Struct，兼容时而包装时而内联的生产方；
+            默认关闭，以免把真正的 bytes 误当作 Struct。
+        strict: 为 True 时要求线上类型与字段声明精确一致（含嵌套与容器元素）：
+            double 字段不再接受单精度 Float，Union 也不会把 Float 匹配到 double 分支。
+            整数族与零值 ZeroTag 仍按常规规则读取。用于校验不可信输入。
+        keep_raw: 需要保留原始字节的顶层字段 tag 集合。传入时额外返回
+            `dict[int, bytes]`，将线上出现的这些 tag 映射到该字段完整的线上字节
+            （含字段头），可用 `decode_raw` 重新解析，便于逐字段验签；
+            重复出现的 tag 以最后一次为准。
+        context: 任意对象，非 None 时以关键字参数 `context` 传给任意深度的
+            `Meta(decoder=...)` 钩子与 `__post_init__`；None 时两者按原方式调用，
+            不产生额外开销。
+        max_depth: 嵌套深度上限，同时约束跳过未知字段时的嵌套层数；调低可更早
+            拒绝恶意的深度嵌套，调高可接受合法的深层结构。None 表示默认上限
+            （类型化解码 48 层，跳过未知字段 100 层），最大不超过 256。
+
+    Returns:
+        反序列化的类实例；传入 keep_raw 时为 `(实例, raw)` 元组。
+
+    Raises:
+        TypeError: 如果类未注册 Schema，freeze/track_presence=True 但 cls 不是
+            Struct 子类，对 TarsDict 传入 coverage/on_unknown/keep_raw，
+            或 keep_raw 含 0..=255 以外的 tag。
+        ValueError: 如果数据格式不正确，或 skip_prefix 超过数据长度。
+        ValidationError: 如果输入长度超过 max_bytes。
+    """
+    ...
+
+def decode_checked(cls: type[_StructT], data: _BytesLike) -> _StructT:
+    """从 Tars 二进制数据反序列化为类实例, 对任意输入只抛出 `ValidationError`.
+
+    适用于模糊测试或处理不可信输入: 解码过程中的任何异常（包括 `__post_init__`
+    抛出的异常）都会转换为 `ValidationError`，原始异常保存在 `__cause__` 中。
+
+    Args:
+        cls: 目标 `Struct` 类型。
+        data: 包含 Tars 编码数据的 bytes 对象。
 
     Returns:
         反序列化的类实例。
 
     Raises:
-        TypeError: 如果类未注册 Schema。
-        ValueError: 如果数据格式不正确。
+        TypeError: 如果类未注册 Schema 或 data 不是 bytes-like 对象。
+        ValidationError: 如果数据无法解码为目标类型。
+    """
+    ...
+
+def decode_value(tp: Any, data: _BytesLike, tag: int = 0) -> Any:
+    """读取 Struct 中指定 Tag 的单个字段, 按给定类型标注解码.
+
+    适用于 Tars RPC 返回值等外层 Struct 仅包裹一个值的场景，无需定义包装 Struct。
+    其他 Tag 的字段会被跳过。
+
+    Args:
+        tp: 字段值的类型标注，支持 `Annotated[T, Meta(...)]`。
+        data: 包含 Tars 编码数据的 bytes 对象。
+        tag: 目标字段的 Tag，默认为 0。
+
+    Returns:
+        解码得到的值；类型为 Optional 且字段缺失时返回 None。
+
+    Raises:
+        TypeError: 如果类型标注不受支持或 data 不是 bytes-like 对象。
+        ValueError: 如果数据格式不正确，或非 Optional 类型的字段缺失。
+        ValidationError: 如果值不满足 `Meta` 约束。
+    """
+    ...
+
+def decode_concatenated(
+    cls: type[_StructT], data: _BytesLike
+) -> ConcatenatedDecoder[_StructT]:
+    """逐个解码首尾相接的多个 Struct, 返回惰性迭代器.
+
+    顶层 Struct 编码时不带 StructBegin/StructEnd，相邻两个 Struct 的字段流无法区分
+    边界。因此只有当每个元素都以 StructBegin 开始、StructEnd 结束（即按嵌套 Struct
+    的形式包裹）时才能可靠拆分；迭代器据此推进到下一个元素，直到数据耗尽。
+
+    Args:
+        cls: 目标 `Struct` 或 `TarsDict` 类型。
+        data: 由多个包裹后的 Struct 拼接而成的 bytes 对象。
+
+    Returns:
+        依次产出解码实例的迭代器，支持 `close()` 与 `with` 语句。
+
+    Raises:
+        TypeError: 如果类未注册 Schema 或 data 不是 bytes-like 对象。
+        ValueError: 迭代时某个元素未以 StructBegin 开始（无法确定边界）、缺少
+            StructEnd，或字段数据格式不正确。
+
+    Examples:
+        ```python
+        data = b"".join(b"\x0a" + encode(u) + b"\x0b" for u in users)
+        with decode_concatenated(User, data) as users:
+            for user in users:
+                ...
+        ```
+    """
+    ...
+
+def decode_many(
+    cls: type[_StructT],
+    data: _BytesLike,
+    *,
+    framed: bool = True,
+    little_endian: bool = False,
+    context: Any = None,
+    max_depth: int | None = None,
+) -> list[_StructT]:
+    """从同一缓冲区批量解码多条记录，一次返回全部实例.
+
+    `framed=True` 时每条记录前为 4 字节大端无符号长度（不含长度头本身），即
+    `encode_many(..., framed=True)` 的输出格式。`framed=False` 时记录须以
+    StructBegin/StructEnd 包裹，拆分规则与 `decode_concatenated` 相同。
+
+    Args:
+        cls: 目标 `Struct` 或 `TarsDict` 类型。
+        data: 待解码的 bytes 对象，空输入返回空列表。
+        framed: 记录是否带长度前缀。
+        little_endian: 为 True 时多字节数值按小端解释；长度前缀始终为大端。
+        context: 非 None 时以关键字参数 `context` 传给字段 decoder 钩子与
+            `__post_init__`。
+        max_depth: 嵌套深度上限；None 表示默认上限。
+
+    Returns:
+        按记录顺序排列的实例列表。
+
+    Raises:
+        TypeError: 如果类未注册 Schema 或 data 不是 bytes-like 对象。
+        ValueError: 如果记录在中途截断、未以 StructBegin 开始，或字段数据格式不正确；
+            消息以 `record <序号>:` 开头，原异常保存在 `__cause__` 中；
+            `ValidationError` 等子类保留原类型。
     """
     ...
 
-def encode_raw(obj: Any) -> bytes:
+def decode_stream(
+    cls: type[_StructT], stream: _Readable, *, max_bytes: int | None = None
+) -> _StructT | None:
+    """从文件对象读取并解码一个以 StructBegin/StructEnd 包裹的 Struct.
+
+    按需调用 `stream.read(n)`，每次只请求当前字段所需的字节数（单次最多 64 KiB），
+    不会越过该 Struct 的 StructEnd；返回后流恰好停在下一条记录的起始处，可循环调用
+    读取记录序列。`read` 返回的字节少于请求数时会继续读取，直到满足或遇到 EOF。
+    报文声明的超大长度只有在数据真实到达时才会占用内存。
+
+    Args:
+        cls: 目标 `Struct` 或 `TarsDict` 类型。
+        stream: 提供 `read(n)` 方法、返回 bytes-like 对象的文件对象。
+        max_bytes: 单条记录的字节上限，读取过程中超出即报错，同时限制压缩字段
+            解压后的长度；None 表示不限制记录长度，解压上限为 64 MiB。
+
+    Returns:
+        解码得到的实例；读取前流已处于 EOF 时返回 None。
+
+    Raises:
+        TypeError: 如果类未注册 Schema，或 `read` 返回的不是 bytes-like 对象。
+        ValueError: 如果记录未以 StructBegin 开始、在字段中途遇到 EOF、长度为负或
+            超出范围，或字段数据格式不正确。
+        ValidationError: 如果记录超过 `max_bytes`。
+
+    Examples:
+        ```python
+        with open("users.bin", "rb") as f:
+            while (user := decode_stream(User, f)) is not None:
+                ...
+        ```
+    """
+    ...
+
+class ConcatenatedDecoder(Iterator[_StructT], Generic[_StructT]):
+    """`decode_concatenated` 返回的惰性迭代器.
+
+    持有输入数据直到 `close()`；关闭后再次迭代或进入 `with` 抛出 `ValueError`。
+    """
+
+    @property
+    def closed(self) -> bool:
+        """是否已关闭."""
+        ...
+    def close(self) -> None:
+        """释放持有的输入数据，重复调用无副作用."""
+        ...
+    def __iter__(self) -> ConcatenatedDecoder[_StructT]: ...
+    def __next__(self) -> _StructT: ...
+    def __enter__(self) -> ConcatenatedDecoder[_StructT]: ...
+    def __exit__(self, *args: object) -> Literal[False]: ...
+
+def encode_raw(
+    obj: Any,
+    bool_tags: set[int] | None = None,
+    *,
+    little_endian: bool = False,
+    max_depth: int | None = None,
+) -> bytes:
     """将对象编码为 Tars 二进制格式 (原始模式).
 
     如果输入是 `TarsDict`，则按结构体编码；否则按其自然类型（Map, List, Int 等）编码。
     Raw 模式下，`Struct` 实例在任意嵌套位置都允许编码为 Struct。
 
+    Tars 没有 bool 线类型，bool 会按 int 写出，无 Schema 往返时会丢失 bool 语义。
+    传入 `bool_tags` 可记录顶层值为 bool 的 tag，再交给 `decode_raw` 还原。
+
     Args:
         obj: 要编码的对象。
+        bool_tags: 可选的 set，编码 `TarsDict` 时收集顶层 bool 值所在的 tag。
+        little_endian: 为 True 时多字节数值按小端写出。
+        max_depth: 嵌套深度上限；None 表示默认上限。
 
     Returns:
         编码后的字节对象。
+
+    Raises:
+        ValueError: 嵌套深度超过上限，或 max_depth 为 0 或超过 256。
     """
     ...
 
-def decode_raw(data: _BytesLike) -> TarsDict:
+def decode_raw(
+    data: _BytesLike, bool_tags: Iterable[int] | None = None
+) -> TarsDict:
     """将字节解码为 TarsDict.
 
     Args:
         data: 包含 Tars 编码数据的 bytes 对象。
+        bool_tags: 可选的顶层 tag 集合（通常来自 `encode_raw` 的记录），
+            这些 tag 上值为 0/1 的 int 会还原为 bool。
 
     Returns:
         解码后的 TarsDict。
@@ -485,6 +1141,98 @@ def probe_struct(data: bytes) -> TarsDict | None:
     """
     ...
 
+def probe_schema(data: bytes) -> str | None:
+    """根据原始字节推断候选 Schema.
+
+    遍历 Tars 原始结构，将观测到的线类型映射为最可能的 Python 类型，
+    用于把未知报文逆向整理为 Struct 定义。存在歧义时以 `|` 列出候选，
+    例如 SimpleList 可能是 `bytes | str`，或可完整解析为嵌套结构时给出 `bytes | Struct{...}`。
+
+    Args:
+        data: 待分析的 Tars 二进制数据。
+
+    Returns:
+        形如 `Struct{0: int, 1: str, 2: list[int]}` 的类型树描述；
+        数据不是完整的 Tars Struct 时返回 None。
+    """
+    ...
+
+def compile_module(module: ModuleType) -> list[type]:
+    """预编译模块中定义的所有 Struct 与 pydantic 模型的 Schema.
+
+    用于在启动阶段集中暴露 Schema 错误，而不是等到首次编解码时才失败。
+    仅处理 `__module__` 与该模块同名的类，从其他模块导入的类会被跳过。
+
+    Args:
+        module: 目标模块。
+
+    Returns:
+        已编译的类列表，顺序同模块命名空间中的定义顺序。
+
+    Raises:
+        TypeError: 某个类的 Schema 无效；异常类型沿用原始错误，消息以类名开头。
+    """
+    ...
+
+def detect_prefix(data: bytes, max_scan: int = 16) -> int | None:
+    """查找首个能解析为完整 Tars Struct 的偏移量.
+
+    用于处理被生产方加了填充或哨兵字节的报文，返回值可直接作为
+    `decode` 的 `skip_prefix` 参数。
+
+    Args:
+        data: 待分析的 bytes。
+        max_scan: 最多尝试的前缀长度。
+
+    Returns:
+        前缀长度；在扫描范围内未找到时返回 None。
+    """
+    ...
+
+def guess_layout(data: bytes) -> list[tuple[Any, ...]]:
+    """列出报文顶层字段的 tag 与线类型，不需要 Schema.
+
+    只读取头部并跳过 payload，用于逆向未知报文时快速查看字段布局。
+    普通字段返回 `(tag, type_name)`，`type_name` 为线类型名（如 `"Int1"`、`"String1"`）；
+    嵌套 StructBegin 返回 `(tag, "struct", children)`，`children` 为该结构体直接字段的
+    同类列表，更深层的结构体不再展开，`children` 为 None。
+
+    Args:
+        data: 待分析的 Tars 二进制数据。
+
+    Returns:
+        按出现顺序排列的字段列表；遇到格式错误时返回错误之前已读取的部分。
+    """
+    ...
+
+class SchemaCoverage:
+    """累计解码统计，用于分析 Schema 字段在线上的覆盖情况.
+
+    通过 `decode(cls, data, coverage=cov)` 逐次更新，只统计顶层 Struct，
+    解码失败的报文不计入：
+    记录每个字段在线上出现的次数，以及 Schema 未声明的未知 Tag。
+    处理完语料后，从未出现的字段是删除候选，未知 Tag 是补充候选。
+
+    Examples:
+        ```python
+        cov = SchemaCoverage()
+        for payload in corpus:
+            decode(User, payload, coverage=cov)
+        print(cov.report()["User"]["never_seen"])
+        ```
+    """
+
+    def __init__(self) -> None: ...
+    def report(self) -> dict[str, dict[str, Any]]:
+        """生成覆盖率报告.
+
+        Returns:
+            以类名为键的 dict，多个类同名时改用 `模块.限定名` 区分；每项包含
+            `decoded`（成功解码次数）、`field_counts`（字段名 -> 出现次数）、
+            `never_seen`（从未出现的字段名列表）与 `unknown_tags`（未知 Tag -> 出现次数）。
+        """
+        ...
+
 class TraceNode:
     """`decode_trace` 返回的调试树节点.
 
@@ -496,6 +1244,9 @@ class TraceNode:
         name: 字段名（有 Schema 时可用）。
         type_name: 类型名（有 Schema 时可用）。
         path: 从根开始的可读路径。
+        start: 节点在输入中的起始偏移（字段头所在位置）。
+        end: 节点在输入中的结束偏移（不含）；容器节点覆盖全部子节点，
+            `data[node.start:node.end]` 即该节点完整的线上字节。
     """
 
     tag: int
@@ -505,6 +1256,8 @@ class TraceNode:
     name: str | None
     type_name: str | None
     path: str
+    start: int
+    end: int
 
     def to_dict(self) -> dict[str, Any]: ...
 
@@ -4,9 +4,11 @@
 并提供了完整的类型提示与文档。
 """
 
+import sys
+from collections.abc import Callable, Iterable
 from typing import Any, TypeVar, get_origin, overload
 
-from ._core import Struct, TarsDict
+from ._core import SchemaCoverage, Struct, TarsDict
 from ._core import (
     decode as _core_decode,
 )
@@ -29,38 +31,77 @@ __all__ = [
 ]
 
 
-def encode(obj: Any) -> bytes:
+def _is_pydantic_model(cls: Any) -> bool:
+    """判断 cls 是否为 `pydantic.BaseModel` 子类 (未导入 pydantic 时恒为 False)."""
+    pydantic = sys.modules.get("pydantic")
+    if pydantic is None or not isinstance(cls, type):
+        return False
+    return issubclass(cls, pydantic.BaseModel)
+
+
+def encode(
+    obj: Any,
+    *,
+    little_endian: bool = False,
+    context: Any = None,
+    max_depth: int | None = None,
+    include: Iterable[str] | None = None,
+    exclude: Iterable[str] | None = None,
+) -> bytes:
     """将对象序列化为 Tars 二进制格式.
 
     该函数会自动根据输入对象的类型选择合适的编码模式：
-    1. **Schema 模式**：如果对象是 `Struct`、`dataclass`、`NamedTuple` 或 `TypedDict` 实例，
+    1. **Schema 模式**：如果对象是 `Struct` 或 `pydantic.BaseModel` 实例，
        将按照其定义的 Schema 进行编码。
     2. **Raw 模式**：如果对象是 `TarsDict`、 `dict`、`list` 或基本类型，
        将进行原始编码（无 Schema）。
 
     Args:
         obj: 要编码的对象。
+        little_endian: 为 True 时多字节整数、浮点数与长字符串长度按小端写出，
+            兼容部分旧客户端的 JCE 报文。默认按 Tars 标准的大端写出。
+        context: 任意对象，Schema 模式下非 None 时以关键字参数 `context` 传给
+            字段的 `Meta(encoder=...)` 钩子。Raw 模式没有钩子，忽略该参数。
+        max_depth: 嵌套深度上限，超过时抛出 `ValueError`；None 表示默认上限。
+        include: 顶层字段名集合，非 None 时只写出其中的字段。仅 Schema 模式可用。
+        exclude: 顶层字段名集合，其中的字段不写出；与 include 同时给出时取差集。
+            仅 Schema 模式可用。
 
     Returns:
         包含序列化数据的 bytes 对象。
 
     Raises:
-        TypeError: 如果对象既不是有效的 Struct 也不是支持的 Raw 类型。
-        ValueError: 如果数据校验失败。
+        TypeError: 如果对象既不是有效的 Struct 也不是支持的 Raw 类型，或对
+            Raw 对象传入了 include/exclude。
+        ValueError: 如果数据校验失败，或 include/exclude 含未知字段名。
     """
+    if include is not None or exclude is not None:
+        return _core_encode(
+            obj,
+            little_endian=little_endian,
+            context=context,
+            max_depth=max_depth,
+            include=include,
+            exclude=exclude,
+        )
+
     # 优先处理显式的 Raw 容器和基本类型
     if isinstance(
         obj, (TarsDict, dict, list, tuple, set, int, float, str, bytes, bool)
     ):
-        return _core_encode_raw(obj)
+        return _core_encode_raw(
+            obj, little_endian=little_endian, max_depth=max_depth
+        )
 
     # 尝试作为 Struct 处理 (Struct)
     # 优化：通过检查特征属性避免 try-except 开销
-    if isinstance(obj, Struct):
-        return _core_encode(obj)
+    if isinstance(obj, Struct) or _is_pydantic_model(type(obj)):
+        return _core_encode(
+            obj, little_endian=little_endian, context=context, max_depth=max_depth
+        )
 
     # 如果不是 Struct，最后尝试 Raw 兜底
-    return _core_encode_raw(obj)
+    return _core_encode_raw(obj, little_endian=little_endian, max_depth=max_depth)
 
 
 @overload
@@ -71,6 +112,12 @@ def decode(data: _BytesLike) -> TarsDict: ...
 def decode(
     data: _BytesLike,
     cls: type[TarsDict],
+    *,
+    skip_prefix: int = 0,
+    max_bytes: int | None = None,
+    little_endian: bool = False,
+    allow_trailing: bool = False,
+    max_depth: int | None = None,
 ) -> TarsDict: ...
 
 
@@ -78,32 +125,173 @@ def decode(
 def decode(
     data: _BytesLike,
     cls: type[_StructT],
+    *,
+    freeze: bool = False,
+    skip_prefix: int = 0,
+    track_presence: bool = False,
+    coverage: SchemaCoverage | None = None,
+    on_unknown: Callable[[int, int, int], object] | None = None,
+    max_bytes: int | None = None,
+    float_as_decimal: bool = False,
+    widen_float: bool = False,
+    little_endian: bool = False,
+    allow_trailing: bool = False,
+    accept_wrapped_structs: bool = False,
+    strict: bool = False,
+    keep_raw: None = None,
+    context: Any = None,
+    max_depth: int | None = None,
 ) -> _StructT: ...
 
 
+@overload
+def decode(
+    data: _BytesLike,
+    cls: type[_StructT],
+    *,
+    freeze: bool = False,
+    skip_prefix: int = 0,
+    track_presence: bool = False,
+    coverage: SchemaCoverage | None = None,
+    on_unknown: Callable[[int, int, int], object] | None = None,
+    max_bytes: int | None = None,
+    float_as_decimal: bool = False,
+    widen_float: bool = False,
+    little_endian: bool = False,
+    allow_trailing: bool = False,
+    accept_wrapped_structs: bool = False,
+    strict: bool = False,
+    keep_raw: Iterable[int],
+    context: Any = None,
+    max_depth: int | None = None,
+) -> tuple[_StructT, dict[int, bytes]]: ...
+
+
 def decode(
     data: _BytesLike,
     cls: type = TarsDict,
+    *,
+    freeze: bool = False,
+    skip_prefix: int = 0,
+    track_presence: bool = False,
+    coverage: SchemaCoverage | None = None,
+    on_unknown: Callable[[int, int, int], object] | None = None,
+    max_bytes: int | None = None,
+    float_as_decimal: bool = False,
+    widen_float: bool = False,
+    little_endian: bool = False,
+    allow_trailing: bool = False,
+    accept_wrapped_structs: bool = False,
+    strict: bool = False,
+    keep_raw: Iterable[int] | None = None,
+    context: Any = None,
+    max_depth: int | None = None,
 ) -> Any:
     """从 Tars 二进制数据反序列化.
 
     Args:
         data: 二进制数据。
         cls: 目标类。省略或传入 TarsDict 时返回 Raw 解码结果。
+        freeze: 为 True 时返回拒绝属性赋值的只读实例，即使类未声明 `frozen`。
+            仅支持 `Struct` 子类。
+        skip_prefix: 解析前跳过的前缀字节数，用于带填充或哨兵字节的报文，
+            可配合 `detect_prefix` 使用。
+        track_presence: 为 True 时在实例的 `__tarsio_present__` 记录线上出现的
+            字段名（frozenset），用于区分显式写出的默认值与缺失后填充的默认值。
+            仅支持 `Struct` 子类。
+        coverage: 可选的 `SchemaCoverage`，累计顶层实例中出现的字段与未知 Tag，
+            用于分析 Schema 覆盖率。不支持 TarsDict。
+        on_unknown: 可选回调，任意深度的 Struct 每遇到一个未知 Tag 时以
+            `(tag, type_code, offset)` 调用，便于记录协议漂移；返回值被忽略，
+            抛出的异常原样传播。不支持 TarsDict。
+        max_bytes: 输入总长度上限，解析前检查，超出时抛出 `ValidationError`；
+            同时限制 `Meta(compress=...)` 字段解压后的长度。None 表示不限制
+            输入长度，解压上限为 64 MiB。
+        float_as_decimal: 为 True 时 float/double 字段解码为 `decimal.Decimal`，
+            避免二进制浮点误差进入金额计算。注意字段声明类型仍为 float，
+            运行时值却是 Decimal；Any 字段与 TarsDict 中的浮点数同样转换。
+        widen_float: 为 True 时线上的单精度 Float 按最短十进制表示转换为 float，
+            如 `1.1f32` 得到 `1.1` 而非 `1.100000023841858`。
+        little_endian: 为 True 时多字节数值按小端解释，需与编码端一致。
+        allow_trailing: 为 True 时忽略解码结束后剩余的字节（如外层封包的填充），
+            覆盖类配置 `forbid_extra_bytes=True`。
+        accept_wrapped_structs: 为 True 时 Struct 字段（含嵌套与容器元素）遇到
+            SimpleList 时按其中的字节解码嵌套 Struct，兼容时而包装时而内联的
+            生产方。仅支持 `Struct` 子类与 pydantic 模型。
+        strict: 为 True 时要求线上类型与字段声明精确一致，double 字段拒绝单精度
+            Float。仅支持 `Struct` 子类与 pydantic 模型。
+        keep_raw: 需要保留原始字节的顶层字段 tag 集合。传入时返回
+            `(实例, raw)`，`raw` 将这些 tag 映射到字段完整的线上字节（含字段头），
+            可用 `decode_raw` 重新解析，便于逐字段验签。不支持 TarsDict。
+        context: 任意对象，非 None 时以关键字参数 `context` 传给字段的
+            `Meta(decoder=...)` 钩子与 `__post_init__`。Raw 解码没有钩子，忽略该参数。
+        max_depth: 嵌套深度上限，同时约束跳过未知字段时的嵌套层数；
+            None 表示默认上限。
 
     Returns:
-        反序列化的类实例或 TarsDict。
+        反序列化的类实例或 TarsDict；传入 keep_raw 时为 `(实例, raw)` 元组。
 
     Raises:
-        TypeError: 参数类型错误、目标类未注册 Schema、或目标类不是 Struct/TarsDict。
-        ValueError: 数据格式不正确。
+        TypeError: 参数类型错误、目标类未注册 Schema、或目标类不是 Struct/TarsDict/pydantic 模型。
+        ValueError: 数据格式不正确，或 skip_prefix 超过数据长度。
+        ValidationError: 输入长度超过 max_bytes。
     """
     origin_cls = get_origin(cls) or cls
 
+    is_struct = isinstance(origin_cls, type) and issubclass(origin_cls, Struct)
+    if freeze and not is_struct:
+        raise TypeError("freeze=True requires a Struct subclass")
+    if track_presence and not is_struct:
+        raise TypeError("track_presence=True requires a Struct subclass")
+
     if origin_cls is TarsDict:
+        if coverage is not None:
+            raise TypeError("coverage requires a Struct schema class")
+        if on_unknown is not None:
+            raise TypeError("on_unknown requires a Struct schema class")
+        if keep_raw is not None:
+            raise TypeError("keep_raw requires a Struct schema class")
+        if (
+            skip_prefix
+            or max_bytes is not None
+            or little_endian
+            or allow_trailing
+            or max_depth is not None
+        ):
+            return _core_decode(
+                TarsDict,
+                data,
+                skip_prefix=skip_prefix,
+                max_bytes=max_bytes,
+                little_endian=little_endian,
+                allow_trailing=allow_trailing,
+                max_depth=max_depth,
+            )
         return _core_decode_raw(data)
 
-    if isinstance(origin_cls, type) and issubclass(origin_cls, Struct):
-        return _core_decode(origin_cls, data)
+    if isinstance(origin_cls, type) and (
+        issubclass(origin_cls, Struct) or _is_pydantic_model(origin_cls)
+    ):
+        return _core_decode(
+            origin_cls,
+            data,
+            freeze=freeze,
+            skip_prefix=skip_prefix,
+            track_presence=track_presence,
+            coverage=coverage,
+            on_unknown=on_unknown,
+            max_bytes=max_bytes,
+            float_as_decimal=float_as_decimal,
+            widen_float=widen_float,
+            little_endian=little_endian,
+            allow_trailing=allow_trailing,
+            accept_wrapped_structs=accept_wrapped_structs,
+            strict=strict,
+            keep_raw=keep_raw,
+            context=context,
+            max_depth=max_depth,
+        )
 
-    raise TypeError("decode cls must be TarsDict or a Struct subclass")
+    raise TypeError(
+        "decode cls must be TarsDict, a Struct subclass or a pydantic model"
+    )
@@ -6,6 +6,7 @@
 - 提供 `type_info()` / `struct_info()` 的返回对象结构（`kind` 分支 + 关联字段）
 """
 
+from collections.abc import Callable
 from typing import Any, TypeAlias, TypeVar
 
 T = TypeVar("T")
@@ -35,6 +36,8 @@ class IntType(BasicType):
     lt: float | None
     ge: float | None
     le: float | None
+    multiple_of: float | None
+    choices: list[int] | None
 
 class StrType(BasicType):
     """字符串类型.
@@ -45,6 +48,7 @@ class StrType(BasicType):
     min_length: int | None
     max_length: int | None
     pattern: str | None
+    choices: list[str] | None
 
 class FloatType(BasicType):
     """浮点类型（运行时对应 double 语义）.
@@ -56,6 +60,7 @@ class FloatType(BasicType):
     lt: float | None
     ge: float | None
     le: float | None
+    multiple_of: float | None
 
 class BoolType(BasicType):
     """布尔类型（在 JCE 编码层面通常以 int 表达）.
@@ -66,11 +71,43 @@ class BoolType(BasicType):
 class BytesType(BasicType):
     """二进制类型（运行时会被视为 byte-list 的特殊形式）.
 
-    编码：`SimpleList`。
+    编码：`SimpleList`。`bytearray` 同样映射为该类型，`mutable` 为 True。
     """
 
     min_length: int | None
     max_length: int | None
+    fixed_length: int | None
+    mutable: bool
+
+class IpAddressType(BasicType):
+    """IP 地址类型（`ipaddress.IPv4Address` / `IPv6Address`）.
+
+    编码：`SimpleList`，内容为 `.packed` 的 4 或 16 字节。
+
+    Attributes:
+        cls: 声明的地址类。
+        version: 地址版本（4 或 6）。
+    """
+
+    cls: type
+    version: int
+
+class PathType(BasicType):
+    """路径类型（`pathlib.PurePath` 及其子类）.
+
+    编码：`String1` / `String4`，内容为 `str(path)`。
+
+    Attributes:
+        cls: 声明的路径类。
+    """
+
+    cls: type
+
+class ComplexType(BasicType):
+    """复数类型（`complex`）.
+
+    编码：`StructBegin` ... `StructEnd`，tag 0 为实部、tag 1 为虚部（double）。
+    """
 
 class AnyType(BasicType):
     """动态类型（运行时根据值推断编码）.
@@ -97,6 +134,44 @@ class EnumType(CompoundType):
     cls: type
     value_type: TypeInfo
 
+class FlagType(CompoundType):
+    """Flag 类型：`enum.Flag` 子类（含 `IntFlag`）.
+
+    编码：按成员的整数值映射为 Tars 整数，解码时以 `cls(value)` 构造，
+    多个成员的按位组合还原为复合成员。不受 `enum_mode="name"` 影响。
+
+    Attributes:
+        cls: Flag 类型。
+    """
+
+    cls: type
+
+class SubclassType(CompoundType):
+    """标量子类类型：`int`/`str`/`float`/`bytes` 的直接子类（如 `class Port(int)`）.
+
+    编码：按底层类型映射，解码时以 `cls(value)` 构造子类实例。
+
+    Attributes:
+        cls: 子类。
+        value_type: 底层类型的内省结果。
+    """
+
+    cls: type
+    value_type: TypeInfo
+
+class LiteralType(CompoundType):
+    """Literal 类型：`Literal[v1, v2, ...]`.
+
+    编码：按取值的底层类型映射，混合类型的取值对应 Union。
+
+    Attributes:
+        values: 允许的取值（不含 None）。
+        value_type: 取值底层类型的内省结果。
+    """
+
+    values: tuple[Any, ...]
+    value_type: TypeInfo
+
 class UnionType(CompoundType):
     """Union 类型（非 Optional 形式）.
 
@@ -148,30 +223,36 @@ class VarTupleType(CompoundType):
     max_length: int | None
 
 class MapType(CompoundType):
-    """映射类型：`dict[K, V]`.
+    """映射类型：`dict[K, V]` / `defaultdict[K, V]` / `dict` 子类.
 
     编码：`Map`。
 
     Attributes:
         key_type: 键类型。
         value_type: 值类型。
+        default_factory: `defaultdict` 的工厂函数，普通 dict 为 None。
+        cls: 用户定义的 `dict` 子类，普通 dict 为 None。
     """
 
     key_type: TypeInfo
     value_type: TypeInfo
+    default_factory: Callable[[], Any] | None
+    cls: type | None
     min_length: int | None
     max_length: int | None
 
 class SetType(CompoundType):
     """集合类型：`set[T]` / `frozenset[T]`.
 
-    编码：`List`，解码为 set。
+    编码：`List`，解码为 set；`frozenset[T]` 解码为 frozenset。
 
     Attributes:
         item_type: 元素类型。
+        frozen: 是否为 `frozenset[T]`。
     """
 
     item_type: TypeInfo
+    frozen: bool
     min_length: int | None
     max_length: int | None
 
@@ -250,12 +331,18 @@ TypeInfo: TypeAlias = (
     | FloatType
     | BoolType
     | BytesType
+    | IpAddressType
+    | PathType
+    | ComplexType
     | AnyType
     | NoneType
     | TypedDictType
     | NamedTupleType
     | DataclassType
     | EnumType
+    | FlagType
+    | SubclassType
+    | LiteralType
     | UnionType
     | ListType
     | TupleType
@@ -332,3 +419,154 @@ def struct_info(cls: type) -> StructInfo | None:
     Raises:
         TypeError: 当字段缺少 tag、tag 重复、混用整数 tag 与 `Meta`，或字段类型不受支持时抛出。
     """
+
+def is_flat(cls: type) -> bool:
+    """判断 Struct 是否为扁平结构.
+
+    扁平结构只包含基础类型字段（int/float/str/bool/bytes 及其 Optional 形式），
+    不含嵌套 Struct、容器、Union 或其他特殊类型，且字段未设置 endian/compress/encoder
+    等改变编解码行为的 `Meta` 选项或取值约束（`alias` 除外），编解码开销最低。
+
+    Args:
+        cls: 需要判断的 `tarsio.Struct` 子类。
+
+    Returns:
+        编译期计算的扁平标记。
+
+    Raises:
+        TypeError: cls 不是可编译 Schema 的类型时抛出。
+    """
+
+def diff_schema(old: type, new: type) -> dict[str, list[dict[str, Any]]]:
+    """比较两个 Struct 版本的 Schema，生成迁移报告.
+
+    字段按名称配对；嵌套 Struct（含 Optional、list/tuple/set 元素与 dict 值中的
+    Struct）递归比较，嵌套字段的 `path` 以 `.` 连接，容器元素以 `[]` 标记
+    （如 `"items[].name"`）。
+
+    Args:
+        old: 旧版本 Struct 类。
+        new: 新版本 Struct 类。
+
+    Returns:
+        以变更类别为键的 dict：
+
+        - `added` / `removed`：`{"path", "tag", "type"}`
+        - `tag_changed`：`{"path", "old_tag", "new_tag"}`
+        - `type_changed`：`{"path", "old_type", "new_type"}`
+        - `optionality_changed`：`{"path", "old_optional", "new_optional"}`
+        - `version_changed`：`{"path", "old_version", "new_version"}`，顶层 Struct
+          的 `path` 为空串；版本号来自类属性 `__tarsio_version__`
+
+    Raises:
+        TypeError: old 或 new 不是可编译 Schema 的类型时抛出。
+    """
+
+def to_tars_idl(cls: type) -> str:
+    """将 Struct 的 Schema 导出为 Tars IDL 文本.
+
+    嵌套 Struct 递归收集，按依赖顺序（被引用者在前）各自输出一个 `struct` 定义，
+    `cls` 自身位于最后。必填字段输出 `require`，其余输出 `optional`。
+    整数值的 Enum 输出为 `enum` 定义并按名称引用（别名成员不重复输出），
+    其他 Enum 按成员值类型输出。
+
+    Args:
+        cls: 需要导出的 `tarsio.Struct` 子类。
+
+    Returns:
+        IDL 文本，定义之间以空行分隔。
+
+    Raises:
+        TypeError: cls 不是可编译 Schema 的类型，或字段类型无法用 Tars IDL 表达
+            （如 Any、Union、TarsDict、异构 tuple）时抛出。
+    """
+
+def json_schema(cls: type) -> dict[str, Any]:
+    """将 Struct 的 Schema 导出为 JSON Schema（draft 2020-12）.
+
+    描述 `to_builtins` 输出的 dict 形式：属性以字段名（或 `Meta(alias=...)`）为键，
+    必填字段列入 `required`。嵌套 Struct 收集到 `$defs` 并以 `$ref` 引用，
+    `cls` 自身的递归引用写为 `{"$ref": "#"}`。`Meta` 的 `gt`/`ge`/`lt`/`le` 映射为
+    `exclusiveMinimum`/`minimum`/`exclusiveMaximum`/`maximum`，长度约束按类型映射为
+    `minLength`/`minItems`/`minProperties` 等；Enum 与 Literal 输出取值的 `enum` 数组，
+    bytes 输出 base64 编码的字符串。
+
+    Args:
+        cls: 需要导出的 `tarsio.Struct` 子类。
+
+    Returns:
+        可直接交给 `json.dumps` 的 JSON Schema dict。
+
+    Raises:
+        TypeError: cls 不是可编译 Schema 的类型时抛出。
+    """
+
+def schema_hash(cls: type) -> str:
+    """计算 Struct Schema 的稳定指纹.
+
+    指纹覆盖类名、`__tarsio_version__`、各字段的 tag/名称/类型/可选性，并递归包含
+    嵌套 Struct；任意一项变化都会改变结果。同一 Schema 在不同进程中结果相同，
+    可在滚动发布时比对两端的 Schema 是否一致。
+
+    Args:
+        cls: 需要计算指纹的 `tarsio.Struct` 子类。
+
+    Returns:
+        16 位十六进制字符串。
+
+    Raises:
+        TypeError: cls 不是可编译 Schema 的类型时抛出。
+    """
+
+def wire_size_estimate(obj: Any) -> int:
+    """计算 Struct 实例编码后的字节数，不实际生成输出.
+
+    Tars 的编码长度由取值唯一确定，因此结果是精确值，与 `len(encode(obj))` 相同，
+    可用于预分配缓冲区或上报报文大小指标。字节序不影响长度。
+
+    Args:
+        obj: `tarsio.Struct` 实例。
+
+    Returns:
+        编码后的字节数。
+
+    Raises:
+        TypeError: obj 不是可编码的 Struct 实例时抛出。
+        ValueError: 缺少必填字段、类型不匹配或递归深度超过限制时抛出。
+    """
+
+def to_builtins(obj: Any) -> dict[str, Any]:
+    """将 Struct 实例递归转换为由内置类型组成的 dict.
+
+    嵌套 Struct 转为以字段名为键的 dict，list/tuple/set 转为 list，dict（含 TarsDict）
+    转为 dict（键保持原样），其余值（基础类型、bytes、Enum 等）原样保留。
+    所有已赋值的字段都会输出，包括值为 None 或等于默认值的字段；未赋值的字段跳过。
+    声明了 `Meta(alias=...)` 的字段以别名为键。
+
+    Args:
+        obj: `tarsio.Struct` 实例。
+
+    Returns:
+        字段名到转换后值的 dict，顺序按 tag。
+
+    Raises:
+        TypeError: obj 不是 Struct 实例时抛出。
+        ValueError: 嵌套层数超过上限（如存在循环引用）时抛出。
+    """
+
+def astuple(obj: Any) -> tuple[Any, ...]:
+    """将 Struct 实例按 tag 顺序转换为字段值组成的 tuple.
+
+    转换规则与 `to_builtins` 相同，但 Struct（含嵌套）转为 tuple 而非 dict；
+    未赋值的字段以 None 占位，使各位置始终与 tag 顺序对应。
+
+    Args:
+        obj: `tarsio.Struct` 实例。
+
+    Returns:
+        按 tag 顺序排列的字段值 tuple。
+
+    Raises:
+        TypeError: obj 不是 Struct 实例时抛出。
+        ValueError: 嵌套层数超过上限（如存在循环引用）时抛出。
+    """
@@ -8,9 +8,12 @@ import pytest
 from tarsio._core import (
     TarsDict,
     decode,
+    decode_checked,
     decode_raw,
     decode_trace,
     encode_raw,
+    guess_layout,
+    probe_schema,
     probe_struct,
 )
 
@@ -275,6 +278,40 @@ def test_decode_raw_rejects_map_size_exceeding_remaining_bytes() -> None:
         decode_raw(data)
 
 
+def test_decode_rejects_map_size_below_minimum_entry_bytes() -> None:
+    """Map 长度超过剩余字节可容纳的条目数 (每条至少 2 字节) 时应立即报错."""
+    from typing import Annotated
+
+    from tarsio import Struct
+
+    class S(Struct):
+        m: Annotated[dict[int, int], 0]
+
+    # 声明 3 个条目, 仅剩 4 字节 (至少需要 6 字节)
+    data = bytes.fromhex("0800030C1C0C1C")
+    with pytest.raises(ValueError, match="needs at least 6 bytes, remaining 4"):
+        decode_raw(data)
+    with pytest.raises(ValueError, match="needs at least 6 bytes, remaining 4"):
+        decode(S, data)
+
+
+def test_decode_rejects_huge_declared_list_size() -> None:
+    """伪造的超大 List 长度应在读取元素前报错."""
+    from typing import Annotated
+
+    from tarsio import Struct
+
+    class S(Struct):
+        items: Annotated[list[int], 0]
+
+    data = bytes.fromhex("09027FFFFFFF0C")
+    match = "declared length 2147483647 exceeds remaining bytes 1"
+    with pytest.raises(ValueError, match=match):
+        decode_raw(data)
+    with pytest.raises(ValueError, match=match):
+        decode(S, data)
+
+
 def test_decode_raw_max_depth_exceeded() -> None:
     """测试 Raw 解码深度限制."""
     data = bytes.fromhex("0A" * 101 + "0B" * 101)
@@ -372,6 +409,29 @@ def test_raw_recursion_limit() -> None:
         encode_raw(d)
 
 
+def test_decode_trace_nodes_carry_byte_ranges() -> None:
+    """decode_trace 的节点记录字节范围, 容器节点覆盖全部子节点."""
+    # {0: {0: 300}, 1: [1], 2: {"k": 5}}
+    data = bytes.fromhex(
+        "0A01012C0B" + "190001" + "0001" + "280001" + "06016B" + "1005"
+    )
+    root = decode_trace(data)
+    assert (root.start, root.end) == (0, len(data))
+
+    inner, items, mapping = root.children
+    assert data[inner.start : inner.end] == bytes.fromhex("0A01012C0B")
+    assert data[inner.children[0].start : inner.children[0].end] == bytes.fromhex(
+        "01012C"
+    )
+    assert (items.start, items.end) == (5, 10)
+    assert (items.children[0].start, items.children[0].end) == (8, 10)
+    key, value = mapping.children
+    assert (mapping.start, mapping.end) == (10, 18)
+    assert data[key.start : key.end] == bytes.fromhex("06016B")
+    assert data[value.start : value.end] == bytes.fromhex("1005")
+    assert root.to_dict()["children"][1]["end"] == 10
+
+
 def test_decode_trace_depth_exceeded() -> None:
     """decode_trace 深度超过阈值应抛 ValueError."""
     data = bytes.fromhex("0A" * 101 + "0B" * 101)
@@ -391,6 +451,76 @@ def test_probe_struct_valid() -> None:
     assert probe_struct(bytes.fromhex("0A11")) is None
 
 
+def test_raw_roundtrip_restores_bool_tags() -> None:
+    """encode_raw 记录的 bool_tags 交给 decode_raw 后应还原 bool."""
+    obj = TarsDict({0: True, 1: False, 2: 1, 3: "s"})
+    tags: set[int] = set()
+    data = encode_raw(obj, bool_tags=tags)
+    assert tags == {0, 1}
+
+    assert decode_raw(data) == {0: 1, 1: 0, 2: 1, 3: "s"}
+    restored = decode_raw(data, bool_tags=tags)
+    assert restored[0] is True
+    assert restored[1] is False
+    assert restored[2] == 1
+    assert type(restored[2]) is int
+
+
+def test_probe_schema_infers_candidate_types() -> None:
+    """probe_schema 应从原始字节推断类型树, 并为 SimpleList 给出候选."""
+    inner = encode_raw(TarsDict({0: 1}))
+    data = encode_raw(
+        TarsDict(
+            {
+                0: 1,
+                1: "s",
+                2: [1, 2],
+                3: {"k": 1.5},
+                4: TarsDict({0: "x"}),
+                5: b"hello",
+                6: inner,
+                7: [],
+            }
+        )
+    )
+    assert probe_schema(data) == (
+        "Struct{0: int, 1: str, 2: list[int], 3: dict[str, float], "
+        "4: Struct{0: str}, 5: bytes | str, 6: bytes | Struct{0: int}, 7: list[Any]}"
+    )
+
+    mixed = encode_raw(TarsDict({0: [1, "a"]}))
+    assert probe_schema(mixed) == "Struct{0: list[int | str]}"
+    assert probe_schema(bytes.fromhex("0F")) is None
+    assert probe_schema(bytes.fromhex("0A11")) is None
+
+
+def test_guess_layout_lists_fields_and_stops_at_malformed_input() -> None:
+    """guess_layout 应列出顶层字段与一层嵌套字段, 遇到损坏数据时返回已读部分."""
+    data = encode_raw(
+        TarsDict(
+            {
+                0: 1,
+                1: "s",
+                2: [1, 2],
+                3: TarsDict({0: 300, 1: TarsDict({0: 1})}),
+                4: b"ab",
+            }
+        )
+    )
+    assert guess_layout(data) == [
+        (0, "Int1"),
+        (1, "String1"),
+        (2, "List"),
+        (3, "struct", [(0, "Int2"), (1, "struct", None)]),
+        (4, "SimpleList"),
+    ]
+
+    assert guess_layout(data[:-1]) == guess_layout(data)[:4]
+    truncated = bytes.fromhex("00011A0C")
+    assert guess_layout(truncated) == [(0, "Int1"), (1, "struct", [(0, "ZeroTag")])]
+    assert guess_layout(b"") == []
+
+
 def test_decode_schema_accepts_buffer_protocol_input() -> None:
     """Schema decode 应接受 bytearray 和 memoryview 输入."""
     from tarsio import Struct
@@ -404,3 +534,164 @@ def test_decode_schema_accepts_buffer_protocol_input() -> None:
 
     assert u1.uid == 7
     assert u2.uid == 7
+
+
+def test_decode_checked_returns_instance_for_valid_input() -> None:
+    """decode_checked 对合法输入的结果应与 decode 一致."""
+    from tarsio import Struct
+
+    class User(Struct):
+        uid: int
+        name: str = ""
+
+    data = encode_raw(TarsDict({0: 7, 1: "a"}))
+    assert decode_checked(User, data) == decode(User, data)
+
+
+def test_decode_checked_wraps_post_init_error() -> None:
+    """decode_checked 应将 __post_init__ 的异常转换为 ValidationError."""
+    from tarsio import Struct, ValidationError
+
+    class Guarded(Struct):
+        uid: int
+
+        def __post_init__(self) -> None:
+            raise RuntimeError("boom")
+
+    data = encode_raw(TarsDict({0: 1}))
+    with pytest.raises(RuntimeError, match="boom"):
+        decode(Guarded, data)
+    with pytest.raises(ValidationError, match="boom") as exc_info:
+        decode_checked(Guarded, data)
+    assert isinstance(exc_info.value.__cause__, RuntimeError)
+
+
+def test_decode_checked_rejects_non_struct_class() -> None:
+    """decode_checked 的目标类型错误仍抛出 TypeError."""
+    with pytest.raises(TypeError):
+        decode_checked(int, b"")
+
+
+def test_decode_checked_random_input_only_raises_validation_error() -> None:
+    """随机字节与变异输入只应得到实例或 ValidationError."""
+    import contextlib
+    import random
+
+    from tarsio import Struct, ValidationError, encode, field
+
+    class Inner(Struct):
+        a: int
+        b: str = ""
+
+    class Outer(Struct):
+        items: list[Inner]
+        mapping: dict[str, int]
+        blob: bytes | None = None
+        choice: int | str = 0
+        pair: tuple[int, str] = (0, "")
+        tags: set[int] = field(default_factory=set)
+        nested: Inner | None = None
+
+    seed = Outer(
+        [Inner(1, "x"), Inner(-5, "yy")],
+        {"k": 1, "v": 2},
+        b"\x00\xff",
+        "s",
+        (3, "t"),
+        {1, 2},
+        Inner(9),
+    )
+    valid = encode(seed)
+    rng = random.Random(20240601)
+
+    samples: list[bytes] = [rng.randbytes(rng.randrange(0, 64)) for _ in range(300)]
+    for _ in range(300):
+        mutated = bytearray(valid)
+        for _ in range(rng.randrange(1, 4)):
+            mutated[rng.randrange(len(mutated))] = rng.randrange(256)
+        cut = rng.randrange(len(mutated) + 1)
+        samples.append(bytes(mutated[:cut]) if rng.random() < 0.3 else bytes(mutated))
+
+    for data in samples:
+        for cls in (Inner, Outer):
+            with contextlib.suppress(ValidationError):
+                decode_checked(cls, data)
+
+
+def test_random_schemas_survive_random_and_mutated_input() -> None:
+    """随机生成的 Schema 解码随机字节与变异报文时只返回实例或抛出 ValidationError."""
+    import contextlib
+    import random
+    from typing import Annotated, Any, Optional, Union
+
+    from tarsio import Struct, ValidationError, encode
+
+    rng = random.Random(20261017)
+    leaves: list[Any] = [int, str, bytes, float, bool]
+
+    def random_type(depth: int) -> Any:
+        if depth >= 3 or rng.random() < 0.4:
+            return rng.choice(leaves)
+        kind = rng.randrange(5)
+        inner = random_type(depth + 1)
+        if kind == 0:
+            return list[inner]
+        if kind == 1:
+            return dict[rng.choice([int, str]), inner]
+        if kind == 2:
+            return Optional[inner]  # noqa: UP045
+        if kind == 3:
+            return Union[int, str]  # noqa: UP007
+        return random_struct(depth + 1)
+
+    def random_value(tp: Any) -> Any:
+        origin = getattr(tp, "__origin__", None)
+        if tp is int:
+            return rng.randrange(-(2**40), 2**40)
+        if tp is str:
+            return "".join(rng.choice("ab中") for _ in range(rng.randrange(4)))
+        if tp is bytes:
+            return rng.randbytes(rng.randrange(6))
+        if tp is float:
+            return rng.uniform(-1e6, 1e6)
+        if tp is bool:
+            return rng.random() < 0.5
+        if origin is list:
+            return [random_value(tp.__args__[0]) for _ in range(rng.randrange(3))]
+        if origin is dict:
+            key, val = tp.__args__
+            size = rng.randrange(3)
+            return {random_value(key): random_value(val) for _ in range(size)}
+        if origin is Union:
+            members = [a for a in tp.__args__ if a is not type(None)]
+            return random_value(rng.choice(members))
+        return tp(*(random_value(a) for a in tp.__field_types__))
+
+    counter = iter(range(10_000))
+
+    def random_struct(depth: int) -> type:
+        field_types = [random_type(depth) for _ in range(rng.randrange(1, 5))]
+        annotations = {f"f{i}": Annotated[tp, i] for i, tp in enumerate(field_types)}
+        cls = type(f"Fuzz{next(counter)}", (Struct,), {"__annotations__": annotations})
+        cls.__field_types__ = field_types  # type: ignore[attr-defined]
+        return cls
+
+    for _ in range(40):
+        cls = random_struct(0)
+        obj = random_value(cls)
+        valid = encode(obj)
+        assert decode_checked(cls, valid) == obj
+        samples = [rng.randbytes(rng.randrange(0, 48)) for _ in range(30)]
+        for _ in range(30):
+            mutated = bytearray(valid)
+            for _ in range(rng.randrange(1, 4)):
+                if mutated and rng.random() < 0.7:
+                    mutated[rng.randrange(len(mutated))] = rng.randrange(256)
+                else:
+                    mutated.insert(rng.randrange(len(mutated) + 1), rng.randrange(256))
+            samples.append(bytes(mutated[: rng.randrange(len(mutated) + 1)]))
+        for data in samples:
+            with contextlib.suppress(ValidationError):
+                decode_checked(cls, data)
+            with contextlib.suppress(ValueError):
+                decode_raw(data)
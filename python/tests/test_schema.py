@@ -1,6 +1,7 @@
 """测试 Schema/Meta/Inspect 相关行为."""
 
-from typing import Annotated, Generic, Optional, TypeVar, cast
+from enum import Enum, IntEnum
+from typing import Annotated, Any, Generic, Optional, TypeVar, cast
 
 import pytest
 from tarsio import inspect as tinspect
@@ -8,8 +9,10 @@ from tarsio._core import (
     NODEFAULT,
     Meta,
     Struct,
+    Tag,
     TarsDict,
     ValidationError,
+    decode_raw,
     encode_raw,
     field,
 )
@@ -36,6 +39,35 @@ def test_mix_int_tag_and_meta_constraints_allowed() -> None:
     assert obj.uid == 7
 
 
+def test_tag_marker_with_meta_constraints() -> None:
+    """Tag(n) 与裸整数 Tag 等价, 可与 Meta 约束组合, 但不能重复声明 Tag."""
+
+    class User(Struct):
+        uid: Annotated[int, Tag(1), Meta(gt=0)]
+        name: Annotated[str, Meta(min_len=1), Tag(3)] = "x"
+
+    info = tinspect.struct_info(User)
+    assert info is not None
+    assert [(f.name, f.tag) for f in info.fields] == [("uid", 1), ("name", 3)]
+    assert User.decode(encode_raw(TarsDict({1: 7}))).uid == 7
+    with pytest.raises(ValidationError):
+        User.decode(encode_raw(TarsDict({1: 0})))
+    assert repr(Tag(5)) == "Tag(5)"
+
+    with pytest.raises(TypeError, match="Multiple integer tags"):
+
+        class Twice(Struct):
+            uid: Annotated[int, Tag(1), 2]
+
+    with pytest.raises(TypeError, match="cannot mix Annotated integer tag"):
+
+        class Mixed(Struct):
+            uid: Annotated[int, Tag(1)] = field(tag=2)
+
+    with pytest.raises(TypeError, match="Tag must be in range"):
+        Tag(256)
+
+
 def test_meta_without_tag_is_allowed() -> None:
     """Meta 不再承载 tag，缺省由自动分配生效."""
 
@@ -140,6 +172,51 @@ def test_duplicate_tag_raises() -> None:
             b: Annotated[int, 1]
 
 
+def test_duplicate_tag_reports_origin_classes() -> None:
+    """多层继承中 Tag 冲突时错误信息应指明各字段的定义类."""
+
+    class Base(Struct):
+        x: Annotated[int, 3]
+
+    class Middle(Base):
+        m: Annotated[int, 4]
+
+    with pytest.raises(TypeError, match=r"tag 3 declared in Base\.x and Derived\.y"):
+
+        class Derived(Middle):
+            y: Annotated[int, 3]
+
+    redeclared = r"tag 7 declared in Redeclared\.x and Redeclared\.y"
+    with pytest.raises(TypeError, match=redeclared):
+
+        class Redeclared(Middle):
+            x: Annotated[int, 7]
+            y: Annotated[int, 7]
+
+
+def test_stale_schema_capsule_recompiles_for_redefined_class() -> None:
+    """类被重新定义后残留的旧 Schema 不应被复用, 而应重新编译."""
+
+    class User(Struct):
+        id: Annotated[int, 0]
+
+    stale = User.__tarsio_schema__
+    old_user = User
+
+    class User(Struct):  # noqa: F811
+        name: Annotated[str, 0]
+        age: Annotated[int, 1] = 0
+
+    type.__setattr__(User, "__tarsio_schema__", stale)
+
+    obj = User("alice", 3)
+    restored = User.decode(obj.encode())
+    assert restored.name == "alice"
+    assert restored.age == 3
+    assert [f.name for f in tinspect.struct_info(User).fields] == ["name", "age"]
+    assert old_user.decode(old_user(7).encode()).id == 7
+
+
 def test_tag_upper_limit_raises_error() -> None:
     """Tag 超过上限 255 时抛出 ValueError / TypeError."""
     # 根据目前的具体实现可能抛出 TypeError (Schema构建时) 或者是 ValueError
@@ -240,6 +317,196 @@ def test_string_pattern_validation_raises() -> None:
         User.decode(encode_raw(TarsDict({1: "aa12"})))
 
 
+def test_bytes_fixed_len_validation() -> None:
+    """bytes 的 fixed_len 约束在构造、编码与解码时要求精确长度."""
+
+    class Key(Struct):
+        digest: Annotated[bytes, Meta(fixed_len=4)] = field(tag=1)
+
+    key = Key(b"abcd")
+    assert Key.decode(key.encode()) == key
+
+    with pytest.raises(ValidationError, match="must be exactly 4 bytes, got 3"):
+        Key(b"abc")
+    with pytest.raises(ValidationError, match="must be exactly 4 bytes, got 5"):
+        Key.decode(encode_raw(TarsDict({1: b"abcde"})))
+
+    key.digest = b"ab"
+    with pytest.raises(ValidationError, match="Field 'digest' must be exactly 4"):
+        key.encode()
+
+    info = tinspect.type_info(Annotated[bytes, Meta(fixed_len=4)])
+    assert isinstance(info, tinspect.BytesType)
+    assert info.fixed_length == 4
+
+    with pytest.raises(TypeError, match="with fixed_len must be annotated as bytes"):
+
+        class Bad(Struct):
+            name: Annotated[str, Meta(fixed_len=4)] = field(tag=1)
+
+
+@pytest.mark.parametrize(
+    ("tp", "choices", "allowed", "disallowed"),
+    [
+        (int, [1, 2, 3], 2, 5),
+        (str, ["queued", "done"], "done", "lost"),
+    ],
+)
+def test_meta_choices_validation(
+    tp: type, choices: list, allowed: object, disallowed: object
+) -> None:
+    """Meta(choices=...) 在构造、编码与解码时限制 int/str 字段的取值."""
+
+    value_type = Annotated[tp, Meta(choices=choices)]
+
+    class Job(Struct):
+        value: value_type = field(tag=0)  # type: ignore[valid-type]
+
+    job = Job(allowed)
+    assert Job.decode(job.encode()) == job
+
+    with pytest.raises(ValidationError, match="must be one of"):
+        Job(disallowed)
+    with pytest.raises(ValidationError, match="must be one of"):
+        Job.decode(encode_raw(TarsDict({0: disallowed})))
+
+    job.value = disallowed
+    with pytest.raises(ValidationError, match="Field 'value' must be one of"):
+        job.encode()
+
+    info = tinspect.type_info(value_type)
+    assert info.choices == choices  # type: ignore[union-attr]
+
+
+def test_meta_choices_rejects_mismatched_types() -> None:
+    """choices 元素类型与字段类型不符, 或混合 int/str 时抛出 TypeError."""
+    with pytest.raises(TypeError, match="list of int or a list of str"):
+        Meta(choices=[1, "a"])  # type: ignore[list-item]
+
+    with pytest.raises(TypeError, match="with str choices must be annotated as str"):
+
+        class Bad(Struct):
+            code: Annotated[int, Meta(choices=["a"])] = field(tag=0)
+
+
+def test_meta_choices_rejects_empty_and_bool_values() -> None:
+    """choices 为空时抛出 ValueError, int 取值集合中不允许出现 bool."""
+    with pytest.raises(ValueError, match="must not be empty"):
+        Meta(choices=[])
+
+    with pytest.raises(TypeError, match="must not contain bool"):
+        Meta(choices=[True, 2])
+
+
+@pytest.mark.parametrize(
+    ("tp", "step", "allowed", "disallowed"),
+    [
+        (int, 1000, 1_700_000_000_000, 1_700_000_000_001),
+        (int, 2, 2**53, 2**53 + 1),
+        (int, 2, 2**60, 2**60 + 1),
+        (float, 0.1, 0.3, 0.25),
+    ],
+)
+def test_meta_multiple_of_validation(
+    tp: type, step: float, allowed: float, disallowed: float
+) -> None:
+    """Meta(multiple_of=...) 在构造与解码时要求数值为步长的整数倍."""
+    value_type = Annotated[tp, Meta(multiple_of=step)]
+
+    class Tick(Struct):
+        value: value_type = field(tag=0)  # type: ignore[valid-type]
+
+    tick = Tick(allowed)
+    assert Tick.decode(tick.encode()) == tick
+
+    with pytest.raises(ValidationError, match=f"must be a multiple of {step}"):
+        Tick(disallowed)
+    with pytest.raises(ValidationError, match="must be a multiple of"):
+        Tick.decode(encode_raw(TarsDict({0: disallowed})))
+
+    info = tinspect.type_info(value_type)
+    assert info.multiple_of == step  # type: ignore[union-attr]
+
+    with pytest.raises(ValueError, match="multiple_of must be a positive number"):
+        Meta(multiple_of=0)
+
+
+@pytest.mark.parametrize(
+    ("wire", "value", "payload"),
+    [
+        ("int8", 0, b"\x00\x00"),
+        ("int16", -2, b"\x01\xff\xfe"),
+        ("int32", 1, b"\x02\x00\x00\x00\x01"),
+        ("int64", 1, b"\x03" + (1).to_bytes(8, "big")),
+        ("uint8", 255, b"\x00\xff"),
+        ("uint16", 65535, b"\x01\xff\xff"),
+        ("uint32", 2**32 - 1, b"\x02\xff\xff\xff\xff"),
+    ],
+)
+def test_meta_wire_writes_fixed_width(wire: str, value: int, payload: bytes) -> None:
+    """Meta(wire=...) 固定写出声明宽度, 无符号值解码时按该宽度还原."""
+    value_type = Annotated[int, Meta(wire=wire)]  # type: ignore[arg-type]
+
+    class Packet(Struct):
+        value: value_type = field(tag=0)  # type: ignore[valid-type]
+
+    assert Packet(value).encode() == payload
+    assert Packet.decode(payload).value == value
+
+
+def test_meta_wire_range_validation() -> None:
+    """Meta(wire=...) 超出宽度范围时编码与解码抛出 ValidationError."""
+
+    class Header(Struct):
+        port: Annotated[int, Meta(wire="uint16")] = field(tag=0)
+        delta: Annotated[int, Meta(wire="int8")] = field(tag=1, default=0)
+
+    # 窄类型与 ZeroTag 仍可解码.
+    assert Header.decode(encode_raw(TarsDict({0: 80}))).port == 80
+
+    with pytest.raises(ValidationError, match=r"out of range for wire 'uint16'"):
+        Header(65536).encode()
+    with pytest.raises(ValidationError, match=r"out of range for wire 'uint16'"):
+        Header(-1).encode()
+    with pytest.raises(ValidationError, match=r"out of range for wire 'int8'"):
+        Header(1, delta=128).encode()
+    with pytest.raises(ValidationError, match=r"out of range for wire 'uint16'"):
+        Header.decode(encode_raw(TarsDict({0: 70000})))
+    with pytest.raises(ValidationError, match=r"out of range for wire 'int8'"):
+        Header.decode(encode_raw(TarsDict({0: 1, 1: 300})))
+
+    with pytest.raises(ValueError, match="Meta wire must be one of"):
+        Meta(wire="uint64")  # type: ignore[arg-type]
+    with pytest.raises(TypeError, match="with wire must be annotated as int"):
+
+        class Bad(Struct):
+            name: Annotated[str, Meta(wire="int8")] = field(tag=0)
+
+
+def test_meta_chunk_size_splits_bytes_into_simplelist_list() -> None:
+    """Meta(chunk_size=...) 将 bytes 拆为 SimpleList 块组成的 List, 解码时拼接还原."""
+
+    class Blob(Struct):
+        data: Annotated[bytes, Meta(chunk_size=4)] = field(tag=1)
+
+    payload = bytes(range(10))
+    encoded = Blob(payload).encode()
+    assert encoded[:3] == b"\x19\x00\x03"  # tag 1 List, 3 块
+    raw = decode_raw(encoded)
+    assert raw[1] == [payload[0:4], payload[4:8], payload[8:10]]
+    assert Blob.decode(encoded).data == payload
+    assert Blob.decode(Blob(b"").encode()).data == b""
+    # 未分块的 SimpleList 同样可以解码.
+    assert Blob.decode(encode_raw(TarsDict({1: payload}))).data == payload
+
+    with pytest.raises(ValueError, match="chunk_size must be a positive integer"):
+        Meta(chunk_size=0)
+    with pytest.raises(TypeError, match="with chunk_size must be annotated as bytes"):
+
+        class Bad(Struct):
+            name: Annotated[str, Meta(chunk_size=4)] = field(tag=0)
+
+
 def test_tuple_length_constraints_validation_raises() -> None:
     """Tuple 字段应应用 min_len/max_len 约束."""
 
@@ -298,7 +565,7 @@ def test_type_info_optional() -> None:
 def test_type_info_unsupported_type_raises() -> None:
     """不支持类型应抛 TypeError."""
     with pytest.raises(TypeError, match="Unsupported Tars type"):
-        tinspect.type_info(complex)  # type: ignore[arg-type]
+        tinspect.type_info(range)  # type: ignore[arg-type]
 
 
 def test_struct_info_fields_and_order() -> None:
@@ -313,6 +580,294 @@ def test_struct_info_fields_and_order() -> None:
     assert [f.name for f in info.fields] == ["a", "b"]
 
 
+def test_is_flat_reports_primitive_only_structs() -> None:
+    """只含基础类型字段的 Struct 为扁平结构, 含嵌套 Struct 的不是."""
+
+    class Point(Struct):
+        x: Annotated[int, 0]
+        label: Annotated[Optional[str], 1] = None  # noqa: UP045
+        raw: Annotated[bytes, 2] = b""
+
+    class Shape(Struct):
+        origin: Annotated[Point, 0]
+
+    assert tinspect.is_flat(Point) is True
+    assert tinspect.is_flat(Shape) is False
+
+    for tp, meta in (
+        (int, Meta(endian="little")),
+        (bytes, Meta(compress="zlib")),
+        (bytes, Meta(encoder=bytes)),
+        (bytes, Meta(min_len=1)),
+    ):
+
+        class Tuned(Struct):
+            raw: Annotated[tp, 0, meta]  # type: ignore[valid-type]
+
+        assert tinspect.is_flat(Tuned) is False
+
+    class Aliased(Struct):
+        raw: Annotated[bytes, 0, Meta(alias="Raw")]
+
+    assert tinspect.is_flat(Aliased) is True
+
+
+def test_diff_schema_reports_each_change_category() -> None:
+    """diff_schema 按类别报告字段变更, 并递归比较嵌套 Struct."""
+
+    class AddrV1(Struct):
+        city: Annotated[str, 0]
+        zip: Annotated[int, 1]
+
+    class AddrV2(Struct):
+        city: Annotated[str, 0]
+        zip: Annotated[str, 1]
+
+    class UserV1(Struct):
+        id: Annotated[int, 0]
+        name: Annotated[str, 1]
+        nick: Annotated[str, 2]
+        addr: Annotated[AddrV1, 3]
+        history: Annotated[list[AddrV1], 4]
+
+    class UserV2(Struct, kw_only=True):
+        id: Annotated[int, 0]
+        name: Annotated[str, 5]
+        nick: Annotated[Optional[str], 2] = None  # noqa: UP045
+        addr: Annotated[AddrV2, 3]
+        history: Annotated[list[AddrV2], 4]
+        email: Annotated[str, 6] = ""
+
+    report = tinspect.diff_schema(UserV1, UserV2)
+    assert report["added"] == [{"path": "email", "tag": 6, "type": "str"}]
+    assert report["removed"] == []
+    assert report["tag_changed"] == [{"path": "name", "old_tag": 1, "new_tag": 5}]
+    assert report["type_changed"] == [
+        {"path": "addr.zip", "old_type": "int", "new_type": "str"},
+        {"path": "history[].zip", "old_type": "int", "new_type": "str"},
+    ]
+    assert report["optionality_changed"] == [
+        {"path": "nick", "old_optional": False, "new_optional": True}
+    ]
+
+    reverse = tinspect.diff_schema(UserV2, UserV1)
+    assert reverse["removed"] == [{"path": "email", "tag": 6, "type": "str"}]
+    assert tinspect.diff_schema(UserV1, UserV1) == {
+        "added": [],
+        "removed": [],
+        "tag_changed": [],
+        "type_changed": [],
+        "optionality_changed": [],
+        "version_changed": [],
+    }
+
+
+def test_to_tars_idl_emits_nested_structs_in_dependency_order() -> None:
+    """to_tars_idl 先输出被引用的嵌套 Struct 与整数 Enum, 并区分 require/optional."""
+
+    class Addr(Struct):
+        city: Annotated[str, 0]
+
+    class User(Struct, kw_only=True):
+        uid: Annotated[int, 0]
+        name: Annotated[Optional[str], 1] = None  # noqa: UP045
+        addr: Annotated[Addr, 2]
+        scores: Annotated[dict[str, float], 3] = {}
+        history: Annotated[list[Addr], 4] = []
+
+    assert tinspect.to_tars_idl(User) == (
+        "struct Addr {\n"
+        "    0 require string city;\n"
+        "};\n"
+        "\n"
+        "struct User {\n"
+        "    0 require int uid;\n"
+        "    1 optional string name;\n"
+        "    2 require Addr addr;\n"
+        "    3 optional map<string, double> scores;\n"
+        "    4 optional vector<Addr> history;\n"
+        "};\n"
+    )
+
+    class Color(IntEnum):
+        RED = 1
+        GREEN = 2
+        CRIMSON = 1
+
+    class Level(str, Enum):
+        LOW = "low"
+
+    class Paint(Struct):
+        color: Annotated[Color, 0]
+        palette: Annotated[dict[str, list[Color]], 1] = {}
+        level: Annotated[Level, 2] = Level.LOW
+        owner: Annotated[Optional[User], 3] = None  # noqa: UP045
+
+    assert tinspect.to_tars_idl(Paint) == (
+        "enum Color {\n"
+        "    RED = 1,\n"
+        "    GREEN = 2\n"
+        "};\n"
+        "\n"
+        + tinspect.to_tars_idl(User)
+        + "\n"
+        "struct Paint {\n"
+        "    0 require Color color;\n"
+        "    1 optional map<string, vector<Color>> palette;\n"
+        "    2 optional string level;\n"
+        "    3 optional User owner;\n"
+        "};\n"
+    )
+
+    class Loose(Struct):
+        data: Annotated[Any, 0]
+
+    with pytest.raises(TypeError, match="cannot be expressed in Tars IDL"):
+        tinspect.to_tars_idl(Loose)
+
+
+def test_json_schema_maps_fields_constraints_and_nested_structs() -> None:
+    """json_schema 输出 draft 2020-12 Schema, 嵌套 Struct 放入 $defs 并映射 Meta 约束."""
+
+    class Color(IntEnum):
+        RED = 1
+        GREEN = 2
+
+    class Addr(Struct):
+        city: Annotated[str, 0, Meta(min_len=1, pattern="^[a-z]+$")]
+
+    class User(Struct, kw_only=True):
+        uid: Annotated[int, 0, Meta(gt=0, le=100)]
+        name: Annotated[Optional[str], 1] = None  # noqa: UP045
+        addr: Annotated[Addr, 2]
+        history: Annotated[list[Addr], 3, Meta(max_len=3)] = []
+        color: Annotated[Color, 4] = Color.RED
+        parent: Annotated[Optional["User"], 5] = None  # noqa: UP045
+        scores: Annotated[dict[str, float], 6, Meta(alias="scoreMap")] = {}
+
+    addr_ref = {"$ref": "#/$defs/Addr"}
+    assert tinspect.json_schema(User) == {
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "User",
+        "type": "object",
+        "properties": {
+            "uid": {"type": "integer", "exclusiveMinimum": 0, "maximum": 100},
+            "name": {"anyOf": [{"type": "string"}, {"type": "null"}]},
+            "addr": addr_ref,
+            "history": {"type": "array", "items": addr_ref, "maxItems": 3},
+            "color": {"title": "Color", "enum": [1, 2]},
+            "parent": {"anyOf": [{"$ref": "#"}, {"type": "null"}]},
+            "scoreMap": {"type": "object", "additionalProperties": {"type": "number"}},
+        },
+        "required": ["uid", "addr"],
+        "$defs": {
+            "Addr": {
+                "title": "Addr",
+                "type": "object",
+                "properties": {
+                    "city": {"type": "string", "minLength": 1, "pattern": "^[a-z]+$"}
+                },
+                "required": ["city"],
+            }
+        },
+    }
+
+
+def test_schema_version_is_stored_and_affects_hash() -> None:
+    """__tarsio_version__ 参与 schema_hash, 并在 diff_schema 中报告变化."""
+
+    class Addr(Struct):
+        city: Annotated[str, 0]
+
+    class UserV1(Struct):
+        __tarsio_version__ = 1
+        id: Annotated[int, 0]
+        addr: Annotated[Addr, 1]
+
+    class UserV2(Struct):
+        __tarsio_version__ = 2
+        id: Annotated[int, 0]
+        addr: Annotated[Addr, 1]
+
+    class Unversioned(Struct):
+        id: Annotated[int, 0]
+        addr: Annotated[Addr, 1]
+
+    h1 = tinspect.schema_hash(UserV1)
+    assert len(h1) == 16
+    assert h1 == tinspect.schema_hash(UserV1)
+    assert h1 != tinspect.schema_hash(UserV2)
+    assert h1 != tinspect.schema_hash(Unversioned)
+
+    report = tinspect.diff_schema(UserV1, UserV2)
+    assert report["version_changed"] == [
+        {"path": "", "old_version": 1, "new_version": 2}
+    ]
+    assert report["type_changed"] == []
+
+    def make_user(addr_cls: type) -> type:
+        class UserV1(Struct):
+            __tarsio_version__ = 1
+            id: Annotated[int, 0]
+            addr: Annotated[addr_cls, 1]  # type: ignore[valid-type]
+
+        return UserV1
+
+    class Addr(Struct):  # type: ignore[no-redef]
+        city: Annotated[str, 0]
+        zip: Annotated[str, 1] = ""
+
+    # 外层完全相同时, 嵌套 Struct 的字段变化也会改变指纹.
+    assert tinspect.schema_hash(make_user(Addr)) != h1
+
+    with pytest.raises(TypeError, match="__tarsio_version__ must be an int"):
+
+        class Bad(Struct):
+            __tarsio_version__ = "1"
+            id: Annotated[int, 0]
+
+
+def test_to_builtins_and_astuple_convert_nested_structs() -> None:
+    """to_builtins/astuple 递归转换嵌套 Struct 与容器, 循环引用抛 ValueError."""
+
+    class Addr(Struct):
+        city: Annotated[str, 0]
+
+    class User(Struct):
+        uid: Annotated[int, 0]
+        addrs: Annotated[list[Addr], 1]
+        extra: Annotated[dict[str, Addr], 2]
+        nick: Annotated[Optional[str], 3] = None  # noqa: UP045
+        tags: Annotated[set[str], 4] = set()
+
+    user = User(1, [Addr("SZ")], {"home": Addr("BJ")}, tags={"a"})
+    assert tinspect.to_builtins(user) == {
+        "uid": 1,
+        "addrs": [{"city": "SZ"}],
+        "extra": {"home": {"city": "BJ"}},
+        "nick": None,
+        "tags": ["a"],
+    }
+    assert tinspect.astuple(user) == (1, [("SZ",)], {"home": ("BJ",)}, None, ["a"])
+
+    partial = User.__new__(User)
+    partial.uid = 2
+    partial.extra = {}
+    assert tinspect.to_builtins(partial) == {"uid": 2, "extra": {}}
+    assert tinspect.astuple(partial) == (2, None, {}, None, None)
+
+    with pytest.raises(TypeError, match="expected a Struct instance"):
+        tinspect.to_builtins({"uid": 1})
+
+    class Holder(Struct):
+        items: Annotated[list[Any], 0]
+
+    cyclic = Holder([])
+    cyclic.items.append(cyclic)
+    with pytest.raises(ValueError, match="Recursion depth exceeded"):
+        tinspect.to_builtins(cyclic)
+
+
 def test_struct_info_generic_template_returns_fields() -> None:
     """未具体化泛型模板的 struct_info 应返回字段信息."""
     t_type = TypeVar("t_type")
@@ -410,3 +965,47 @@ def test_struct_info_mix_annotated_tag_and_field_tag_raises() -> None:
 
         class Sample(Struct):
             a: Annotated[int, 1] = field(tag=1)
+
+
+def test_wire_size_estimate_matches_encoded_length() -> None:
+    """wire_size_estimate 应与实际编码长度一致."""
+
+    class Inner(Struct):
+        a: Annotated[int, 0]
+        b: Annotated[str, 1]
+
+    class Outer(Struct):
+        n: Annotated[int, 0]
+        f: Annotated[float, 1]
+        s: Annotated[str, 20]
+        data: Annotated[bytes, 2]
+        inner: Annotated[Inner, 3]
+        items: Annotated[list[Inner], 4]
+        m: Annotated[dict[str, int], 5]
+        opt: Annotated[Optional[int], 6] = None
+        blob: Annotated[bytes, 7, Meta(compress="zlib")] = b""
+
+    class Marked(Struct, omit_defaults=True, emit_empty_marker=True):
+        a: Annotated[int, 0] = 0
+
+    samples: list[Any] = [
+        Outer(n=0, f=0.0, s="", data=b"", inner=Inner(0, ""), items=[], m={}),
+        Outer(
+            n=70000,
+            f=1.5,
+            s="x" * 300,
+            data=b"\x00" * 40,
+            inner=Inner(-1, "y"),
+            items=[Inner(i, str(i)) for i in range(3)],
+            m={"k": 1 << 40},
+            opt=5,
+            blob=b"z" * 100,
+        ),
+        Inner(1, "a"),
+        Marked(),
+    ]
+    for obj in samples:
+        assert tinspect.wire_size_estimate(obj) == len(obj.encode())
+
+    with pytest.raises(TypeError):
+        tinspect.wire_size_estimate(object())
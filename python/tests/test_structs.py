@@ -3,10 +3,22 @@
 验证 Struct 构造、配置、默认值、演进兼容性等 API 契约.
 """
 
+import io
+from decimal import Decimal
 from typing import Annotated, Any, Generic, Optional, TypeVar
 
 import pytest
 from tarsio import decode as public_decode
+from tarsio import encode as public_encode
+from tarsio import (
+    SchemaCoverage,
+    decode_concatenated,
+    decode_many,
+    decode_stream,
+    decode_value,
+    detect_prefix,
+    encode_many,
+)
 from tarsio._core import (
     NODEFAULT,
     Meta,
@@ -15,7 +27,9 @@ from tarsio._core import (
     ValidationError,
     decode,
     decode_raw,
+    decode_value,
     encode,
+    encode_into,
     encode_raw,
     field,
 )
@@ -49,6 +63,27 @@ class Node(Struct):
     next: Annotated[Optional["Node"], 1] = None
 
 
+class Department(Struct):
+    """与 Employee 互相引用, 定义时 Employee 尚不存在."""
+
+    name: Annotated[str, 0]
+    members: Annotated[list["Employee"], 1] = []
+
+
+class Employee(Struct):
+    """互相引用结构体的另一半."""
+
+    name: Annotated[str, 0]
+    dept: Annotated[Optional[Department], 1] = None
+
+
+class FrozenPoint(Struct, frozen=True):
+    """冻结的坐标点."""
+
+    x: Annotated[int, 0]
+    origin: Annotated[Optional[Point], 1] = None
+
+
 # ==========================================
 # 构造函数行为测试
 # ==========================================
@@ -190,6 +225,48 @@ def test_init_pattern_constraints_raises_validation_error() -> None:
         Demo(code="aa12")
 
 
+def test_meta_little_endian_field_roundtrip() -> None:
+    """Meta(endian="little") 字段按小端写出, 其余字段保持大端."""
+
+    class Mixed(Struct):
+        a: Annotated[int, 0]
+        b: Annotated[int, 1, Meta(endian="little")]
+        c: Annotated[float, 2, Meta(endian="little", gt=0)]
+
+    obj = Mixed(0x0102, 0x01020304, 1.5)
+    data = encode(obj)
+    assert data.startswith(bytes.fromhex("0101021204030201"))
+    assert decode(Mixed, data) == obj
+    assert decode_raw(data)[1] == 0x04030201
+
+
+def test_meta_big_endian_overrides_little_endian_message() -> None:
+    """little_endian=True 时 Meta(endian="big") 字段仍按大端读写."""
+
+    class Mixed(Struct):
+        a: Annotated[int, 0]
+        b: Annotated[int, 1, Meta(endian="big")]
+        c: Annotated[float, 2, Meta(endian="big")]
+
+    obj = Mixed(0x0102, 0x01020304, 1.5)
+    data = encode(obj, little_endian=True)
+    assert data.startswith(bytes.fromhex("0102011201020304"))
+    assert data.endswith(bytes.fromhex("3ff8000000000000"))
+    assert decode(Mixed, data, little_endian=True) == obj
+    assert decode(Mixed, encode(obj)) == obj
+
+
+def test_meta_little_endian_rejects_non_numeric_field() -> None:
+    """endian 仅适用于 int/float 字段, 非法取值应被拒绝."""
+    with pytest.raises(TypeError, match="endian='little'"):
+
+        class Bad(Struct):
+            s: Annotated[str, 0, Meta(endian="little")]
+
+    with pytest.raises(ValueError, match="endian must be"):
+        Meta(endian="middle")
+
+
 # ==========================================
 # 默认值与 Optional 行为测试
 # ==========================================
@@ -340,6 +417,31 @@ def test_wrap_simplelist_struct_field_roundtrip() -> None:
     assert restored.inner.val == 7
 
 
+def test_accept_wrapped_structs_decodes_both_wire_forms() -> None:
+    """accept_wrapped_structs=True 时普通 Struct 字段同时接受内联与 SimpleList 包装."""
+
+    class Inner(Struct):
+        val: Annotated[int, 0]
+
+    class Plain(Struct):
+        inner: Annotated[Inner, 0]
+        items: Annotated[list[Inner], 1] = []  # noqa: RUF012
+
+    class Wrapped(Struct):
+        inner: Annotated[Inner, 0] = field(wrap_simplelist=True)
+
+    inline = encode(Plain(Inner(1), [Inner(2)]))
+    wrapped = encode(Wrapped(Inner(3)))
+    assert wrapped[0] == 0x0D
+
+    assert decode(Plain, inline, accept_wrapped_structs=True).items == [Inner(2)]
+    assert decode(Plain, wrapped, accept_wrapped_structs=True).inner == Inner(3)
+    assert Plain.decode(wrapped, accept_wrapped_structs=True).inner == Inner(3)
+
+    with pytest.raises(ValueError, match="must be encoded as Struct"):
+        decode(Plain, wrapped)
+
+
 def test_wrap_simplelist_tarsdict_field_roundtrip() -> None:
     """TarsDict 字段启用 wrap_simplelist 后应按 SimpleList 互通."""
 
@@ -356,8 +458,8 @@ def test_wrap_simplelist_tarsdict_field_roundtrip() -> None:
     assert restored.payload[1] == "x"
 
 
-def test_wrap_simplelist_requires_simplelist_wire() -> None:
-    """wrap_simplelist 字段解码时应严格要求 SimpleList wire."""
+def test_wrap_simplelist_accepts_inline_and_wrapped_wire() -> None:
+    """wrap_simplelist 字段解码时同时接受内联 Struct 与 SimpleList 包装."""
 
     class Inner(Struct):
         val: Annotated[int, 0]
@@ -368,9 +470,19 @@ def test_wrap_simplelist_requires_simplelist_wire() -> None:
     class WrappedOuter(Struct):
         inner: Annotated[Inner, 0] = field(wrap_simplelist=True)
 
-    data = encode(PlainOuter(inner=Inner(3)))
+    class DictOuter(Struct):
+        payload: Annotated[TarsDict, 0] = field(wrap_simplelist=True)
+
+    inline = encode(PlainOuter(inner=Inner(3)))
+    wrapped = encode(WrappedOuter(inner=Inner(3)))
+    assert inline[0] == 0x0A
+    assert wrapped[0] == 0x0D
+    assert decode(WrappedOuter, inline).inner == Inner(3)
+    assert decode(WrappedOuter, wrapped).inner == Inner(3)
+    assert decode(DictOuter, inline).payload == TarsDict({0: 3})
+
     with pytest.raises(ValueError, match="expects SimpleList"):
-        decode(WrappedOuter, data)
+        decode(WrappedOuter, encode_raw(TarsDict({0: 3})))
 
 
 def test_wrap_simplelist_rejects_non_struct_and_non_tarsdict_field() -> None:
@@ -529,6 +641,15 @@ def test_struct_decode_recursion_limit_exceeded() -> None:
         decode(Node, data)
 
 
+def test_struct_decode_repeated_tag_limit_exceeded() -> None:
+    """同一 Tag 重复出现过多次时应中止解码, 避免重复 Tag 放大解码开销."""
+    data = b"\x00\x01" * 100_000 + b"\x16\x01a"
+    with pytest.raises(ValueError, match="Too many fields read for struct 'User'"):
+        decode(User, data)
+
+    assert decode(User, b"\x00\x01" * 3 + b"\x16\x01a") == User(1, "a")
+
+
 # ==========================================
 # 协议容错性测试 (Unknown Tags / Empty)
 # ==========================================
@@ -572,6 +693,356 @@ def test_frozen_struct_is_immutable() -> None:
         u.id = 2  # pyright: ignore[reportAttributeAccessIssue]
 
 
+@pytest.mark.parametrize("algorithm", ["zlib", "gzip"])
+def test_meta_compress_bytes_roundtrip(algorithm: str) -> None:
+    """Meta(compress=...) 编码时压缩 bytes 字段, 解码时解压."""
+    import os
+    import zlib
+
+    class Blob(Struct):
+        data: Annotated[bytes, 0, Meta(compress=algorithm)]
+        note: Annotated[Optional[bytes], 1, Meta(compress=algorithm)] = None  # noqa: UP045
+
+    compressible = b"tarsio" * 1000
+    incompressible = os.urandom(512)
+    for payload in (compressible, incompressible, b""):
+        obj = Blob(payload, payload)
+        assert decode(Blob, encode(obj)) == obj
+
+    assert len(encode(Blob(compressible))) < len(compressible) // 10
+    if algorithm == "zlib":
+        wire = decode_raw(encode(Blob(compressible)))[0]
+        assert zlib.decompress(wire) == compressible
+
+    with pytest.raises(ValueError, match="Failed to decompress"):
+        decode(Blob, encode_raw(TarsDict({0: b"not compressed"})))
+    truncated = decode_raw(encode(Blob(compressible)))[0][:-8]
+    with pytest.raises(ValueError, match="incomplete compressed data"):
+        decode(Blob, encode_raw(TarsDict({0: truncated})))
+
+    bomb = encode(Blob(bytes(1_000_000)))
+    assert len(bomb) < 10_000
+    with pytest.raises(ValueError, match="decompressed size exceeds 10000 bytes"):
+        decode(Blob, bomb, max_bytes=10_000)
+    assert decode(Blob, bomb).data == bytes(1_000_000)
+
+    with pytest.raises(TypeError, match="with compress must be annotated as bytes"):
+
+        class Bad(Struct):
+            v: Annotated[str, 0, Meta(compress=algorithm)]
+
+
+def test_meta_accept_bytes_decodes_utf8_for_str_field() -> None:
+    """Meta(accept_bytes=True) 时 str 字段接受 bytes 并按 UTF-8 解码."""
+
+    class Msg(Struct):
+        text: Annotated[str, 0, Meta(accept_bytes=True)]
+        note: Annotated[Optional[str], 1, Meta(accept_bytes=True)] = None  # noqa: UP045
+
+    from_str = Msg("你好", "x")
+    from_bytes = Msg("你好".encode(), b"x")
+    assert from_bytes.text == "你好"
+    assert from_bytes == from_str
+    assert encode(from_bytes) == encode(from_str)
+
+    loose = Msg("a")
+    loose.text = b"late"  # pyright: ignore[reportAttributeAccessIssue]
+    assert decode(Msg, encode(loose)).text == "late"
+
+    with pytest.raises(ValidationError, match="not valid UTF-8"):
+        Msg(b"\xff\xfe")
+
+    class Strict(Struct):
+        text: Annotated[str, 0]
+
+    with pytest.raises(ValidationError, match="type mismatch"):
+        Strict(b"abc")  # pyright: ignore[reportArgumentType]
+
+    with pytest.raises(TypeError, match="accept_bytes=True must be annotated as str"):
+
+        class Bad(Struct):
+            v: Annotated[bytes, 0, Meta(accept_bytes=True)]
+
+
+def test_meta_as_bytes_keeps_non_utf8_string_field_as_bytes() -> None:
+    """Meta(as_bytes=True) 时 str 字段按原始 bytes 读写, 不做 UTF-8 校验."""
+
+    class Legacy(Struct):
+        name: Annotated[str, 0, Meta(as_bytes=True, max_len=4)]
+
+    raw = b"\xff\xfe"
+    data = encode(Legacy(raw))  # pyright: ignore[reportArgumentType]
+    assert data == b"\x06\x02\xff\xfe"
+    assert decode(Legacy, data).name == raw
+    assert decode(Legacy, encode(Legacy("ab"))).name == b"ab"
+
+    class Plain(Struct):
+        name: Annotated[str, 0]
+
+    with pytest.raises(ValidationError, match="Invalid UTF-8"):
+        decode(Plain, data)
+
+    with pytest.raises(ValidationError, match="length must be <= 4"):
+        Legacy(b"\x00" * 5)  # pyright: ignore[reportArgumentType]
+
+    with pytest.raises(TypeError, match="as_bytes=True must be annotated as str"):
+
+        class Bad(Struct):
+            v: Annotated[bytes, 0, Meta(as_bytes=True)]
+
+
+def test_meta_normalize_encodes_equivalent_strings_identically() -> None:
+    """Meta(normalize="NFC") 时组合与分解形式的等价字符串编码结果相同."""
+
+    class Name(Struct):
+        text: Annotated[str, 0, Meta(normalize="NFC")]
+
+    composed = "caf\u00e9"
+    decomposed = "cafe\u0301"
+    assert encode(Name(composed)) == encode(Name(decomposed))
+    assert decode(Name, encode(Name(decomposed))).text == composed
+
+    with pytest.raises(ValueError, match="Meta normalize must be one of"):
+        Meta(normalize="nfc")  # pyright: ignore[reportArgumentType]
+
+    with pytest.raises(TypeError, match="normalize must be annotated as str"):
+
+        class Bad(Struct):
+            v: Annotated[bytes, 0, Meta(normalize="NFC")]
+
+
+def test_decode_freeze_returns_read_only_instance() -> None:
+    """decode(freeze=True) 返回的实例拒绝属性赋值, 普通解码不受影响."""
+    data = encode(User(1, "a"))
+
+    frozen = decode(User, data, freeze=True)
+    with pytest.raises(AttributeError, match="frozen instance"):
+        frozen.uid = 2
+    assert frozen == User(1, "a")
+
+    snapshots = (public_decode(data, User, freeze=True), User.decode(data, freeze=True))
+    for snapshot in snapshots:
+        with pytest.raises(AttributeError):
+            snapshot.name = "b"
+
+    mutable = decode(User, data)
+    mutable.uid = 2
+    assert mutable.uid == 2
+
+    with pytest.raises(TypeError, match="freeze=True requires a Struct subclass"):
+        public_decode(data, TarsDict, freeze=True)
+
+
+def test_decode_freeze_survives_copy_and_replace() -> None:
+    """copy/deepcopy/replace 得到的新实例保留 decode(freeze=True) 的只读标记."""
+    import copy
+
+    frozen = decode(User, encode(User(1, "a")), freeze=True)
+    for clone in (copy.copy(frozen), copy.deepcopy(frozen), frozen.replace(name="b")):
+        with pytest.raises(AttributeError, match="frozen instance"):
+            clone.uid = 2
+
+    clone = copy.copy(decode(User, encode(User(1, "a"))))
+    clone.uid = 2
+    assert clone.uid == 2
+
+
+def test_decode_skip_prefix_ignores_leading_padding() -> None:
+    """skip_prefix 跳过填充字节后可正常解码, detect_prefix 可定位真实报文起点."""
+    payload = encode(User(7, "bob"))
+    padded = b"\xff\xfe\xfd\xfc" + payload
+
+    with pytest.raises(ValueError):
+        decode(User, padded)
+
+    assert detect_prefix(padded) == 4
+    assert detect_prefix(payload) == 0
+    assert detect_prefix(b"\xff" * 8) is None
+
+    assert decode(User, padded, skip_prefix=4) == User(7, "bob")
+    assert public_decode(padded, User, skip_prefix=4) == User(7, "bob")
+    assert public_decode(padded, skip_prefix=4) == decode_raw(payload)
+
+    with pytest.raises(ValueError, match="skip_prefix 64 exceeds data length"):
+        decode(User, padded, skip_prefix=64)
+
+
+def test_decode_track_presence_matches_wire_fields() -> None:
+    """track_presence=True 时 __tarsio_present__ 记录线上实际出现的字段名."""
+
+    class Profile(Struct):
+        uid: Annotated[int, 0]
+        nick: Annotated[str, 1] = ""
+        age: Annotated[Optional[int], 2] = None  # noqa: UP045
+
+    data = encode_raw(TarsDict({0: 7, 1: ""}))
+    obj = decode(Profile, data, track_presence=True)
+    assert obj == Profile(7)
+    assert obj.__tarsio_present__ == frozenset({"uid", "nick"})
+
+    assert public_decode(data, Profile, track_presence=True).__tarsio_present__ == (
+        frozenset({"uid", "nick"})
+    )
+    assert Profile.decode(data, track_presence=True).__tarsio_present__ is not None
+    assert decode(Profile, data).__tarsio_present__ is None
+    assert Profile(7).__tarsio_present__ is None
+
+    with pytest.raises(TypeError, match="track_presence=True requires a Struct"):
+        public_decode(data, TarsDict, track_presence=True)
+
+
+def test_schema_coverage_reports_unseen_fields_and_unknown_tags() -> None:
+    """SchemaCoverage 累计字段出现次数, 报告从未出现的字段与未知 Tag."""
+
+    class Profile(Struct):
+        uid: Annotated[int, 0]
+        nick: Annotated[str, 1] = ""
+        legacy: Annotated[Optional[int], 2] = None  # noqa: UP045
+
+    cov = SchemaCoverage()
+    decode(Profile, encode_raw(TarsDict({0: 1, 1: "a", 9: 5})), coverage=cov)
+    public_decode(encode_raw(TarsDict({0: 2, 9: 6, 10: "x"})), Profile, coverage=cov)
+    Profile.decode(encode_raw(TarsDict({0: 3})), coverage=cov)
+
+    assert cov.report() == {
+        "Profile": {
+            "decoded": 3,
+            "field_counts": {"uid": 3, "nick": 1, "legacy": 0},
+            "never_seen": ["legacy"],
+            "unknown_tags": {9: 2, 10: 1},
+        }
+    }
+    assert decode(Profile, encode_raw(TarsDict({0: 4}))) == Profile(4)
+    assert cov.report()["Profile"]["decoded"] == 3
+
+    with pytest.raises(TypeError, match="coverage requires a Struct"):
+        public_decode(b"", TarsDict, coverage=cov)
+
+
+def test_schema_coverage_counts_only_successful_decodes() -> None:
+    """失败的解码不计入统计; 钩子中的嵌套解码可共用同一个 SchemaCoverage."""
+    cov = SchemaCoverage()
+
+    class Item(Struct):
+        uid: Annotated[int, 0]
+
+    def nested(value: int) -> int:
+        decode(Item, encode_raw(TarsDict({0: value})), coverage=cov)
+        return value
+
+    class Wrapper(Struct):
+        uid: Annotated[int, 0, Meta(decoder=nested)]
+
+    with pytest.raises(ValueError, match="Missing required field"):
+        decode(Item, encode_raw(TarsDict({5: 1})), coverage=cov)
+    assert cov.report() == {}
+
+    decode(Wrapper, encode_raw(TarsDict({0: 1})), coverage=cov)
+    assert cov.report()["Item"]["decoded"] == 1
+    assert cov.report()["Wrapper"]["decoded"] == 1
+
+    def make_item() -> type:
+        class Item(Struct):
+            name: Annotated[str, 0]
+
+        return Item
+
+    other = make_item()
+    decode(other, encode_raw(TarsDict({0: "a"})), coverage=cov)
+    keys = sorted(cov.report())
+    assert "Wrapper" in keys
+    items = [k for k in keys if k.endswith(".Item")]
+    assert len(items) == 2
+    assert all(k.startswith(__name__) for k in items)
+
+
+def test_decode_on_unknown_callback_sees_unknown_tags() -> None:
+    """on_unknown 回调在跳过未知 Tag 前以 (tag, type_code, offset) 触发."""
+
+    class Known(Struct):
+        uid: Annotated[int, 0]
+
+    class Strict(Struct, forbid_unknown_tags=True):
+        uid: Annotated[int, 0]
+
+    data = encode_raw(TarsDict({0: 1, 3: "x", 7: 2}))
+    seen: list[tuple[int, int, int]] = []
+    obj = decode(Known, data, on_unknown=lambda *args: seen.append(args))
+    assert obj == Known(1)
+    # 00 01 | 36 01 78 | 70 02
+    assert seen == [(3, 6, 2), (7, 0, 5)]
+
+    seen.clear()
+    public_decode(data, Known, on_unknown=lambda *args: seen.append(args))
+    Known.decode(data, on_unknown=lambda *args: seen.append(args))
+    assert [tag for tag, _, _ in seen] == [3, 7, 3, 7]
+
+    seen.clear()
+    with pytest.raises(ValueError, match="forbid_unknown_tags"):
+        decode(Strict, data, on_unknown=lambda *args: seen.append(args))
+    assert seen == [(3, 6, 2)]
+
+    def abort(tag: int, type_code: int, offset: int) -> None:
+        raise RuntimeError(f"drift at tag {tag}")
+
+    with pytest.raises(RuntimeError, match="drift at tag 3"):
+        decode(Known, data, on_unknown=abort)
+
+
+def test_decode_on_unknown_reaches_nested_structs() -> None:
+    """on_unknown 对嵌套 Struct 与容器中的 Struct 同样生效, 返回值被忽略."""
+
+    class Known(Struct):
+        uid: Annotated[int, 0]
+
+    class Outer(Struct):
+        inner: Annotated[Known, 0]
+        items: Annotated[list[Known], 1]
+
+    data = encode_raw(
+        TarsDict({0: TarsDict({0: 1, 5: "y"}), 1: [TarsDict({0: 2, 6: 3})]})
+    )
+    seen: list[tuple[int, int, int]] = []
+    obj = decode(Outer, data, on_unknown=lambda *args: seen.append(args))
+    assert obj == Outer(Known(1), [Known(2)])
+    # 0a 00 01 | 56 01 79 | 0b 19 00 01 0a 00 02 | 60 03 | 0b 0b
+    assert seen == [(5, 6, 3), (6, 0, 13)]
+
+    assert decode(Outer, data, on_unknown=lambda *args: False) == obj
+
+
+def test_decode_keep_raw_returns_field_wire_bytes() -> None:
+    """keep_raw 返回指定 tag 的完整线上字节, 重新解析得到相同的值."""
+
+    class Inner(Struct):
+        val: Annotated[int, 0]
+
+    class Signed(Struct):
+        uid: Annotated[int, 0]
+        name: Annotated[str, 1]
+        inner: Annotated[Inner, 3]
+        sig: Annotated[bytes, 4] = b""
+
+    obj = Signed(7, "alice", Inner(9), b"\x01")
+    data = obj.encode()
+    restored, raw = decode(Signed, data, keep_raw={1, 3, 5})
+    assert restored == obj
+    assert set(raw) == {1, 3}
+    assert raw[1] == b"\x16\x05alice"
+    assert decode_raw(raw[1]) == {1: "alice"}
+    assert decode_raw(raw[3]) == {3: {0: 9}}
+    assert b"".join(raw.values()) in data
+
+    restored, raw = public_decode(data, Signed, keep_raw=[0])
+    assert restored == obj
+    assert raw == {0: b"\x00\x07"}
+
+    with pytest.raises(TypeError, match="keep_raw must be an iterable"):
+        decode(Signed, data, keep_raw=[256])
+    with pytest.raises(TypeError, match="keep_raw requires a Struct"):
+        public_decode(data, TarsDict, keep_raw=[0])
+
+
 def test_frozen_struct_is_hashable() -> None:
     """frozen=True 时实例可哈希."""
 
@@ -634,6 +1105,40 @@ def test_struct_repr_omit_defaults_option() -> None:
     assert "b=3" in repr(c)
 
 
+def test_struct_str_fields_option() -> None:
+    """str_fields 配置后 str() 只展示所列字段, 未配置时与 repr() 相同."""
+
+    class Named(Struct, str_fields=["name"]):
+        uid: Annotated[int, 0]
+        name: Annotated[str, 1]
+
+    obj = Named(1, "Ada")
+    assert str(obj) == "Named(name='Ada')"
+    assert repr(obj) == "Named(uid=1, name='Ada')"
+    assert str(obj) != repr(obj)
+    assert Named.__struct_config__.str_fields == ["name"]
+
+    user = User(uid=1, name="a")
+    assert str(user) == repr(user)
+
+    with pytest.raises(TypeError, match="str_fields contains unknown field 'nope'"):
+
+        class Bad(Struct, str_fields=["nope"]):
+            uid: Annotated[int, 0]
+
+
+def test_struct_str_uses_overridden_repr() -> None:
+    """未配置 str_fields 时 str() 调用子类覆盖的 __repr__."""
+
+    class Custom(Struct):
+        uid: Annotated[int, 0]
+
+        def __repr__(self) -> str:
+            return f"<Custom {self.uid}>"
+
+    assert str(Custom(7)) == "<Custom 7>"
+
+
 def test_struct_kw_only_option() -> None:
     """kw_only=True 时构造函数仅接受关键字参数."""
 
@@ -706,6 +1211,54 @@ def test_omit_defaults_skips_encoding() -> None:
     assert raw[1] == 2
 
 
+def test_omit_none_false_rejects_none_fields() -> None:
+    """omit_none=False 时 None 字段编码报错, omit_defaults 认定的默认 None 仍被省略."""
+
+    class Strict(Struct, omit_none=False):
+        a: Annotated[int, 0]
+        b: Annotated[Optional[int], 1] = None
+
+    assert Strict.__struct_config__.omit_none is False
+    assert decode_raw(encode(Strict(1, 2))) == {0: 1, 1: 2}
+    with pytest.raises(ValueError, match="Field 'b' is None but omit_none=False"):
+        encode(Strict(1))
+
+    class Compact(Struct, omit_none=False, omit_defaults=True):
+        a: Annotated[Optional[int], 0] = None
+        b: Annotated[Optional[int], 1] = 5
+
+    assert encode(Compact()) == b""
+    with pytest.raises(ValueError, match="Field 'b' is None"):
+        encode(Compact(b=None))
+
+
+def test_emit_empty_marker_for_all_default_struct() -> None:
+    """emit_empty_marker=True 时空结构体编码为单个 StructEnd 标记, 解码忽略该标记."""
+
+    class Plain(Struct, omit_defaults=True):
+        a: int = 0
+
+    class Marked(Struct, omit_defaults=True, emit_empty_marker=True):
+        a: int = 0
+        b: str = ""
+
+    assert encode(Plain()) == b""
+    assert encode(Marked()) == b"\x0b"
+    assert Marked.__struct_config__.emit_empty_marker is True
+    assert decode(Marked, b"\x0b") == Marked()
+    assert decode(Marked, b"") == Marked()
+
+    # 非空结构体不写标记
+    assert encode(Marked(a=1)) == encode(Plain(a=1))
+    assert decode(Marked, encode(Marked(a=1))) == Marked(a=1)
+
+    class Holder(Struct):
+        inner: Marked = field(wrap_simplelist=True)
+
+    holder = Holder(Marked())
+    assert decode(Holder, encode(holder)) == holder
+
+
 def test_signature_tag_order_and_kwonly_handling() -> None:
     """Signature 应反映字段顺序和 kw_only 设置."""
     import inspect
@@ -744,6 +1297,68 @@ def test_copy_returns_new_instance() -> None:
     assert u is not u2
 
 
+def test_deepcopy_copies_nested_values_and_shared_references() -> None:
+    """__deepcopy__ 递归复制嵌套值, 并通过 memo 保留共享引用与循环引用."""
+    import copy
+
+    class Bag(Struct):
+        items: Annotated[list[int], 0]
+        child: Annotated[Optional["Bag"], 1] = None
+
+    shared = [1, 2]
+    pair = copy.deepcopy([Bag(shared, Bag([3])), Bag(shared)])
+    assert pair[0] == Bag([1, 2], Bag([3]))
+    assert pair[0].items is not shared
+    assert pair[0].items is pair[1].items
+
+    loop = Bag([])
+    loop.child = loop
+    loop_copy = copy.deepcopy(loop)
+    assert loop_copy.child is loop_copy
+
+    frozen = FrozenPoint(3, Point(1, 2))
+    frozen_copy = copy.deepcopy(frozen)
+    assert frozen_copy == frozen
+    assert frozen_copy.origin is not frozen.origin
+
+
+def test_pickle_round_trip_preserves_fields() -> None:
+    """pickle 往返后实例相等, frozen 实例同样适用."""
+    import pickle
+
+    for obj in (
+        User(1, "a"),
+        Node(1, Node(2)),
+        FrozenPoint(3, Point(1, 2)),
+    ):
+        restored = pickle.loads(pickle.dumps(obj))
+        assert restored == obj
+        assert type(restored) is type(obj)
+
+    frozen = pickle.loads(pickle.dumps(FrozenPoint(3)))
+    assert hash(frozen) == hash(FrozenPoint(3))
+    with pytest.raises(AttributeError):
+        frozen.x = 4  # type: ignore[misc]
+
+
+def test_pickle_preserves_frozen_instance_and_extra_attributes() -> None:
+    """pickle 往返保留 decode(freeze=True) 的只读标记与实例 __dict__ 中的额外属性."""
+    import pickle
+
+    snapshot = decode(User, User(1, "a").encode(), freeze=True)
+    restored = pickle.loads(pickle.dumps(snapshot))
+    assert restored == snapshot
+    with pytest.raises(AttributeError):
+        restored.uid = 2
+
+    user = User(1, "a")
+    user.note = "extra"  # type: ignore[attr-defined]
+    restored_user = pickle.loads(pickle.dumps(user))
+    assert restored_user.note == "extra"  # type: ignore[attr-defined]
+    restored_user.uid = 2
+    assert restored_user.uid == 2
+
+
 def test_post_init_runs_after_init() -> None:
     """__post_init__ 应在构造完成后执行."""
 
@@ -888,6 +1503,17 @@ def test_rich_repr_omit_defaults_follows_repr_config() -> None:
     assert c.__rich_repr__() == [("b", 3)]
 
 
+def test_rich_repr_marks_optional_none_with_default() -> None:
+    """值为 None 的 Optional 字段以三元组给出, 便于 rich 省略."""
+
+    class C(Struct):
+        a: Annotated[int, 0]
+        b: Annotated[str | None, 1] = None
+
+    assert C(1).__rich_repr__() == [("a", 1), ("b", None, None)]
+    assert C(1, "x").__rich_repr__() == [("a", 1), ("b", "x")]
+
+
 # ==========================================
 # 不变量测试 (Invariants)
 # ==========================================
@@ -1081,6 +1707,121 @@ def test_evolution_backward_compatibility_skips_unknown_bytes_field() -> None:
     pass
 
 
+def test_encode_into_appends_to_bytearray() -> None:
+    """encode_into 追加编码结果并返回写入字节数, 仍检测重入编码."""
+    buffer = bytearray(b"\xff")
+    written = encode_into(User(1, "a"), buffer)
+    assert written == len(encode(User(1, "a")))
+    assert buffer == b"\xff" + encode(User(1, "a"))
+
+    encode_into(User(2, "b"), buffer)
+    assert buffer[1 + written :] == encode(User(2, "b"))
+
+    with pytest.raises(TypeError):
+        encode_into(User(1, "a"), b"")  # type: ignore[arg-type]
+
+    class Sneaky:
+        def __eq__(self, other: object) -> bool:
+            encode(User(1, "a"))
+            return False
+
+    class Holder(Struct, omit_defaults=True):
+        v: Annotated[Any, 0] = 0
+
+    with pytest.raises(RuntimeError, match="Re-entrant encode detected"):
+        encode_into(Holder(Sneaky()), bytearray())
+
+
+def test_mutually_recursive_structs_compile_on_first_use() -> None:
+    """引用后定义类的 Struct 推迟编译, 名称仍无法解析时在首次使用时报 NameError."""
+    dept = Department("R&D", [Employee("a", Department("sub"))])
+    assert decode(Department, encode(dept)) == dept
+    assert Department.__struct_fields__ == ("name", "members")
+
+    class Orphan(Struct):
+        ref: Annotated[Optional["Missing"], 0] = None  # noqa: F821
+
+    with pytest.raises(NameError, match="unresolved forward reference"):
+        Orphan()
+
+
+def test_encode_include_exclude_filters_top_level_fields() -> None:
+    """include/exclude 只写出选中的顶层字段, 未知字段名报 ValueError."""
+
+    class Account(Struct, omit_defaults=True):
+        uid: Annotated[int, 0]
+        email: Annotated[str, 1]
+        note: Annotated[str, 2] = ""
+
+    acct = Account(7, "a@b.c", "hi")
+    assert decode_raw(public_encode(acct, exclude={"email"})) == {0: 7, 2: "hi"}
+    assert decode_raw(encode(acct, include={"uid", "note"})) == {0: 7, 2: "hi"}
+    assert decode_raw(encode(acct, include={"uid", "email"}, exclude=["email"])) == {
+        0: 7
+    }
+    assert decode_raw(encode(Account(7, "x"), include={"note", "uid"})) == {0: 7}
+
+    with pytest.raises(ValueError, match="unknown field 'password'"):
+        encode(acct, exclude={"password"})
+    with pytest.raises(TypeError):
+        encode(acct, include="uid")  # type: ignore[arg-type]
+    with pytest.raises(TypeError):
+        public_encode({0: 1}, exclude={"uid"})
+
+
+def test_encode_many_matches_per_object_encode_and_frames() -> None:
+    """encode_many 逐条结果与 encode 一致, framed 输出带 4 字节大端长度前缀."""
+    users = [User(1, "a"), User(2, "bb"), User(3, "")]
+    encoded = [encode(u) for u in users]
+    assert encode_many(users) == encoded
+    assert encode_many(iter(users), little_endian=True) == [
+        encode(u, little_endian=True) for u in users
+    ]
+
+    framed = encode_many(users, framed=True)
+    assert framed == b"".join(len(b).to_bytes(4, "big") + b for b in encoded)
+
+    assert encode_many([]) == []
+    assert encode_many([], framed=True) == b""
+
+    class Other(Struct):
+        uid: Annotated[int, 0]
+
+    with pytest.raises(TypeError, match="at index 1"):
+        encode_many([User(1, "a"), Other(1)])
+
+
+def test_decode_many_reads_framed_and_wrapped_records() -> None:
+    """decode_many 还原 encode_many 的分帧输出, 截断记录报错带记录序号."""
+    users = [User(1, "a"), User(2, "bb"), User(3, "")]
+    framed = encode_many(users, framed=True)
+    assert decode_many(User, framed) == users
+    assert decode_many(User, b"") == []
+    le_framed = encode_many(users, framed=True, little_endian=True)
+    assert decode_many(User, le_framed, little_endian=True) == users
+
+    wrapped = b"".join(b"\x0a" + encode(u) + b"\x0b" for u in users)
+    assert decode_many(User, wrapped, framed=False) == users
+
+    with pytest.raises(ValueError, match="^record 2: Truncated record"):
+        decode_many(User, framed[:-1])
+    with pytest.raises(ValueError, match="^record 2: Unterminated struct"):
+        decode_many(User, wrapped[:-1], framed=False)
+
+
+def test_decode_many_prefixes_validation_errors_with_record_index() -> None:
+    """decode_many 中后续记录的约束校验失败同样带记录序号, 并保留 ValidationError 类型."""
+
+    class Bounded(Struct):
+        v: Annotated[int, 0, Meta(ge=2)]
+
+    framed = encode_many([Bounded(2), Bounded(3)], framed=True)
+    bad = framed.replace(b"\x00\x03", b"\x00\x01")
+    with pytest.raises(ValidationError, match="^record 1: .*>= 2") as info:
+        decode_many(Bounded, bad)
+    assert isinstance(info.value.__cause__, ValidationError)
+
+
 def test_reentrant_encode_error_message_mentions_common_triggers() -> None:
     """递归编码错误应提示常见原因."""
     # Construct self-referencing dict
@@ -1127,3 +1868,488 @@ def test_invariant_trailing_bytes_raises_value_error() -> None:
     data = bytes.fromhex("0001FF")
     with pytest.raises(ValueError, match="Trailing bytes after decode"):
         decode(S, data)
+
+
+def test_trailing_bytes_ignored_by_config_or_allow_trailing() -> None:
+    """forbid_extra_bytes=False 或 allow_trailing=True 时忽略尾随字节."""
+
+    class Strict(Struct):
+        a: Annotated[int, 0]
+
+    class Lenient(Struct, forbid_extra_bytes=False):
+        a: Annotated[int, 0]
+
+    data = bytes.fromhex("00010B0000")
+    assert Strict.__struct_config__.forbid_extra_bytes is True
+    assert Lenient.__struct_config__.forbid_extra_bytes is False
+    assert decode(Lenient, data) == Lenient(1)
+    assert decode(Strict, data, allow_trailing=True) == Strict(1)
+    assert Strict.decode(data, allow_trailing=True) == Strict(1)
+    assert public_decode(data, allow_trailing=True) == {0: 1}
+    with pytest.raises(ValueError, match="Trailing bytes after decode"):
+        decode(Strict, data)
+
+
+def test_decode_error_reports_path_and_byte_offset() -> None:
+    """解码失败的消息同时包含字段路径与出错时的字节偏移."""
+
+    class Item(Struct):
+        v: Annotated[int, 0]
+
+    class Box(Struct):
+        a: Annotated[int, 0]
+        items: Annotated[list[Item], 1]
+
+    data = encode(Box(1, [Item(1), Item(2)]))
+    bad = data[:-3] + b"\x06" + data[-2:]
+    with pytest.raises(
+        ValidationError, match=r"^Error at <root>\.items\[1\]\.v: .* at byte offset 11$"
+    ):
+        decode(Box, bad)
+    with pytest.raises(ValidationError, match=r"at byte offset 3$"):
+        decode_raw(b"\x00\x01\x12\x00")
+
+
+def test_decode_concatenated_splits_wrapped_structs() -> None:
+    """decode_concatenated 按 StructBegin/StructEnd 拆分拼接的 Struct."""
+    users = [User(uid=1, name="a"), User(uid=2, name="bb"), User(uid=0, name="")]
+    data = b"".join(b"\x0a" + encode(u) + b"\x0b" for u in users)
+    assert list(decode_concatenated(User, data)) == users
+    assert list(decode_concatenated(User, b"")) == []
+
+    dicts = list(decode_concatenated(TarsDict, data))
+    assert dicts[1] == TarsDict({0: 2, 1: "bb"})
+
+    bare = encode(users[0]) + encode(users[1])
+    with pytest.raises(ValueError, match="Cannot split concatenated structs"):
+        list(decode_concatenated(User, bare))
+    with pytest.raises(ValueError, match="Unterminated struct at offset"):
+        list(decode_concatenated(User, data[:-1]))
+
+
+def test_decode_concatenated_close_and_context_manager() -> None:
+    """decode_concatenated 的迭代器支持 with 与 close, 关闭后使用抛出 ValueError."""
+    users = [User(uid=1, name="a"), User(uid=2, name="bb")]
+    data = b"".join(b"\x0a" + encode(u) + b"\x0b" for u in users)
+
+    with decode_concatenated(User, data) as it:
+        assert next(it) == users[0]
+        assert not it.closed
+    assert it.closed
+    with pytest.raises(ValueError, match="closed ConcatenatedDecoder"):
+        next(it)
+    with pytest.raises(ValueError, match="closed ConcatenatedDecoder"):
+        iter(it)
+    with pytest.raises(ValueError, match="closed ConcatenatedDecoder"):
+        with it:
+            pass
+
+    it = decode_concatenated(User, data)
+    it.close()
+    it.close()
+    with pytest.raises(ValueError, match="closed ConcatenatedDecoder"):
+        list(it)
+
+
+def test_decode_stream_reads_one_record_per_call() -> None:
+    """decode_stream 每次只读取一条记录, 兼容短读, 字段中途 EOF 抛出 ValidationError."""
+
+    class Trickle(io.RawIOBase):
+        def __init__(self, data: bytes) -> None:
+            self.data = data
+            self.pos = 0
+
+        def read(self, size: int = -1) -> bytes:
+            chunk = self.data[self.pos : self.pos + min(size, 2)]
+            self.pos += len(chunk)
+            return chunk
+
+    users = [User(uid=1, name="a" * 300), User(uid=2, name="bb")]
+    data = b"".join(b"\x0a" + encode(u) + b"\x0b" for u in users)
+    stream = Trickle(data + b"next")
+    assert decode_stream(User, stream) == users[0]
+    assert decode_stream(User, stream) == users[1]
+    assert stream.read(4) == b"ne"
+
+    buf = io.BytesIO(data)
+    assert decode_stream(TarsDict, buf) == TarsDict({0: 1, 1: "a" * 300})
+    assert decode_stream(User, buf) == users[1]
+    assert decode_stream(User, buf) is None
+
+    with pytest.raises(ValidationError, match="Unexpected end of buffer"):
+        decode_stream(User, io.BytesIO(data[:5]))
+    with pytest.raises(ValueError, match="expected StructBegin"):
+        decode_stream(User, io.BytesIO(encode(users[1])))
+
+
+def test_decode_stream_bounds_reads_for_hostile_lengths() -> None:
+    """decode_stream 按块读取声明的超大长度, 拒绝越界长度并支持 max_bytes."""
+
+    class Recording(io.BytesIO):
+        def __init__(self, data: bytes) -> None:
+            super().__init__(data)
+            self.requests: list[int] = []
+
+        def read(self, size: int | None = -1) -> bytes:
+            self.requests.append(-1 if size is None else size)
+            return super().read(size)
+
+    # String4 声明 0xFFFFFFF0 字节, 实际只有 8 字节.
+    hostile = Recording(b"\x0a\x07\xff\xff\xff\xf0ab")
+    with pytest.raises(ValidationError, match="Unexpected end of buffer"):
+        decode_stream(TarsDict, hostile)
+    assert max(hostile.requests) <= 64 * 1024
+
+    # List 长度用 Int8 写出 2**40, 不能被截断成合法的 i32.
+    overflow = b"\x0a\x09\x03" + (2**40).to_bytes(8, "big") + b"\x0b"
+    with pytest.raises(ValueError, match="Invalid size"):
+        decode_stream(TarsDict, io.BytesIO(overflow))
+
+    data = b"\x0a" + encode(User(uid=1, name="a" * 300)) + b"\x0b"
+    with pytest.raises(ValidationError, match="exceeds 64 bytes"):
+        decode_stream(User, io.BytesIO(data), max_bytes=64)
+    assert decode_stream(User, io.BytesIO(data), max_bytes=len(data)) == User(
+        uid=1, name="a" * 300
+    )
+
+
+def test_decode_float_as_decimal_returns_decimal() -> None:
+    """float_as_decimal=True 时 float/double 字段 (含嵌套) 解码为 Decimal."""
+
+    class Line(Struct):
+        price: Annotated[float, 0]
+
+    class Order(Struct):
+        amount: Annotated[float, 0]
+        lines: Annotated[list[Line], 1]
+        qty: Annotated[int, 2] = 0
+
+    data = encode(Order(0.1, [Line(2.675)], 3))
+    order = public_decode(data, Order, float_as_decimal=True)
+    assert order.amount == Decimal("0.1")
+    assert isinstance(order.amount, Decimal)
+    assert order.lines[0].price == Decimal("2.675")
+    assert order.qty == 3
+
+    assert Order.decode(data, float_as_decimal=True).amount == Decimal("0.1")
+    assert type(decode(Order, data).amount) is float
+
+
+def test_decode_float_as_decimal_covers_any_and_tars_dict() -> None:
+    """float_as_decimal 同样作用于 Any 字段、TarsDict 字段与 TarsDict 顶层解码."""
+
+    class Loose(Struct):
+        extra: Annotated[Any, 0]
+        raw: Annotated[TarsDict, 1]
+
+    data = encode(Loose([0.1, {"k": 2.5}], TarsDict({0: 0.2})))
+    loose = public_decode(data, Loose, float_as_decimal=True)
+    assert loose.extra == [Decimal("0.1"), {"k": Decimal("2.5")}]
+    assert loose.raw == TarsDict({0: Decimal("0.2")})
+
+    top = decode(TarsDict, encode_raw(TarsDict({0: 0.1})), float_as_decimal=True)
+    assert top == TarsDict({0: Decimal("0.1")})
+    assert isinstance(top[0], Decimal)
+    assert type(decode(TarsDict, encode_raw(TarsDict({0: 0.1})))[0]) is float
+
+
+def test_bytes_builtin_matches_encode() -> None:
+    """bytes(obj) 返回与 encode() 相同的 Tars 编码."""
+
+    class Point(Struct):
+        x: Annotated[int, 0]
+        y: Annotated[int, 1]
+
+    p = Point(1, 300)
+    assert bytes(p) == p.encode()
+    assert Point.decode(bytes(p)) == p
+
+
+def test_little_endian_round_trip_reverses_payload_bytes() -> None:
+    """little_endian=True 时多字节数值按小端读写, 按大端解码会得到错位的值."""
+
+    class Inner(Struct):
+        ratio: Annotated[float, 0]
+
+    class Packet(Struct):
+        seq: Annotated[int, 0]
+        inner: Annotated[Inner, 1]
+        items: Annotated[list[int], 2]
+
+    obj = Packet(0x1234, Inner(1.5), [70000])
+    data = public_encode(obj, little_endian=True)
+    assert data[:3] == b"\x01\x34\x12"
+    assert public_encode(obj)[:3] == b"\x01\x12\x34"
+    assert public_decode(data, Packet, little_endian=True) == obj
+    assert decode(Packet, data, little_endian=True) == obj
+
+    raw = public_encode(TarsDict({0: 0x1234}), little_endian=True)
+    assert raw == b"\x01\x34\x12"
+    assert public_decode(raw, little_endian=True) == TarsDict({0: 0x1234})
+
+    assert public_decode(data, Packet).seq == 0x3412
+
+
+def test_decode_widen_float_removes_f32_artifacts() -> None:
+    """widen_float=True 时线上的 f32 按最短十进制表示转换, 1.1f32 得到 1.1."""
+
+    class Reading(Struct):
+        value: Annotated[float, 0]
+        values: Annotated[list[float], 1] = []
+
+    # Float (type 4) payload 0x3F8CCCCD 即 1.1f32.
+    data = b"\x04\x3f\x8c\xcc\xcd" + b"\x19\x00\x01\x04\x3f\x8c\xcc\xcd"
+    assert decode(Reading, data).value == 1.100000023841858
+    widened = public_decode(data, Reading, widen_float=True)
+    assert widened.value == 1.1
+    assert widened.values == [1.1]
+    assert Reading.decode(data, widen_float=True).value == 1.1
+
+
+def test_decode_mode_applies_to_tars_dict_and_resets_for_nested_entries() -> None:
+    """解码选项作用于 TarsDict 顶层解码; 钩子中的其他解码入口恢复默认模式."""
+    data = b"\x04\x3f\x8c\xcc\xcd"
+    assert decode(TarsDict, data, widen_float=True)[0] == 1.1
+    assert decode(TarsDict, data)[0] == 1.100000023841858
+
+    inner = encode_raw(TarsDict({0: 0.1}))
+    seen: list[object] = []
+
+    def peek(value: float) -> float:
+        seen.append(decode_raw(inner)[0])
+        seen.append(decode_value(float, inner))
+        return value
+
+    class Price(Struct):
+        amount: Annotated[float, 0, Meta(decoder=peek)]
+
+    assert decode(Price, encode(Price(0.5)), float_as_decimal=True).amount == Decimal(
+        "0.5"
+    )
+    assert [type(v) for v in seen] == [float, float]
+
+
+def test_decode_strict_rejects_float_for_double_field() -> None:
+    """strict=True 时 double 字段 (含容器元素) 拒绝单精度 Float, ZeroTag 仍可读取."""
+
+    class Reading(Struct):
+        value: Annotated[float, 0]
+        values: Annotated[list[float], 1] = []
+
+    data = b"\x04\x3f\x8c\xcc\xcd"
+    assert decode(Reading, data).value == 1.100000023841858
+    with pytest.raises(ValueError, match="expected Double for double field, got Float"):
+        decode(Reading, data, strict=True)
+    with pytest.raises(ValueError, match="expected Double for double field, got Float"):
+        Reading.decode(data, strict=True)
+
+    nested = b"\x0c\x19\x00\x01\x04\x3f\x8c\xcc\xcd"
+    assert decode(Reading, nested).values == [1.100000023841858]
+    with pytest.raises(ValueError, match="got Float"):
+        public_decode(nested, Reading, strict=True)
+
+    exact = encode(Reading(1.5, [0.0, 2.5]))
+    assert decode(Reading, exact, strict=True) == Reading(1.5, [0.0, 2.5])
+
+
+def test_decode_into_overwrites_existing_instance() -> None:
+    """decode_into 就地覆盖字段并补默认值, frozen 实例拒绝写入."""
+
+    class Pooled(Struct):
+        uid: int = field(tag=0)
+        tags: list[str] = field(tag=1, default_factory=list)
+
+        def __post_init__(self) -> None:
+            self.uid += 1000
+
+    obj = Pooled(1, ["a"])
+    obj.decode_into(encode_raw(TarsDict({0: 7})))
+    assert (obj.uid, obj.tags) == (1007, [])
+
+    with pytest.raises(ValueError, match="Trailing bytes"):
+        obj.decode_into(encode_raw(TarsDict({0: 7})) + b"\xff")
+
+    frozen = User.decode(encode(User(uid=1, name="a")), freeze=True)
+    with pytest.raises(AttributeError, match="frozen"):
+        frozen.decode_into(encode(User(uid=2, name="b")))
+    assert frozen.uid == 1
+
+
+def test_field_alias_tags_accepts_legacy_tag_on_decode() -> None:
+    """alias_tags 解码时接受旧 Tag, 编码始终写主 Tag."""
+
+    class Renamed(Struct):
+        uid: int = field(tag=0)
+        name: str = field(tag=5, alias_tags=[2], default="")
+
+    assert Renamed.decode(encode_raw(TarsDict({0: 1, 5: "new"}))) == Renamed(1, "new")
+    assert Renamed.decode(encode_raw(TarsDict({0: 1, 2: "old"}))) == Renamed(1, "old")
+    assert decode_raw(encode(Renamed(1, "x"))) == TarsDict({0: 1, 5: "x"})
+
+    with pytest.raises(TypeError, match="alias tag 0 collides with field 'uid'"):
+
+        class Clash(Struct):
+            uid: int = field(tag=0)
+            name: str = field(tag=5, alias_tags=[0])
+
+    with pytest.raises(TypeError, match="alias_tags"):
+        field(tag=1, alias_tags=[256])
+
+
+def test_decode_max_bytes_rejects_oversized_payload() -> None:
+    """max_bytes 在解析前按输入总长拦截, 恰好等于上限时放行."""
+    user = User(uid=1, name="abc")
+    data = encode(user)
+    size = len(data)
+
+    assert public_decode(data, User, max_bytes=size) == user
+    assert User.decode(data, max_bytes=size) == user
+    assert public_decode(data, max_bytes=size) == TarsDict({0: 1, 1: "abc"})
+
+    with pytest.raises(ValidationError, match=f"payload exceeds {size - 1} bytes"):
+        public_decode(data, User, max_bytes=size - 1)
+    with pytest.raises(ValidationError, match="payload exceeds"):
+        User.decode(data, max_bytes=size - 1)
+    with pytest.raises(ValidationError, match="payload exceeds"):
+        public_decode(data, max_bytes=size - 1)
+    # 上限先于格式校验生效.
+    with pytest.raises(ValidationError, match="payload exceeds"):
+        public_decode(b"\xff" * 8, User, max_bytes=4)
+
+
+def test_decode_value_reads_wrapped_return_value() -> None:
+    """decode_value 按类型标注解码指定 Tag 的单个返回值."""
+    assert decode_value(int, encode_raw(TarsDict({0: 42}))) == 42
+    assert decode_value(str, encode_raw(TarsDict({0: 7, 1: "ok"})), tag=1) == "ok"
+
+    user = User(uid=1, name="a")
+    data = encode_raw(TarsDict({0: -1, 2: user}))
+    assert decode_value(User, data, tag=2) == user
+
+    assert decode_value(Optional[int], data, tag=5) is None
+    with pytest.raises(ValueError, match="Tag 5 not found"):
+        decode_value(int, data, tag=5)
+    with pytest.raises(ValidationError):
+        decode_value(Annotated[int, Meta(ge=0)], data)
+
+
+def test_meta_encoder_decoder_hooks_transform_field_values() -> None:
+    """Meta 编解码钩子转换字段值, 钩子异常包装为 ValueError."""
+
+    class Packet(Struct):
+        digest: Annotated[
+            bytes, 0, Meta(encoder=bytes.fromhex, decoder=lambda b: b.hex())
+        ]
+
+    data = Packet("beef").encode()
+    assert decode_raw(data) == TarsDict({0: b"\xbe\xef"})
+    assert Packet.decode(data).digest == "beef"
+
+    with pytest.raises(ValueError, match="encoder failed") as exc_info:
+        Packet("zz").encode()
+    assert isinstance(exc_info.value.__cause__, ValueError)
+
+    def reject(value: int) -> int:
+        raise ValueError("bad")
+
+    class Strict(Struct):
+        v: Annotated[int, 0, Meta(decoder=reject)]
+
+    with pytest.raises(ValidationError, match="decoder failed"):
+        Strict.decode(Strict(1).encode())
+
+    with pytest.raises(TypeError, match="must be callable"):
+        Meta(encoder=3)  # type: ignore[arg-type]
+
+
+def test_encode_decode_context_reaches_hooks_and_post_init() -> None:
+    """context 以关键字参数传给字段钩子与解码时的 __post_init__."""
+    seen: list[Any] = []
+
+    def scale(value: int, *, context: dict[str, int]) -> int:
+        return value * context["factor"]
+
+    class Price(Struct):
+        cents: Annotated[int, 0, Meta(encoder=scale, decoder=scale)]
+
+        def __post_init__(self, *, context: Any = None) -> None:
+            seen.append(context)
+
+    ctx = {"factor": 10}
+    data = public_encode(Price(5), context=ctx)
+    assert decode_raw(data) == TarsDict({0: 50})
+    assert public_decode(data, Price, context=ctx).cents == 500
+    assert Price.decode(data, context=ctx).cents == 500
+    assert decode(Price, data, context=ctx).cents == 500
+
+    buffer = bytearray()
+    encode_into(Price(1), buffer, context=ctx)
+    assert decode_raw(bytes(buffer)) == TarsDict({0: 10})
+
+    assert seen == [None, ctx, ctx, ctx, None]
+
+    with pytest.raises(ValueError, match="encoder failed"):
+        encode(Price(5))
+
+
+def test_replace_method_reruns_post_init_on_frozen_struct() -> None:
+    """replace 在 frozen 实例上生成新实例并重新调用 __post_init__."""
+    calls: list[int] = []
+
+    class Point(Struct, frozen=True):
+        x: Annotated[int, 0]
+        y: Annotated[int, 1] = 0
+
+        def __post_init__(self) -> None:
+            calls.append(self.x)
+
+    p = Point(1, 2)
+    moved = p.replace(x=5)
+    assert calls == [1, 5]
+    assert (moved.x, moved.y) == (5, 2)
+    assert (p.x, p.y) == (1, 2)
+
+    with pytest.raises(TypeError, match="unexpected keyword argument"):
+        p.replace(z=1)  # pyright: ignore[reportCallIssue]
+
+
+def test_max_depth_overrides_default_recursion_limit() -> None:
+    """max_depth 可调低或调高编解码的嵌套深度上限, 默认行为不变."""
+
+    def chain(length: int) -> Node:
+        head = Node(0)
+        for i in range(1, length):
+            head = Node(i, head)
+        return head
+
+    shallow = chain(5)
+    with pytest.raises(ValueError, match=r"Recursion depth exceeded \(max=3"):
+        public_encode(shallow, max_depth=3)
+    data = encode(shallow)
+    with pytest.raises(ValueError, match="Recursion depth exceeded"):
+        decode(Node, data, max_depth=3)
+    with pytest.raises(ValueError, match="Recursion depth exceeded"):
+        Node.decode(data, max_depth=3)
+    assert public_decode(data, Node, max_depth=100) == shallow
+
+    deep = chain(40)
+    with pytest.raises(ValueError, match="Recursion depth exceeded"):
+        encode(deep)
+    deep_data = encode(deep, max_depth=200)
+    with pytest.raises(ValueError, match="Recursion depth exceeded"):
+        decode(Node, deep_data)
+    assert decode(Node, deep_data, max_depth=200) == deep
+
+    with pytest.raises(ValueError, match="max_depth must be a positive integer"):
+        encode(shallow, max_depth=0)
+
+
+def test_max_depth_rejects_values_above_ceiling() -> None:
+    """过大的 max_depth 被拒绝, 深层嵌套输入不会耗尽栈空间."""
+    nested = b"\x0a" * 200000 + b"\x0b" * 200000
+    with pytest.raises(ValueError, match="max_depth must not exceed 256"):
+        decode(TarsDict, nested, max_depth=10**7)
+    with pytest.raises(ValueError, match="max_depth must not exceed 256"):
+        encode(Node(0), max_depth=257)
+    with pytest.raises(ValueError, match="Recursion depth exceeded"):
+        decode(TarsDict, nested, max_depth=256)
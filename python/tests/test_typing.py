@@ -4,6 +4,7 @@
 """
 
 import sys
+import types
 from collections.abc import (
     Collection,
     Mapping,
@@ -14,7 +15,9 @@ from collections.abc import (
     Set,
 )
 from dataclasses import dataclass
-from enum import Enum, IntEnum
+from enum import Enum, Flag, IntEnum, IntFlag
+from ipaddress import IPv4Address, IPv6Address
+from pathlib import Path, PurePosixPath, PureWindowsPath
 from typing import (
     Annotated,
     Any,
@@ -30,7 +33,18 @@ from typing import (
 )
 
 import pytest
-from tarsio._core import Meta, Struct, ValidationError, decode, encode, inspect
+from tarsio._core import (
+    Meta,
+    Struct,
+    TarsDict,
+    ValidationError,
+    compile_module,
+    decode,
+    decode_raw,
+    encode,
+    encode_raw,
+    inspect,
+)
 from typing_extensions import (
     NamedTuple,
     NotRequired,
@@ -67,6 +81,119 @@ def test_primitives_roundtrip() -> None:
     assert decoded.by == b"\x01\x02"
 
 
+def test_int_subclass_field_decodes_to_subclass() -> None:
+    """验证 int 子类字段按 int 编码并解码为子类实例."""
+
+    class Port(int):
+        pass
+
+    class Endpoint(Struct):
+        port: Annotated[Port, 0]
+        backups: Annotated[list[Port], 1]
+
+    data = encode(Endpoint(Port(8080), [Port(80)]))
+    assert data == bytes.fromhex("011f901900010050")
+
+    decoded = decode(Endpoint, data)
+    assert decoded.port == 8080
+    assert type(decoded.port) is Port
+    assert type(decoded.backups[0]) is Port
+    assert inspect.type_info(Port).kind == "subclass"
+
+
+def test_bytearray_field_decodes_to_mutable_buffer() -> None:
+    """验证 bytearray 字段线上同 bytes, 解码得到可就地修改的 bytearray."""
+
+    class Blob(Struct):
+        data: Annotated[bytearray, 0]
+        chunks: Annotated[list[bytearray], 1] = []
+
+    obj = Blob(bytearray(b"ab"), [bytearray(b"c")])
+    data = encode(obj)
+    assert data == encode(Blob(b"ab", [b"c"]))  # type: ignore[arg-type]
+
+    decoded = decode(Blob, data)
+    assert type(decoded.data) is bytearray
+    assert type(decoded.chunks[0]) is bytearray
+    decoded.data[0] = ord("A")
+    assert decode(Blob, encode(decoded)).data == bytearray(b"Ab")
+
+    info = inspect.type_info(bytearray)
+    assert isinstance(info, inspect.BytesType)
+    assert info.mutable is True
+    assert inspect.type_info(bytes).mutable is False
+
+
+def test_complex_field_roundtrips_as_two_doubles() -> None:
+    """complex 编码为实部/虚部两个 double 的 Struct, 解码精确还原."""
+
+    class Signal(Struct):
+        z: Annotated[complex, 0]
+        samples: Annotated[list[complex], 1] = []
+        scale: Annotated[Optional[complex], 2] = None
+
+    obj = Signal(complex(1.5, -2.25), [1j], cast(Any, 3))
+    data = encode(obj)
+    assert decode_raw(data)[0] == TarsDict({0: 1.5, 1: -2.25})
+
+    decoded = decode(Signal, data)
+    assert decoded.z == complex(1.5, -2.25)
+    assert decoded.samples == [1j]
+    assert type(decoded.scale) is complex
+    assert decoded.scale == 3
+
+    assert inspect.type_info(complex).kind == "complex"
+    with pytest.raises(ValidationError, match="type mismatch"):
+        Signal(cast(Any, True))
+
+
+def test_ip_address_roundtrip_as_packed_bytes() -> None:
+    """验证 IP 地址按 packed 字节编码并按长度还原."""
+
+    class Host(Struct):
+        v4: Annotated[IPv4Address, 0]
+        v6: Annotated[IPv6Address, 1]
+        either: Annotated[IPv4Address | IPv6Address, 2]
+        raw: Annotated[Any, 3]
+
+    obj = Host(
+        IPv4Address("10.0.0.1"),
+        IPv6Address("::1"),
+        IPv6Address("fe80::1"),
+        IPv4Address("1.2.3.4"),
+    )
+    decoded = decode(Host, encode(obj))
+
+    assert decoded.v4 == IPv4Address("10.0.0.1")
+    assert decoded.v6 == IPv6Address("::1")
+    assert decoded.either == IPv6Address("fe80::1")
+    assert decoded.raw == b"\x01\x02\x03\x04"
+    assert inspect.type_info(IPv6Address).kind == "ipaddress"
+
+    with pytest.raises(ValidationError, match="4 or 16 bytes"):
+        decode(Host, bytes.fromhex("0d0000030102030b"))
+
+
+def test_path_roundtrip_as_string() -> None:
+    """验证 Path 按字符串编码并还原为声明的路径类."""
+
+    class Files(Struct):
+        local: Annotated[Path, 0]
+        remote: Annotated[PureWindowsPath, 1]
+        raw: Annotated[Any, 2]
+
+    obj = Files(Path("/tmp/a"), PureWindowsPath("C:/data"), PurePosixPath("/etc"))
+    data = encode(obj)
+    decoded = decode(Files, data)
+
+    assert decoded.local == Path("/tmp/a")
+    assert type(decoded.local) is type(Path("/tmp/a"))
+    assert type(decoded.remote) is PureWindowsPath
+    assert decoded.raw == "/etc"
+    assert decode_raw(data)[1] == "C:\\data"
+    assert inspect.type_info(Path).kind == "path"
+
+
 # ==========================================
 # 2. Containers (容器类型)
 # ==========================================
@@ -96,6 +223,23 @@ def test_tuple_fixed_roundtrip() -> None:
     assert decoded.t == (1, "a", 1.1)
 
 
+@pytest.mark.parametrize("wire", [[1, 2], [1, 2, 3, 4]])
+def test_tuple_length_mismatch_reports_counts(wire: list[int]) -> None:
+    """定长 tuple 元素个数不符时, 编解码错误包含字段路径与期望/实际个数."""
+    class Point(Struct):
+        coords: Annotated[tuple[int, int, int], 0]
+
+    msg = f"coords: expected 3-tuple, got {len(wire)} elements on wire"
+    with pytest.raises(ValidationError, match=msg):
+        decode(Point, encode_raw(TarsDict({0: wire})))
+
+    obj = Point((1, 2, 3))
+    obj.coords = tuple(wire)  # pyright: ignore[reportAttributeAccessIssue]
+    msg = f"Field 'coords': expected 3-tuple, got {len(wire)} elements"
+    with pytest.raises(TypeError, match=msg):
+        encode(obj)
+
+
 def test_tuple_variable_roundtrip() -> None:
     """验证变长 tuple[T, ...] 的编解码."""
 
@@ -120,6 +264,28 @@ def test_set_roundtrip() -> None:
     assert decoded.fs == frozenset({3, 4})
 
 
+def test_frozenset_field_decodes_to_frozenset() -> None:
+    """frozenset[T] 字段解码为 frozenset, 冻结结构体保持可哈希."""
+
+    class Tags(Struct):
+        ids: Annotated[frozenset[int], 0]
+        names: Annotated[set[str], 1]
+
+    decoded = decode(Tags, encode(Tags(frozenset({1, 2}), {"a"})))
+    assert type(decoded.ids) is frozenset
+    assert type(decoded.names) is set
+
+    class Frozen(Struct, frozen=True):
+        ids: Annotated[frozenset[int], 0]
+
+    restored = decode(Frozen, encode(Frozen(frozenset({3}))))
+    assert hash(restored) == hash(Frozen(frozenset({3})))
+
+    set_info = cast(inspect.SetType, inspect.type_info(frozenset[int]))
+    assert set_info.frozen is True
+    assert cast(inspect.SetType, inspect.type_info(set[int])).frozen is False
+
+
 def test_dict_roundtrip() -> None:
     """验证 dict[K, V] 的编解码."""
 
@@ -133,6 +299,146 @@ def test_dict_roundtrip() -> None:
     assert decoded.d2 == {2: "b"}
 
 
+def test_map_tuple_keys_roundtrip() -> None:
+    """验证 tuple 作为 Map key 时解码为可哈希的 tuple."""
+
+    class TupleKeyStruct(Struct):
+        fixed: dict[tuple[int, int], str]
+        var: dict[tuple[int, ...], str]
+        loose: dict[Any, str]
+
+    obj = TupleKeyStruct(
+        {(1, 2): "a", (3, 4): "b"},
+        {(5,): "c", (6, 7, 8): "d"},
+        {(1, (2, 3)): "e"},
+    )
+    restored = decode(TupleKeyStruct, encode(obj))
+    assert restored == obj
+    assert all(type(k) is tuple for k in restored.fixed)
+
+
+def test_dict_subclass_roundtrip_keeps_type() -> None:
+    """验证 dict 子类字段解码后保留声明的子类, 包括自定义 __init__."""
+
+    class LowerDict(dict[str, int]):
+        def __init__(self, items: Any = ()) -> None:
+            super().__init__({k.lower(): v for k, v in dict(items).items()})
+
+    class Registry(dict):
+        def __init__(self) -> None:
+            super().__init__()
+            self.loaded = True
+
+    class Config(Struct):
+        headers: Annotated[LowerDict, 0]
+        extra: Annotated[Registry[str, list[int]], 1]
+
+    extra = Registry()
+    extra["a"] = [1, 2]
+    obj = Config(LowerDict({"Content-Type": 1}), extra)
+    restored = decode(Config, encode(obj))
+    assert type(restored.headers) is LowerDict
+    assert restored.headers == {"content-type": 1}
+    assert type(restored.extra) is Registry
+    assert restored.extra == {"a": [1, 2]}
+    assert restored.extra.loaded is True
+
+    info = inspect.type_info(LowerDict)
+    assert isinstance(info, inspect.MapType)
+    assert info.cls is LowerDict
+    assert isinstance(info.key_type, inspect.StrType)
+    assert inspect.type_info(dict[str, int]).cls is None
+
+
+def test_ordered_dict_field_decodes_to_ordered_dict() -> None:
+    """验证 OrderedDict[K, V] 字段解码为 OrderedDict 并保持线上顺序."""
+    from collections import OrderedDict
+
+    class Headers(Struct):
+        items: Annotated[OrderedDict[str, int], 0]
+
+    obj = Headers(OrderedDict([("b", 1), ("a", 2)]))
+    restored = decode(Headers, encode(obj))
+    assert type(restored.items) is OrderedDict
+    assert list(restored.items.items()) == [("b", 1), ("a", 2)]
+
+    info = inspect.type_info(OrderedDict[str, int])
+    assert isinstance(info, inspect.MapType)
+    assert info.cls is OrderedDict
+
+
+def test_defaultdict_enum_keys_roundtrip() -> None:
+    """验证 defaultdict[Enum, V] 解码还原 default_factory 与枚举成员 key."""
+    from collections import defaultdict
+
+    class Color(IntEnum):
+        RED = 1
+        BLUE = 2
+
+    class Palette(Struct):
+        groups: Annotated[defaultdict[Color, list[int]], 0]
+        counts: Annotated[defaultdict[str, int], 1]
+
+    obj = Palette(
+        defaultdict(list, {Color.RED: [1, 2], Color.BLUE: []}),
+        defaultdict(int, {"a": 3}),
+    )
+    restored = decode(Palette, encode(obj))
+    assert restored == obj
+    assert type(restored.groups) is defaultdict
+    assert restored.groups.default_factory is list
+    assert all(type(k) is Color for k in restored.groups)
+    assert restored.groups[Color.RED] == [1, 2]
+    assert restored.counts.default_factory is int
+    assert restored.counts["missing"] == 0
+
+    info = inspect.type_info(defaultdict[Color, list[int]])
+    assert isinstance(info, inspect.MapType)
+    assert info.default_factory is list
+    assert inspect.type_info(dict[str, int]).default_factory is None
+
+    with pytest.raises(TypeError, match="Cannot derive defaultdict default_factory"):
+
+        class Bad(Struct):
+            m: Annotated[defaultdict[str, Any], 0]
+
+
+def test_map_accepts_non_dict_mapping() -> None:
+    """验证 Map 字段接受 MappingProxyType、ChainMap 与自定义 Mapping."""
+    from collections import ChainMap
+    from collections.abc import Iterator
+    from types import MappingProxyType
+
+    class Pairs(Mapping[str, int]):
+        def __init__(self, *pairs: tuple[str, int]) -> None:
+            self._pairs = pairs
+
+        def __getitem__(self, key: str) -> int:
+            for k, v in self._pairs:
+                if k == key:
+                    return v
+            raise KeyError(key)
+
+        def __iter__(self) -> Iterator[str]:
+            return (k for k, _ in self._pairs)
+
+        def __len__(self) -> int:
+            return len(self._pairs)
+
+    class DictStruct(Struct):
+        d: dict[str, int]
+
+    expected = encode(DictStruct({"a": 1, "b": 2}))
+    for value in (
+        MappingProxyType({"a": 1, "b": 2}),
+        ChainMap({"a": 1}, {"b": 2, "a": 9}),
+        Pairs(("a", 1), ("b", 2)),
+    ):
+        obj = DictStruct(cast(Any, value))
+        assert decode(DictStruct, encode(obj)).d == {"a": 1, "b": 2}
+    assert encode(DictStruct(cast(Any, Pairs(("a", 1), ("b", 2))))) == expected
+
+
 # ==========================================
 # 3. Abstract Base Classes (抽象基类)
 # ==========================================
@@ -231,6 +537,198 @@ def test_namedtuple_support() -> None:
     assert decoded.p == Point(10, 20)
 
 
+def test_namedtuple_partial_annotations() -> None:
+    """验证部分注解的 NamedTuple 按 _fields 全量编解码, 未注解字段按 Any 处理."""
+    from collections import namedtuple
+
+    Base = namedtuple("Base", ["x", "y", "z"])
+
+    class Partial(Base):
+        __slots__ = ()
+        __annotations__ = {"x": int}
+
+    class Child(Partial):
+        __slots__ = ()
+
+    class Wrap(Struct):
+        p: Annotated[Partial, 0]
+        c: Annotated[Child, 1]
+
+    obj = Wrap(Partial(1, "a", b"b"), Child(2, "c", 3))
+    decoded = decode(Wrap, encode(obj))
+    assert decoded.p == Partial(1, "a", b"b")
+    assert type(decoded.p) is Partial
+    assert decoded.c == Child(2, "c", 3)
+
+    info = cast(Any, inspect.type_info(Child))
+    assert info.kind == "namedtuple"
+    assert [item.kind for item in info.items] == ["int", "any", "any"]
+
+
+@pytest.mark.parametrize(
+    ("typ", "zero", "nonzero"),
+    [(int, 0, 7), (float, 0.0, 1.5), (str, "", "x"), (bool, False, True)],
+    ids=["int", "float", "str", "bool"],
+)
+def test_optional_primitive_present_falsy_vs_absent(
+    typ: type, zero: Any, nonzero: Any
+) -> None:
+    """验证 Optional 基础类型中显式假值与缺失字段可区分."""
+
+    class WithDefault(Struct):
+        value: Annotated[Optional[typ], 0] = None  # noqa: UP045
+
+    class NoDefault(Struct):
+        value: Annotated[Optional[typ], 0]  # noqa: UP045
+
+    for cls in (WithDefault, NoDefault):
+        for value in (zero, nonzero):
+            decoded = decode(cls, encode(cls(value)))
+            assert decoded.value == value
+            assert decoded.value is not None
+            assert type(decoded.value) is typ
+        assert decode(cls, encode(cls(None))).value is None
+        assert decode(cls, b"").value is None
+
+
+def test_pydantic_model_roundtrip() -> None:
+    """验证 pydantic.BaseModel 子类 (含嵌套模型) 的编解码."""
+    pydantic = pytest.importorskip("pydantic")
+
+    class Item(pydantic.BaseModel):
+        sku: str
+        qty: int = 1
+
+    class Order(pydantic.BaseModel):
+        oid: Annotated[int, 0]
+        items: Annotated[list[Item], 1]
+        note: Annotated[Optional[str], 2] = None  # noqa: UP045
+        tags: Annotated[list[str], 3] = pydantic.Field(default_factory=list)
+
+    class Envelope(Struct):
+        order: Order
+
+    order = Order(oid=7, items=[Item(sku="a"), Item(sku="b", qty=3)], tags=["x"])
+    decoded = decode(Order, encode(order))
+    assert decoded == order
+    assert isinstance(decoded.items[0], Item)
+
+    minimal = decode(Order, encode(Order(oid=1, items=[])))
+    assert minimal.note is None
+    assert minimal.tags == []
+
+    env = decode(Envelope, encode(Envelope(order)))
+    assert env.order == order
+
+    from tarsio import decode as public_decode
+    from tarsio import encode as public_encode
+
+    assert public_decode(public_encode(order), Order) == order
+
+    info = inspect.struct_info(Order)
+    assert info is not None
+    assert [(f.name, f.tag) for f in info.fields] == [
+        ("oid", 0),
+        ("items", 1),
+        ("note", 2),
+        ("tags", 3),
+    ]
+
+
+def test_pydantic_field_alias_used_for_construction() -> None:
+    """pydantic 字段别名作为解码时的构造关键字, 无法直接传参的别名在编译时报错."""
+    pydantic = pytest.importorskip("pydantic")
+
+    class Aliased(pydantic.BaseModel):
+        user_id: Annotated[int, 0] = pydantic.Field(alias="userId")
+        nick: Annotated[str, 1] = pydantic.Field(
+            default="", validation_alias="nickName"
+        )
+
+    obj = Aliased(userId=1, nickName="n")
+    assert decode(Aliased, encode(obj)) == obj
+
+    class Chosen(pydantic.BaseModel):
+        value: Annotated[int, 0] = pydantic.Field(
+            validation_alias=pydantic.AliasChoices("v", "val")
+        )
+
+    with pytest.raises(TypeError, match="non-string validation_alias"):
+        encode(Chosen(v=1))
+
+    class ByName(pydantic.BaseModel):
+        model_config = pydantic.ConfigDict(populate_by_name=True)
+
+        value: Annotated[int, 0] = pydantic.Field(
+            validation_alias=pydantic.AliasChoices("v", "val")
+        )
+
+    assert decode(ByName, encode(ByName(v=2))) == ByName(v=2)
+
+
+def test_compile_module_reports_invalid_class_by_name() -> None:
+    """compile_module 预编译模块内定义的类, 出错时异常消息以类名开头."""
+    pytest.importorskip("pydantic")
+
+    module = types.ModuleType("schemas")
+    source = (
+        "from typing import Annotated\n"
+        "import pydantic\n"
+        "from tarsio import Struct\n"
+        "class Good(Struct):\n"
+        "    a: Annotated[int, 0]\n"
+        "class Bad(pydantic.BaseModel):\n"
+        "    a: Annotated[int, 0]\n"
+        "    b: Annotated[int, 0]\n"
+    )
+    exec(source, module.__dict__)  # noqa: S102
+
+    with pytest.raises(TypeError, match=r"^Bad: Duplicate tag 0"):
+        compile_module(module)
+
+    del module.Bad
+    assert compile_module(module) == [module.Good]
+
+
+def test_pydantic_field_constraints_checked_on_wire() -> None:
+    """pydantic Field 约束映射为线级约束, 且解码经 __init__ 运行 pydantic 校验."""
+    pydantic = pytest.importorskip("pydantic")
+
+    class Scored(pydantic.BaseModel):
+        score: int = pydantic.Field(gt=0)
+        name: str = pydantic.Field(default="n", min_length=1)
+
+    class Loose(Struct):
+        score: int
+        name: str = "n"
+
+    info = inspect.struct_info(Scored)
+    assert info is not None
+    score_type = cast(inspect.IntType, info.fields[0].type)
+    name_type = cast(inspect.StrType, info.fields[1].type)
+    assert score_type.gt == 0
+    assert name_type.min_length == 1
+
+    with pytest.raises(ValidationError, match="must be > 0"):
+        decode(Scored, encode(Loose(0)))
+    with pytest.raises(ValidationError, match="length must be >= 1"):
+        decode(Scored, encode(Loose(1, "")))
+    assert decode(Scored, encode(Loose(5))).score == 5
+
+    class Checked(pydantic.BaseModel):
+        score: int
+
+        @pydantic.field_validator("score")
+        @classmethod
+        def _even(cls, v: int) -> int:
+            if v % 2:
+                raise ValueError("score must be even")
+            return v
+
+    with pytest.raises(ValidationError, match="score must be even"):
+        decode(Checked, encode(Loose(3)))
+
+
 def test_typeddict_support() -> None:
     """验证 TypedDict 支持 (含 Required/NotRequired)."""
 
@@ -275,6 +773,44 @@ def test_typeddict_runtime_accepts_dataclass_like_map_value() -> None:
     assert decoded.payload == {"id": 1, "name": "dc"}
 
 
+def test_meta_alias_renames_python_side_keys_only() -> None:
+    """Meta(alias) 只改变 TypedDict 解码结果与 to_builtins 的键名, 线上编码不变."""
+
+    class User(TypedDict):
+        user_id: Annotated[int, Meta(alias="userId")]
+        name: NotRequired[Annotated[str, Meta(alias="displayName")]]
+
+    class Plain(TypedDict):
+        user_id: int
+        name: NotRequired[str]
+
+    class Event(Struct):
+        user: Annotated[User, 0]
+        created_at: Annotated[int, 1, Meta(alias="createdAt")]
+
+    class PlainEvent(Struct):
+        user: Annotated[Plain, 0]
+        created_at: Annotated[int, 1]
+
+    aliased: Any = {"userId": 1, "displayName": "a"}
+    data = encode(Event(aliased, 5))
+    assert data == encode(PlainEvent({"user_id": 1, "name": "a"}, 5))
+
+    restored = decode(Event, data)
+    assert restored.user == {"userId": 1, "displayName": "a"}
+    assert encode(Event({"user_id": 1, "name": "a"}, 5)) == data
+    assert inspect.to_builtins(restored) == {
+        "user": {"userId": 1, "displayName": "a"},
+        "createdAt": 5,
+    }
+
+    with pytest.raises(TypeError, match="collides with field 'a'"):
+
+        class Clash(Struct):
+            a: Annotated[int, 0]
+            b: Annotated[int, 1, Meta(alias="a")]
+
+
 # ==========================================
 # 5. Logic & Markers (逻辑与标记)
 # ==========================================
@@ -303,6 +839,78 @@ def test_optional_union() -> None:
     assert dec2.uni_opt is None
 
 
+def test_optional_none_in_containers_rejected() -> None:
+    """容器中 Optional 元素为 None 时编码报错, 非 None 元素正常往返."""
+
+    class Sparse(Struct):
+        scores: Annotated[dict[str, Optional[int]], 0]
+        items: Annotated[list[Optional[int]], 1]
+        pair: Annotated[tuple[int, Optional[str]], 2]
+
+    obj = Sparse({"a": 1, "b": 2}, [1, 2, 3], (1, "x"))
+    assert decode(Sparse, encode(obj)) == obj
+
+    for bad in (
+        Sparse({"a": None, "b": 2}, [], (1, "x")),
+        Sparse({}, [1, None, 3], (1, "x")),
+        Sparse({}, [], (1, None)),
+    ):
+        with pytest.raises(ValueError, match="no null marker"):
+            encode(bad)
+
+
+def test_union_of_structs_in_map_values_round_trip() -> None:
+    """Union 中的多个 Struct 变体按线上字段结构还原为对应类型."""
+
+    class Click(Struct):
+        x: Annotated[int, 0]
+        y: Annotated[int, 1] = 0
+
+    class Key(Struct):
+        code: Annotated[str, 0]
+
+    class Events(Struct):
+        items: Annotated[dict[str, Click | Key], 0]
+
+    obj = Events({"a": Click(1, 2), "b": Key("esc"), "c": Click(0)})
+    decoded = decode(Events, encode(obj))
+    assert decoded == obj
+    assert [type(v) for v in decoded.items.values()] == [Click, Key, Click]
+
+
+def test_union_bool_int_disambiguates_by_value() -> None:
+    """验证 Union[bool, int] 中 bool 与非 0/1 的 int 分别还原为各自类型."""
+
+    class Flag(Struct):
+        v: Annotated[Union[bool, int], 0]
+
+    flag = decode(Flag, encode(Flag(True)))
+    assert flag.v is True
+    num = decode(Flag, encode(Flag(5)))
+    assert num.v == 5
+    assert type(num.v) is int
+
+    wide = decode(Flag, encode(Flag(256), little_endian=True), little_endian=True)
+    assert wide.v == 256
+    assert type(wide.v) is int
+
+
+def test_union_none_error_suggests_optional() -> None:
+    """验证非 Optional 的 Union 遇到 None 时提示使用 Optional."""
+
+    class Items(Struct):
+        items: Annotated[list[int | str], 0]
+
+    obj = Items([1, "a"])
+    obj.items = [1, None]  # type: ignore[list-item]
+    with pytest.raises(
+        TypeError,
+        match=r"Field 'items': None given but Union\[int, str\] "
+        r"doesn't include None; use Optional",
+    ):
+        encode(obj)
+
+
 def test_any_type() -> None:
     """验证 Any 类型 (动态推断)."""
 
@@ -323,6 +931,29 @@ def test_any_type() -> None:
         assert dec.val == exp
 
 
+def test_any_preserve_bool_round_trips_bools() -> None:
+    """Meta(any_preserve_bool=True) 时 Any 字段中的 bool 解码后仍为 bool."""
+
+    class Flagged(Struct):
+        val: Annotated[Any, 0, Meta(any_preserve_bool=True)]
+
+    class Plain(Struct):
+        val: Annotated[Any, 0]
+
+    for flag in (True, False):
+        assert decode(Flagged, encode(Flagged(flag))).val is flag
+        assert decode(Plain, encode(Plain(flag))).val == int(flag)
+
+    assert decode(Flagged, encode(Flagged(1))).val == 1
+    assert type(decode(Flagged, encode(Flagged(1))).val) is int
+    assert decode(Flagged, encode(Flagged({0: "x"}))).val == {0: "x"}
+
+    with pytest.raises(TypeError, match="any_preserve_bool=True must be annotated"):
+
+        class Bad(Struct):
+            val: Annotated[int, 0, Meta(any_preserve_bool=True)]
+
+
 def test_literal_type() -> None:
     """验证 Literal 类型."""
 
@@ -336,6 +967,50 @@ def test_literal_type() -> None:
     assert dec.code == 1
 
 
+def test_literal_type_info_preserves_values() -> None:
+    """type_info 对 Literal 返回 LiteralType, 保留取值与底层类型."""
+    info = cast(inspect.LiteralType, inspect.type_info(Literal["ok", "err"]))
+    assert info.kind == "literal"
+    assert info.values == ("ok", "err")
+    assert info.value_type.kind == "str"
+
+    mixed = cast(inspect.LiteralType, inspect.type_info(Literal[1, "a"]))
+    assert mixed.values == (1, "a")
+    assert mixed.value_type.kind == "union"
+
+    with_none = cast(inspect.LiteralType, inspect.type_info(Literal["x", None]))
+    assert with_none.values == ("x",)
+
+    class Mixed(Struct):
+        v: Annotated[Literal[1, "a"], 0]
+
+    assert decode(Mixed, encode(Mixed("a"))).v == "a"
+    assert decode(Mixed, encode(Mixed(1))).v == 1
+
+
+def test_literal_decode_rejects_values_outside_members() -> None:
+    """解码 Literal 字段时取值不在成员内抛 ValidationError 并带字段路径."""
+
+    class Lit(Struct):
+        status: Annotated[Literal["ok", "err"], 0]
+        codes: Annotated[list[Literal[1, "x"]], 1] = []  # noqa: RUF012
+
+    assert decode(Lit, encode(Lit("err", [1, "x"]))).codes == [1, "x"]
+
+    with pytest.raises(ValidationError, match=r"<root>\.status: .*got 'bad'"):
+        decode(Lit, encode_raw(TarsDict({0: "bad"})))
+    with pytest.raises(ValidationError, match=r"<root>\.codes\[1\]: .*got 2"):
+        decode(Lit, encode_raw(TarsDict({0: "ok", 1: [1, 2]})))
+    with pytest.raises(ValidationError, match="got 'y'"):
+        decode(Lit, encode_raw(TarsDict({0: "ok", 1: ["y"]})))
+
+    class Flag(Struct):
+        v: Annotated[Literal[1], 0]
+
+    with pytest.raises(ValidationError, match="type mismatch"):
+        Flag(True)  # pyright: ignore[reportArgumentType]
+
+
 def test_newtype_final_alias() -> None:
     """验证 NewType, Final, TypeAlias."""
     MyInt = NewType("MyInt", int)
@@ -352,6 +1027,53 @@ def test_newtype_final_alias() -> None:
     assert dec.a == 3
 
 
+def test_final_constant_excluded_from_fields() -> None:
+    """未显式指定 tag 的 Final 赋值视为类常量, 不参与编解码."""
+
+    class Consts(Struct):
+        VERSION: Final[int] = 3
+        KIND: Final = "demo"
+        value: int
+
+    assert [f.name for f in inspect.struct_info(Consts).fields] == ["value"]
+    assert Consts.VERSION == 3
+    assert Consts.KIND == "demo"
+
+    obj = Consts(7)
+    assert obj.VERSION == 3
+    assert decode(Consts, encode(obj)) == obj
+    assert encode(obj) == encode(Consts(7))
+
+    class Child(Consts):
+        extra: str = "x"
+
+    assert [f.name for f in inspect.struct_info(Child).fields] == ["value", "extra"]
+    assert Child.VERSION == 3
+
+
+def test_final_field_with_explicit_tag() -> None:
+    """显式指定 tag 的 Final 注解仍是字段, 赋值作为默认值."""
+    from tarsio import field
+
+    class Versioned(Struct):
+        version: Annotated[Final[int], 0] = 1
+        limit: Final[Annotated[int, 1]] = 10
+        name: Final[str] = field(tag=2, default="n")
+
+    info = inspect.struct_info(Versioned)
+    assert [(f.name, f.tag) for f in info.fields] == [
+        ("version", 0),
+        ("limit", 1),
+        ("name", 2),
+    ]
+
+    obj = Versioned(version=2)
+    decoded = decode(Versioned, encode(obj))
+    assert decoded.version == 2
+    assert decoded.limit == 10
+    assert decoded.name == "n"
+
+
 def test_generic_typevar_bound_runtime_validation() -> None:
     """未具体化 TypeVar(bound=...) 应按 bound 做运行时校验."""
     t_bound = TypeVar("t_bound", bound=int)
@@ -430,6 +1152,136 @@ def test_enum_types() -> None:
     assert dec2.lvl == Level.LOW
 
 
+def test_enum_coerce_raw_value() -> None:
+    """Meta(coerce_enum=True) 时接受成员或原始值, 非法原始值抛 ValidationError."""
+
+    class Color(Enum):
+        RED = 1
+        BLUE = "blue"
+
+    class Paint(Struct):
+        c: Annotated[Color, 0, Meta(coerce_enum=True)]
+        o: Annotated[Optional[Color], 1, Meta(coerce_enum=True)] = None
+
+    from_member = Paint(Color.BLUE, Color.RED)
+    from_raw = Paint(cast(Any, "blue"), cast(Any, 1))
+    assert from_raw.c is Color.BLUE
+    assert from_raw.o is Color.RED
+    assert encode(from_raw) == encode(from_member)
+    assert decode(Paint, encode(from_raw)) == from_member
+
+    late = Paint(Color.RED)
+    late.c = cast(Any, "blue")
+    assert decode(Paint, encode(late)).c is Color.BLUE
+
+    with pytest.raises(ValidationError, match="not a valid Color member"):
+        Paint(cast(Any, 3))
+
+    late.c = cast(Any, 3)
+    with pytest.raises(ValidationError, match="not a valid Color member"):
+        encode(late)
+
+    with pytest.raises(TypeError, match="coerce_enum=True"):
+
+        class Bad(Struct):
+            v: Annotated[int, 0, Meta(coerce_enum=True)]
+
+
+def test_enum_mode_name_struct_and_field_level() -> None:
+    """enum_mode="name" 按成员名称编码, Meta(enum_mode=...) 可按字段覆盖."""
+
+    class Level(IntEnum):
+        LOW = 10
+        HIGH = 20
+
+    class ByName(Struct, enum_mode="name"):
+        lvl: Annotated[Level, 0]
+        many: Annotated[list[Level], 1]
+        legacy: Annotated[Level, 2, Meta(enum_mode="value")]
+
+    class ByValue(Struct):
+        lvl: Annotated[Level, 0, Meta(enum_mode="name")]
+        raw: Annotated[Optional[Level], 1] = None  # noqa: UP045
+
+    assert ByName.__struct_config__.enum_mode == "name"
+    assert ByValue.__struct_config__.enum_mode == "value"
+
+    obj = ByName(Level.HIGH, [Level.LOW, Level.HIGH], Level.LOW)
+    assert decode_raw(encode(obj)) == {0: "HIGH", 1: ["LOW", "HIGH"], 2: 10}
+    assert decode(ByName, encode(obj)) == obj
+
+    field_obj = ByValue(Level.LOW, Level.HIGH)
+    assert decode_raw(encode(field_obj)) == {0: "LOW", 1: 20}
+    assert decode(ByValue, encode(field_obj)) == field_obj
+
+    unknown = encode(ByValue(Level.LOW)).replace(b"LOW", b"MID")
+    with pytest.raises(ValueError, match="'MID' is not a valid Level member name"):
+        decode(ByValue, unknown)
+
+    with pytest.raises(ValueError, match="enum_mode must be 'value' or 'name'"):
+
+        class Bad(Struct, enum_mode="label"):
+            lvl: Annotated[Level, 0]
+
+    with pytest.raises(TypeError, match="enum_mode must be annotated with an Enum"):
+
+        class NotEnum(Struct):
+            v: Annotated[int, 0, Meta(enum_mode="name")]
+
+
+def test_enum_mode_name_handles_member_aliases() -> None:
+    """enum_mode="name" 编码别名成员时写出规范名称, 解码同时接受规范名称与别名."""
+
+    class Color(Enum):
+        RED = 1
+        CRIMSON = 1
+        BLUE = 2
+
+    class Paint(Struct, enum_mode="name"):
+        color: Annotated[Color, 0]
+
+    data = encode(Paint(Color.CRIMSON))
+    assert decode_raw(data) == {0: "RED"}
+    assert decode(Paint, data).color is Color.RED
+
+    alias_data = data.replace(b"\x06\x03RED", b"\x06\x07CRIMSON")
+    assert decode(Paint, alias_data).color is Color.RED
+
+
+def test_flag_fields_roundtrip_combined_members() -> None:
+    """Flag/IntFlag 字段按整数值编码, 组合值解码为对应的复合成员."""
+
+    class Perm(IntFlag):
+        READ = 1
+        WRITE = 2
+        EXEC = 4
+
+    class Mode(Flag):
+        A = 1
+        B = 2
+
+    class Access(Struct, enum_mode="name"):
+        perm: Annotated[Perm, 0]
+        mode: Annotated[Mode, 1]
+        raw: Annotated[Perm, 2, Meta(coerce_enum=True)] = Perm.READ
+
+    obj = Access(Perm.READ | Perm.WRITE, Mode.A | Mode.B, cast(Any, 5))
+    assert obj.raw == Perm.READ | Perm.EXEC
+    data = encode(obj)
+    assert decode_raw(data) == {0: 3, 1: 3, 2: 5}
+
+    decoded = decode(Access, data)
+    assert decoded.perm == Perm.READ | Perm.WRITE
+    assert isinstance(decoded.perm, Perm)
+    assert decoded.mode == Mode.A | Mode.B
+    assert decoded.raw == Perm.READ | Perm.EXEC
+
+    assert inspect.type_info(Access).fields[0].type.kind == "flag"
+
+    with pytest.raises(ValidationError, match="type mismatch"):
+        Access(cast(Any, 3), Mode.A)
+
+
 if sys.version_info >= (3, 11):
     from enum import StrEnum
 
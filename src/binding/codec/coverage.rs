@@ -0,0 +1,130 @@
+//! Schema 覆盖率统计.
+//!
+//! 在语料上累计解码时每个字段在线上出现的次数, 以及 Schema 未声明的未知 Tag,
+//! 用于发现可删除的字段与需补充的字段.
+
+use crate::binding::core::StructDef;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyType};
+use std::collections::{BTreeMap, HashMap};
+
+/// 单次顶层解码观察到的字段与未知 Tag.
+///
+/// 解码过程中只写入这里, 整个解码成功后才由 [`SchemaCoverage::record`] 合并,
+/// 失败的解码不计入统计, 钩子中的嵌套解码也可以使用同一个 `SchemaCoverage`.
+#[derive(Default)]
+pub(crate) struct CoverageHit {
+    /// 第 idx 个字段 (按 `fields_sorted`) 是否在线上出现.
+    pub(crate) seen: Vec<bool>,
+    pub(crate) unknown_tags: Vec<u8>,
+}
+
+/// 单个 Struct 类的累计统计.
+struct ClassCoverage {
+    /// 持有类的强引用, 保证 `index` 中的类地址在统计期间不会被复用.
+    cls: Py<PyType>,
+    name: String,
+    decoded: u64,
+    /// 按 Tag 排序的 (字段名, 出现次数).
+    fields: Vec<(String, u64)>,
+    unknown_tags: BTreeMap<u8, u64>,
+}
+
+/// 累计解码统计, 分析 Schema 字段在线上的覆盖情况.
+///
+/// 通过 `decode(cls, data, coverage=cov)` 逐次更新, 仅统计顶层 Struct.
+#[pyclass(module = "tarsio._core")]
+#[derive(Default)]
+pub struct SchemaCoverage {
+    classes: Vec<ClassCoverage>,
+    /// 类对象地址 -> `classes` 下标.
+    index: HashMap<usize, usize>,
+}
+
+impl SchemaCoverage {
+    /// 合并一次成功解码的观察结果.
+    pub(crate) fn record(&mut self, cls: &Bound<'_, PyType>, def: &StructDef, hit: &CoverageHit) {
+        let slot = *self.index.entry(cls.as_ptr() as usize).or_insert_with(|| {
+            self.classes.push(ClassCoverage {
+                cls: cls.clone().unbind(),
+                name: def.name.clone(),
+                decoded: 0,
+                fields: def
+                    .fields_sorted
+                    .iter()
+                    .map(|f| (f.name.clone(), 0))
+                    .collect(),
+                unknown_tags: BTreeMap::new(),
+            });
+            self.classes.len() - 1
+        });
+        let entry = &mut self.classes[slot];
+        entry.decoded += 1;
+        for ((_, count), seen) in entry.fields.iter_mut().zip(&hit.seen) {
+            if *seen {
+                *count += 1;
+            }
+        }
+        for tag in &hit.unknown_tags {
+            *entry.unknown_tags.entry(*tag).or_insert(0) += 1;
+        }
+    }
+}
+
+#[pymethods]
+impl SchemaCoverage {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// 生成覆盖率报告.
+    ///
+    /// Returns:
+    ///     以类名为键的 dict, 多个类同名时改用 `模块.限定名` 区分; 每项包含
+    ///     `decoded` (解码次数)、`field_counts` (字段名 -> 出现次数)、
+    ///     `never_seen` (从未出现的字段名列表) 与 `unknown_tags` (未知 Tag -> 出现次数).
+    fn report<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let out = PyDict::new(py);
+        let mut name_counts: HashMap<&str, usize> = HashMap::new();
+        for class in &self.classes {
+            *name_counts.entry(class.name.as_str()).or_insert(0) += 1;
+        }
+        for class in &self.classes {
+            let field_counts = PyDict::new(py);
+            let never_seen = PyList::empty(py);
+            for (name, count) in &class.fields {
+                field_counts.set_item(name, count)?;
+                if *count == 0 {
+                    never_seen.append(name)?;
+                }
+            }
+            let unknown_tags = PyDict::new(py);
+            for (tag, count) in &class.unknown_tags {
+                unknown_tags.set_item(tag, count)?;
+            }
+            let entry = PyDict::new(py);
+            entry.set_item("decoded", class.decoded)?;
+            entry.set_item("field_counts", field_counts)?;
+            entry.set_item("never_seen", never_seen)?;
+            entry.set_item("unknown_tags", unknown_tags)?;
+            if name_counts[class.name.as_str()] > 1 {
+                let cls = class.cls.bind(py);
+                let key = format!("{}.{}", cls.module()?, cls.qualname()?);
+                out.set_item(key, entry)?;
+            } else {
+                out.set_item(&class.name, entry)?;
+            }
+        }
+        Ok(out)
+    }
+
+    fn __repr__(&self) -> String {
+        let decoded: u64 = self.classes.iter().map(|c| c.decoded).sum();
+        format!(
+            "<SchemaCoverage classes={} decoded={}>",
+            self.classes.len(),
+            decoded
+        )
+    }
+}
@@ -1,54 +1,700 @@
+use crate::binding::codec::coverage::{CoverageHit, SchemaCoverage};
 use crate::binding::codec::raw::{
-    decode_any_struct_fields, decode_any_value, decode_raw_from_bytes, read_size_non_negative,
+    decode_any_struct_fields, decode_any_value, decode_raw_from_bytes, decode_struct_fields,
+    hashable_map_key, read_map_size_non_negative, read_simple_list_bytes, read_size_non_negative,
+    tagged_bool,
 };
-use crate::binding::error::{DeError, DeResult, PathItem};
+use crate::binding::compiler::compile_type_annotation;
+use crate::binding::core::{DEFAULT_MAX_DECOMPRESSED, literal_values_repr};
+use crate::binding::error::{DeError, DeResult, PathItem, ValidationError};
 use crate::binding::instantiate::run_post_init;
-use crate::binding::ir::{Constraints, StructDef, TypeExpr, WireType};
-use crate::binding::schema::{TarsDict, ensure_schema_for_class};
-use crate::binding::utils::{check_depth, class_from_type, try_coerce_buffer_to_bytes};
+use crate::binding::ir::{Constraints, Endian, FieldDef, StructDef, TypeExpr, WireInt, WireType};
+use crate::binding::schema::{Struct, TarsDict, ensure_schema_for_class};
+use crate::binding::utils::{
+    DepthLimitGuard, MAX_FIELDS_READ, check_depth, class_from_type, max_depth_override,
+    try_coerce_buffer_to_bytes, with_stdlib_cache,
+};
 use crate::binding::validation::{
-    validate_constraints_on_value, validate_length_constraints_raw,
+    HookContextGuard, call_field_hook, hook_context, literal_contains,
+    validate_constraints_on_value, validate_int_constraints_raw, validate_length_constraints_raw,
     validate_numeric_constraints_raw,
 };
 use crate::codec::consts::TarsType;
-use crate::codec::reader::TarsReader;
+use crate::codec::reader::{
+    ByteSource, DEFAULT_MAX_SKIP_DEPTH, TarsReader, read_head_from, skip_value,
+};
 use pyo3::ffi;
 use pyo3::prelude::*;
-use pyo3::types::{PyAny, PyBytes, PyDict, PySet, PyTuple, PyType};
+use pyo3::types::{
+    PyAny, PyBool, PyByteArray, PyBytes, PyComplex, PyDict, PyFloat, PyFrozenSet, PyList, PySet,
+    PyTuple, PyType,
+};
 use simdutf8::basic::from_utf8;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::sync::atomic::Ordering;
+
+/// 创建顶层读取器; 调用方传入 `max_depth` 时同时作为跳过未知字段的深度上限.
+fn top_level_reader(data: &[u8], little_endian: bool) -> TarsReader<'_> {
+    let reader = TarsReader::new(data).with_little_endian(little_endian);
+    match max_depth_override() {
+        Some(limit) => reader.with_max_depth(limit),
+        None => reader,
+    }
+}
 
 /// 将 Tars 二进制数据解码为 Struct 实例(Schema API).
 ///
 /// Args:
 ///     cls: 目标 Struct 类型.
 ///     data: 待解码的 bytes.
+///     freeze: 为 True 时返回的实例拒绝属性赋值, 即使类未声明 `frozen`.
+///     skip_prefix: 解析前跳过的前缀字节数, 用于带填充或哨兵字节的报文.
+///     track_presence: 为 True 时在实例的 `__tarsio_present__` 记录线上出现的字段名,
+///         用于区分"显式写出默认值"与"缺失后取默认值".
+///     coverage: 可选的 `SchemaCoverage`, 累计本次解码中出现的字段与未知 Tag.
+///     on_unknown: 可选回调, 任意深度的 Struct 每遇到一个未知 Tag 时以
+///         `(tag, type_code, offset)` 调用, 在跳过该字段 (或按 `forbid_unknown_tags` 报错)
+///         之前执行; offset 为字段头在 skip_prefix 之后数据中的偏移 (SimpleList 包装的
+///         嵌套 Struct 为包装内的偏移). 返回值被忽略, 回调抛出的异常原样传播.
+///     max_bytes: 输入长度上限, 解析前检查, 超出即报错; 同时限制压缩字段解压后的长度.
+///         None 表示不限制输入长度, 解压上限为 `DEFAULT_MAX_DECOMPRESSED`.
+///     float_as_decimal: 为 True 时 float/double 字段解码为 `decimal.Decimal`
+///         (按最短往返文本构造), 字段声明类型仍为 float. Any 字段与 TarsDict 中的浮点数同样转换.
+///     widen_float: 为 True 时线上的单精度 Float 按其最短十进制表示转换为 Python float,
+///         如 `1.1f32` 得到 `1.1` 而非 `1.100000023841858`. Any 字段与 TarsDict 中的
+///         单精度 Float 同样转换.
+///     little_endian: 为 True 时多字节数值按小端解释, 兼容部分旧客户端的 JCE 报文.
+///     allow_trailing: 为 True 时忽略解码结束后剩余的字节 (如外层封包的填充),
+///         覆盖类配置 `forbid_extra_bytes=True`.
+///     accept_wrapped_structs: 为 True 时 Struct 字段 (任意深度) 也接受 SimpleList 包装的
+///         嵌套序列化, 兼容时而包装时而内联的生产方; bytes 字段不受影响.
+///     strict: 为 True 时要求线上类型与字段声明精确一致: double 字段不再接受单精度 Float,
+///         整数族 (Int1/Int2/Int4/Int8) 与零值 ZeroTag 仍按原规则读取. 作用于任意深度.
+///     keep_raw: 需要保留原始字节的顶层字段 tag 集合. 传入时返回 `(实例, raw)`,
+///         `raw` 将线上出现的这些 tag 映射到该字段完整的线上字节 (含字段头),
+///         可直接用 `decode_raw` 重新解析; 重复出现的 tag 以最后一次为准.
+///     context: 任意对象, 非 None 时以关键字参数 `context` 传给任意深度的
+///         `Meta(decoder=...)` 钩子与 `__post_init__`; None 时两者按原方式调用.
+///     max_depth: 嵌套深度上限, 同时约束跳过未知字段时的嵌套层数; None 表示默认上限
+///         (类型化解码 `MAX_DEPTH`, 跳过未知字段 100).
 ///
 /// Returns:
-///     解码得到的实例.
+///     解码得到的实例; 传入 keep_raw 时为 `(实例, dict[int, bytes])`.
 ///
 /// Raises:
-///     TypeError: cls 未注册 Schema.
-///     ValueError: 数据格式不正确、缺少必填字段、或递归深度超过限制.
+///     TypeError: cls 未注册 Schema, freeze/track_presence=True 但 cls 不是 Struct 子类,
+///         对 TarsDict 传入 coverage/on_unknown/keep_raw, 或 keep_raw 含非法 tag.
+///     ValueError: 数据格式不正确、缺少必填字段、递归深度超过限制,
+///         或 skip_prefix 超过数据长度.
+///     ValidationError: 输入长度超过 max_bytes.
 #[pyfunction]
+#[pyo3(signature = (
+    cls,
+    data,
+    *,
+    freeze=false,
+    skip_prefix=0,
+    track_presence=false,
+    coverage=None,
+    on_unknown=None,
+    max_bytes=None,
+    float_as_decimal=false,
+    widen_float=false,
+    little_endian=false,
+    allow_trailing=false,
+    accept_wrapped_structs=false,
+    strict=false,
+    keep_raw=None,
+    context=None,
+    max_depth=None
+))]
+#[allow(clippy::too_many_arguments)]
 pub fn decode<'py>(
     py: Python<'py>,
     cls: &Bound<'py, PyType>,
     data: &Bound<'py, PyAny>,
+    freeze: bool,
+    skip_prefix: usize,
+    track_presence: bool,
+    coverage: Option<&Bound<'py, SchemaCoverage>>,
+    on_unknown: Option<&Bound<'py, PyAny>>,
+    max_bytes: Option<usize>,
+    float_as_decimal: bool,
+    widen_float: bool,
+    little_endian: bool,
+    allow_trailing: bool,
+    accept_wrapped_structs: bool,
+    strict: bool,
+    keep_raw: Option<&Bound<'py, PyAny>>,
+    context: Option<Py<PyAny>>,
+    max_depth: Option<usize>,
 ) -> PyResult<Bound<'py, PyAny>> {
+    let _context = HookContextGuard::set(context);
+    let _depth = DepthLimitGuard::set(max_depth)?;
+    let bytes = try_coerce_buffer_to_bytes(data)?.ok_or_else(|| {
+        pyo3::exceptions::PyTypeError::new_err("argument 'data': expected a bytes-like object")
+    })?;
+    let raw_tags = keep_raw.map(extract_raw_tags).transpose()?;
+    let raw = raw_tags.as_ref().map(|_| PyDict::new(py));
+    let obj = decode_object(
+        py,
+        cls,
+        bytes.as_bytes(),
+        skip_prefix,
+        track_presence,
+        coverage,
+        on_unknown,
+        max_bytes,
+        DecodeMode {
+            as_decimal: float_as_decimal,
+            widen_f32: widen_float,
+            accept_wrapped_structs,
+            strict,
+            max_decompressed: max_bytes,
+        },
+        little_endian,
+        allow_trailing,
+        raw_tags.as_ref().zip(raw.as_ref()),
+    )?;
+    if freeze {
+        freeze_instance(&obj)?;
+    }
+    match raw {
+        Some(raw) => Ok(PyTuple::new(py, [obj, raw.into_any()])?.into_any()),
+        None => Ok(obj),
+    }
+}
+
+/// 解析 `keep_raw` 参数: 任意可迭代的 tag 集合.
+fn extract_raw_tags(value: &Bound<'_, PyAny>) -> PyResult<HashSet<u8>> {
+    let invalid = || {
+        pyo3::exceptions::PyTypeError::new_err(
+            "keep_raw must be an iterable of tags in range 0..=255",
+        )
+    };
+    let mut tags = HashSet::new();
+    for item in value.try_iter().map_err(|_| invalid())? {
+        tags.insert(item?.extract::<u8>().map_err(|_| invalid())?);
+    }
+    Ok(tags)
+}
+
+/// 为解码结果设置实例级只读标记, 之后的属性赋值由 `Struct.__setattr__` 拒绝.
+///
+/// 仅冻结顶层实例, 嵌套的 Struct 与容器值保持原样.
+pub(crate) fn freeze_instance(obj: &Bound<'_, PyAny>) -> PyResult<()> {
+    let instance = obj.cast::<Struct>().map_err(|_| {
+        pyo3::exceptions::PyTypeError::new_err("freeze=True requires a Struct subclass")
+    })?;
+    instance
+        .borrow()
+        .frozen_instance
+        .store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// 将 Tars 二进制数据解码为 Struct 实例, 对任意输入只抛出 `ValidationError`.
+///
+/// 适用于模糊测试等需要对不可信输入统一分类错误的场景: 解码过程中产生的
+/// 任何异常 (包括 `__post_init__` 抛出的异常) 都会转换为 `ValidationError`,
+/// 原始异常保存在 `__cause__` 中.
+///
+/// Args:
+///     cls: 目标 Struct 类型.
+///     data: 待解码的 bytes.
+///
+/// Returns:
+///     解码得到的实例.
+///
+/// Raises:
+///     TypeError: cls 未注册 Schema 或 data 不是 bytes-like 对象.
+///     ValidationError: 数据无法解码为 cls 实例.
+#[pyfunction]
+pub fn decode_checked<'py>(
+    py: Python<'py>,
+    cls: &Bound<'py, PyType>,
+    data: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let bytes = try_coerce_buffer_to_bytes(data)?.ok_or_else(|| {
+        pyo3::exceptions::PyTypeError::new_err("argument 'data': expected a bytes-like object")
+    })?;
+    // 调用方错误 (cls 不是 Struct) 与输入无关, 保持 TypeError.
+    if !cls.is_subclass_of::<TarsDict>()? {
+        ensure_schema_for_class(py, cls)?;
+    }
+
+    // release 构建为 panic = "abort", 这里的 catch_unwind 只在 debug/fuzz 构建中生效;
+    // 解码路径本身不应包含任何 panic.
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        decode_object(
+            py,
+            cls,
+            bytes.as_bytes(),
+            0,
+            false,
+            None,
+            None,
+            None,
+            DecodeMode::default(),
+            false,
+            false,
+            None,
+        )
+    }));
+    match result {
+        Ok(Ok(obj)) => Ok(obj),
+        Ok(Err(err)) => {
+            if err.is_instance_of::<ValidationError>(py)
+                || !err.is_instance_of::<pyo3::exceptions::PyException>(py)
+            {
+                return Err(err);
+            }
+            let wrapped = ValidationError::new_err(format!("Decode failed: {}", err));
+            wrapped.set_cause(py, Some(err));
+            Err(wrapped)
+        }
+        Err(_) => Err(ValidationError::new_err(
+            "Decode failed: internal decoder error",
+        )),
+    }
+}
+
+/// 从 Struct 中读取指定 Tag 的单个字段值, 按给定类型标注解码.
+///
+/// 适用于 Tars RPC 返回值等"外层 Struct 仅包一个值"的场景, 无需为每个返回值
+/// 定义包装 Struct. 其他 Tag 的字段会被跳过.
+///
+/// Args:
+///     tp: 字段值的类型标注, 支持 `Annotated[T, Meta(...)]`.
+///     data: 待解码的 bytes.
+///     tag: 目标字段的 Tag.
+///
+/// Returns:
+///     解码得到的值; 类型为 Optional 且字段缺失时返回 None.
+///
+/// Raises:
+///     TypeError: 类型标注不受支持.
+///     ValueError: 数据格式不正确, 或非 Optional 类型的字段缺失.
+#[pyfunction]
+#[pyo3(signature = (tp, data, tag=0))]
+pub fn decode_value<'py>(
+    py: Python<'py>,
+    tp: &Bound<'py, PyAny>,
+    data: &Bound<'py, PyAny>,
+    tag: u8,
+) -> PyResult<Bound<'py, PyAny>> {
+    let bytes = try_coerce_buffer_to_bytes(data)?.ok_or_else(|| {
+        pyo3::exceptions::PyTypeError::new_err("argument 'data': expected a bytes-like object")
+    })?;
+    let (type_expr, constraints) = compile_type_annotation(py, tp)?;
+
+    let _mode = DecodeModeGuard::set(DecodeMode::default());
+    let mut reader = TarsReader::new(bytes.as_bytes());
+    while !reader.is_end() {
+        let (field_tag, type_id) = reader.read_head().map_err(|e| {
+            DeError::new(format!("Failed to read field head: {}", e))
+                .at_offset(reader.position())
+                .to_pyerr(py)
+        })?;
+        if type_id == TarsType::StructEnd {
+            break;
+        }
+        if field_tag != tag {
+            reader.skip_field(type_id).map_err(|e| {
+                DeError::new(format!("Failed to skip tag {}: {}", field_tag, e))
+                    .at_offset(reader.position())
+                    .to_pyerr(py)
+            })?;
+            continue;
+        }
+        let value = deserialize_value(
+            py,
+            &mut reader,
+            type_id,
+            &type_expr,
+            constraints.as_deref(),
+            1,
+        )
+        .map_err(|e| {
+            e.prepend(PathItem::Tag(tag))
+                .at_offset(reader.position())
+                .to_pyerr(py)
+        })?;
+        if let Some(c) = constraints.as_deref() {
+            validate_constraints_on_value(&value, c, None)?;
+        }
+        return Ok(value);
+    }
+
+    if type_expr.is_optional() {
+        return Ok(py.None().into_bound(py));
+    }
+    Err(pyo3::exceptions::PyValueError::new_err(format!(
+        "Tag {} not found in payload",
+        tag
+    )))
+}
+
+/// 逐个解码首尾相接的多个 Struct, 返回惰性迭代器.
+///
+/// 顶层 Struct 编码时不带 StructBegin/StructEnd, 相邻两个 Struct 的字段流无法区分边界.
+/// 因此每个元素必须以 StructBegin 头开始、StructEnd 结束 (即按嵌套 Struct 的形式编码),
+/// 迭代器据此确定每个元素消耗的字节数并推进到下一个元素, 直到数据耗尽.
+///
+/// Args:
+///     cls: 目标 Struct 或 TarsDict 类型.
+///     data: 待解码的 bytes.
+///
+/// Returns:
+///     依次产出解码实例的迭代器.
+///
+/// Raises:
+///     TypeError: cls 未注册 Schema, 或 data 不是 bytes-like 对象.
+///     ValueError: 迭代过程中某个元素未以 StructBegin 开始 (无法确定边界)、
+///         缺少 StructEnd, 或字段数据格式不正确.
+#[pyfunction]
+pub fn decode_concatenated<'py>(
+    py: Python<'py>,
+    cls: &Bound<'py, PyType>,
+    data: &Bound<'py, PyAny>,
+) -> PyResult<ConcatenatedDecoder> {
+    let bytes = try_coerce_buffer_to_bytes(data)?.ok_or_else(|| {
+        pyo3::exceptions::PyTypeError::new_err("argument 'data': expected a bytes-like object")
+    })?;
+    if !cls.is_subclass_of::<TarsDict>()? {
+        ensure_schema_for_class(py, cls)?;
+    }
+    Ok(ConcatenatedDecoder {
+        cls: cls.clone().unbind(),
+        data: Some(bytes.unbind()),
+        pos: 0,
+    })
+}
+
+/// `decode_concatenated` 返回的迭代器, 记录下一个元素的起始偏移.
+///
+/// 持有输入数据的引用直到迭代结束或 `close()`; 关闭后释放数据, 再次迭代抛出 `ValueError`.
+/// 支持 `with` 语句, 退出时自动关闭.
+#[pyclass(module = "tarsio._core")]
+pub struct ConcatenatedDecoder {
+    cls: Py<PyType>,
+    /// 输入数据, `close()` 后为 None.
+    data: Option<Py<PyBytes>>,
+    pos: usize,
+}
+
+#[pymethods]
+impl ConcatenatedDecoder {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyResult<PyRef<'_, Self>> {
+        slf.open_data()?;
+        Ok(slf)
+    }
+
+    /// 释放持有的输入数据, 之后的迭代抛出 `ValueError`. 重复调用无副作用.
+    fn close(&mut self) {
+        self.data = None;
+    }
+
+    /// 是否已关闭.
+    #[getter]
+    fn closed(&self) -> bool {
+        self.data.is_none()
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyResult<PyRef<'_, Self>> {
+        slf.open_data()?;
+        Ok(slf)
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, _args: &Bound<'_, PyTuple>) -> bool {
+        self.close();
+        false
+    }
+
+    fn __next__<'py>(&mut self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let data = self.open_data()?.bind(py).as_bytes();
+        let start = self.pos;
+        if start >= data.len() {
+            return Ok(None);
+        }
+        let (body_start, end) = wrapped_struct_bounds(data, start, false)?;
+        let _mode = DecodeModeGuard::set(DecodeMode::default());
+        let obj = decode_wrapped_body(
+            py,
+            self.cls.bind(py),
+            &data[body_start..end],
+            body_start,
+            false,
+        )?;
+        self.pos = end;
+        Ok(Some(obj))
+    }
+}
+
+/// 定位从 `start` 开始、以 StructBegin/StructEnd 包裹的 Struct, 返回 body 的起止偏移.
+///
+/// body 不含 StructBegin 头, 包含结尾的 StructEnd.
+fn wrapped_struct_bounds(
+    data: &[u8],
+    start: usize,
+    little_endian: bool,
+) -> PyResult<(usize, usize)> {
+    let mut reader = TarsReader::new(&data[start..]);
+    let (_, type_id) = reader.read_head().map_err(|e| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "Failed to read struct head at offset {}: {}",
+            start, e
+        ))
+    })?;
+    if type_id != TarsType::StructBegin {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Cannot split concatenated structs at offset {}: expected StructBegin, got {:?}; \
+             each struct must be wrapped in StructBegin/StructEnd",
+            start, type_id
+        )));
+    }
+    let body_start = start + reader.position() as usize;
+
+    // 先跳过整个 Struct 以确认 StructEnd 存在, 避免截断数据被当作完整元素解码.
+    let mut scan = top_level_reader(&data[body_start..], little_endian);
+    scan.skip_field(TarsType::StructBegin).map_err(|e| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "Unterminated struct at offset {}: {}",
+            start, e
+        ))
+    })?;
+    Ok((body_start, body_start + scan.position() as usize))
+}
+
+/// 解码以 StructBegin/StructEnd 包裹的单个 Struct 的字段部分 (不含 StructBegin 头).
+///
+/// `base` 为 body 在原始输入中的起始偏移, 用于错误消息中的字节偏移.
+fn decode_wrapped_body<'py>(
+    py: Python<'py>,
+    cls: &Bound<'py, PyType>,
+    body: &[u8],
+    base: usize,
+    little_endian: bool,
+) -> PyResult<Bound<'py, PyAny>> {
+    let mut reader = top_level_reader(body, little_endian);
+    if cls.is_subclass_of::<TarsDict>()? {
+        let dict = decode_any_struct_fields(py, &mut reader, 1)
+            .map_err(|e| e.at_offset(base as u64 + reader.position()).to_pyerr(py))?;
+        if cls.is(dict.get_type().as_any()) {
+            return Ok(dict.into_any());
+        }
+        return cls.call1((dict,));
+    }
+    let def = ensure_schema_for_class(py, cls)?;
+    deserialize_struct(py, cls, &mut reader, &def, 0, TopLevelOptions::default())
+        .map_err(|e| e.at_offset(base as u64 + reader.position()).to_pyerr(py))
+}
+
+impl ConcatenatedDecoder {
+    /// 返回尚未释放的输入数据, 已关闭时报错.
+    fn open_data(&self) -> PyResult<&Py<PyBytes>> {
+        self.data.as_ref().ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err("operation on closed ConcatenatedDecoder")
+        })
+    }
+}
+
+/// 批量解码同一缓冲区中的多条记录, 一次返回全部实例.
+///
+/// `framed=True` 时每条记录前为 4 字节大端无符号长度 (不含长度头本身), 与
+/// `encode_many(framed=True)` 的输出对应, 记录本身为普通的顶层编码.
+/// `framed=False` 时按 `decode_concatenated` 的规则拆分以 StructBegin/StructEnd
+/// 包裹的记录.
+///
+/// Args:
+///     cls: 目标 Struct 或 TarsDict 类型.
+///     data: 待解码的 bytes.
+///     framed: 记录是否带长度前缀.
+///     little_endian: 为 True 时多字节数值按小端解释; 长度前缀始终为大端.
+///     context: 非 None 时以关键字参数 `context` 传给字段 decoder 钩子与 `__post_init__`.
+///     max_depth: 嵌套深度上限; None 表示默认上限.
+///
+/// Returns:
+///     按记录顺序排列的实例列表.
+///
+/// Raises:
+///     TypeError: cls 未注册 Schema, 或 data 不是 bytes-like 对象.
+///     ValueError: 记录在中途截断、未以 StructBegin 开始, 或字段数据格式不正确;
+///         消息以 `record <序号>:` 开头. 约束校验失败等 ValueError 子类保留原类型.
+#[pyfunction]
+#[pyo3(signature = (cls, data, *, framed=true, little_endian=false, context=None, max_depth=None))]
+pub fn decode_many<'py>(
+    py: Python<'py>,
+    cls: &Bound<'py, PyType>,
+    data: &Bound<'py, PyAny>,
+    framed: bool,
+    little_endian: bool,
+    context: Option<Py<PyAny>>,
+    max_depth: Option<usize>,
+) -> PyResult<Bound<'py, PyList>> {
+    let _context = HookContextGuard::set(context);
+    let _depth = DepthLimitGuard::set(max_depth)?;
     let bytes = try_coerce_buffer_to_bytes(data)?.ok_or_else(|| {
         pyo3::exceptions::PyTypeError::new_err("argument 'data': expected a bytes-like object")
     })?;
-    decode_object(py, cls, bytes.as_bytes())
+    let data = bytes.as_bytes();
+    if !cls.is_subclass_of::<TarsDict>()? {
+        ensure_schema_for_class(py, cls)?;
+    }
+
+    let _mode = DecodeModeGuard::set(DecodeMode::default());
+    let out = PyList::empty(py);
+    let mut pos = 0;
+    while pos < data.len() {
+        let idx = out.len();
+        let (obj, end) = decode_one_record(py, cls, data, pos, framed, little_endian)
+            .map_err(|e| with_record_context(py, idx, e))?;
+        out.append(obj)?;
+        pos = end;
+    }
+    Ok(out)
+}
+
+/// 解码 `decode_many` 中从 `start` 开始的一条记录, 返回实例与下一条记录的起始偏移.
+fn decode_one_record<'py>(
+    py: Python<'py>,
+    cls: &Bound<'py, PyType>,
+    data: &[u8],
+    start: usize,
+    framed: bool,
+    little_endian: bool,
+) -> PyResult<(Bound<'py, PyAny>, usize)> {
+    if !framed {
+        let (body_start, end) = wrapped_struct_bounds(data, start, little_endian)?;
+        let obj = decode_wrapped_body(py, cls, &data[body_start..end], body_start, little_endian)?;
+        return Ok((obj, end));
+    }
+    let truncated = |needed: usize| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "Truncated record at offset {}: need {} bytes, got {}",
+            start,
+            needed,
+            data.len() - start
+        ))
+    };
+    let header: [u8; 4] = data
+        .get(start..start + 4)
+        .and_then(|h| h.try_into().ok())
+        .ok_or_else(|| truncated(4))?;
+    let len = u32::from_be_bytes(header) as usize;
+    let end = start + 4 + len;
+    let payload = data.get(start + 4..end).ok_or_else(|| truncated(4 + len))?;
+    let obj = decode_object(
+        py,
+        cls,
+        payload,
+        0,
+        false,
+        None,
+        None,
+        None,
+        DecodeMode::default(),
+        little_endian,
+        false,
+        None,
+    )?;
+    Ok((obj, end))
+}
+
+/// 为 `decode_many` 中的 ValueError (含 ValidationError 等子类) 加上记录序号前缀.
+///
+/// 尽量保留原异常类型, 无法以单个消息参数构造时退回 ValueError; 原异常保存在 `__cause__` 中.
+fn with_record_context(py: Python<'_>, idx: usize, err: PyErr) -> PyErr {
+    if !err.is_instance_of::<pyo3::exceptions::PyValueError>(py) {
+        return err;
+    }
+    let msg = format!("record {}: {}", idx, err.value(py));
+    let wrapped = match err.get_type(py).call1((msg.as_str(),)) {
+        Ok(exc) => PyErr::from_value(exc),
+        Err(_) => pyo3::exceptions::PyValueError::new_err(msg),
+    };
+    wrapped.set_cause(py, Some(err));
+    wrapped
 }
 
 /// 内部:将字节解码为 Tars Struct 实例.
+#[cfg_attr(
+    feature = "trace",
+    tracing::instrument(
+        name = "tarsio.decode",
+        level = "debug",
+        skip_all,
+        fields(class = %cls, bytes = data.len(), fields = tracing::field::Empty)
+    )
+)]
+#[allow(clippy::too_many_arguments)]
 pub fn decode_object<'py>(
     py: Python<'py>,
     cls: &Bound<'py, PyType>,
     data: &[u8],
+    skip_prefix: usize,
+    track_presence: bool,
+    coverage: Option<&Bound<'py, SchemaCoverage>>,
+    on_unknown: Option<&Bound<'py, PyAny>>,
+    max_bytes: Option<usize>,
+    mode: DecodeMode,
+    little_endian: bool,
+    allow_trailing: bool,
+    keep_raw: Option<(&HashSet<u8>, &Bound<'py, PyDict>)>,
 ) -> PyResult<Bound<'py, PyAny>> {
+    // 输入总长的粗粒度上限, 在任何解析之前拒绝超大报文.
+    if let Some(limit) = max_bytes
+        && data.len() > limit
+    {
+        return Err(ValidationError::new_err(format!(
+            "payload exceeds {} bytes (got {})",
+            limit,
+            data.len()
+        )));
+    }
+    if track_presence && !cls.is_subclass_of::<Struct>()? {
+        return Err(pyo3::exceptions::PyTypeError::new_err(
+            "track_presence=True requires a Struct subclass",
+        ));
+    }
+    let data = data.get(skip_prefix..).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "skip_prefix {} exceeds data length {}",
+            skip_prefix,
+            data.len()
+        ))
+    })?;
+    let _mode = DecodeModeGuard::with_unknown_hook(mode, on_unknown.map(|c| c.clone().unbind()));
     if cls.is_subclass_of::<TarsDict>()? {
-        let dict = decode_raw_from_bytes(py, data)?;
+        if coverage.is_some() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "coverage requires a Struct schema class",
+            ));
+        }
+        if on_unknown.is_some() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "on_unknown requires a Struct schema class",
+            ));
+        }
+        if keep_raw.is_some() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "keep_raw requires a Struct schema class",
+            ));
+        }
+        let dict = if little_endian || allow_trailing {
+            let mut reader = top_level_reader(data, little_endian);
+            let dict = decode_struct_fields(py, &mut reader, true, 0)?;
+            if !allow_trailing && !reader.is_end() {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "Trailing bytes after decode_raw",
+                ));
+            }
+            dict
+        } else {
+            decode_raw_from_bytes(py, data)?
+        };
         if cls.is(dict.get_type().as_any()) {
             return Ok(dict.into_any());
         }
@@ -57,15 +703,340 @@ pub fn decode_object<'py>(
     }
     // 校验 schema 是否存在并获取
     let def = ensure_schema_for_class(py, cls)?;
+    #[cfg(feature = "trace")]
+    tracing::Span::current().record("fields", def.fields_sorted.len());
+
+    let mut hit = coverage.map(|_| CoverageHit::default());
+    let mut reader = top_level_reader(data, little_endian);
+    let mut raw_spans = Vec::new();
+    let options = TopLevelOptions {
+        track_presence,
+        coverage: hit.as_mut(),
+        target: None,
+        keep_raw: keep_raw.map(|(tags, _)| (tags, &mut raw_spans)),
+    };
+    let res = deserialize_struct(py, cls, &mut reader, &def, 0, options)
+        .map_err(|e| e.at_offset(reader.position()).to_pyerr(py))?;
+    if def.forbid_extra_bytes && !allow_trailing && !reader.is_end() {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "Trailing bytes after decode",
+        ));
+    }
+    if let (Some(coverage), Some(hit)) = (coverage, hit) {
+        coverage.try_borrow_mut()?.record(cls, &def, &hit);
+    }
+    if let Some((_, raw)) = keep_raw {
+        for (tag, start, end) in raw_spans {
+            if let Some(slice) = data.get(start as usize..end as usize) {
+                raw.set_item(tag, PyBytes::new(py, slice))?;
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// 将 Tars 二进制数据解码并就地写入已有的 Struct 实例.
+///
+/// 与 `decode_object` 共享 tag 查找、默认值填充、尾随字节检查与 `__post_init__` 调用,
+/// 区别仅在于字段写入传入的实例而非新分配的对象. 解码失败时实例可能已被部分覆盖.
+pub(crate) fn decode_into_instance<'py>(
+    py: Python<'py>,
+    instance: &Bound<'py, Struct>,
+    data: &[u8],
+) -> PyResult<()> {
+    let cls = instance.get_type();
+    let def = ensure_schema_for_class(py, &cls)?;
+    // 上一次解码记录的出现字段集合不再适用于新数据.
+    instance.borrow_mut().present = None;
 
+    let _mode = DecodeModeGuard::set(DecodeMode::default());
     let mut reader = TarsReader::new(data);
-    let res = deserialize_struct(py, cls, &mut reader, &def, 0).map_err(|e| e.to_pyerr(py))?;
-    if !reader.is_end() {
+    let options = TopLevelOptions {
+        target: Some(instance.as_any()),
+        ..TopLevelOptions::default()
+    };
+    deserialize_struct(py, &cls, &mut reader, &def, 0, options)
+        .map_err(|e| e.at_offset(reader.position()).to_pyerr(py))?;
+    if def.forbid_extra_bytes && !reader.is_end() {
         return Err(pyo3::exceptions::PyValueError::new_err(
             "Trailing bytes after decode",
         ));
     }
-    Ok(res)
+    Ok(())
+}
+
+/// 从文件对象中读取并解码一个以 StructBegin/StructEnd 包裹的 Struct.
+///
+/// 通过 `stream.read(n)` 按需读取, 只请求当前字段所需的字节 (单次最多
+/// `STREAM_READ_CHUNK` 字节), 不会越过该 Struct 的 StructEnd, 因此返回后流恰好停在
+/// 下一条记录的起始处, 可循环调用读取记录序列. `read` 返回的字节少于请求数时会继续读取,
+/// 直到满足或遇到 EOF; 报文声明的超大长度只会在数据真实到达时才占用内存.
+///
+/// Args:
+///     cls: 目标 Struct 或 TarsDict 类型.
+///     stream: 提供 `read(n)` 方法、返回 bytes-like 对象的文件对象.
+///     max_bytes: 单条记录 (含 StructBegin/StructEnd) 的字节上限, 读取过程中超出即报错,
+///         同时限制压缩字段解压后的长度; None 表示不限制记录长度.
+///
+/// Returns:
+///     解码得到的实例; 读取前流已处于 EOF 时返回 None.
+///
+/// Raises:
+///     TypeError: cls 未注册 Schema, 或 `read` 返回的不是 bytes-like 对象.
+///     ValueError: 记录未以 StructBegin 开始、在字段中途遇到 EOF、长度非法、
+///         字段数据格式不正确或递归深度超过限制.
+///     ValidationError: 记录超过 max_bytes.
+#[pyfunction]
+#[pyo3(signature = (cls, stream, *, max_bytes=None))]
+pub fn decode_stream<'py>(
+    py: Python<'py>,
+    cls: &Bound<'py, PyType>,
+    stream: &Bound<'py, PyAny>,
+    max_bytes: Option<usize>,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    if !cls.is_subclass_of::<TarsDict>()? {
+        ensure_schema_for_class(py, cls)?;
+    }
+    let mut src = StreamSource {
+        stream: stream.clone(),
+        buf: Vec::new(),
+        pos: 0,
+        max_bytes,
+        py_err: None,
+    };
+    match src.fill(1) {
+        Ok(Err(_)) => return Ok(None),
+        Ok(Ok(())) => {}
+        Err(()) => return Err(src.take_py_err()),
+    }
+    let (_, type_id) = read_head_from(&mut src).map_err(|e| src.error_to_pyerr(py, e))?;
+    if type_id != TarsType::StructBegin {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Cannot decode stream record: expected StructBegin, got {:?}; \
+             each record must be wrapped in StructBegin/StructEnd",
+            type_id
+        )));
+    }
+    let body_start = src.pos;
+    let max_depth = max_depth_override().unwrap_or(DEFAULT_MAX_SKIP_DEPTH);
+    skip_value(&mut src, TarsType::StructBegin, 0, max_depth)
+        .map_err(|e| src.error_to_pyerr(py, e))?;
+    let _mode = DecodeModeGuard::set(DecodeMode {
+        max_decompressed: max_bytes,
+        ..DecodeMode::default()
+    });
+    decode_wrapped_body(py, cls, &src.buf[body_start..src.pos], body_start, false).map(Some)
+}
+
+/// `decode_stream` 单次调用 `read` 请求的最大字节数.
+const STREAM_READ_CHUNK: usize = 64 * 1024;
+
+/// `decode_stream` 的输入缓冲: 从文件对象按需精确读取, 只追加当前字段需要的字节.
+struct StreamSource<'py> {
+    stream: Bound<'py, PyAny>,
+    buf: Vec<u8>,
+    /// 已消费的字节数; `read` 多返回的字节留在 `buf` 中, 不计入消费.
+    pos: usize,
+    max_bytes: Option<usize>,
+    /// `read` 抛出的异常或 max_bytes 超限, 经 [`ByteSource`] 传出时暂存于此.
+    py_err: Option<PyErr>,
+}
+
+impl StreamSource<'_> {
+    /// 确保缓冲中至少还有 `n` 个未消费字节, 流提前结束时返回已缓冲的字节数.
+    ///
+    /// 每次 `read` 最多请求 `STREAM_READ_CHUNK` 字节; Python 异常暂存到 `py_err`.
+    fn fill(&mut self, n: usize) -> Result<Result<(), usize>, ()> {
+        while self.buf.len() - self.pos < n {
+            let want = (n - (self.buf.len() - self.pos)).min(STREAM_READ_CHUNK);
+            let bytes = self
+                .stream
+                .call_method1(pyo3::intern!(self.stream.py(), "read"), (want,))
+                .and_then(|chunk| {
+                    try_coerce_buffer_to_bytes(&chunk)?.ok_or_else(|| {
+                        pyo3::exceptions::PyTypeError::new_err(
+                            "stream.read() must return a bytes-like object",
+                        )
+                    })
+                })
+                .map_err(|e| self.py_err = Some(e))?;
+            if bytes.as_bytes().is_empty() {
+                return Ok(Err(self.buf.len() - self.pos));
+            }
+            self.buf.extend_from_slice(bytes.as_bytes());
+        }
+        Ok(Ok(()))
+    }
+
+    fn take_py_err(&mut self) -> PyErr {
+        self.py_err
+            .take()
+            .unwrap_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("stream read failed"))
+    }
+
+    /// 将跳过过程中的错误转换为 Python 异常, 优先返回暂存的 Python 异常.
+    fn error_to_pyerr(&mut self, py: Python<'_>, err: crate::codec::error::Error) -> PyErr {
+        if self.py_err.is_some() {
+            return self.take_py_err();
+        }
+        DeError::new(err.to_string())
+            .at_offset(self.pos as u64)
+            .to_pyerr(py)
+    }
+}
+
+impl ByteSource for StreamSource<'_> {
+    fn take(&mut self, n: usize) -> crate::codec::error::Result<&[u8]> {
+        if let Some(limit) = self.max_bytes
+            && self.pos.saturating_add(n) > limit
+        {
+            self.py_err = Some(ValidationError::new_err(format!(
+                "stream record exceeds {} bytes",
+                limit
+            )));
+            return Err(crate::codec::error::Error::new(
+                self.pos,
+                "max_bytes exceeded",
+            ));
+        }
+        match self.fill(n) {
+            Ok(Ok(())) => {}
+            Ok(Err(available)) => {
+                return Err(crate::codec::error::Error::buffer_overflow(
+                    self.pos, n, available,
+                ));
+            }
+            Err(()) => {
+                return Err(crate::codec::error::Error::new(
+                    self.pos,
+                    "stream read failed",
+                ));
+            }
+        }
+        let start = self.pos;
+        self.pos += n;
+        Ok(&self.buf[start..self.pos])
+    }
+
+    fn offset(&self) -> usize {
+        self.pos
+    }
+
+    fn is_little_endian(&self) -> bool {
+        false
+    }
+}
+
+/// 作用于任意嵌套深度的解码选项, 对应 `decode` 的 `float_as_decimal`、`widen_float`、
+/// `accept_wrapped_structs` 与 `strict`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DecodeMode {
+    /// float/double 解码为 `decimal.Decimal`.
+    pub as_decimal: bool,
+    /// 单精度 Float 按最短十进制表示转换为 Python float.
+    pub widen_f32: bool,
+    /// Struct 字段遇到 SimpleList 时按其中的字节解码嵌套 Struct.
+    pub accept_wrapped_structs: bool,
+    /// 线上类型必须与声明的 WireType 精确一致 (double 字段拒绝 Float).
+    pub strict: bool,
+    /// `Meta(compress=...)` 字段解压后的长度上限, 取自 `max_bytes`;
+    /// None 时使用 [`DEFAULT_MAX_DECOMPRESSED`].
+    pub max_decompressed: Option<usize>,
+}
+
+thread_local! {
+    // 当前线程的解码模式, 由 `DecodeModeGuard` 在解码入口设置.
+    static DECODE_MODE: Cell<DecodeMode> = const {
+        Cell::new(DecodeMode {
+            as_decimal: false,
+            widen_f32: false,
+            accept_wrapped_structs: false,
+            strict: false,
+            max_decompressed: None,
+        })
+    };
+    // 当前 `decode` 调用的 `on_unknown` 回调, 与解码模式一同由 `DecodeModeGuard` 管理.
+    static UNKNOWN_TAG_HOOK: RefCell<Option<Py<PyAny>>> = const { RefCell::new(None) };
+}
+
+/// 在作用域内设置解码模式与 `on_unknown` 回调, 离开时恢复先前取值.
+///
+/// 浮点数与嵌套 Struct 可出现在任意深度, 以线程局部状态代替逐层传参;
+/// 恢复旧值保证 `__post_init__` 中的嵌套解码互不影响.
+pub(crate) struct DecodeModeGuard(DecodeMode, Option<Py<PyAny>>);
+
+impl DecodeModeGuard {
+    /// 设置解码模式并清除 `on_unknown` 回调, 供不接受该回调的解码入口使用.
+    pub(crate) fn set(mode: DecodeMode) -> Self {
+        Self::with_unknown_hook(mode, None)
+    }
+
+    pub(crate) fn with_unknown_hook(mode: DecodeMode, on_unknown: Option<Py<PyAny>>) -> Self {
+        Self(
+            DECODE_MODE.with(|cell| cell.replace(mode)),
+            UNKNOWN_TAG_HOOK.with(|cell| cell.replace(on_unknown)),
+        )
+    }
+}
+
+impl Drop for DecodeModeGuard {
+    fn drop(&mut self) {
+        DECODE_MODE.with(|cell| cell.set(self.0));
+        let previous = self.1.take();
+        UNKNOWN_TAG_HOOK.with(|cell| *cell.borrow_mut() = previous);
+    }
+}
+
+/// 将解码出的浮点数转换为 Python 对象; `float_as_decimal` 模式下以最短往返文本构造 Decimal.
+pub(crate) fn float_to_py<'py>(
+    py: Python<'py>,
+    value: f64,
+    text: impl FnOnce() -> String,
+) -> DeResult<Bound<'py, PyAny>> {
+    if DECODE_MODE.with(Cell::get).as_decimal {
+        return with_stdlib_cache(py, |cache| cache.decimal_type.bind(py).call1((text(),)))
+            .map_err(DeError::wrap);
+    }
+    Ok(PyFloat::new(py, value).into_any())
+}
+
+/// 将线上的单精度 Float 转换为 Python 对象.
+///
+/// 默认直接提升为 f64, 会暴露二进制误差 (`1.1f32` 变为 `1.100000023841858`);
+/// `widen_float` 模式下改为按 f32 的最短十进制表示解析, 得到 `1.1`.
+pub(crate) fn f32_to_py<'py>(py: Python<'py>, value: f32) -> DeResult<Bound<'py, PyAny>> {
+    let widened = if DECODE_MODE.with(Cell::get).widen_f32 {
+        value.to_string().parse::<f64>().unwrap_or(value as f64)
+    } else {
+        value as f64
+    };
+    float_to_py(py, widened, || value.to_string())
+}
+
+/// `strict` 模式下拒绝把线上的单精度 Float 读入 double 字段.
+fn reject_strict_float_widening() -> DeResult<()> {
+    if DECODE_MODE.with(Cell::get).strict {
+        return Err(DeError::new(
+            "Strict mode: expected Double for double field, got Float".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// `keep_raw` 记录的字段字节范围: `(tag, 字段头偏移, 字段结束偏移)`.
+type RawSpan = (u8, u64, u64);
+
+/// 仅作用于顶层 Struct 的解码选项; 嵌套 Struct 使用 `Default`.
+#[derive(Default)]
+struct TopLevelOptions<'a, 'py> {
+    /// 将线上出现的字段名记录到实例的 `__tarsio_present__`.
+    track_presence: bool,
+    /// 读完字段后写入出现的字段与未知 Tag, 由调用方在解码成功后合并.
+    coverage: Option<&'a mut CoverageHit>,
+    /// 就地写入的已有实例 (`decode_into`), None 时新分配对象.
+    target: Option<&'a Bound<'py, PyAny>>,
+    /// `keep_raw` 请求的 tag 与记录到的字段字节范围.
+    keep_raw: Option<(&'a HashSet<u8>, &'a mut Vec<RawSpan>)>,
 }
 
 /// 从读取器中反序列化结构体.
@@ -75,23 +1046,37 @@ fn deserialize_struct<'py>(
     reader: &mut TarsReader,
     def: &StructDef,
     depth: usize,
+    options: TopLevelOptions<'_, 'py>,
 ) -> DeResult<Bound<'py, PyAny>> {
+    let TopLevelOptions {
+        track_presence,
+        coverage,
+        target,
+        mut keep_raw,
+    } = options;
     check_depth(depth).map_err(DeError::wrap)?;
 
     let field_count = def.fields_sorted.len();
 
-    // 预分配 Python 对象
-    // SAFETY:
-    // 1. `cls` 是有效的 Python 类型对象；`PyType_GenericAlloc` 返回新引用。
-    // 2. 若返回空指针则 Python 异常已设置，立即以 `PyErr::fetch` 包装返回。
-    // 3. `Bound::from_owned_ptr` 正确接管该新引用所有权。
-    let instance = unsafe {
-        let type_ptr = cls.as_ptr() as *mut ffi::PyTypeObject;
-        let obj_ptr = ffi::PyType_GenericAlloc(type_ptr, 0);
-        if obj_ptr.is_null() {
-            return Err(DeError::wrap(PyErr::fetch(py)));
-        }
-        Bound::from_owned_ptr(py, obj_ptr)
+    let sink = if let Some(target) = target {
+        StructSink::Instance(target.clone())
+    } else if def.is_pydantic {
+        StructSink::Kwargs(PyDict::new(py))
+    } else {
+        // 预分配 Python 对象
+        // SAFETY:
+        // 1. `cls` 是有效的 Python 类型对象；`PyType_GenericAlloc` 返回新引用。
+        // 2. 若返回空指针则 Python 异常已设置，立即以 `PyErr::fetch` 包装返回。
+        // 3. `Bound::from_owned_ptr` 正确接管该新引用所有权。
+        let instance = unsafe {
+            let type_ptr = cls.as_ptr() as *mut ffi::PyTypeObject;
+            let obj_ptr = ffi::PyType_GenericAlloc(type_ptr, 0);
+            if obj_ptr.is_null() {
+                return Err(DeError::wrap(PyErr::fetch(py)));
+            }
+            Bound::from_owned_ptr(py, obj_ptr)
+        };
+        StructSink::Instance(instance)
     };
 
     // 使用位掩码追踪已见字段 (支持高达 64 个字段)
@@ -102,8 +1087,13 @@ fn deserialize_struct<'py>(
         None
     };
 
+    // 统计字段读取次数 (含重复 Tag), 防止重复 Tag 放大解码开销
+    let mut fields_read: usize = 0;
+    let mut unknown_tags: Vec<u8> = Vec::new();
+
     // 读取字段,直到遇到 StructEnd 或 EOF
     while !reader.is_end() {
+        let head_offset = reader.position();
         let (tag, type_id) = match reader.read_head() {
             Ok(h) => h,
             Err(_) => break,
@@ -113,6 +1103,14 @@ fn deserialize_struct<'py>(
             break;
         }
 
+        fields_read += 1;
+        if fields_read > MAX_FIELDS_READ {
+            return Err(DeError::new(format!(
+                "Too many fields read for struct '{}' (max={}), payload repeats tags",
+                def.name, MAX_FIELDS_READ
+            )));
+        }
+
         let idx_opt = if (tag as usize) < def.tag_lookup_vec.len() {
             def.tag_lookup_vec[tag as usize]
         } else {
@@ -122,9 +1120,12 @@ fn deserialize_struct<'py>(
         if let Some(idx) = idx_opt {
             let field = &def.fields_sorted[idx];
             let value_result: DeResult<Bound<'py, PyAny>> = if field.wrap_simplelist {
-                if type_id != TarsType::SimpleList {
+                if type_id == TarsType::StructBegin {
+                    // 兼容未包装的生产方: 内联 Struct 按普通嵌套字段解码.
+                    deserialize_value(py, reader, type_id, &field.ty, None, depth + 1)
+                } else if type_id != TarsType::SimpleList {
                     Err(DeError::new(format!(
-                        "Field '{}' expects SimpleList(bytes) payload",
+                        "Field '{}' expects SimpleList(bytes) or inline Struct payload",
                         field.name
                     )))
                 } else {
@@ -138,28 +1139,17 @@ fn deserialize_struct<'py>(
                         let payload = reader.read_bytes(len).map_err(|e| {
                             DeError::new(format!("Failed to read SimpleList bytes: {e}"))
                         })?;
-                        match &field.ty {
-                            TypeExpr::Struct(cls_obj) => {
-                                let nested_cls = class_from_type(py, cls_obj);
-                                let nested_def = ensure_schema_for_class(py, &nested_cls)
-                                    .map_err(DeError::wrap)?;
-                                let mut inner_reader = TarsReader::new(payload);
-                                let res = deserialize_struct(
-                                    py,
-                                    &nested_cls,
-                                    &mut inner_reader,
-                                    &nested_def,
-                                    depth + 1,
-                                )?;
-                                if !inner_reader.is_end() {
-                                    return Err(DeError::new(
-                                        "Trailing bytes after SimpleList decode".into(),
-                                    ));
-                                }
-                                Ok(res)
-                            }
+                        match &field.ty {
+                            TypeExpr::Struct(cls_obj) => decode_wrapped_struct(
+                                py,
+                                payload,
+                                reader.is_little_endian(),
+                                cls_obj,
+                                depth,
+                            ),
                             TypeExpr::TarsDict => {
-                                let mut inner_reader = TarsReader::new(payload);
+                                let mut inner_reader = TarsReader::new(payload)
+                                    .with_little_endian(reader.is_little_endian());
                                 let dict = crate::binding::codec::raw::decode_struct_fields(
                                     py,
                                     &mut inner_reader,
@@ -184,6 +1174,29 @@ fn deserialize_struct<'py>(
                         }
                     }
                 }
+            } else if let Some(compression) = field.compress {
+                let limit = DECODE_MODE
+                    .with(Cell::get)
+                    .max_decompressed
+                    .unwrap_or(DEFAULT_MAX_DECOMPRESSED);
+                deserialize_bytes_value(py, reader, type_id, None).and_then(|compressed| {
+                    compression.decompress(py, &compressed, limit).map_err(|e| {
+                        DeError::new(format!(
+                            "Failed to decompress {:?} payload: {}",
+                            compression, e
+                        ))
+                    })
+                })
+            } else if field.chunk_size.is_some() {
+                deserialize_chunked_bytes(py, reader, type_id)
+            } else if field.as_bytes {
+                deserialize_raw_string(py, reader, type_id)
+            } else if field.any_preserve_bool {
+                deserialize_any_preserving_bool(py, reader, type_id, depth + 1)
+            } else if let Some(endian) = field.endian {
+                deserialize_with_endian(py, reader, type_id, endian, &field.ty)
+            } else if let Some(wire) = field.wire {
+                deserialize_wire_int(py, reader, type_id, wire, &field.name)
             } else {
                 deserialize_value(
                     py,
@@ -201,23 +1214,14 @@ fn deserialize_struct<'py>(
                     .map_err(DeError::wrap)
                     .map_err(|e| e.prepend(PathItem::Field(field.name.clone())))?;
             }
+            let value = match &field.decoder {
+                Some(hook) => call_field_hook(field, "decoder", hook, &value)
+                    .map_err(DeError::wrap)
+                    .map_err(|e| e.prepend(PathItem::Field(field.name.clone())))?,
+                None => value,
+            };
 
-            // 直接设置属性
-            // SAFETY:
-            // 1. `instance`、字段名 `name_py`、以及 `value` 均为当前 GIL 下有效对象。
-            // 2. `PyObject_GenericSetAttr` 不窃取 `value` 引用。
-            // 3. 若返回非 0，Python 异常已设置并通过 `PyErr::fetch` 传播。
-            unsafe {
-                let name_py = field.name_py.bind(py);
-                let res = ffi::PyObject_GenericSetAttr(
-                    instance.as_ptr(),
-                    name_py.as_ptr(),
-                    value.as_ptr(),
-                );
-                if res != 0 {
-                    return Err(DeError::wrap(PyErr::fetch(py)));
-                }
-            }
+            sink.set(py, field, &value)?;
 
             if let Some(vec) = seen_vec.as_mut() {
                 vec[idx] = true;
@@ -225,6 +1229,14 @@ fn deserialize_struct<'py>(
                 seen_mask |= 1 << idx;
             }
         } else {
+            // 回调的返回值被忽略, 只有抛出的异常会中止解码.
+            let callback = UNKNOWN_TAG_HOOK
+                .with(|cell| cell.borrow().as_ref().map(|hook| hook.bind(py).clone()));
+            if let Some(callback) = callback {
+                callback
+                    .call1((tag, type_id as u8, head_offset))
+                    .map_err(DeError::passthrough)?;
+            }
             if def.forbid_unknown_tags {
                 return Err(DeError::new(format!(
                     "Unknown tag {} found in deserialization (forbid_unknown_tags=True)",
@@ -234,9 +1246,28 @@ fn deserialize_struct<'py>(
             reader
                 .skip_field(type_id)
                 .map_err(|e| DeError::new(format!("Failed to skip unknown tag {}: {}", tag, e)))?;
+            if coverage.is_some() {
+                unknown_tags.push(tag);
+            }
+        }
+
+        if let Some((tags, spans)) = keep_raw.as_mut()
+            && tags.contains(&tag)
+        {
+            spans.push((tag, head_offset, reader.position()));
         }
     }
 
+    if let Some(hit) = coverage {
+        hit.seen = (0..field_count)
+            .map(|idx| match &seen_vec {
+                Some(vec) => vec[idx],
+                None => (seen_mask & (1 << idx)) != 0,
+            })
+            .collect();
+        hit.unknown_tags = unknown_tags;
+    }
+
     // 处理未出现的字段 (默认值/必填检查)
     for (idx, field) in def.fields_sorted.iter().enumerate() {
         let is_seen = if let Some(vec) = &seen_vec {
@@ -262,25 +1293,24 @@ fn deserialize_struct<'py>(
             };
 
             if let Some(val) = value_opt {
-                // SAFETY:
-                // 1. 与上方字段写入相同，目标对象与属性名/属性值均有效。
-                // 2. C API 失败时异常由 Python 设置，立即抓取返回。
-                unsafe {
-                    let name_py = field.name_py.bind(py);
-                    let res = ffi::PyObject_GenericSetAttr(
-                        instance.as_ptr(),
-                        name_py.as_ptr(),
-                        val.as_ptr(),
-                    );
-                    if res != 0 {
-                        return Err(DeError::wrap(PyErr::fetch(py)));
-                    }
-                }
+                sink.set(py, field, &val)?;
             }
         }
     }
 
-    if let Err(err) = run_post_init(instance.as_any()) {
+    let instance = match sink {
+        StructSink::Instance(instance) => instance,
+        // pydantic 模型经 `__init__` 构造, 其校验错误 (ValueError 子类) 按普通解码错误包装.
+        StructSink::Kwargs(kwargs) => {
+            return cls.call((), Some(&kwargs)).map_err(DeError::wrap);
+        }
+    };
+
+    if track_presence {
+        record_presence(&instance, def, &seen_vec, seen_mask).map_err(DeError::wrap)?;
+    }
+
+    if let Err(err) = run_post_init(instance.as_any(), hook_context(py).as_ref()) {
         if err.is_instance_of::<pyo3::exceptions::PyTypeError>(py)
             || err.is_instance_of::<pyo3::exceptions::PyValueError>(py)
         {
@@ -292,6 +1322,68 @@ fn deserialize_struct<'py>(
     Ok(instance)
 }
 
+/// 将线上出现的字段名集合写入实例的 `__tarsio_present__`.
+fn record_presence(
+    instance: &Bound<'_, PyAny>,
+    def: &StructDef,
+    seen_vec: &Option<Vec<bool>>,
+    seen_mask: u64,
+) -> PyResult<()> {
+    let py = instance.py();
+    let names = def
+        .fields_sorted
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| match seen_vec {
+            Some(vec) => vec[*idx],
+            None => (seen_mask & (1 << idx)) != 0,
+        })
+        .map(|(_, field)| field.name_py.bind(py));
+    let present = PyFrozenSet::new(py, names)?;
+    instance.cast::<Struct>()?.borrow_mut().present = Some(present.unbind());
+    Ok(())
+}
+
+/// 结构体字段的写入目标.
+enum StructSink<'py> {
+    /// 预分配的 Struct 实例, 字段直接写入槽位.
+    Instance(Bound<'py, PyAny>),
+    /// 构造参数, 解码结束后经 `cls(**kwargs)` 创建实例; 键为 pydantic 构造别名或字段名.
+    Kwargs(Bound<'py, PyDict>),
+}
+
+impl<'py> StructSink<'py> {
+    #[inline]
+    fn set(&self, py: Python<'py>, field: &FieldDef, value: &Bound<'py, PyAny>) -> DeResult<()> {
+        let name_py = field.name_py.bind(py);
+        match self {
+            StructSink::Instance(instance) => {
+                // 直接设置属性
+                // SAFETY:
+                // 1. `instance`、字段名 `name_py`、以及 `value` 均为当前 GIL 下有效对象。
+                // 2. `PyObject_GenericSetAttr` 不窃取 `value` 引用。
+                // 3. 若返回非 0，Python 异常已设置并通过 `PyErr::fetch` 传播。
+                unsafe {
+                    let res = ffi::PyObject_GenericSetAttr(
+                        instance.as_ptr(),
+                        name_py.as_ptr(),
+                        value.as_ptr(),
+                    );
+                    if res != 0 {
+                        return Err(DeError::wrap(PyErr::fetch(py)));
+                    }
+                }
+                Ok(())
+            }
+            StructSink::Kwargs(kwargs) => match field.alias.as_deref() {
+                Some(alias) => kwargs.set_item(alias, value),
+                None => kwargs.set_item(name_py, value),
+            }
+            .map_err(DeError::wrap),
+        }
+    }
+}
+
 /// 根据 TypeExpr 反序列化单个值.
 fn deserialize_value<'py>(
     py: Python<'py>,
@@ -309,11 +1401,48 @@ fn deserialize_value<'py>(
         }
         TypeExpr::Any => decode_any_value(py, reader, type_id, depth),
         TypeExpr::Bytes => deserialize_bytes_value(py, reader, type_id, constraints),
+        TypeExpr::ByteArray => {
+            let bytes = deserialize_bytes_value(py, reader, type_id, constraints)?;
+            let bytes = bytes.cast::<PyBytes>().map_err(PyErr::from)?;
+            Ok(PyByteArray::new(py, bytes.as_bytes()).into_any())
+        }
+        TypeExpr::Complex => deserialize_complex(py, reader, type_id),
+        TypeExpr::IpAddress(_) => deserialize_ip_address(py, reader, type_id),
+        TypeExpr::Path(cls) => {
+            let text = deserialize_primitive(py, reader, type_id, &WireType::String, None)?;
+            cls.bind(py).call1((text,)).map_err(DeError::wrap)
+        }
         TypeExpr::NoneType => Ok(py.None().into_bound(py)),
         TypeExpr::Enum(enum_cls, inner) => {
             deserialize_enum(py, reader, type_id, enum_cls, inner, depth)
         }
-        TypeExpr::Set(inner) => deserialize_set(py, reader, type_id, inner, constraints, depth),
+        TypeExpr::EnumName(enum_cls) => deserialize_enum_name(py, reader, type_id, enum_cls, depth),
+        TypeExpr::Flag(flag_cls) => {
+            // `cls(value)` 会把多个成员的按位组合还原为复合成员, 而非按名称查找单一成员.
+            let value = deserialize_primitive(py, reader, type_id, &WireType::Long, None)?;
+            flag_cls.bind(py).call1((value,)).map_err(DeError::wrap)
+        }
+        TypeExpr::Literal(values, inner) => {
+            let value = deserialize_value(py, reader, type_id, inner, constraints, depth)?;
+            if !literal_contains(values, &value).map_err(DeError::wrap)? {
+                return Err(DeError::new(format!(
+                    "Value must be one of Literal[{}], got {}",
+                    literal_values_repr(py, values),
+                    value.repr().map(|r| r.to_string()).unwrap_or_default()
+                )));
+            }
+            Ok(value)
+        }
+        TypeExpr::Subclass(cls, inner) => {
+            let value = deserialize_value(py, reader, type_id, inner, constraints, depth)?;
+            cls.bind(py).call1((value,)).map_err(DeError::wrap)
+        }
+        TypeExpr::Set(inner) => {
+            deserialize_set(py, reader, type_id, inner, constraints, false, depth)
+        }
+        TypeExpr::FrozenSet(inner) => {
+            deserialize_set(py, reader, type_id, inner, constraints, true, depth)
+        }
         TypeExpr::Union(variants, _) => {
             decode_union_value(py, reader, type_id, variants, constraints, depth)
         }
@@ -330,9 +1459,11 @@ fn deserialize_value<'py>(
                 validate_length_constraints_raw(len, c, None).map_err(DeError::wrap)?;
             }
             if len != items.len() {
-                return Err(DeError::new(
-                    "Tuple length does not match annotation".into(),
-                ));
+                return Err(DeError::new(format!(
+                    "expected {}-tuple, got {} elements on wire",
+                    items.len(),
+                    len
+                )));
             }
             let tuple = build_fixed_tuple(py, reader, items, depth)?;
             let instance = cls.bind(py).call1(tuple).map_err(DeError::wrap)?;
@@ -344,7 +1475,7 @@ fn deserialize_value<'py>(
                     "Dataclass value must be encoded as Map".into(),
                 ));
             }
-            let len = read_size_non_negative(reader, "map")?;
+            let len = read_map_size_non_negative(reader)?;
             let dict = PyDict::new(py);
             for _ in 0..len {
                 let (_, kt) = reader
@@ -391,21 +1522,69 @@ fn deserialize_value<'py>(
         TypeExpr::Map(k_type, v_type) => {
             deserialize_map_value(py, reader, type_id, k_type, v_type, constraints, depth)
         }
-        TypeExpr::TypedDict => deserialize_map_value(
-            py,
-            reader,
-            type_id,
-            &TypeExpr::Primitive(WireType::String),
-            &TypeExpr::Any,
-            constraints,
-            depth,
-        ),
+        TypeExpr::DefaultDict(k_type, v_type, factory) => {
+            let dict =
+                deserialize_map_value(py, reader, type_id, k_type, v_type, constraints, depth)?;
+            py.import("collections")
+                .and_then(|m| m.getattr("defaultdict"))
+                .and_then(|cls| cls.call1((factory.bind(py), dict)))
+                .map_err(DeError::wrap)
+        }
+        TypeExpr::DictSubclass(k_type, v_type, cls) => {
+            let dict =
+                deserialize_map_value(py, reader, type_id, k_type, v_type, constraints, depth)?;
+            build_dict_subclass(cls.bind(py), &dict).map_err(DeError::wrap)
+        }
+        TypeExpr::TypedDict(aliases) => {
+            let value = deserialize_map_value(
+                py,
+                reader,
+                type_id,
+                &TypeExpr::Primitive(WireType::String),
+                &TypeExpr::Any,
+                constraints,
+                depth,
+            )?;
+            if let Ok(dict) = value.cast::<PyDict>() {
+                // 线上键为字段名, 结果 dict 改用 `Meta(alias=...)` 声明的公开键名.
+                for (name, alias) in aliases {
+                    if let Some(item) = dict.get_item(name).map_err(DeError::wrap)? {
+                        dict.del_item(name).map_err(DeError::wrap)?;
+                        dict.set_item(alias, item).map_err(DeError::wrap)?;
+                    }
+                }
+            }
+            Ok(value)
+        }
         TypeExpr::Optional(inner) => {
             deserialize_optional(py, reader, type_id, inner, constraints, depth)
         }
     }
 }
 
+/// 以解码得到的键值构造 `dict` 子类实例.
+///
+/// 优先调用 `cls(items)`; 若子类自定义的 `__init__` 不接受映射参数 (抛出 TypeError),
+/// 退回无参构造后以 `dict.update` 写入, 绕过子类可能覆盖的 `update`.
+fn build_dict_subclass<'py>(
+    cls: &Bound<'py, PyType>,
+    items: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = cls.py();
+    match cls.call1((items,)) {
+        Ok(instance) => Ok(instance),
+        Err(err) if err.is_instance_of::<pyo3::exceptions::PyTypeError>(py) => {
+            let Ok(instance) = cls.call0() else {
+                return Err(err);
+            };
+            let items = items.cast::<PyDict>()?;
+            instance.cast::<PyDict>()?.update(items.as_mapping())?;
+            Ok(instance)
+        }
+        Err(err) => Err(err),
+    }
+}
+
 fn deserialize_primitive<'py>(
     py: Python<'py>,
     reader: &mut TarsReader,
@@ -420,7 +1599,7 @@ fn deserialize_primitive<'py>(
                 .map_err(|e| DeError::new(format!("Failed to read int: {}", e)))?;
 
             if let Some(c) = constraints {
-                validate_numeric_constraints_raw(v as f64, c, None).map_err(DeError::wrap)?;
+                validate_int_constraints_raw(v as i128, c, None).map_err(DeError::wrap)?;
             }
 
             Ok(v.into_pyobject(py)
@@ -447,9 +1626,19 @@ fn deserialize_primitive<'py>(
                 validate_numeric_constraints_raw(v as f64, c, None).map_err(DeError::wrap)?;
             }
 
-            Ok(v.into_pyobject(py)
-                .map_err(|e| DeError::new(e.to_string()))?
-                .into_any())
+            f32_to_py(py, v)
+        }
+        WireType::Double if type_id == TarsType::Float => {
+            reject_strict_float_widening()?;
+            let v = reader
+                .read_float(type_id)
+                .map_err(|e| DeError::new(format!("Failed to read double: {}", e)))?;
+
+            if let Some(c) = constraints {
+                validate_numeric_constraints_raw(v as f64, c, None).map_err(DeError::wrap)?;
+            }
+
+            f32_to_py(py, v)
         }
         WireType::Double => {
             let v = reader
@@ -460,9 +1649,7 @@ fn deserialize_primitive<'py>(
                 validate_numeric_constraints_raw(v, c, None).map_err(DeError::wrap)?;
             }
 
-            Ok(v.into_pyobject(py)
-                .map_err(|e| DeError::new(e.to_string()))?
-                .into_any())
+            float_to_py(py, v, || v.to_string())
         }
         WireType::String => {
             let bytes = reader
@@ -482,6 +1669,157 @@ fn deserialize_primitive<'py>(
     }
 }
 
+/// 按字段声明的字节序读取数值字段 (`Meta(endian=...)`), 不受调用方 `little_endian` 影响.
+///
+/// 约束校验由调用方在字段值构造后统一执行.
+fn deserialize_with_endian<'py>(
+    py: Python<'py>,
+    reader: &mut TarsReader,
+    type_id: TarsType,
+    endian: Endian,
+    type_expr: &TypeExpr,
+) -> DeResult<Bound<'py, PyAny>> {
+    let outer = reader.is_little_endian();
+    reader.set_little_endian(endian == Endian::Little);
+    let result = read_endian_number(py, reader, type_id, endian, type_expr);
+    reader.set_little_endian(outer);
+    result
+}
+
+/// 以读取器当前字节序读取 int/float 字段值, 供 [`deserialize_with_endian`] 使用.
+fn read_endian_number<'py>(
+    py: Python<'py>,
+    reader: &mut TarsReader,
+    type_id: TarsType,
+    endian: Endian,
+    type_expr: &TypeExpr,
+) -> DeResult<Bound<'py, PyAny>> {
+    let obj = match type_expr {
+        TypeExpr::Primitive(WireType::Int | WireType::Long) => reader
+            .read_int(type_id)
+            .map_err(|e| DeError::new(format!("Failed to read int: {}", e)))?
+            .into_pyobject(py)
+            .map_err(|e| DeError::new(e.to_string()))?
+            .into_any(),
+        TypeExpr::Primitive(WireType::Float) => {
+            let v = reader
+                .read_float(type_id)
+                .map_err(|e| DeError::new(format!("Failed to read float: {}", e)))?;
+            f32_to_py(py, v)?
+        }
+        TypeExpr::Primitive(WireType::Double) if type_id == TarsType::Float => {
+            reject_strict_float_widening()?;
+            let v = reader
+                .read_float(type_id)
+                .map_err(|e| DeError::new(format!("Failed to read double: {}", e)))?;
+            f32_to_py(py, v)?
+        }
+        TypeExpr::Primitive(WireType::Double) => {
+            let v = reader
+                .read_double(type_id)
+                .map_err(|e| DeError::new(format!("Failed to read double: {}", e)))?;
+            float_to_py(py, v, || v.to_string())?
+        }
+        _ => {
+            return Err(DeError::new(format!(
+                "endian='{}' is only supported for int and float fields",
+                endian.name()
+            )));
+        }
+    };
+    Ok(obj)
+}
+
+/// 解码 `Meta(chunk_size=...)` 字段: 按顺序拼接 List 中的 SimpleList 块.
+///
+/// 也接受未分块的单个 SimpleList, 便于与不分块的对端互通.
+fn deserialize_chunked_bytes<'py>(
+    py: Python<'py>,
+    reader: &mut TarsReader,
+    type_id: TarsType,
+) -> DeResult<Bound<'py, PyAny>> {
+    if type_id == TarsType::SimpleList {
+        return deserialize_bytes_value(py, reader, type_id, None);
+    }
+    if type_id != TarsType::List {
+        return Err(DeError::new(
+            "Chunked bytes must be encoded as List of SimpleList".into(),
+        ));
+    }
+    let count = read_size_non_negative(reader, "List")?;
+    let mut buf = Vec::new();
+    for idx in 0..count {
+        let (_, chunk_type) = reader
+            .read_head()
+            .map_err(|e| DeError::new(format!("Failed to read chunk {} head: {}", idx, e)))?;
+        if chunk_type != TarsType::SimpleList {
+            return Err(DeError::new(format!(
+                "Chunk {} must be encoded as SimpleList, got {:?}",
+                idx, chunk_type
+            )));
+        }
+        let chunk = reader
+            .read_simplelist_bytes()
+            .map_err(|e| DeError::new(format!("Failed to read chunk {}: {}", idx, e)))?;
+        buf.extend_from_slice(chunk);
+    }
+    Ok(PyBytes::new(py, &buf).into_any())
+}
+
+/// 解码 `Meta(as_bytes=True)` 字段: 以 `bytes` 返回 String 的原始内容, 不做 UTF-8 校验.
+fn deserialize_raw_string<'py>(
+    py: Python<'py>,
+    reader: &mut TarsReader,
+    type_id: TarsType,
+) -> DeResult<Bound<'py, PyAny>> {
+    if !matches!(type_id, TarsType::String1 | TarsType::String4) {
+        return Err(DeError::new(format!(
+            "as_bytes field must be encoded as String, got {:?}",
+            type_id
+        )));
+    }
+    let raw = reader
+        .read_string(type_id)
+        .map_err(|e| DeError::new(format!("Failed to read string: {}", e)))?;
+    Ok(PyBytes::new(py, raw).into_any())
+}
+
+/// 解码 `Meta(any_preserve_bool=True)` 的 Any 字段: 标记结构体还原为 bool, 其余按 Any 解码.
+fn deserialize_any_preserving_bool<'py>(
+    py: Python<'py>,
+    reader: &mut TarsReader,
+    type_id: TarsType,
+    depth: usize,
+) -> DeResult<Bound<'py, PyAny>> {
+    let value = decode_any_value(py, reader, type_id, depth)?;
+    if let Ok(dict) = value.cast::<PyDict>()
+        && let Some(flag) = tagged_bool(dict).map_err(DeError::wrap)?
+    {
+        return Ok(PyBool::new(py, flag).to_owned().into_any());
+    }
+    Ok(value)
+}
+
+/// 解码 `Meta(wire=...)` 字段: 接受任意整数宽度, 无符号类型按声明宽度还原后校验范围.
+fn deserialize_wire_int<'py>(
+    py: Python<'py>,
+    reader: &mut TarsReader,
+    type_id: TarsType,
+    wire: WireInt,
+    field_name: &str,
+) -> DeResult<Bound<'py, PyAny>> {
+    let raw = reader
+        .read_int(type_id)
+        .map_err(|e| DeError::new(format!("Failed to read int: {}", e)))?;
+    let value = wire.from_wire(type_id, raw);
+    wire.check_range(field_name, value)
+        .map_err(|msg| DeError::wrap(ValidationError::new_err(msg)))?;
+    value
+        .into_pyobject(py)
+        .map(|v| v.into_any())
+        .map_err(|e| DeError::new(e.to_string()))
+}
+
 fn deserialize_enum<'py>(
     py: Python<'py>,
     reader: &mut TarsReader,
@@ -496,12 +1834,35 @@ fn deserialize_enum<'py>(
     Ok(enum_value)
 }
 
+/// 按成员名称解码枚举 (`enum_mode="name"`), 查找 `__members__` 以兼容别名.
+fn deserialize_enum_name<'py>(
+    py: Python<'py>,
+    reader: &mut TarsReader,
+    type_id: TarsType,
+    enum_cls: &pyo3::Py<PyType>,
+    depth: usize,
+) -> DeResult<Bound<'py, PyAny>> {
+    let name_type = TypeExpr::Primitive(WireType::String);
+    let name = deserialize_value(py, reader, type_id, &name_type, None, depth + 1)?;
+    let cls = enum_cls.bind(py);
+    let members = cls.getattr("__members__").map_err(DeError::wrap)?;
+    match members.get_item(&name) {
+        Ok(member) => Ok(member),
+        Err(_) => Err(DeError::new(format!(
+            "{} is not a valid {} member name",
+            name.repr().map_err(DeError::wrap)?,
+            cls.name().map_err(DeError::wrap)?
+        ))),
+    }
+}
+
 fn deserialize_set<'py>(
     py: Python<'py>,
     reader: &mut TarsReader,
     type_id: TarsType,
     inner: &TypeExpr,
     constraints: Option<&Constraints>,
+    frozen: bool,
     depth: usize,
 ) -> DeResult<Bound<'py, PyAny>> {
     if type_id != TarsType::List {
@@ -521,6 +1882,10 @@ fn deserialize_set<'py>(
         let item = deserialize_value(py, reader, item_type, inner, None, depth + 1)?;
         set.add(item).map_err(DeError::wrap)?;
     }
+    if frozen {
+        let frozen_set = PyFrozenSet::new(py, set.iter()).map_err(DeError::wrap)?;
+        return Ok(frozen_set.into_any());
+    }
     Ok(set.into_any())
 }
 
@@ -533,12 +1898,53 @@ fn deserialize_struct_value<'py>(
 ) -> DeResult<Bound<'py, PyAny>> {
     let nested_cls = class_from_type(py, cls_obj);
     let nested_def = ensure_schema_for_class(py, &nested_cls).map_err(DeError::wrap)?;
+    if type_id == TarsType::SimpleList && DECODE_MODE.with(Cell::get).accept_wrapped_structs {
+        let little_endian = reader.is_little_endian();
+        let payload = read_simple_list_bytes(reader)?;
+        return decode_wrapped_struct(py, payload, little_endian, cls_obj, depth);
+    }
     if type_id != TarsType::StructBegin {
         return Err(DeError::new(
             "Struct value must be encoded as Struct".into(),
         ));
     }
-    deserialize_struct(py, &nested_cls, reader, &nested_def, depth + 1)
+    deserialize_struct(
+        py,
+        &nested_cls,
+        reader,
+        &nested_def,
+        depth + 1,
+        TopLevelOptions::default(),
+    )
+}
+
+/// 将 SimpleList 中的字节解码为嵌套 Struct, 要求恰好消费全部字节.
+///
+/// `little_endian` 沿用外层读取器的字节序.
+fn decode_wrapped_struct<'py>(
+    py: Python<'py>,
+    payload: &[u8],
+    little_endian: bool,
+    cls_obj: &Py<PyType>,
+    depth: usize,
+) -> DeResult<Bound<'py, PyAny>> {
+    let nested_cls = class_from_type(py, cls_obj);
+    let nested_def = ensure_schema_for_class(py, &nested_cls).map_err(DeError::wrap)?;
+    let mut inner_reader = TarsReader::new(payload).with_little_endian(little_endian);
+    let res = deserialize_struct(
+        py,
+        &nested_cls,
+        &mut inner_reader,
+        &nested_def,
+        depth + 1,
+        TopLevelOptions::default(),
+    )?;
+    if !inner_reader.is_end() {
+        return Err(DeError::new(
+            "Trailing bytes after SimpleList decode".into(),
+        ));
+    }
+    Ok(res)
 }
 
 fn deserialize_tarsdict_value<'py>(
@@ -588,6 +1994,72 @@ fn deserialize_bytes_value<'py>(
     Ok(PyBytes::new(py, bytes).into_any())
 }
 
+/// 按字节长度还原 `IPv4Address` (4 字节) 或 `IPv6Address` (16 字节).
+/// 解码 `complex`: Struct 中 tag 0 为实部、tag 1 为虚部, 缺失的部分按 0 处理.
+fn deserialize_complex<'py>(
+    py: Python<'py>,
+    reader: &mut TarsReader,
+    type_id: TarsType,
+) -> DeResult<Bound<'py, PyAny>> {
+    if type_id != TarsType::StructBegin {
+        return Err(DeError::new(format!(
+            "Complex value must be encoded as Struct, got {:?}",
+            type_id
+        )));
+    }
+    let mut parts = [0.0f64; 2];
+    loop {
+        let (tag, part_type) = reader
+            .read_head()
+            .map_err(|e| DeError::new(format!("Failed to read complex field: {}", e)))?;
+        if part_type == TarsType::StructEnd {
+            break;
+        }
+        match tag {
+            0 | 1 => {
+                if part_type == TarsType::Float {
+                    reject_strict_float_widening()?;
+                }
+                parts[tag as usize] = reader
+                    .read_double(part_type)
+                    .map_err(|e| DeError::new(format!("Failed to read double: {}", e)))?;
+            }
+            _ => reader
+                .skip_field(part_type)
+                .map_err(|e| DeError::new(format!("Failed to skip complex field: {}", e)))?,
+        }
+    }
+    Ok(PyComplex::from_doubles(py, parts[0], parts[1]).into_any())
+}
+
+fn deserialize_ip_address<'py>(
+    py: Python<'py>,
+    reader: &mut TarsReader,
+    type_id: TarsType,
+) -> DeResult<Bound<'py, PyAny>> {
+    let packed = deserialize_bytes_value(py, reader, type_id, None)?;
+    let len = packed
+        .cast::<PyBytes>()
+        .map_err(PyErr::from)?
+        .as_bytes()
+        .len();
+    if len != 4 && len != 16 {
+        return Err(DeError::new(format!(
+            "IP address must be 4 or 16 bytes, got {}",
+            len
+        )));
+    }
+    with_stdlib_cache(py, |cache| {
+        let cls = if len == 4 {
+            &cache.ipv4_type
+        } else {
+            &cache.ipv6_type
+        };
+        cls.bind(py).call1((packed,))
+    })
+    .map_err(DeError::wrap)
+}
+
 fn deserialize_list_value<'py>(
     py: Python<'py>,
     reader: &mut TarsReader,
@@ -663,9 +2135,11 @@ fn deserialize_tuple_value<'py>(
         validate_length_constraints_raw(len, c, None).map_err(DeError::wrap)?;
     }
     if len != items.len() {
-        return Err(DeError::new(
-            "Tuple length does not match annotation".into(),
-        ));
+        return Err(DeError::new(format!(
+            "expected {}-tuple, got {} elements on wire",
+            items.len(),
+            len
+        )));
     }
     let tuple = build_fixed_tuple(py, reader, items, depth)?;
     Ok(tuple.into_any())
@@ -680,7 +2154,7 @@ fn deserialize_map_value<'py>(
     constraints: Option<&Constraints>,
     depth: usize,
 ) -> DeResult<Bound<'py, PyAny>> {
-    let len = read_size_non_negative(reader, "map")?;
+    let len = read_map_size_non_negative(reader)?;
 
     if let Some(c) = constraints {
         validate_length_constraints_raw(len, c, None).map_err(DeError::wrap)?;
@@ -692,6 +2166,7 @@ fn deserialize_map_value<'py>(
             .read_head()
             .map_err(|e| DeError::new(format!("Failed to read map key head: {}", e)))?;
         let key = deserialize_value(py, reader, kt, k_type, None, depth + 1)
+            .and_then(hashable_map_key)
             .map_err(|e| e.prepend(PathItem::Key("<key>".into())))?;
 
         let (_, vt) = reader
@@ -800,16 +2275,135 @@ fn decode_union_value<'py>(
     constraints: Option<&Constraints>,
     depth: usize,
 ) -> DeResult<Bound<'py, PyAny>> {
-    for variant in variants {
-        if union_variant_matches_type_id(variant, type_id) {
-            return deserialize_value(py, reader, type_id, variant, constraints, depth + 1);
+    if type_id == TarsType::StructBegin
+        && let Some(variant) = select_struct_variant(py, reader, variants)?
+    {
+        return deserialize_value(py, reader, type_id, variant, constraints, depth + 1);
+    }
+    for (i, variant) in variants.iter().enumerate() {
+        if !union_variant_matches_type_id(variant, type_id) {
+            continue;
+        }
+        // 线上不区分 bool 与 int: 取值不是 0/1 时让给后续同样匹配的变体 (如 `Union[bool, int]`).
+        if matches!(variant, TypeExpr::Primitive(WireType::Bool))
+            && !wire_int_is_bool_like(reader, type_id)
+            && variants[i + 1..]
+                .iter()
+                .any(|rest| union_variant_matches_type_id(rest, type_id))
+        {
+            continue;
         }
+        return deserialize_value(py, reader, type_id, variant, constraints, depth + 1);
     }
     Err(DeError::new(
         "Union value does not match any variant".into(),
     ))
 }
 
+/// 预读当前整数值 (不移动读取位置), 判断其是否为 0 或 1.
+fn wire_int_is_bool_like(reader: &TarsReader, type_id: TarsType) -> bool {
+    matches!(
+        TarsReader::new(reader.remaining())
+            .with_little_endian(reader.is_little_endian())
+            .read_int(type_id),
+        Ok(0 | 1)
+    )
+}
+
+/// 为 Union 中的多个 Struct 变体按 payload 的字段结构选择目标类型.
+///
+/// 预扫描当前 Struct 的顶层字段头 (不移动读取位置), 依次尝试:
+/// 1. 所有 Tag 均为已知字段、线上类型兼容且必填字段齐全的第一个变体;
+/// 2. 忽略未知 Tag 后满足上述条件的第一个变体.
+///
+/// Struct 变体不足两个或均不匹配时返回 None, 交由按线上类型的常规分发处理.
+fn select_struct_variant<'a>(
+    py: Python<'_>,
+    reader: &TarsReader,
+    variants: &'a [TypeExpr],
+) -> DeResult<Option<&'a TypeExpr>> {
+    let mut candidates = Vec::new();
+    for variant in variants {
+        let inner = match variant {
+            TypeExpr::Optional(inner) => inner.as_ref(),
+            other => other,
+        };
+        if let TypeExpr::Struct(cls_obj) = inner {
+            let def = ensure_schema_for_class(py, &class_from_type(py, cls_obj))
+                .map_err(DeError::wrap)?;
+            candidates.push((variant, def));
+        }
+    }
+    if candidates.len() < 2 {
+        return Ok(None);
+    }
+
+    // 预扫描失败 (数据截断等) 时不做选择, 错误由随后的常规解码报告.
+    let mut scan =
+        TarsReader::new(reader.remaining()).with_little_endian(reader.is_little_endian());
+    let mut wire_fields = Vec::new();
+    loop {
+        let Ok((tag, type_id)) = scan.read_head() else {
+            return Ok(None);
+        };
+        if type_id == TarsType::StructEnd {
+            break;
+        }
+        wire_fields.push((tag, type_id));
+        if scan.skip_field(type_id).is_err() {
+            return Ok(None);
+        }
+    }
+
+    for allow_unknown in [false, true] {
+        for (variant, def) in &candidates {
+            if struct_accepts_wire_fields(def, &wire_fields, allow_unknown) {
+                return Ok(Some(*variant));
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn struct_accepts_wire_fields(
+    def: &StructDef,
+    wire_fields: &[(u8, TarsType)],
+    allow_unknown: bool,
+) -> bool {
+    let mut seen = vec![false; def.fields_sorted.len()];
+    for &(tag, type_id) in wire_fields {
+        match def.tag_lookup_vec.get(tag as usize).copied().flatten() {
+            Some(idx) => {
+                if !field_accepts_type_id(&def.fields_sorted[idx], type_id) {
+                    return false;
+                }
+                seen[idx] = true;
+            }
+            None if allow_unknown => {}
+            None => return false,
+        }
+    }
+    def.fields_sorted.iter().zip(seen).all(|(field, seen)| {
+        seen || !field.is_required
+            || field.is_optional
+            || field.default_value.is_some()
+            || field.default_factory.is_some()
+    })
+}
+
+fn field_accepts_type_id(field: &FieldDef, type_id: TarsType) -> bool {
+    if field.wrap_simplelist || field.compress.is_some() {
+        return type_id == TarsType::SimpleList;
+    }
+    if field.chunk_size.is_some() {
+        return matches!(type_id, TarsType::List | TarsType::SimpleList);
+    }
+    if field.wire.is_some() {
+        return union_variant_matches_type_id(&TypeExpr::Primitive(WireType::Int), type_id);
+    }
+    union_variant_matches_type_id(&field.ty, type_id)
+}
+
 fn union_variant_matches_type_id(variant: &TypeExpr, type_id: TarsType) -> bool {
     match variant {
         TypeExpr::Any => true,
@@ -832,28 +2426,41 @@ fn union_variant_matches_type_id(variant: &TypeExpr, type_id: TarsType) -> bool
                     | TarsType::Int8
             ),
             WireType::Float => matches!(type_id, TarsType::ZeroTag | TarsType::Float),
-            WireType::Double => matches!(
-                type_id,
-                TarsType::ZeroTag | TarsType::Float | TarsType::Double
-            ),
+            WireType::Double => match type_id {
+                TarsType::ZeroTag | TarsType::Double => true,
+                TarsType::Float => !DECODE_MODE.with(Cell::get).strict,
+                _ => false,
+            },
             WireType::String => matches!(type_id, TarsType::String1 | TarsType::String4),
             _ => false,
         },
-        TypeExpr::Enum(_, inner) => union_variant_matches_type_id(inner, type_id),
+        TypeExpr::Enum(_, inner) | TypeExpr::Literal(_, inner) | TypeExpr::Subclass(_, inner) => {
+            union_variant_matches_type_id(inner, type_id)
+        }
+        TypeExpr::EnumName(_) | TypeExpr::Path(_) => {
+            matches!(type_id, TarsType::String1 | TarsType::String4)
+        }
+        TypeExpr::Flag(_) => {
+            union_variant_matches_type_id(&TypeExpr::Primitive(WireType::Long), type_id)
+        }
         TypeExpr::Union(items, _) => items
             .iter()
             .any(|item| union_variant_matches_type_id(item, type_id)),
         TypeExpr::Struct(_) => type_id == TarsType::StructBegin,
-        TypeExpr::TarsDict => type_id == TarsType::StructBegin,
-        TypeExpr::Bytes => type_id == TarsType::SimpleList,
+        TypeExpr::TarsDict | TypeExpr::Complex => type_id == TarsType::StructBegin,
+        TypeExpr::Bytes | TypeExpr::ByteArray | TypeExpr::IpAddress(_) => {
+            type_id == TarsType::SimpleList
+        }
         TypeExpr::NamedTuple(_, _) => matches!(type_id, TarsType::List | TarsType::SimpleList),
         TypeExpr::Dataclass(_) => type_id == TarsType::Map,
         TypeExpr::List(_) | TypeExpr::VarTuple(_) | TypeExpr::Tuple(_) => {
             matches!(type_id, TarsType::List | TarsType::SimpleList)
         }
-        TypeExpr::Set(_) => type_id == TarsType::List,
-        TypeExpr::Map(_, _) => type_id == TarsType::Map,
-        TypeExpr::TypedDict => type_id == TarsType::Map,
+        TypeExpr::Set(_) | TypeExpr::FrozenSet(_) => type_id == TarsType::List,
+        TypeExpr::Map(_, _) | TypeExpr::DefaultDict(_, _, _) | TypeExpr::DictSubclass(_, _, _) => {
+            type_id == TarsType::Map
+        }
+        TypeExpr::TypedDict(_) => type_id == TarsType::Map,
         TypeExpr::Optional(inner) => union_variant_matches_type_id(inner, type_id),
     }
 }
@@ -1,4 +1,6 @@
+pub mod coverage;
 pub mod de;
+pub mod probe;
 pub mod raw;
 pub mod ser;
 pub mod trace;
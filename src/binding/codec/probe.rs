@@ -0,0 +1,328 @@
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+use simdutf8::basic::from_utf8;
+use std::collections::BTreeMap;
+
+use crate::binding::utils::MAX_DEPTH;
+use crate::codec::consts::TarsType;
+use crate::codec::reader::TarsReader;
+
+/// 从线上数据推断出的候选类型.
+#[derive(Clone, PartialEq)]
+enum Guess {
+    /// 无法观测到元素的类型 (如空 list/map).
+    Any,
+    Int,
+    Float,
+    Str,
+    Bytes,
+    /// SimpleList 中的字节恰好是合法 UTF-8, 可能是 bytes 或 str.
+    BytesOrStr,
+    /// SimpleList 中的字节可完整解析为 Struct (`wrap_simplelist` 字段).
+    BytesOrStruct(BTreeMap<u8, Guess>),
+    Struct(BTreeMap<u8, Guess>),
+    List(Box<Guess>),
+    Map(Box<Guess>, Box<Guess>),
+    Union(Vec<Guess>),
+}
+
+impl Guess {
+    /// 合并两次观测的结果; 无法统一时退化为 Union.
+    fn merge(self, other: Guess) -> Guess {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Guess::Any, b) => b,
+            (a, Guess::Any) => a,
+            (Guess::Struct(a), Guess::Struct(b)) => Guess::Struct(merge_fields(a, b)),
+            (Guess::BytesOrStruct(a), Guess::BytesOrStruct(b)) => {
+                Guess::BytesOrStruct(merge_fields(a, b))
+            }
+            (Guess::List(a), Guess::List(b)) => Guess::List(Box::new(a.merge(*b))),
+            (Guess::Map(ak, av), Guess::Map(bk, bv)) => {
+                Guess::Map(Box::new(ak.merge(*bk)), Box::new(av.merge(*bv)))
+            }
+            (Guess::Union(mut items), other) => {
+                push_alternative(&mut items, other);
+                Guess::Union(items)
+            }
+            (a, Guess::Union(items)) => {
+                let mut merged = vec![a];
+                for item in items {
+                    push_alternative(&mut merged, item);
+                }
+                Guess::Union(merged)
+            }
+            (a, b) => Guess::Union(vec![a, b]),
+        }
+    }
+
+    fn render(&self, out: &mut String) {
+        match self {
+            Guess::Any => out.push_str("Any"),
+            Guess::Int => out.push_str("int"),
+            Guess::Float => out.push_str("float"),
+            Guess::Str => out.push_str("str"),
+            Guess::Bytes => out.push_str("bytes"),
+            Guess::BytesOrStr => out.push_str("bytes | str"),
+            Guess::BytesOrStruct(fields) => {
+                out.push_str("bytes | ");
+                render_struct(fields, out);
+            }
+            Guess::Struct(fields) => render_struct(fields, out),
+            Guess::List(inner) => {
+                out.push_str("list[");
+                inner.render(out);
+                out.push(']');
+            }
+            Guess::Map(k, v) => {
+                out.push_str("dict[");
+                k.render(out);
+                out.push_str(", ");
+                v.render(out);
+                out.push(']');
+            }
+            Guess::Union(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(" | ");
+                    }
+                    item.render(out);
+                }
+            }
+        }
+    }
+}
+
+fn push_alternative(items: &mut Vec<Guess>, guess: Guess) {
+    if !items.contains(&guess) {
+        items.push(guess);
+    }
+}
+
+fn merge_fields(mut a: BTreeMap<u8, Guess>, b: BTreeMap<u8, Guess>) -> BTreeMap<u8, Guess> {
+    for (tag, guess) in b {
+        let merged = match a.remove(&tag) {
+            Some(existing) => existing.merge(guess),
+            None => guess,
+        };
+        a.insert(tag, merged);
+    }
+    a
+}
+
+fn render_struct(fields: &BTreeMap<u8, Guess>, out: &mut String) {
+    out.push_str("Struct{");
+    for (i, (tag, guess)) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        out.push_str(&tag.to_string());
+        out.push_str(": ");
+        guess.render(out);
+    }
+    out.push('}');
+}
+
+/// 读取 Struct 字段直到 StructEnd 或数据末尾.
+fn guess_struct(
+    reader: &mut TarsReader,
+    nested: bool,
+    depth: usize,
+) -> Option<BTreeMap<u8, Guess>> {
+    if depth >= MAX_DEPTH {
+        return None;
+    }
+    let mut fields = BTreeMap::new();
+    while !reader.is_end() {
+        let (tag, type_id) = reader.read_head().ok()?;
+        if type_id == TarsType::StructEnd {
+            return nested.then_some(fields);
+        }
+        if fields.contains_key(&tag) {
+            return None;
+        }
+        let guess = guess_value(reader, type_id, depth + 1)?;
+        fields.insert(tag, guess);
+    }
+    (!nested).then_some(fields)
+}
+
+fn guess_value(reader: &mut TarsReader, type_id: TarsType, depth: usize) -> Option<Guess> {
+    if depth >= MAX_DEPTH {
+        return None;
+    }
+    match type_id {
+        TarsType::ZeroTag | TarsType::Int1 | TarsType::Int2 | TarsType::Int4 | TarsType::Int8 => {
+            reader.read_int(type_id).ok()?;
+            Some(Guess::Int)
+        }
+        TarsType::Float => {
+            reader.read_float(type_id).ok()?;
+            Some(Guess::Float)
+        }
+        TarsType::Double => {
+            reader.read_double(type_id).ok()?;
+            Some(Guess::Float)
+        }
+        TarsType::String1 | TarsType::String4 => {
+            let bytes = reader.read_string(type_id).ok()?;
+            Some(if from_utf8(bytes).is_ok() {
+                Guess::Str
+            } else {
+                Guess::Bytes
+            })
+        }
+        TarsType::StructBegin => guess_struct(reader, true, depth + 1).map(Guess::Struct),
+        TarsType::StructEnd => None,
+        TarsType::List => {
+            let len = usize::try_from(reader.read_size().ok()?).ok()?;
+            let mut inner = Guess::Any;
+            for _ in 0..len {
+                let (_, item_type) = reader.read_head().ok()?;
+                inner = inner.merge(guess_value(reader, item_type, depth + 1)?);
+            }
+            Some(Guess::List(Box::new(inner)))
+        }
+        TarsType::Map => {
+            let len = usize::try_from(reader.read_size().ok()?).ok()?;
+            let mut key = Guess::Any;
+            let mut value = Guess::Any;
+            for _ in 0..len {
+                let (_, key_type) = reader.read_head().ok()?;
+                key = key.merge(guess_value(reader, key_type, depth + 1)?);
+                let (_, value_type) = reader.read_head().ok()?;
+                value = value.merge(guess_value(reader, value_type, depth + 1)?);
+            }
+            Some(Guess::Map(Box::new(key), Box::new(value)))
+        }
+        TarsType::SimpleList => {
+            let bytes = reader.read_simplelist_bytes().ok()?;
+            Some(guess_simplelist(bytes, depth))
+        }
+    }
+}
+
+/// SimpleList 只能观测到字节, 按可能性给出候选: 嵌套 Struct, UTF-8 文本, 或原始 bytes.
+fn guess_simplelist(bytes: &[u8], depth: usize) -> Guess {
+    if !bytes.is_empty() {
+        let mut inner = TarsReader::new(bytes);
+        if let Some(fields) = guess_struct(&mut inner, false, depth + 1)
+            && inner.is_end()
+            && !fields.is_empty()
+        {
+            return Guess::BytesOrStruct(fields);
+        }
+        if from_utf8(bytes).is_ok() {
+            return Guess::BytesOrStr;
+        }
+    }
+    Guess::Bytes
+}
+
+/// 根据原始字节推断候选 Schema.
+///
+/// 遍历 Tars 原始结构, 将观测到的线类型映射为最可能的 Python 类型,
+/// 输出形如 `Struct{0: int, 1: str, 2: list[int]}` 的类型树描述.
+/// 存在歧义时以 `|` 列出候选, 例如 SimpleList 可能是 `bytes | str`.
+///
+/// Args:
+///     data: 待分析的 Tars 二进制数据.
+///
+/// Returns:
+///     推断出的 Schema 描述; 数据不是完整的 Tars Struct 时返回 None.
+#[pyfunction]
+pub fn probe_schema(data: &[u8]) -> Option<String> {
+    let mut reader = TarsReader::new(data);
+    let fields = guess_struct(&mut reader, false, 0)?;
+    if !reader.is_end() || fields.is_empty() {
+        return None;
+    }
+    let mut out = String::new();
+    render_struct(&fields, &mut out);
+    Some(out)
+}
+
+/// 查找首个能解析为完整 Tars Struct 的偏移量.
+///
+/// 用于处理被生产方加了填充或哨兵字节的报文: 从偏移 0 开始逐字节尝试,
+/// 返回第一个使剩余数据恰好构成非空 Struct 的位置, 可直接作为 `decode` 的
+/// `skip_prefix` 参数.
+///
+/// Args:
+///     data: 待分析的 bytes.
+///     max_scan: 最多尝试的前缀长度.
+///
+/// Returns:
+///     前缀长度; 在扫描范围内未找到时返回 None.
+#[pyfunction]
+#[pyo3(signature = (data, max_scan=16))]
+pub fn detect_prefix(data: &[u8], max_scan: usize) -> Option<usize> {
+    (0..data.len().min(max_scan.saturating_add(1))).find(|&offset| {
+        let mut reader = TarsReader::new(&data[offset..]);
+        guess_struct(&mut reader, false, 0).is_some_and(|fields| !fields.is_empty())
+            && reader.is_end()
+    })
+}
+
+/// 读取一层 Struct 的字段头部, 记录 tag 与线类型, 跳过各字段的 payload.
+///
+/// `expand` 为 true 时展开嵌套 StructBegin 的直接字段 (不再继续向下展开).
+/// 遇到格式错误时停止, 返回已读取的部分; 附带的 bool 表示是否正常读到结尾
+/// (顶层为数据末尾, 嵌套层为 StructEnd).
+fn layout_fields<'py>(
+    py: Python<'py>,
+    reader: &mut TarsReader,
+    nested: bool,
+    expand: bool,
+) -> PyResult<(Bound<'py, PyList>, bool)> {
+    let fields = PyList::empty(py);
+    loop {
+        if reader.is_end() {
+            return Ok((fields, !nested));
+        }
+        let Ok((tag, type_id)) = reader.read_head() else {
+            return Ok((fields, false));
+        };
+        match type_id {
+            TarsType::StructEnd => return Ok((fields, nested)),
+            TarsType::StructBegin if expand => {
+                let (children, complete) = layout_fields(py, reader, true, false)?;
+                fields.append((tag, "struct", children))?;
+                if !complete {
+                    return Ok((fields, false));
+                }
+            }
+            TarsType::StructBegin => {
+                let complete = reader.skip_field(type_id).is_ok();
+                fields.append((tag, "struct", py.None()))?;
+                if !complete {
+                    return Ok((fields, false));
+                }
+            }
+            _ => {
+                if reader.skip_field(type_id).is_err() {
+                    return Ok((fields, false));
+                }
+                fields.append((tag, format!("{:?}", type_id)))?;
+            }
+        }
+    }
+}
+
+/// 列出报文顶层字段的 tag 与线类型, 不需要 Schema.
+///
+/// 只读取头部并跳过 payload, 用于逆向未知报文时快速查看字段布局.
+/// 普通字段返回 `(tag, type_name)`, `type_name` 为线类型名 (如 `"Int1"`、`"String1"`);
+/// 嵌套 StructBegin 返回 `(tag, "struct", children)`, 其中 `children` 为该结构体
+/// 直接字段的同类列表, 更深层的结构体不再展开, `children` 为 None.
+///
+/// Args:
+///     data: 待分析的 Tars 二进制数据.
+///
+/// Returns:
+///     按出现顺序排列的字段列表; 遇到格式错误时返回错误之前已读取的部分.
+#[pyfunction]
+pub fn guess_layout<'py>(py: Python<'py>, data: &[u8]) -> PyResult<Bound<'py, PyList>> {
+    let mut reader = TarsReader::new(data);
+    layout_fields(py, &mut reader, false, true).map(|(fields, _)| fields)
+}
@@ -4,19 +4,25 @@ use pyo3::ffi;
 use pyo3::prelude::*;
 use pyo3::types::{
     PyAny, PyBool, PyBytes, PyDict, PyFloat, PyFrozenSet, PyList, PySequence, PySet, PyString,
+    PyTuple,
 };
 use simdutf8::basic::from_utf8;
 use std::cell::RefCell;
 
 use smallvec::SmallVec;
 
+use crate::binding::codec::de::{DecodeMode, DecodeModeGuard, f32_to_py, float_to_py};
 use crate::binding::codec::ser;
 use crate::binding::error::{DeError, DeResult, PathItem};
-use crate::binding::ir::{StructDef, TypeExpr};
+use crate::binding::ir::{FieldDef, StructDef, TypeExpr};
 use crate::binding::schema::{TarsDict, ensure_schema_for_class};
 use crate::binding::utils::{
-    PySequenceFast, check_depth, check_exact_sequence_type, dataclass_fields, maybe_shrink_buffer,
-    try_coerce_buffer_to_bytes, with_stdlib_cache,
+    DepthLimitGuard, PySequenceFast, check_depth, check_exact_sequence_type, dataclass_fields,
+    maybe_shrink_buffer, try_coerce_buffer_to_bytes, with_stdlib_cache,
+};
+use crate::binding::validation::{
+    call_field_hook, check_choices, check_fixed_len, coerce_bytes_field, coerce_enum_field,
+    normalize_str_field,
 };
 use crate::codec::consts::TarsType;
 use crate::codec::reader::TarsReader;
@@ -26,10 +32,15 @@ thread_local! {
     static RAW_ENCODE_BUFFER: RefCell<Vec<u8>> = RefCell::new(Vec::with_capacity(128));
 }
 
+/// 按 tag 顺序写出 Struct 的字段.
+///
+/// `skip` 与 `def.fields_sorted` 一一对应, 为 true 的字段不写出 (必填字段也不报错),
+/// 用于顶层 `encode(include=..., exclude=...)`; 嵌套 Struct 传 None.
 pub(crate) fn serialize_struct_fields<W, F>(
     writer: &mut TarsWriter<W>,
     obj: &Bound<'_, PyAny>,
     def: &StructDef,
+    skip: Option<&[bool]>,
     depth: usize,
     enable_wrap_simplelist: bool,
     serialize_typed: &F,
@@ -40,32 +51,56 @@ where
 {
     check_depth(depth)?;
 
-    for field in &def.fields_sorted {
+    for (idx, field) in def.fields_sorted.iter().enumerate() {
+        if skip.is_some_and(|skip| skip[idx]) {
+            continue;
+        }
         let value = obj.getattr(field.name_py.bind(obj.py())).ok();
 
         match value {
             Some(val) => {
                 if val.is_none() {
-                    // 可选字段为 None 时跳过
-                    continue;
-                }
-                if def.omit_defaults {
-                    if let Some(default_val) = &field.default_value {
-                        if val.eq(default_val.bind(obj.py()))? {
-                            continue;
-                        }
-                    } else if field.is_optional && val.is_none() {
+                    // 可选字段为 None 时跳过; omit_none=False 时仅跳过被 omit_defaults 视为默认值的 None.
+                    let is_default = def.omit_defaults
+                        && field
+                            .default_value
+                            .as_ref()
+                            .map_or(field.is_optional, |d| d.bind(obj.py()).is_none());
+                    if def.omit_none || is_default {
                         continue;
                     }
+                    return Err(PyValueError::new_err(format!(
+                        "Field '{}' is None but omit_none=False requires a value on the wire",
+                        field.name
+                    )));
                 }
+                if def.omit_defaults
+                    && let Some(default_val) = &field.default_value
+                    && val.eq(default_val.bind(obj.py()))?
+                {
+                    continue;
+                }
+                let val = match &field.encoder {
+                    Some(hook) => call_field_hook(field, "encoder", hook, &val)?,
+                    None => val,
+                };
                 if enable_wrap_simplelist && field.wrap_simplelist {
                     let payload = match &field.ty {
                         TypeExpr::Struct(cls_obj) => {
                             let cls = crate::binding::utils::class_from_type(obj.py(), cls_obj);
                             let nested_def = ensure_schema_for_class(obj.py(), &cls)?;
-                            ser::encode_struct_payload_to_vec(&val, &nested_def, depth + 1)?
+                            ser::encode_struct_payload_to_vec(
+                                &val,
+                                &nested_def,
+                                depth + 1,
+                                writer.is_little_endian(),
+                            )?
                         }
-                        TypeExpr::TarsDict => ser::encode_tarsdict_payload_to_vec(&val, depth + 1)?,
+                        TypeExpr::TarsDict => ser::encode_tarsdict_payload_to_vec(
+                            &val,
+                            depth + 1,
+                            writer.is_little_endian(),
+                        )?,
                         _ => {
                             return Err(PyTypeError::new_err(format!(
                                 "Field '{}' with wrap_simplelist=True must be Struct or TarsDict",
@@ -76,7 +111,41 @@ where
                     writer.write_bytes(field.tag, &payload);
                     continue;
                 }
-                serialize_typed(writer, field.tag, &field.ty, &val, depth + 1)?;
+                let val = coerce_bytes_field(field, coerce_enum_field(field, val)?)?;
+                let val = normalize_str_field(field, val)?;
+                check_fixed_len(field, &val)?;
+                check_choices(field, &val)?;
+                ser::check_tuple_field_len(field, &val)?;
+                if let Some(compression) = field.compress {
+                    let compressed = compression.compress(obj.py(), &val)?;
+                    let bytes = compressed.cast::<PyBytes>()?;
+                    writer.write_bytes(field.tag, bytes.as_bytes());
+                    continue;
+                }
+                if let Some(chunk_size) = field.chunk_size {
+                    ser::serialize_chunked_bytes(writer, field.tag, chunk_size, &val)?;
+                    continue;
+                }
+                if field.as_bytes
+                    && let Some(bytes) = try_coerce_buffer_to_bytes(&val)?
+                {
+                    writer.write_string_bytes(field.tag, bytes.as_bytes());
+                    continue;
+                }
+                if field.any_preserve_bool && val.is_instance_of::<PyBool>() {
+                    write_tagged_bool(writer, field.tag, val.is_truthy()?);
+                    continue;
+                }
+                if let Some(endian) = field.endian {
+                    ser::serialize_with_endian(writer, field.tag, endian, &field.ty, &val)?;
+                    continue;
+                }
+                if let Some(wire) = field.wire {
+                    ser::serialize_wire_int(writer, field, wire, &val)?;
+                    continue;
+                }
+                serialize_typed(writer, field.tag, &field.ty, &val, depth + 1)
+                    .map_err(|e| with_field_context(obj.py(), field, e))?;
             }
             None => {
                 if field.is_required {
@@ -91,6 +160,16 @@ where
     Ok(())
 }
 
+/// 为字段编码中的 TypeError 加上字段名前缀, 原异常保存在 `__cause__` 中.
+fn with_field_context(py: Python<'_>, field: &FieldDef, err: PyErr) -> PyErr {
+    if !err.get_type(py).is(py.get_type::<PyTypeError>()) {
+        return err;
+    }
+    let wrapped = PyTypeError::new_err(format!("Field '{}': {}", field.name, err.value(py)));
+    wrapped.set_cause(py, Some(err));
+    wrapped
+}
+
 pub(crate) fn write_tarsdict_fields<W, F>(
     writer: &mut TarsWriter<W>,
     dict: &Bound<'_, PyDict>,
@@ -121,6 +200,41 @@ where
     Ok(())
 }
 
+/// `Meta(any_preserve_bool=True)` 标记结构体中 tag 0 的取值.
+const BOOL_MARKER: &str = "__tarsio_bool__";
+
+/// 将 bool 写为 tarsio 专有的标记结构体 `{0: BOOL_MARKER, 1: 0 | 1}`.
+fn write_tagged_bool<W: BufMut>(writer: &mut TarsWriter<W>, tag: u8, value: bool) {
+    writer.write_tag(tag, TarsType::StructBegin);
+    writer.write_string(0, BOOL_MARKER);
+    writer.write_int(1, i64::from(value));
+    writer.write_tag(0, TarsType::StructEnd);
+}
+
+/// 识别 `write_tagged_bool` 写出的标记结构体, 不匹配时返回 None.
+pub(crate) fn tagged_bool(dict: &Bound<'_, PyDict>) -> PyResult<Option<bool>> {
+    if dict.len() != 2 {
+        return Ok(None);
+    }
+    let Some(marker) = dict.get_item(0)? else {
+        return Ok(None);
+    };
+    let Ok(marker) = marker.cast::<PyString>() else {
+        return Ok(None);
+    };
+    if marker.to_str()? != BOOL_MARKER {
+        return Ok(None);
+    }
+    let Some(flag) = dict.get_item(1)? else {
+        return Ok(None);
+    };
+    match flag.extract::<i64>() {
+        Ok(0) => Ok(Some(false)),
+        Ok(1) => Ok(Some(true)),
+        _ => Ok(None),
+    }
+}
+
 pub(crate) fn serialize_any<W, F>(
     writer: &mut TarsWriter<W>,
     tag: u8,
@@ -160,17 +274,28 @@ where
         return Ok(());
     }
 
-    let is_enum = with_stdlib_cache(value.py(), |cache| {
+    let is_stdlib = with_stdlib_cache(value.py(), |cache| {
         let py = value.py();
         if value.is_instance(cache.enum_type.bind(py).as_any())? {
             let inner = value.getattr("value")?;
             serialize_any(writer, tag, &inner, depth + 1, serialize_typed)?;
             return Ok(true);
         }
+        if value.is_instance(cache.ipv4_type.bind(py).as_any())?
+            || value.is_instance(cache.ipv6_type.bind(py).as_any())?
+        {
+            let packed = value.getattr("packed")?;
+            writer.write_bytes(tag, packed.cast::<PyBytes>()?.as_bytes());
+            return Ok(true);
+        }
+        if value.is_instance(cache.pure_path_type.bind(py).as_any())? {
+            writer.write_string(tag, value.str()?.to_str()?);
+            return Ok(true);
+        }
         Ok(false)
     })?;
 
-    if is_enum {
+    if is_stdlib {
         return Ok(());
     }
 
@@ -185,7 +310,7 @@ where
     let cls = value.get_type();
     if let Ok(def) = ensure_schema_for_class(value.py(), &cls) {
         writer.write_tag(tag, TarsType::StructBegin);
-        serialize_struct_fields(writer, value, &def, depth + 1, false, serialize_typed)?;
+        serialize_struct_fields(writer, value, &def, None, depth + 1, false, serialize_typed)?;
         writer.write_tag(0, TarsType::StructEnd);
         return Ok(());
     }
@@ -279,7 +404,25 @@ pub(crate) fn read_size_non_negative(reader: &mut TarsReader, context: &str) ->
     Ok(len)
 }
 
-fn read_simple_list_bytes<'a>(reader: &'a mut TarsReader) -> DeResult<&'a [u8]> {
+/// 读取 Map 长度, 并按每个条目至少 2 字节 (键头 + 值头) 校验剩余数据量.
+///
+/// 伪造的超大长度会在读取条目前立即报错, 避免逐条读取失败.
+#[inline]
+pub(crate) fn read_map_size_non_negative(reader: &mut TarsReader) -> DeResult<usize> {
+    let len = read_size_non_negative(reader, "map")?;
+    let remaining = reader.remaining().len();
+    if len.saturating_mul(2) > remaining {
+        return Err(DeError::new(format!(
+            "Invalid map size: declared length {} needs at least {} bytes, remaining {}",
+            len,
+            len.saturating_mul(2),
+            remaining
+        )));
+    }
+    Ok(len)
+}
+
+pub(crate) fn read_simple_list_bytes<'a>(reader: &'a mut TarsReader) -> DeResult<&'a [u8]> {
     let subtype = reader
         .read_u8()
         .map_err(|e| DeError::new(format!("Failed to read SimpleList subtype: {e}")))?;
@@ -313,17 +456,13 @@ pub(crate) fn decode_any_value<'py>(
             let v = reader
                 .read_float(type_id)
                 .map_err(|e| DeError::new(format!("Failed to read float: {e}")))?;
-            Ok(v.into_pyobject(py)
-                .map_err(|e| DeError::new(e.to_string()))?
-                .into_any())
+            f32_to_py(py, v)
         }
         TarsType::Double => {
             let v = reader
                 .read_double(type_id)
                 .map_err(|e| DeError::new(format!("Failed to read double: {e}")))?;
-            Ok(v.into_pyobject(py)
-                .map_err(|e| DeError::new(e.to_string()))?
-                .into_any())
+            float_to_py(py, v, || v.to_string())
         }
         TarsType::String1 | TarsType::String4 => {
             let bytes = reader
@@ -408,7 +547,7 @@ fn decode_any_map<'py>(
     depth: usize,
 ) -> DeResult<Bound<'py, PyAny>> {
     check_depth(depth).map_err(DeError::wrap)?;
-    let len = read_size_non_negative(reader, "map")?;
+    let len = read_map_size_non_negative(reader)?;
     let dict = PyDict::new(py);
     for _ in 0..len {
         let (_, kt) = reader
@@ -424,6 +563,7 @@ fn decode_any_map<'py>(
         let val = decode_any_value(py, reader, vt, depth + 1)
             .map_err(|e| e.prepend(PathItem::Key(key.to_string())))?;
 
+        let key = hashable_map_key(key)?;
         if key.hash().is_err() {
             return Err(DeError::new("Map key must be hashable".into()));
         }
@@ -432,6 +572,23 @@ fn decode_any_map<'py>(
     Ok(dict.into_any())
 }
 
+/// 将解码出的 list 形式 Map key 还原为 tuple.
+///
+/// tuple 在线上编码为 List, 无类型信息时会被还原为不可哈希的 list;
+/// 作为 key 时按 tuple 重建 (嵌套 list 一并转换), 其余值原样返回.
+pub(crate) fn hashable_map_key<'py>(key: Bound<'py, PyAny>) -> DeResult<Bound<'py, PyAny>> {
+    let Ok(list) = key.cast::<PyList>() else {
+        return Ok(key);
+    };
+    let items = list
+        .iter()
+        .map(hashable_map_key)
+        .collect::<DeResult<Vec<_>>>()?;
+    Ok(PyTuple::new(key.py(), items)
+        .map_err(DeError::wrap)?
+        .into_any())
+}
+
 fn decode_any_simple_list<'py>(
     py: Python<'py>,
     reader: &mut TarsReader,
@@ -442,30 +599,55 @@ fn decode_any_simple_list<'py>(
 
 /// 将 TarsDict 编码为 Tars 二进制数据.
 ///
+/// Tars 没有 bool 线类型, bool 按 int 写出; 传入 `bool_tags` 时,
+/// 顶层值为 bool 的 tag 会被记录到该 set 中, 供 `decode_raw` 还原.
+///
 /// Args:
 ///     obj: dict[int, TarsValue],tag 范围为 0-255.
+///     bool_tags: 可选的 set, 用于收集顶层 bool 值所在的 tag.
+///     little_endian: 为 True 时多字节数值按小端写出.
+///     max_depth: 嵌套深度上限; None 表示默认的 MAX_DEPTH.
 ///
 /// Returns:
 ///     编码后的 bytes.
 ///
 /// Raises:
 ///     TypeError: obj 不是 dict,或 tag 超出 0-255,或值类型不受支持.
-///     ValueError: 递归深度超过 MAX_DEPTH.
+///     ValueError: 递归深度超过 max_depth, 或 max_depth 为 0.
 #[pyfunction]
-pub fn encode_raw(py: Python<'_>, obj: &Bound<'_, PyAny>) -> PyResult<Py<PyBytes>> {
+#[pyo3(signature = (obj, bool_tags=None, *, little_endian=false, max_depth=None))]
+pub fn encode_raw(
+    py: Python<'_>,
+    obj: &Bound<'_, PyAny>,
+    bool_tags: Option<&Bound<'_, PySet>>,
+    little_endian: bool,
+    max_depth: Option<usize>,
+) -> PyResult<Py<PyBytes>> {
+    let _depth = DepthLimitGuard::set(max_depth)?;
     if let Ok(dict) = obj.cast::<PyDict>()
         && obj.is_instance_of::<TarsDict>()
     {
+        if let Some(tags) = bool_tags {
+            for (tag, value) in dict.iter() {
+                if value.is_instance_of::<PyBool>() {
+                    tags.add(tag)?;
+                }
+            }
+        }
         if dict.is_empty() {
             return Ok(PyBytes::new(py, &[]).unbind());
         }
-        return encode_raw_dict_to_pybytes(py, dict, 0);
+        return encode_raw_dict_to_pybytes(py, dict, 0, little_endian);
     }
 
-    encode_raw_value_to_pybytes(py, obj)
+    encode_raw_value_to_pybytes(py, obj, little_endian)
 }
 
-fn encode_raw_value_to_pybytes(py: Python<'_>, obj: &Bound<'_, PyAny>) -> PyResult<Py<PyBytes>> {
+fn encode_raw_value_to_pybytes(
+    py: Python<'_>,
+    obj: &Bound<'_, PyAny>,
+    little_endian: bool,
+) -> PyResult<Py<PyBytes>> {
     RAW_ENCODE_BUFFER.with(|cell| {
         let mut buffer = cell
             .try_borrow_mut()
@@ -473,7 +655,8 @@ fn encode_raw_value_to_pybytes(py: Python<'_>, obj: &Bound<'_, PyAny>) -> PyResu
         buffer.clear();
 
         {
-            let mut writer = TarsWriter::with_buffer(&mut *buffer);
+            let mut writer =
+                TarsWriter::with_buffer(&mut *buffer).with_little_endian(little_endian);
             encode_value(&mut writer, 0, obj, 0)?;
         }
 
@@ -489,6 +672,8 @@ fn encode_raw_value_to_pybytes(py: Python<'_>, obj: &Bound<'_, PyAny>) -> PyResu
 ///
 /// Args:
 ///     data: 待解码的 bytes.
+///     bool_tags: 可选的顶层 tag 集合 (通常来自 `encode_raw` 的记录),
+///         这些 tag 上值为 0/1 的 int 会还原为 bool.
 ///
 /// Returns:
 ///     解码后的 dict[int, TarsValue] (实际返回 TarsDict 实例).
@@ -496,10 +681,31 @@ fn encode_raw_value_to_pybytes(py: Python<'_>, obj: &Bound<'_, PyAny>) -> PyResu
 /// Raises:
 ///     ValueError: 数据格式不正确、存在 trailing bytes、或递归深度超过 MAX_DEPTH.
 #[pyfunction]
-pub fn decode_raw<'py>(py: Python<'py>, data: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyDict>> {
+#[pyo3(signature = (data, bool_tags=None))]
+pub fn decode_raw<'py>(
+    py: Python<'py>,
+    data: &Bound<'py, PyAny>,
+    bool_tags: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyDict>> {
     let bytes = try_coerce_buffer_to_bytes(data)?
         .ok_or_else(|| PyTypeError::new_err("argument 'data': expected a bytes-like object"))?;
-    decode_raw_from_bytes(py, bytes.as_bytes())
+    let _mode = DecodeModeGuard::set(DecodeMode::default());
+    let dict = decode_raw_from_bytes(py, bytes.as_bytes())?;
+    if let Some(tags) = bool_tags {
+        for tag in tags.try_iter()? {
+            let tag = tag?;
+            let Some(value) = dict.get_item(&tag)? else {
+                continue;
+            };
+            if value.is_instance_of::<PyBool>() {
+                continue;
+            }
+            if let Ok(v @ (0 | 1)) = value.extract::<i64>() {
+                dict.set_item(tag, v == 1)?;
+            }
+        }
+    }
+    Ok(dict)
 }
 
 pub fn decode_raw_from_bytes<'py>(py: Python<'py>, data: &[u8]) -> PyResult<Bound<'py, PyDict>> {
@@ -517,6 +723,7 @@ fn encode_raw_dict_to_pybytes(
     py: Python<'_>,
     dict: &Bound<'_, PyDict>,
     depth: usize,
+    little_endian: bool,
 ) -> PyResult<Py<PyBytes>> {
     check_depth(depth)?;
 
@@ -527,7 +734,7 @@ fn encode_raw_dict_to_pybytes(
         buffer.clear();
 
         {
-            let mut writer = TarsWriter::with_buffer(&mut *buffer);
+            let mut writer = TarsWriter::with_buffer(&mut *buffer).with_little_endian(little_endian);
             // Top-level object for encode_raw must be a Struct (dict[int, TarsValue])
             let mut fields: SmallVec<[(u8, Bound<'_, PyAny>); 16]> = SmallVec::with_capacity(dict.len());
             for (key, value) in dict.iter() {
@@ -624,7 +831,8 @@ fn decode_value<'py>(
     if type_id == TarsType::StructBegin {
         return decode_struct_fields(py, reader, true, depth + 1).map(|d| d.into_any());
     }
-    decode_any_value(py, reader, type_id, depth).map_err(|e| e.to_pyerr(py))
+    decode_any_value(py, reader, type_id, depth)
+        .map_err(|e| e.at_offset(reader.position()).to_pyerr(py))
 }
 
 /// 启发式探测字节数据是否为一个有效的 Tars Struct.
@@ -645,6 +853,7 @@ pub fn probe_struct<'py>(py: Python<'py>, data: &[u8]) -> Option<Bound<'py, PyDi
         return None;
     }
 
+    let _mode = DecodeModeGuard::set(DecodeMode::default());
     let mut reader = TarsReader::new(data);
     if let Ok(dict) = decode_struct_fields(py, &mut reader, true, 0)
         && reader.is_end()
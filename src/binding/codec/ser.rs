@@ -1,25 +1,57 @@
-use pyo3::exceptions::{PyRuntimeError, PyTypeError};
+use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::{PyAny, PyBytes, PyDict, PyFrozenSet, PySequence, PySet, PyString};
+use pyo3::types::{
+    PyAny, PyByteArray, PyBytes, PyComplex, PyDict, PyFrozenSet, PyList, PyMapping, PySequence,
+    PySet, PyString,
+};
 use std::cell::RefCell;
 
 use bytes::BufMut;
 
 use crate::binding::codec::raw::{serialize_any, serialize_struct_fields, write_tarsdict_fields};
-use crate::binding::ir::{StructDef, TypeExpr, UnionCache, WireType};
+use crate::binding::error::ValidationError;
+use crate::binding::ir::{Endian, FieldDef, StructDef, TypeExpr, UnionCache, WireInt, WireType};
 use crate::binding::schema::{TarsDict, ensure_schema_for_class};
 use crate::binding::utils::{
-    PySequenceFast, check_depth, check_exact_sequence_type, class_from_type, dataclass_fields,
-    maybe_shrink_buffer, try_coerce_buffer_to_bytes,
+    DepthLimitGuard, PySequenceFast, check_depth, check_exact_sequence_type, class_from_type,
+    dataclass_fields, maybe_shrink_buffer, try_coerce_buffer_to_bytes,
 };
-use crate::binding::validation::value_matches_type;
+use crate::binding::validation::{HookContextGuard, value_matches_type};
 use crate::codec::consts::TarsType;
-use crate::codec::writer::TarsWriter;
+use crate::codec::writer::{SizeCounter, TarsWriter};
 
 thread_local! {
     static ENCODE_BUFFER: RefCell<Vec<u8>> = RefCell::new(Vec::with_capacity(128));
 }
 
+/// 固定长度 tuple 的元素个数与标注不一致.
+fn tuple_len_error(expected: usize, got: usize) -> PyErr {
+    PyTypeError::new_err(format!("expected {}-tuple, got {} elements", expected, got))
+}
+
+/// 编码前检查 tuple 字段的元素个数, 错误信息带字段名.
+///
+/// 非序列值交由后续编码报告类型错误.
+pub(crate) fn check_tuple_field_len(field: &FieldDef, val: &Bound<'_, PyAny>) -> PyResult<()> {
+    let items: &[TypeExpr] = match &field.ty {
+        TypeExpr::Tuple(items) => items,
+        TypeExpr::Optional(inner) => match inner.as_ref() {
+            TypeExpr::Tuple(items) => items,
+            _ => return Ok(()),
+        },
+        _ => return Ok(()),
+    };
+    match val.len() {
+        Ok(len) if len != items.len() => Err(PyTypeError::new_err(format!(
+            "Field '{}': expected {}-tuple, got {} elements",
+            field.name,
+            items.len(),
+            len
+        ))),
+        _ => Ok(()),
+    }
+}
+
 fn serialize_tuple_like(
     writer: &mut TarsWriter<impl BufMut>,
     tag: u8,
@@ -27,33 +59,30 @@ fn serialize_tuple_like(
     val: &Bound<'_, PyAny>,
     depth: usize,
 ) -> PyResult<()> {
-    writer.write_tag(tag, TarsType::List);
     let expected = items.len();
     if let Some(is_list) = check_exact_sequence_type(val) {
         let seq_fast = PySequenceFast::new_exact(val, is_list)?;
         let len = seq_fast.len();
         if len != expected {
-            return Err(PyTypeError::new_err(
-                "Tuple value length does not match annotation",
-            ));
+            return Err(tuple_len_error(expected, len));
         }
+        writer.write_tag(tag, TarsType::List);
         writer.write_int(0, len as i64);
         for (idx, item_type) in items.iter().enumerate() {
             let item = seq_fast.get_item(val.py(), idx)?;
-            serialize_impl(writer, 0, item_type, &item, depth + 1)?;
+            serialize_element(writer, 0, item_type, &item, depth + 1)?;
         }
     } else {
         let seq = val.extract::<Bound<'_, PySequence>>()?;
         let len = seq.len()?;
         if len != expected {
-            return Err(PyTypeError::new_err(
-                "Tuple value length does not match annotation",
-            ));
+            return Err(tuple_len_error(expected, len));
         }
+        writer.write_tag(tag, TarsType::List);
         writer.write_int(0, len as i64);
         for (idx, item_type) in items.iter().enumerate() {
             let item = seq.get_item(idx)?;
-            serialize_impl(writer, 0, item_type, &item, depth + 1)?;
+            serialize_element(writer, 0, item_type, &item, depth + 1)?;
         }
     }
     Ok(())
@@ -63,22 +92,268 @@ fn serialize_tuple_like(
 ///
 /// Args:
 ///     obj: Struct 实例.
+///     little_endian: 为 True 时多字节数值按小端写出, 兼容部分旧客户端的 JCE 报文.
+///     context: 任意对象, 非 None 时以关键字参数 `context` 传给任意深度的
+///         `Meta(encoder=...)` 钩子; None 时钩子按原方式调用.
+///     max_depth: 嵌套深度上限; None 表示默认的 `MAX_DEPTH`.
+///     include: 顶层字段名集合, 非 None 时只写出其中的字段.
+///     exclude: 顶层字段名集合, 其中的字段不写出; 与 include 同时给出时取差集.
+///         被筛掉的必填字段不报错, 保留下来的字段仍受 `omit_defaults` 等配置约束.
 ///
 /// Returns:
 ///     编码后的 bytes.
 ///
 /// Raises:
-///     TypeError: obj 不是已注册的 Struct.
+///     TypeError: obj 不是已注册的 Struct, 或 include/exclude 不是字段名集合.
+///     ValueError: 缺少必填字段、类型不匹配、递归深度超过限制,
+///         或 include/exclude 含未知字段名.
+#[pyfunction]
+#[pyo3(signature = (
+    obj,
+    *,
+    little_endian=false,
+    context=None,
+    max_depth=None,
+    include=None,
+    exclude=None
+))]
+pub fn encode(
+    py: Python<'_>,
+    obj: &Bound<'_, PyAny>,
+    little_endian: bool,
+    context: Option<Py<PyAny>>,
+    max_depth: Option<usize>,
+    include: Option<&Bound<'_, PyAny>>,
+    exclude: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Py<PyBytes>> {
+    let _context = HookContextGuard::set(context);
+    let _depth = DepthLimitGuard::set(max_depth)?;
+    encode_object_to_pybytes(py, obj, little_endian, include, exclude)
+}
+
+/// 将 `include`/`exclude` 字段名集合转换为与 `fields_sorted` 对应的跳过掩码.
+///
+/// 两者均为 None 时返回 None, 编码路径不做任何筛选.
+fn field_skip_mask(
+    def: &StructDef,
+    include: Option<&Bound<'_, PyAny>>,
+    exclude: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Option<Vec<bool>>> {
+    if include.is_none() && exclude.is_none() {
+        return Ok(None);
+    }
+    let field_indices = |arg: &str, names: &Bound<'_, PyAny>| -> PyResult<Vec<usize>> {
+        if names.is_instance_of::<PyString>() {
+            return Err(PyTypeError::new_err(format!(
+                "{} must be a collection of field names, not str",
+                arg
+            )));
+        }
+        let mut indices = Vec::new();
+        for name in names.try_iter()? {
+            let name = name?;
+            let name = name.cast::<PyString>().map_err(|_| {
+                PyTypeError::new_err(format!("{} must contain field names (str)", arg))
+            })?;
+            let name = name.to_str()?;
+            let idx = def
+                .fields_sorted
+                .iter()
+                .position(|f| f.name == name)
+                .ok_or_else(|| {
+                    PyValueError::new_err(format!(
+                        "{}: unknown field '{}' for {}",
+                        arg, name, def.name
+                    ))
+                })?;
+            indices.push(idx);
+        }
+        Ok(indices)
+    };
+
+    let mut skip = vec![include.is_some(); def.fields_sorted.len()];
+    if let Some(include) = include {
+        for idx in field_indices("include", include)? {
+            skip[idx] = false;
+        }
+    }
+    if let Some(exclude) = exclude {
+        for idx in field_indices("exclude", exclude)? {
+            skip[idx] = true;
+        }
+    }
+    Ok(Some(skip))
+}
+
+/// 将 Struct 实例编码后追加到调用方提供的 bytearray, 省去生成中间 bytes 对象的拷贝.
+///
+/// Args:
+///     obj: Struct 实例.
+///     buffer: 目标 bytearray, 编码结果追加在已有内容之后.
+///     little_endian: 为 True 时多字节数值按小端写出.
+///     context: 非 None 时以关键字参数 `context` 传给字段 encoder 钩子.
+///     max_depth: 嵌套深度上限; None 表示默认的 `MAX_DEPTH`.
+///
+/// Returns:
+///     本次写入的字节数.
+///
+/// Raises:
+///     TypeError: obj 不是已注册的 Struct, 或 buffer 不是 bytearray.
 ///     ValueError: 缺少必填字段、类型不匹配、或递归深度超过限制.
+///     BufferError: buffer 正被 memoryview 等对象导出, 无法扩容.
 #[pyfunction]
-pub fn encode(py: Python<'_>, obj: &Bound<'_, PyAny>) -> PyResult<Py<PyBytes>> {
-    encode_object_to_pybytes(py, obj)
+#[pyo3(signature = (obj, buffer, *, little_endian=false, context=None, max_depth=None))]
+pub fn encode_into(
+    py: Python<'_>,
+    obj: &Bound<'_, PyAny>,
+    buffer: &Bound<'_, PyByteArray>,
+    little_endian: bool,
+    context: Option<Py<PyAny>>,
+    max_depth: Option<usize>,
+) -> PyResult<usize> {
+    let _context = HookContextGuard::set(context);
+    let _depth = DepthLimitGuard::set(max_depth)?;
+    let cls = obj.get_type();
+    let def = ensure_schema_for_class(py, &cls)?;
+    with_encoded_struct(obj, &def, None, little_endian, |bytes| {
+        let start = buffer.len();
+        buffer.resize(start + bytes.len())?;
+        // SAFETY:
+        // 1. `resize` 成功后 bytearray 至少有 `start + bytes.len()` 字节.
+        // 2. 取得切片到拷贝结束之间不执行任何 Python 代码, 缓冲区不会被重新分配.
+        unsafe {
+            buffer.as_bytes_mut()[start..].copy_from_slice(bytes);
+        }
+        Ok(bytes.len())
+    })
 }
 
-pub fn encode_object_to_pybytes(py: Python<'_>, obj: &Bound<'_, PyAny>) -> PyResult<Py<PyBytes>> {
+/// 批量编码同一 Struct 类的多个实例, Schema 只解析一次, 并复用同一块编码缓冲区.
+///
+/// `framed=True` 时输出单个 bytes, 每条记录前写 4 字节大端无符号长度 (不含长度头
+/// 本身), 长度头不受 `little_endian` 影响.
+///
+/// Args:
+///     objs: Struct 实例的可迭代对象, 所有元素必须是同一个类 (不接受子类混入).
+///     framed: 为 True 时返回带长度前缀的拼接结果, 否则返回逐条编码的 list.
+///     little_endian: 为 True 时多字节数值按小端写出.
+///     context: 非 None 时以关键字参数 `context` 传给字段 encoder 钩子.
+///     max_depth: 嵌套深度上限; None 表示默认的 `MAX_DEPTH`.
+///
+/// Returns:
+///     `framed=False` 时为与 `objs` 一一对应的 `list[bytes]`; 否则为单个 bytes.
+///
+/// Raises:
+///     TypeError: 元素不是已注册的 Struct, 或与第一个元素的类不同.
+///     ValueError: 缺少必填字段、类型不匹配、递归深度超过限制, 或单条记录超过 4 GiB.
+#[pyfunction]
+#[pyo3(signature = (objs, *, framed=false, little_endian=false, context=None, max_depth=None))]
+pub fn encode_many(
+    py: Python<'_>,
+    objs: &Bound<'_, PyAny>,
+    framed: bool,
+    little_endian: bool,
+    context: Option<Py<PyAny>>,
+    max_depth: Option<usize>,
+) -> PyResult<Py<PyAny>> {
+    let _context = HookContextGuard::set(context);
+    let _depth = DepthLimitGuard::set(max_depth)?;
+    let items = objs
+        .try_iter()?
+        .collect::<PyResult<Vec<Bound<'_, PyAny>>>>()?;
+
+    let Some(first) = items.first() else {
+        return Ok(if framed {
+            PyBytes::new(py, b"").into_any().unbind()
+        } else {
+            PyList::empty(py).into_any().unbind()
+        });
+    };
+    let cls = first.get_type();
+    for (idx, obj) in items.iter().enumerate() {
+        let obj_cls = obj.get_type();
+        if !obj_cls.is(&cls) {
+            return Err(PyTypeError::new_err(format!(
+                "encode_many expects instances of {}, got {} at index {}",
+                cls.name()?,
+                obj_cls.name()?,
+                idx
+            )));
+        }
+    }
+    let def = ensure_schema_for_class(py, &cls)?;
+
+    if framed {
+        let mut out = Vec::new();
+        for obj in &items {
+            with_encoded_struct(obj, &def, None, little_endian, |bytes| {
+                let len = u32::try_from(bytes.len()).map_err(|_| {
+                    PyValueError::new_err("encode_many: record too large for u32 length prefix")
+                })?;
+                out.extend_from_slice(&len.to_be_bytes());
+                out.extend_from_slice(bytes);
+                Ok(())
+            })?;
+        }
+        Ok(PyBytes::new(py, &out).into_any().unbind())
+    } else {
+        let list = PyList::empty(py);
+        for obj in &items {
+            let bytes = with_encoded_struct(obj, &def, None, little_endian, |bytes| {
+                Ok(PyBytes::new(py, bytes))
+            })?;
+            list.append(bytes)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+}
+
+#[cfg_attr(
+    feature = "trace",
+    tracing::instrument(
+        name = "tarsio.encode",
+        level = "debug",
+        skip_all,
+        fields(
+            class = tracing::field::Empty,
+            fields = tracing::field::Empty,
+            bytes = tracing::field::Empty
+        )
+    )
+)]
+pub fn encode_object_to_pybytes(
+    py: Python<'_>,
+    obj: &Bound<'_, PyAny>,
+    little_endian: bool,
+    include: Option<&Bound<'_, PyAny>>,
+    exclude: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Py<PyBytes>> {
     let cls = obj.get_type();
     let def = ensure_schema_for_class(py, &cls)?;
+    let skip = field_skip_mask(&def, include, exclude)?;
+    #[cfg(feature = "trace")]
+    tracing::Span::current()
+        .record("class", def.name.as_str())
+        .record("fields", def.fields_sorted.len());
+
+    with_encoded_struct(obj, &def, skip.as_deref(), little_endian, |bytes| {
+        #[cfg(feature = "trace")]
+        tracing::Span::current().record("bytes", bytes.len());
+        Ok(PyBytes::new(py, bytes).unbind())
+    })
+}
 
+/// 在线程局部缓冲区中编码 Struct 实例, 并把编码结果交给 `f` 处理.
+///
+/// 缓冲区在编码期间保持借用, 编码过程中再次进入 (如字段的 `__repr__` 触发 encode)
+/// 会抛出 `RuntimeError`.
+fn with_encoded_struct<R>(
+    obj: &Bound<'_, PyAny>,
+    def: &StructDef,
+    skip: Option<&[bool]>,
+    little_endian: bool,
+    f: impl FnOnce(&[u8]) -> PyResult<R>,
+) -> PyResult<R> {
     ENCODE_BUFFER.with(|cell| {
         let mut buffer = cell.try_borrow_mut().map_err(|_| {
             PyRuntimeError::new_err("Re-entrant encode detected: thread-local buffer is already borrowed. Possible cause: __repr__/__str__/__eq__ (e.g. debug printing, exception formatting) triggered encode during an ongoing encode.")
@@ -86,48 +361,79 @@ pub fn encode_object_to_pybytes(py: Python<'_>, obj: &Bound<'_, PyAny>) -> PyRes
         buffer.clear();
 
         {
-            let mut writer = TarsWriter::with_buffer(&mut *buffer);
-            serialize_struct_fields(
-                &mut writer,
-                obj,
-                &def,
-                0,
-                true,
-                &serialize_impl_standard,
-            )?;
+            let mut writer = TarsWriter::with_buffer(&mut *buffer).with_little_endian(little_endian);
+            serialize_struct_fields(&mut writer, obj, def, skip, 0, true, &serialize_impl_standard)?;
+            write_empty_marker(&mut writer, def);
         }
 
-        let result = PyBytes::new(py, &buffer[..]).unbind();
-
+        let result = f(&buffer[..]);
         maybe_shrink_buffer(&mut buffer);
-
-        Ok(result)
+        result
     })
 }
 
+/// 计算 Struct 实例编码后的字节数, 不分配输出缓冲区.
+///
+/// 与 [`encode_object_to_pybytes`] 走同一条编码路径, 只统计写出的字节, 结果与
+/// `len(encode(obj))` 一致; 压缩、`wrap_simplelist` 等需要先生成 payload 的字段
+/// 仍会为该字段分配临时缓冲区.
+pub(crate) fn encoded_struct_size(py: Python<'_>, obj: &Bound<'_, PyAny>) -> PyResult<usize> {
+    let cls = obj.get_type();
+    let def = ensure_schema_for_class(py, &cls)?;
+    let mut writer = TarsWriter::with_buffer(SizeCounter::new());
+    serialize_struct_fields(
+        &mut writer,
+        obj,
+        &def,
+        None,
+        0,
+        true,
+        &serialize_impl_standard,
+    )?;
+    if def.emit_empty_marker && writer.written_len() == 0 {
+        writer.write_tag(0, TarsType::StructEnd);
+    }
+    Ok(writer.written_len())
+}
+
 pub(crate) fn encode_struct_payload_to_vec(
     obj: &Bound<'_, PyAny>,
     def: &StructDef,
     depth: usize,
+    little_endian: bool,
 ) -> PyResult<Vec<u8>> {
     let mut payload = Vec::with_capacity(64);
     {
-        let mut nested_writer = TarsWriter::with_buffer(&mut payload);
+        let mut nested_writer =
+            TarsWriter::with_buffer(&mut payload).with_little_endian(little_endian);
         serialize_struct_fields(
             &mut nested_writer,
             obj,
             def,
+            None,
             depth + 1,
             true,
             &serialize_impl_standard,
         )?;
+        write_empty_marker(&mut nested_writer, def);
     }
     Ok(payload)
 }
 
+/// `emit_empty_marker=True` 时, 为未写出任何字段的顶层 payload 补一个 `StructEnd` 头部.
+///
+/// 解码读到 `StructEnd` 即结束字段循环, 因此标记会被自然忽略, 且不会与任何字段 Tag 冲突.
+#[inline]
+fn write_empty_marker(writer: &mut TarsWriter<&mut Vec<u8>>, def: &StructDef) {
+    if def.emit_empty_marker && writer.get_buffer().is_empty() {
+        writer.write_tag(0, TarsType::StructEnd);
+    }
+}
+
 pub(crate) fn encode_tarsdict_payload_to_vec(
     val: &Bound<'_, PyAny>,
     depth: usize,
+    little_endian: bool,
 ) -> PyResult<Vec<u8>> {
     if !val.is_instance_of::<TarsDict>() {
         return Err(PyTypeError::new_err("TarsDict value type mismatch"));
@@ -135,7 +441,8 @@ pub(crate) fn encode_tarsdict_payload_to_vec(
     let dict = val.cast::<PyDict>()?;
     let mut payload = Vec::with_capacity(64);
     {
-        let mut nested_writer = TarsWriter::with_buffer(&mut payload);
+        let mut nested_writer =
+            TarsWriter::with_buffer(&mut payload).with_little_endian(little_endian);
         write_tarsdict_fields(
             &mut nested_writer,
             dict,
@@ -160,17 +467,58 @@ pub(crate) fn serialize_impl(
         TypeExpr::Any => {
             serialize_any(writer, tag, val, depth + 1, &serialize_impl_standard)?;
         }
-        TypeExpr::Bytes => {
+        TypeExpr::Bytes | TypeExpr::ByteArray => {
             let bytes = try_coerce_buffer_to_bytes(val)?
                 .ok_or_else(|| PyTypeError::new_err("Bytes value must be bytes-like"))?;
             writer.write_bytes(tag, bytes.as_bytes());
         }
+        TypeExpr::Complex => {
+            // int/float 按数值塔视为虚部为 0 的复数.
+            let (real, imag) = match val.cast::<PyComplex>() {
+                Ok(c) => (c.real(), c.imag()),
+                Err(_) => (val.extract::<f64>()?, 0.0),
+            };
+            writer.write_tag(tag, TarsType::StructBegin);
+            writer.write_double(0, real);
+            writer.write_double(1, imag);
+            writer.write_tag(0, TarsType::StructEnd);
+        }
+        TypeExpr::IpAddress(cls) => {
+            if !val.is_instance(cls.bind(val.py()).as_any())? {
+                return Err(PyTypeError::new_err("IP address value type mismatch"));
+            }
+            let packed = val.getattr(pyo3::intern!(val.py(), "packed"))?;
+            writer.write_bytes(tag, packed.cast::<PyBytes>()?.as_bytes());
+        }
+        TypeExpr::Path(cls) => {
+            if !val.is_instance(cls.bind(val.py()).as_any())? {
+                return Err(PyTypeError::new_err("Path value type mismatch"));
+            }
+            writer.write_string(tag, val.str()?.to_str()?);
+        }
         TypeExpr::NoneType => {
             return Err(PyTypeError::new_err(
                 "NoneType must be encoded via Optional or Union",
             ));
         }
         TypeExpr::Enum(_, _) => serialize_enum(writer, tag, type_expr, val, depth)?,
+        TypeExpr::Literal(_, inner) | TypeExpr::Subclass(_, inner) => {
+            serialize_impl(writer, tag, inner, val, depth)?
+        }
+        TypeExpr::EnumName(enum_cls) => {
+            if !val.is_instance(enum_cls.bind(val.py()).as_any())? {
+                return Err(PyTypeError::new_err("Enum value type mismatch"));
+            }
+            let name = val.getattr("name")?;
+            writer.write_string(tag, name.cast::<PyString>()?.to_str()?);
+        }
+        TypeExpr::Flag(flag_cls) => {
+            if !val.is_instance(flag_cls.bind(val.py()).as_any())? {
+                return Err(PyTypeError::new_err("Flag value type mismatch"));
+            }
+            let value: i64 = val.getattr("value")?.extract()?;
+            writer.write_int(tag, value);
+        }
         TypeExpr::Union(_, _) => serialize_union(writer, tag, type_expr, val, depth)?,
         TypeExpr::Struct(_)
         | TypeExpr::TarsDict
@@ -178,25 +526,112 @@ pub(crate) fn serialize_impl(
         | TypeExpr::Dataclass(_) => {
             serialize_struct_like(writer, tag, type_expr, val, depth)?;
         }
-        TypeExpr::List(_) | TypeExpr::VarTuple(_) | TypeExpr::Tuple(_) | TypeExpr::Set(_) => {
+        TypeExpr::List(_)
+        | TypeExpr::VarTuple(_)
+        | TypeExpr::Tuple(_)
+        | TypeExpr::Set(_)
+        | TypeExpr::FrozenSet(_) => {
             serialize_list_like(writer, tag, type_expr, val, depth)?;
         }
-        TypeExpr::Map(_, _) => serialize_map_like(writer, tag, type_expr, val, depth)?,
-        TypeExpr::TypedDict => serialize_map_like(
-            writer,
-            tag,
-            &TypeExpr::Map(
+        TypeExpr::Map(_, _) | TypeExpr::DefaultDict(_, _, _) | TypeExpr::DictSubclass(_, _, _) => {
+            serialize_map_like(writer, tag, type_expr, val, depth)?
+        }
+        TypeExpr::TypedDict(aliases) => {
+            let map_type = TypeExpr::Map(
                 Box::new(TypeExpr::Primitive(WireType::String)),
                 Box::new(TypeExpr::Any),
-            ),
-            val,
-            depth,
-        )?,
+            );
+            match val.cast::<PyDict>() {
+                Ok(dict) if !aliases.is_empty() => {
+                    let wire_dict = typeddict_wire_dict(dict, aliases)?;
+                    serialize_map_like(writer, tag, &map_type, wire_dict.as_any(), depth)?
+                }
+                _ => serialize_map_like(writer, tag, &map_type, val, depth)?,
+            }
+        }
         TypeExpr::Optional(_) => serialize_optional(writer, tag, type_expr, val, depth)?,
     }
     Ok(())
 }
 
+/// 将 TypedDict 的 Python 侧键 (别名) 还原为线上使用的字段名.
+///
+/// 别名与字段名同时存在时以别名对应的值为准.
+fn typeddict_wire_dict<'py>(
+    dict: &Bound<'py, PyDict>,
+    aliases: &[(String, String)],
+) -> PyResult<Bound<'py, PyDict>> {
+    let out = dict.copy()?;
+    for (name, alias) in aliases {
+        if let Some(value) = dict.get_item(alias)? {
+            out.del_item(alias)?;
+            out.set_item(name, value)?;
+        }
+    }
+    Ok(out)
+}
+
+/// 按字段声明的字节序写出数值字段 (`Meta(endian=...)`), 不受调用方 `little_endian` 影响.
+///
+/// 编译期已保证字段类型为 int/float, 其余类型在此报错兜底.
+pub(crate) fn serialize_with_endian(
+    writer: &mut TarsWriter<impl BufMut>,
+    tag: u8,
+    endian: Endian,
+    type_expr: &TypeExpr,
+    val: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    let outer = writer.is_little_endian();
+    writer.set_little_endian(endian == Endian::Little);
+    let result = match type_expr {
+        TypeExpr::Primitive(WireType::Int | WireType::Long) => {
+            val.extract().map(|v| writer.write_int(tag, v))
+        }
+        TypeExpr::Primitive(WireType::Float) => val.extract().map(|v| writer.write_float(tag, v)),
+        TypeExpr::Primitive(WireType::Double) => val.extract().map(|v| writer.write_double(tag, v)),
+        _ => Err(PyTypeError::new_err(format!(
+            "endian='{}' is only supported for int and float fields",
+            endian.name()
+        ))),
+    };
+    writer.set_little_endian(outer);
+    result
+}
+
+/// 按 `Meta(wire=...)` 声明的固定宽度写出 int 字段, 超出该宽度范围时抛出 ValidationError.
+pub(crate) fn serialize_wire_int(
+    writer: &mut TarsWriter<impl BufMut>,
+    field: &FieldDef,
+    wire: WireInt,
+    val: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    let v: i64 = val.extract()?;
+    wire.check_range(&field.name, v)
+        .map_err(ValidationError::new_err)?;
+    writer.write_int_fixed(field.tag, v, wire.tars_type());
+    Ok(())
+}
+
+/// 按 `Meta(chunk_size=...)` 把 bytes 字段拆分为 SimpleList 块, 以 List 写出.
+///
+/// 每块最多 `chunk_size` 字节, 空 bytes 写为空 List.
+pub(crate) fn serialize_chunked_bytes(
+    writer: &mut TarsWriter<impl BufMut>,
+    tag: u8,
+    chunk_size: usize,
+    val: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    let bytes = try_coerce_buffer_to_bytes(val)?
+        .ok_or_else(|| PyTypeError::new_err("Expected bytes-like value for chunked bytes"))?;
+    let chunks = bytes.as_bytes().chunks(chunk_size);
+    writer.write_tag(tag, TarsType::List);
+    writer.write_int(0, chunks.len() as i64);
+    for chunk in chunks {
+        writer.write_bytes(0, chunk);
+    }
+    Ok(())
+}
+
 pub(crate) fn serialize_primitive(
     writer: &mut TarsWriter<impl BufMut>,
     tag: u8,
@@ -286,7 +721,15 @@ pub(crate) fn serialize_struct_like(
             let cls = class_from_type(val.py(), cls_obj);
             let def = ensure_schema_for_class(val.py(), &cls)?;
             writer.write_tag(tag, TarsType::StructBegin);
-            serialize_struct_fields(writer, val, &def, depth + 1, true, &serialize_impl_standard)?;
+            serialize_struct_fields(
+                writer,
+                val,
+                &def,
+                None,
+                depth + 1,
+                true,
+                &serialize_impl_standard,
+            )?;
             writer.write_tag(0, TarsType::StructEnd);
         }
         TypeExpr::TarsDict => {
@@ -354,7 +797,7 @@ pub(crate) fn serialize_list_like(
                 writer.write_int(0, len as i64);
                 for i in 0..len {
                     let item = seq_fast.get_item(val.py(), i)?;
-                    serialize_impl(writer, 0, inner, &item, depth + 1)?;
+                    serialize_element(writer, 0, inner, &item, depth + 1)?;
                 }
             } else {
                 let seq = val.extract::<Bound<'_, PySequence>>()?;
@@ -362,21 +805,21 @@ pub(crate) fn serialize_list_like(
                 writer.write_int(0, len as i64);
                 for i in 0..len {
                     let item = seq.get_item(i)?;
-                    serialize_impl(writer, 0, inner, &item, depth + 1)?;
+                    serialize_element(writer, 0, inner, &item, depth + 1)?;
                 }
             }
         }
         TypeExpr::Tuple(items) => {
             serialize_tuple_like(writer, tag, items, val, depth + 1)?;
         }
-        TypeExpr::Set(inner) => {
+        TypeExpr::Set(inner) | TypeExpr::FrozenSet(inner) => {
             writer.write_tag(tag, TarsType::List);
             if val.is_instance_of::<PySet>() {
                 let set = val.cast::<PySet>()?;
                 let len = set.len() as i64;
                 writer.write_int(0, len);
                 for item in set.iter() {
-                    serialize_impl(writer, 0, inner, &item, depth + 1)?;
+                    serialize_element(writer, 0, inner, &item, depth + 1)?;
                 }
                 return Ok(());
             }
@@ -385,7 +828,7 @@ pub(crate) fn serialize_list_like(
                 let len = set.len() as i64;
                 writer.write_int(0, len);
                 for item in set.iter() {
-                    serialize_impl(writer, 0, inner, &item, depth + 1)?;
+                    serialize_element(writer, 0, inner, &item, depth + 1)?;
                 }
                 return Ok(());
             }
@@ -403,33 +846,65 @@ pub(crate) fn serialize_map_like(
     val: &Bound<'_, PyAny>,
     depth: usize,
 ) -> PyResult<()> {
-    if let TypeExpr::Map(k_type, v_type) = type_expr {
+    if let TypeExpr::Map(k_type, v_type)
+    | TypeExpr::DefaultDict(k_type, v_type, _)
+    | TypeExpr::DictSubclass(k_type, v_type, _) = type_expr
+    {
         writer.write_tag(tag, TarsType::Map);
         if let Ok(dict) = val.extract::<Bound<'_, PyDict>>() {
             let len = dict.len();
             writer.write_int(0, len as i64);
 
             for (k, v) in dict {
-                serialize_impl(writer, 0, k_type, &k, depth + 1)?;
-                serialize_impl(writer, 1, v_type, &v, depth + 1)?;
+                serialize_element(writer, 0, k_type, &k, depth + 1)?;
+                serialize_element(writer, 1, v_type, &v, depth + 1)?;
             }
         } else if let Some(fields) = dataclass_fields(val)? {
             let len = fields.len();
             writer.write_int(0, len as i64);
             for (name_any, _field) in fields {
                 let value = val.getattr(name_any.cast::<PyString>()?)?;
-                serialize_impl(writer, 0, k_type, &name_any, depth + 1)?;
-                serialize_impl(writer, 1, v_type, &value, depth + 1)?;
+                serialize_element(writer, 0, k_type, &name_any, depth + 1)?;
+                serialize_element(writer, 1, v_type, &value, depth + 1)?;
+            }
+        } else if let Ok(mapping) = val.cast::<PyMapping>() {
+            // 非 dict 的 Mapping (MappingProxyType, ChainMap, 自定义 Mapping 等) 先经 items()
+            // 物化为列表, 长度以实际迭代结果为准, 避免 __len__ 与迭代不一致导致写出错误计数.
+            let items = mapping.items()?;
+            writer.write_int(0, items.len() as i64);
+            for item in items.iter() {
+                let (k, v) = item.extract::<(Bound<'_, PyAny>, Bound<'_, PyAny>)>()?;
+                serialize_element(writer, 0, k_type, &k, depth + 1)?;
+                serialize_element(writer, 1, v_type, &v, depth + 1)?;
             }
         } else {
             return Err(PyTypeError::new_err(
-                "Map value must be dict or dataclass instance",
+                "Map value must be a Mapping or dataclass instance",
             ));
         }
     }
     Ok(())
 }
 
+/// 写出容器元素 (List/Set/Tuple 的元素与 Map 的键值).
+///
+/// 字段级的 Optional 以省略字段表示 None, 但容器元素个数已写入长度前缀, 省略会导致后续元素错位;
+/// Tars 也没有标准的空值标记, 因此 Optional 元素为 None 时直接报错.
+pub(crate) fn serialize_element(
+    writer: &mut TarsWriter<impl BufMut>,
+    tag: u8,
+    type_expr: &TypeExpr,
+    val: &Bound<'_, PyAny>,
+    depth: usize,
+) -> PyResult<()> {
+    if val.is_none() && type_expr.is_optional() {
+        return Err(PyValueError::new_err(
+            "None is not supported as a container element: Tars has no null marker",
+        ));
+    }
+    serialize_impl(writer, tag, type_expr, val, depth)
+}
+
 pub(crate) fn serialize_optional(
     writer: &mut TarsWriter<impl BufMut>,
     tag: u8,
@@ -473,7 +948,14 @@ fn select_union_variant<'py>(
                 return Ok(variant);
             }
         }
-        return Err(PyTypeError::new_err("Union does not accept None"));
+        return Err(PyTypeError::new_err(format!(
+            "None given but Union[{}] doesn't include None; use Optional",
+            variants
+                .iter()
+                .map(|v| v.annotation_repr(py))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
     }
 
     // 1. O(1) Lookup: 检查缓存中是否存在精确类型匹配
@@ -17,6 +17,10 @@ pub struct TraceNode {
     pub name: Option<String>,
     pub type_name: Option<String>,
     pub path: String,
+    /// 节点在输入中的起始偏移 (字段头所在位置).
+    pub start: u64,
+    /// 节点在输入中的结束偏移 (不含), 容器节点覆盖全部子节点.
+    pub end: u64,
 }
 
 #[pymethods]
@@ -44,6 +48,8 @@ impl TraceNode {
         dict.set_item("name", &self.name)?;
         dict.set_item("type_name", &self.type_name)?;
         dict.set_item("path", &self.path)?;
+        dict.set_item("start", self.start)?;
+        dict.set_item("end", self.end)?;
         Ok(dict.into())
     }
 }
@@ -74,6 +80,8 @@ pub fn decode_trace<'py>(
             name: None,
             type_name: cls.and_then(|c| c.name().ok().map(|s| s.to_string())),
             path: "<root>".to_string(),
+            start: 0,
+            end: 0,
         },
     )?;
 
@@ -166,7 +174,7 @@ fn type_hint_from_expr(py: Python<'_>, type_expr: Option<&TypeExpr>) -> Option<T
         TypeExpr::List(inner) => {
             type_hint_from_expr(py, Some(inner.as_ref())).map(|v| TraceTypeHint::List(Box::new(v)))
         }
-        TypeExpr::Map(k, v) => {
+        TypeExpr::Map(k, v) | TypeExpr::DefaultDict(k, v, _) | TypeExpr::DictSubclass(k, v, _) => {
             let kh = type_hint_from_expr(py, Some(k.as_ref()))?;
             let vh = type_hint_from_expr(py, Some(v.as_ref()))?;
             Some(TraceTypeHint::Map(Box::new(kh), Box::new(vh)))
@@ -184,15 +192,22 @@ fn run_trace_frames<'py>(
         match frame {
             TraceFrame::Struct(frame) => {
                 check_trace_depth(frame.depth)?;
-                if reader.is_end() {
-                    continue;
-                }
-                let (tag, type_id) = match reader.peek_head() {
-                    Ok(h) => h,
-                    Err(_) => continue,
+                let start = reader.position();
+                let head = if reader.is_end() {
+                    None
+                } else {
+                    reader.peek_head().ok()
+                };
+                let (tag, type_id) = match head {
+                    Some(h) => h,
+                    None => {
+                        frame.parent.borrow_mut(py).end = start;
+                        continue;
+                    }
                 };
                 if type_id == TarsType::StructEnd {
                     let _ = reader.read_head();
+                    frame.parent.borrow_mut(py).end = reader.position();
                     continue;
                 }
 
@@ -227,6 +242,8 @@ fn run_trace_frames<'py>(
                         name,
                         type_name,
                         path: path.clone(),
+                        start,
+                        end: start,
                     },
                 )?;
                 frame
@@ -245,109 +262,122 @@ fn run_trace_frames<'py>(
                     depth: next_depth,
                 }));
             }
-            TraceFrame::Value(frame) => match frame.type_id {
-                TarsType::ZeroTag
-                | TarsType::Int1
-                | TarsType::Int2
-                | TarsType::Int4
-                | TarsType::Int8 => {
-                    let v = reader.read_int(frame.type_id).unwrap_or(0);
-                    frame.node.borrow_mut(py).value =
-                        Some(v.into_pyobject(py)?.into_any().unbind());
-                }
-                TarsType::Float => {
-                    let v = reader.read_float(frame.type_id).unwrap_or(0.0);
-                    frame.node.borrow_mut(py).value =
-                        Some(v.into_pyobject(py)?.into_any().unbind());
-                }
-                TarsType::Double => {
-                    let v = reader.read_double(frame.type_id).unwrap_or(0.0);
-                    frame.node.borrow_mut(py).value =
-                        Some(v.into_pyobject(py)?.into_any().unbind());
-                }
-                TarsType::String1 | TarsType::String4 => {
-                    if let Ok(bytes) = reader.read_string(frame.type_id) {
-                        if let Ok(s) = from_utf8(bytes) {
-                            frame.node.borrow_mut(py).value =
-                                Some(s.into_pyobject(py)?.into_any().unbind());
-                        } else {
-                            frame.node.borrow_mut(py).value =
-                                Some(PyBytes::new(py, bytes).into_any().unbind());
+            TraceFrame::Value(frame) => {
+                // 容器节点的结束偏移在其子节点读完后由对应的帧写入.
+                let node = frame.node.clone_ref(py);
+                let pending = matches!(
+                    frame.type_id,
+                    TarsType::StructBegin | TarsType::List | TarsType::Map
+                );
+                match frame.type_id {
+                    TarsType::ZeroTag
+                    | TarsType::Int1
+                    | TarsType::Int2
+                    | TarsType::Int4
+                    | TarsType::Int8 => {
+                        let v = reader.read_int(frame.type_id).unwrap_or(0);
+                        frame.node.borrow_mut(py).value =
+                            Some(v.into_pyobject(py)?.into_any().unbind());
+                    }
+                    TarsType::Float => {
+                        let v = reader.read_float(frame.type_id).unwrap_or(0.0);
+                        frame.node.borrow_mut(py).value =
+                            Some(v.into_pyobject(py)?.into_any().unbind());
+                    }
+                    TarsType::Double => {
+                        let v = reader.read_double(frame.type_id).unwrap_or(0.0);
+                        frame.node.borrow_mut(py).value =
+                            Some(v.into_pyobject(py)?.into_any().unbind());
+                    }
+                    TarsType::String1 | TarsType::String4 => {
+                        if let Ok(bytes) = reader.read_string(frame.type_id) {
+                            if let Ok(s) = from_utf8(bytes) {
+                                frame.node.borrow_mut(py).value =
+                                    Some(s.into_pyobject(py)?.into_any().unbind());
+                            } else {
+                                frame.node.borrow_mut(py).value =
+                                    Some(PyBytes::new(py, bytes).into_any().unbind());
+                            }
                         }
                     }
+                    TarsType::StructBegin => {
+                        let nested_def = match frame.type_hint {
+                            Some(TraceTypeHint::StructDef(def)) => Some(def),
+                            _ => None,
+                        };
+                        stack.push(TraceFrame::Struct(StructFrame {
+                            parent: frame.node,
+                            def: nested_def,
+                            parent_path: frame.path,
+                            depth: frame.depth,
+                        }));
+                    }
+                    TarsType::List => {
+                        let len = reader.read_size().unwrap_or(0) as usize;
+                        frame.node.borrow_mut(py).value = Some(
+                            format!("<List len={}>", len)
+                                .into_pyobject(py)?
+                                .into_any()
+                                .unbind(),
+                        );
+                        let inner_hint = match frame.type_hint {
+                            Some(TraceTypeHint::List(inner)) => Some(*inner),
+                            _ => None,
+                        };
+                        stack.push(TraceFrame::List(ListFrame {
+                            parent: frame.node,
+                            path: frame.path,
+                            len,
+                            idx: 0,
+                            inner_hint,
+                            depth: frame.depth,
+                        }));
+                    }
+                    TarsType::Map => {
+                        let len = reader.read_size().unwrap_or(0) as usize;
+                        frame.node.borrow_mut(py).value = Some(
+                            format!("<Map len={}>", len)
+                                .into_pyobject(py)?
+                                .into_any()
+                                .unbind(),
+                        );
+                        let (key_hint, val_hint) = match frame.type_hint {
+                            Some(TraceTypeHint::Map(key, val)) => (Some(*key), Some(*val)),
+                            _ => (None, None),
+                        };
+                        stack.push(TraceFrame::Map(MapFrame {
+                            parent: frame.node,
+                            path: frame.path,
+                            len,
+                            idx: 0,
+                            key_hint,
+                            val_hint,
+                            depth: frame.depth,
+                            phase: MapPhase::EntryStart,
+                        }));
+                    }
+                    TarsType::SimpleList => {
+                        let _subtype = reader.read_u8().unwrap_or(0);
+                        let len = reader.read_size().unwrap_or(0) as usize;
+                        let bytes = reader.read_bytes(len).unwrap_or(&[]);
+                        frame.node.borrow_mut(py).value =
+                            Some(PyBytes::new(py, bytes).into_any().unbind());
+                        frame.node.borrow_mut(py).jce_type = "SimpleList".to_string();
+                    }
+                    _ => {
+                        frame.node.borrow_mut(py).value =
+                            Some("UNSUPPORTED".into_pyobject(py)?.into_any().unbind());
+                    }
                 }
-                TarsType::StructBegin => {
-                    let nested_def = match frame.type_hint {
-                        Some(TraceTypeHint::StructDef(def)) => Some(def),
-                        _ => None,
-                    };
-                    stack.push(TraceFrame::Struct(StructFrame {
-                        parent: frame.node,
-                        def: nested_def,
-                        parent_path: frame.path,
-                        depth: frame.depth,
-                    }));
-                }
-                TarsType::List => {
-                    let len = reader.read_size().unwrap_or(0) as usize;
-                    frame.node.borrow_mut(py).value = Some(
-                        format!("<List len={}>", len)
-                            .into_pyobject(py)?
-                            .into_any()
-                            .unbind(),
-                    );
-                    let inner_hint = match frame.type_hint {
-                        Some(TraceTypeHint::List(inner)) => Some(*inner),
-                        _ => None,
-                    };
-                    stack.push(TraceFrame::List(ListFrame {
-                        parent: frame.node,
-                        path: frame.path,
-                        len,
-                        idx: 0,
-                        inner_hint,
-                        depth: frame.depth,
-                    }));
-                }
-                TarsType::Map => {
-                    let len = reader.read_size().unwrap_or(0) as usize;
-                    frame.node.borrow_mut(py).value = Some(
-                        format!("<Map len={}>", len)
-                            .into_pyobject(py)?
-                            .into_any()
-                            .unbind(),
-                    );
-                    let (key_hint, val_hint) = match frame.type_hint {
-                        Some(TraceTypeHint::Map(key, val)) => (Some(*key), Some(*val)),
-                        _ => (None, None),
-                    };
-                    stack.push(TraceFrame::Map(MapFrame {
-                        parent: frame.node,
-                        path: frame.path,
-                        len,
-                        idx: 0,
-                        key_hint,
-                        val_hint,
-                        depth: frame.depth,
-                        phase: MapPhase::EntryStart,
-                    }));
-                }
-                TarsType::SimpleList => {
-                    let _subtype = reader.read_u8().unwrap_or(0);
-                    let len = reader.read_size().unwrap_or(0) as usize;
-                    let bytes = reader.read_bytes(len).unwrap_or(&[]);
-                    frame.node.borrow_mut(py).value =
-                        Some(PyBytes::new(py, bytes).into_any().unbind());
-                    frame.node.borrow_mut(py).jce_type = "SimpleList".to_string();
-                }
-                _ => {
-                    frame.node.borrow_mut(py).value =
-                        Some("UNSUPPORTED".into_pyobject(py)?.into_any().unbind());
+                if !pending {
+                    node.borrow_mut(py).end = reader.position();
                 }
-            },
+            }
             TraceFrame::List(frame) => {
                 check_trace_depth(frame.depth)?;
+                let start = reader.position();
                 if frame.idx >= frame.len {
+                    frame.parent.borrow_mut(py).end = start;
                     continue;
                 }
                 let (tag, item_type_id) = reader.read_head().unwrap_or((0, TarsType::ZeroTag));
@@ -362,6 +392,8 @@ fn run_trace_frames<'py>(
                         name: None,
                         type_name: None,
                         path: item_path.clone(),
+                        start,
+                        end: start,
                     },
                 )?;
                 frame
@@ -390,7 +422,9 @@ fn run_trace_frames<'py>(
                 check_trace_depth(frame.depth)?;
                 match frame.phase {
                     MapPhase::EntryStart => {
+                        let start = reader.position();
                         if frame.idx >= frame.len {
+                            frame.parent.borrow_mut(py).end = start;
                             continue;
                         }
                         let (ktag, ktype) = reader.read_head().unwrap_or((0, TarsType::ZeroTag));
@@ -405,6 +439,8 @@ fn run_trace_frames<'py>(
                                 name: Some("<key>".into()),
                                 type_name: None,
                                 path: key_path.clone(),
+                                start,
+                                end: start,
                             },
                         )?;
                         frame
@@ -444,6 +480,7 @@ fn run_trace_frames<'py>(
                             "key".to_string()
                         };
 
+                        let start = reader.position();
                         let (vtag, vtype) = reader.read_head().unwrap_or((1, TarsType::ZeroTag));
                         let val_path = format!("{}[{:?}]", frame.path, key_repr);
                         let val_node = Py::new(
@@ -456,6 +493,8 @@ fn run_trace_frames<'py>(
                                 name: Some(format!("value_of_{}", key_repr)),
                                 type_name: None,
                                 path: val_path.clone(),
+                                start,
+                                end: start,
                             },
                         )?;
                         frame
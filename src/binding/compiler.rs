@@ -8,9 +8,12 @@ use crate::binding::core::{
     nodefault_singleton,
 };
 use crate::binding::ir::{
-    Constraints, FieldDef, StructDef, StructMetaData, TypeExpr, UnionCache, WireType,
+    Choices, Compression, Constraints, Endian, FieldDef, StructDef, StructMetaData, TypeExpr,
+    UnionCache, WireInt, WireType,
+};
+use crate::binding::parse::{
+    ConstraintsIR, TypeInfoIR, introspect_struct_fields, introspect_type_info_ir, is_pydantic_model,
 };
-use crate::binding::parse::{ConstraintsIR, TypeInfoIR, introspect_struct_fields};
 
 fn schema_to_python(py: Python<'_>, def: Arc<StructDef>) -> PyResult<Py<Schema>> {
     Py::new(py, Schema { def })
@@ -38,19 +41,97 @@ pub fn compile_schema_from_info<'py>(
 
         if let Some(existing) = tags_seen.get(&tag) {
             return Err(pyo3::exceptions::PyTypeError::new_err(format!(
-                "Duplicate tag {} in '{}' and '{}'",
-                tag, existing, name
+                "Duplicate tag {} declared in {}.{} and {}.{}",
+                tag,
+                cls.name()?,
+                existing,
+                cls.name()?,
+                name
             )));
         }
         tags_seen.insert(tag, name.clone());
 
         let type_any = field_any.getattr("type")?;
-        let type_expr = parse_type_info(&type_any)?;
+        let enum_mode = field_any
+            .getattr("enum_mode")
+            .ok()
+            .and_then(|v| v.extract::<Option<String>>().ok())
+            .flatten()
+            .map(|m| m == "name");
+        let type_expr = apply_enum_mode(parse_type_info(&type_any)?, &name, enum_mode, &config)?;
         let wrap_simplelist = field_any
             .getattr("wrap_simplelist")
             .ok()
             .and_then(|v| v.extract::<bool>().ok())
             .unwrap_or(false);
+        let alias_tags = field_any
+            .getattr("alias_tags")
+            .ok()
+            .and_then(|v| v.extract::<Vec<u8>>().ok())
+            .unwrap_or_default();
+
+        let endian = field_any
+            .getattr("little_endian")
+            .ok()
+            .and_then(|v| v.extract::<bool>().ok())
+            .and_then(|little| little.then_some(Endian::Little));
+        let coerce_enum = field_any
+            .getattr("coerce_enum")
+            .ok()
+            .and_then(|v| v.extract::<bool>().ok())
+            .unwrap_or(false);
+        let compress = field_any
+            .getattr("compress")
+            .ok()
+            .and_then(|v| v.extract::<Option<String>>().ok())
+            .flatten()
+            .and_then(|c| Compression::from_name(&c));
+        let accept_bytes = field_any
+            .getattr("accept_bytes")
+            .ok()
+            .and_then(|v| v.extract::<bool>().ok())
+            .unwrap_or(false);
+        let wire = field_any
+            .getattr("wire")
+            .ok()
+            .and_then(|v| v.extract::<Option<String>>().ok())
+            .flatten()
+            .and_then(|w| WireInt::from_name(&w));
+        let chunk_size = field_any
+            .getattr("chunk_size")
+            .ok()
+            .and_then(|v| v.extract::<Option<usize>>().ok())
+            .flatten();
+        let as_bytes = field_any
+            .getattr("as_bytes")
+            .ok()
+            .and_then(|v| v.extract::<bool>().ok())
+            .unwrap_or(false);
+        let any_preserve_bool = field_any
+            .getattr("any_preserve_bool")
+            .ok()
+            .and_then(|v| v.extract::<bool>().ok())
+            .unwrap_or(false);
+        let alias = field_any
+            .getattr("alias")
+            .ok()
+            .and_then(|v| v.extract::<Option<String>>().ok())
+            .flatten();
+        let normalize = field_any
+            .getattr("normalize")
+            .ok()
+            .and_then(|v| v.extract::<Option<String>>().ok())
+            .flatten();
+        let encoder = field_any
+            .getattr("encoder")
+            .ok()
+            .filter(|v| !v.is_none())
+            .map(Bound::unbind);
+        let decoder = field_any
+            .getattr("decoder")
+            .ok()
+            .filter(|v| !v.is_none())
+            .map(Bound::unbind);
 
         let is_optional: bool = field_any.getattr("optional")?.extract()?;
         let has_default: bool = field_any.getattr("has_default")?.extract()?;
@@ -93,13 +174,35 @@ pub fn compile_schema_from_info<'py>(
             is_required,
             init: true,
             wrap_simplelist,
+            alias_tags,
+            endian,
+            coerce_enum,
+            compress,
+            accept_bytes,
+            wire,
+            chunk_size,
+            as_bytes,
+            any_preserve_bool,
+            alias,
+            normalize,
+            encoder,
+            decoder,
             constraints,
         });
     }
 
-    compile_schema_from_fields(py, cls, fields_def, config)
+    compile_schema_from_fields(py, cls, fields_def, config, false)
 }
 
+#[cfg_attr(
+    feature = "trace",
+    tracing::instrument(
+        name = "tarsio.compile_schema",
+        level = "debug",
+        skip_all,
+        fields(class = %cls, fields = tracing::field::Empty)
+    )
+)]
 pub fn compile_schema_from_class<'py>(
     py: Python<'py>,
     cls: &Bound<'py, PyType>,
@@ -108,14 +211,56 @@ pub fn compile_schema_from_class<'py>(
     let Some(fields_ir) = introspect_struct_fields(py, cls)? else {
         return Ok(None);
     };
+    #[cfg(feature = "trace")]
+    tracing::Span::current().record("fields", fields_ir.len());
 
     let mut fields_def: Vec<FieldDef> = Vec::with_capacity(fields_ir.len());
     for field in fields_ir {
         let name = field.name;
         let name_py = PyString::intern(py, name.as_str()).unbind();
-        let type_expr = type_info_ir_to_type_expr(py, &field.typ)?;
+        let type_expr = apply_enum_mode(
+            type_info_ir_to_type_expr(py, &field.typ)?,
+            &name,
+            field.constraints.as_ref().and_then(|c| c.enum_by_name),
+            &config,
+        )?;
         let constraints =
             constraints_ir_to_constraints(py, field.constraints.as_ref(), name.as_str())?;
+        let endian = field
+            .constraints
+            .as_ref()
+            .and_then(|c| c.endian.as_deref())
+            .and_then(Endian::from_name);
+        let coerce_enum = field.constraints.as_ref().is_some_and(|c| c.coerce_enum);
+        let compress = field
+            .constraints
+            .as_ref()
+            .and_then(|c| c.compress.as_deref())
+            .and_then(Compression::from_name);
+        let accept_bytes = field.constraints.as_ref().is_some_and(|c| c.accept_bytes);
+        let wire = field
+            .constraints
+            .as_ref()
+            .and_then(|c| c.wire.as_deref())
+            .and_then(WireInt::from_name);
+        let chunk_size = field.constraints.as_ref().and_then(|c| c.chunk_size);
+        let as_bytes = field.constraints.as_ref().is_some_and(|c| c.as_bytes);
+        let any_preserve_bool = field
+            .constraints
+            .as_ref()
+            .is_some_and(|c| c.any_preserve_bool);
+        let alias = field.constraints.as_ref().and_then(|c| c.alias.clone());
+        let normalize = field.constraints.as_ref().and_then(|c| c.normalize.clone());
+        let encoder = field
+            .constraints
+            .as_ref()
+            .and_then(|c| c.encoder.as_ref())
+            .map(|h| h.clone_ref(py));
+        let decoder = field
+            .constraints
+            .as_ref()
+            .and_then(|c| c.decoder.as_ref())
+            .map(|h| h.clone_ref(py));
 
         let default_value = if field.has_default {
             field.default_value.as_ref().map(|v| v.clone_ref(py))
@@ -134,11 +279,58 @@ pub fn compile_schema_from_class<'py>(
             is_required: field.is_required,
             init: field.init,
             wrap_simplelist: field.wrap_simplelist,
+            alias_tags: field.alias_tags,
+            endian,
+            coerce_enum,
+            compress,
+            accept_bytes,
+            wire,
+            chunk_size,
+            as_bytes,
+            any_preserve_bool,
+            alias,
+            normalize,
+            encoder,
+            decoder,
             constraints,
         });
     }
 
-    compile_schema_from_fields(py, cls, fields_def, config)
+    let is_pydantic = is_pydantic_model(py, cls)?;
+    compile_schema_from_fields(py, cls, fields_def, config, is_pydantic)
+}
+
+/// 将任意类型标注编译为运行时类型表达式及其 `Meta` 约束.
+///
+/// 用于无需定义 Struct 的单值解码 (如 `decode_value`).
+pub(crate) fn compile_type_annotation(
+    py: Python<'_>,
+    tp: &Bound<'_, PyAny>,
+) -> PyResult<(TypeExpr, Option<Box<Constraints>>)> {
+    let (typ, constraints) = introspect_type_info_ir(py, tp)?;
+    let type_expr = type_info_ir_to_type_expr(py, &typ)?;
+    let constraints = constraints_ir_to_constraints(py, constraints.as_ref(), "value")?;
+    Ok((type_expr, constraints))
+}
+
+/// 按字段级 `Meta(enum_mode=...)` 或 Struct 级 `enum_mode` 决定枚举的编码方式.
+fn apply_enum_mode(
+    ty: TypeExpr,
+    field_name: &str,
+    field_mode: Option<bool>,
+    config: &SchemaConfig,
+) -> PyResult<TypeExpr> {
+    if field_mode.is_some() && !ty.contains_enum() {
+        return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "Field '{}' with enum_mode must be annotated with an Enum",
+            field_name
+        )));
+    }
+    if field_mode.unwrap_or(config.enum_by_name) {
+        Ok(ty.into_enum_names())
+    } else {
+        Ok(ty)
+    }
 }
 
 fn compile_schema_from_fields<'py>(
@@ -146,6 +338,7 @@ fn compile_schema_from_fields<'py>(
     cls: &Bound<'py, PyType>,
     mut fields_def: Vec<FieldDef>,
     config: SchemaConfig,
+    is_pydantic: bool,
 ) -> PyResult<Option<Arc<StructDef>>> {
     if fields_def.is_empty() {
         return Ok(None);
@@ -158,6 +351,131 @@ fn compile_schema_from_fields<'py>(
                 field.name
             )));
         }
+        if let Some(endian) = field.endian
+            && !matches!(
+                field.ty,
+                TypeExpr::Primitive(
+                    WireType::Int | WireType::Long | WireType::Float | WireType::Double
+                )
+            )
+        {
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "Field '{}' with endian='{}' must be annotated as int or float",
+                field.name,
+                endian.name()
+            )));
+        }
+        if field.wire.is_some() {
+            if !matches!(
+                field.ty,
+                TypeExpr::Primitive(WireType::Int | WireType::Long)
+            ) {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Field '{}' with wire must be annotated as int",
+                    field.name
+                )));
+            }
+            if let Some(endian) = field.endian {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Field '{}' cannot combine wire with endian='{}'",
+                    field.name,
+                    endian.name()
+                )));
+            }
+        }
+        if field.coerce_enum && field.ty.enum_class().is_none() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "Field '{}' with coerce_enum=True must be annotated as an Enum",
+                field.name
+            )));
+        }
+        if field.compress.is_some() && !field.ty.is_bytes() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "Field '{}' with compress must be annotated as bytes",
+                field.name
+            )));
+        }
+        if field.chunk_size.is_some() {
+            if !field.ty.is_bytes() {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Field '{}' with chunk_size must be annotated as bytes",
+                    field.name
+                )));
+            }
+            if field.compress.is_some() {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Field '{}' cannot combine chunk_size with compress",
+                    field.name
+                )));
+            }
+        }
+        if field
+            .constraints
+            .as_deref()
+            .is_some_and(|c| c.fixed_len.is_some())
+            && !field.ty.is_bytes()
+        {
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "Field '{}' with fixed_len must be annotated as bytes",
+                field.name
+            )));
+        }
+        match field
+            .constraints
+            .as_deref()
+            .and_then(|c| c.choices.as_ref())
+        {
+            Some(Choices::Int(_)) if !field.ty.is_int() => {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Field '{}' with int choices must be annotated as int",
+                    field.name
+                )));
+            }
+            Some(Choices::Str(_)) if !field.ty.is_str() => {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Field '{}' with str choices must be annotated as str",
+                    field.name
+                )));
+            }
+            _ => {}
+        }
+        if field.accept_bytes && !field.ty.is_str() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "Field '{}' with accept_bytes=True must be annotated as str",
+                field.name
+            )));
+        }
+        if field.as_bytes {
+            if !field.ty.is_str() {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Field '{}' with as_bytes=True must be annotated as str",
+                    field.name
+                )));
+            }
+            if field.accept_bytes
+                || field
+                    .constraints
+                    .as_deref()
+                    .is_some_and(|c| c.pattern.is_some() || c.choices.is_some())
+            {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Field '{}' with as_bytes=True cannot use accept_bytes, pattern or choices",
+                    field.name
+                )));
+            }
+        }
+        if field.normalize.is_some() && !field.ty.is_str() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "Field '{}' with normalize must be annotated as str",
+                field.name
+            )));
+        }
+        if field.any_preserve_bool && !field.ty.is_any() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "Field '{}' with any_preserve_bool=True must be annotated as Any",
+                field.name
+            )));
+        }
     }
 
     fields_def.sort_by_key(|f| f.tag);
@@ -169,8 +487,21 @@ fn compile_schema_from_fields<'py>(
     for (idx, f) in fields_def.iter().enumerate() {
         name_to_index.insert(f.name.clone(), idx);
         name_ptr_to_index.insert(f.name_py.as_ptr() as usize, idx);
-        if f.tag > max_tag {
-            max_tag = f.tag;
+        max_tag = f
+            .alias_tags
+            .iter()
+            .fold(max_tag.max(f.tag), |acc, &t| acc.max(t));
+    }
+
+    // `to_builtins` 以别名 (未设置时为字段名) 作为 dict 键, 不得与其他字段的键重复.
+    let mut public_keys: HashMap<&str, &str> = HashMap::with_capacity(fields_def.len());
+    for f in &fields_def {
+        let key = f.alias.as_deref().unwrap_or(f.name.as_str());
+        if let Some(other) = public_keys.insert(key, f.name.as_str()) {
+            return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "Field '{}' alias '{}' collides with field '{}'",
+                f.name, key, other
+            )));
         }
     }
 
@@ -183,9 +514,59 @@ fn compile_schema_from_fields<'py>(
     for (idx, f) in fields_def.iter().enumerate() {
         tag_lookup_vec[f.tag as usize] = Some(idx);
     }
+    // 别名 tag 指向同一字段, 仅在解码查找时生效; 不得与任何主 tag 或其他别名冲突.
+    for (idx, f) in fields_def.iter().enumerate() {
+        for &alias in &f.alias_tags {
+            if let Some(other) = tag_lookup_vec[alias as usize] {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Field '{}' alias tag {} collides with field '{}'",
+                    f.name, alias, fields_def[other].name
+                )));
+            }
+            tag_lookup_vec[alias as usize] = Some(idx);
+        }
+    }
+
+    let flat = fields_def.iter().all(is_flat_field);
+
+    let str_fields = match config.str_fields.as_ref() {
+        Some(names) => {
+            let mut idxs = Vec::with_capacity(names.len());
+            for name in names {
+                let idx = meta
+                    .name_to_index
+                    .get(name.as_str())
+                    .copied()
+                    .ok_or_else(|| {
+                        pyo3::exceptions::PyTypeError::new_err(format!(
+                            "str_fields contains unknown field '{}'",
+                            name
+                        ))
+                    })?;
+                idxs.push(idx);
+            }
+            Some(idxs)
+        }
+        None => None,
+    };
+
+    // 版本号沿继承链读取, 子类未覆盖时沿用父类的版本.
+    let version = match cls.getattr("__tarsio_version__") {
+        Ok(v) => Some(v.extract::<i64>().map_err(|_| {
+            pyo3::exceptions::PyTypeError::new_err(format!(
+                "__tarsio_version__ must be an int, got {}",
+                v.get_type()
+                    .name()
+                    .map(|n| n.to_string())
+                    .unwrap_or_default()
+            ))
+        })?),
+        Err(_) => None,
+    };
 
     let def = StructDef {
         class_ptr: cls.as_ptr() as usize,
+        class_ref: cls.clone().unbind(),
         name: cls.name()?.to_string(),
         fields_sorted: fields_def,
         tag_lookup_vec,
@@ -195,10 +576,18 @@ fn compile_schema_from_fields<'py>(
         forbid_unknown_tags: config.forbid_unknown_tags,
         eq: config.eq,
         omit_defaults: config.omit_defaults,
+        omit_none: config.omit_none,
         repr_omit_defaults: config.repr_omit_defaults,
         kw_only: config.kw_only,
         dict: config.dict,
         weakref: config.weakref,
+        emit_empty_marker: config.emit_empty_marker,
+        forbid_extra_bytes: config.forbid_extra_bytes,
+        enum_by_name: config.enum_by_name,
+        str_fields,
+        flat,
+        is_pydantic,
+        version,
     };
 
     let def = Arc::new(def);
@@ -209,6 +598,10 @@ fn compile_schema_from_fields<'py>(
             .borrow_mut()
             .insert(cls.as_ptr() as usize, Arc::downgrade(&def));
     });
+    // pydantic 模型自带签名与 match_args, 只挂载 schema.
+    if is_pydantic {
+        return Ok(Some(def));
+    }
 
     let mut field_names = Vec::with_capacity(def.fields_sorted.len());
     for field in &def.fields_sorted {
@@ -226,6 +619,26 @@ fn compile_schema_from_fields<'py>(
     Ok(Some(def))
 }
 
+/// 字段是否满足扁平结构的要求: 基础类型, 且未设置改变编解码行为的 `Meta` 选项或取值约束.
+///
+/// `Meta(alias=...)` 只影响 Python 侧的 dict 表示, 不计入.
+fn is_flat_field(f: &FieldDef) -> bool {
+    f.ty.is_flat_scalar()
+        && !f.wrap_simplelist
+        && f.endian.is_none()
+        && !f.coerce_enum
+        && f.compress.is_none()
+        && !f.accept_bytes
+        && f.wire.is_none()
+        && f.chunk_size.is_none()
+        && !f.as_bytes
+        && !f.any_preserve_bool
+        && f.normalize.is_none()
+        && f.encoder.is_none()
+        && f.decoder.is_none()
+        && f.constraints.is_none()
+}
+
 fn type_info_ir_to_type_expr(py: Python<'_>, typ: &TypeInfoIR) -> PyResult<TypeExpr> {
     match typ {
         TypeInfoIR::Int => Ok(TypeExpr::Primitive(WireType::Int)),
@@ -233,9 +646,17 @@ fn type_info_ir_to_type_expr(py: Python<'_>, typ: &TypeInfoIR) -> PyResult<TypeE
         TypeInfoIR::Float => Ok(TypeExpr::Primitive(WireType::Double)),
         TypeInfoIR::Bool => Ok(TypeExpr::Primitive(WireType::Bool)),
         TypeInfoIR::Bytes => Ok(TypeExpr::Bytes),
+        TypeInfoIR::ByteArray => Ok(TypeExpr::ByteArray),
+        TypeInfoIR::Complex => Ok(TypeExpr::Complex),
+        TypeInfoIR::IpAddress(cls) => Ok(TypeExpr::IpAddress(cls.clone_ref(py))),
+        TypeInfoIR::Path(cls) => Ok(TypeExpr::Path(cls.clone_ref(py))),
+        TypeInfoIR::Subclass(cls, inner) => Ok(TypeExpr::Subclass(
+            cls.clone_ref(py),
+            Box::new(type_info_ir_to_type_expr(py, inner)?),
+        )),
         TypeInfoIR::Any => Ok(TypeExpr::Any),
         TypeInfoIR::NoneType => Ok(TypeExpr::NoneType),
-        TypeInfoIR::TypedDict => Ok(TypeExpr::TypedDict),
+        TypeInfoIR::TypedDict(aliases) => Ok(TypeExpr::TypedDict(aliases.clone())),
         TypeInfoIR::Dataclass(cls) => Ok(TypeExpr::Dataclass(cls.clone_ref(py))),
         TypeInfoIR::NamedTuple(cls, items) => {
             let mut out = Vec::with_capacity(items.len());
@@ -248,10 +669,18 @@ fn type_info_ir_to_type_expr(py: Python<'_>, typ: &TypeInfoIR) -> PyResult<TypeE
         TypeInfoIR::Set(inner) => Ok(TypeExpr::Set(Box::new(type_info_ir_to_type_expr(
             py, inner,
         )?))),
+        TypeInfoIR::FrozenSet(inner) => Ok(TypeExpr::FrozenSet(Box::new(
+            type_info_ir_to_type_expr(py, inner)?,
+        ))),
+        TypeInfoIR::Literal(values, inner) => Ok(TypeExpr::Literal(
+            values.iter().map(|v| v.clone_ref(py)).collect(),
+            Box::new(type_info_ir_to_type_expr(py, inner)?),
+        )),
         TypeInfoIR::Enum(cls, inner) => Ok(TypeExpr::Enum(
             cls.clone_ref(py),
             Box::new(type_info_ir_to_type_expr(py, inner)?),
         )),
+        TypeInfoIR::Flag(cls) => Ok(TypeExpr::Flag(cls.clone_ref(py))),
         TypeInfoIR::Union(items) => {
             let mut variants = Vec::with_capacity(items.len());
             for item in items {
@@ -276,6 +705,16 @@ fn type_info_ir_to_type_expr(py: Python<'_>, typ: &TypeInfoIR) -> PyResult<TypeE
             Box::new(type_info_ir_to_type_expr(py, k)?),
             Box::new(type_info_ir_to_type_expr(py, v)?),
         )),
+        TypeInfoIR::DefaultDict(k, v, factory) => Ok(TypeExpr::DefaultDict(
+            Box::new(type_info_ir_to_type_expr(py, k)?),
+            Box::new(type_info_ir_to_type_expr(py, v)?),
+            factory.clone_ref(py),
+        )),
+        TypeInfoIR::DictSubclass(k, v, cls) => Ok(TypeExpr::DictSubclass(
+            Box::new(type_info_ir_to_type_expr(py, k)?),
+            Box::new(type_info_ir_to_type_expr(py, v)?),
+            cls.clone_ref(py),
+        )),
         TypeInfoIR::Optional(inner) => Ok(TypeExpr::Optional(Box::new(type_info_ir_to_type_expr(
             py, inner,
         )?))),
@@ -297,9 +736,12 @@ fn constraints_ir_to_constraints(
         c.lt.is_some(),
         c.ge.is_some(),
         c.le.is_some(),
+        c.multiple_of.is_some(),
         c.min_len.is_some(),
         c.max_len.is_some(),
+        c.fixed_len.is_some(),
         c.pattern.is_some(),
+        c.choices.is_some(),
     ) {
         return Ok(None);
     }
@@ -323,9 +765,12 @@ fn constraints_ir_to_constraints(
         lt: c.lt,
         ge: c.ge,
         le: c.le,
+        multiple_of: c.multiple_of,
         min_len: c.min_len,
         max_len: c.max_len,
+        fixed_len: c.fixed_len,
         pattern,
+        choices: c.choices.clone(),
     })))
 }
 
@@ -381,7 +826,14 @@ fn parse_type_info(obj: &Bound<'_, PyAny>) -> PyResult<TypeExpr> {
         "str" => Ok(TypeExpr::Primitive(WireType::String)),
         "float" => Ok(TypeExpr::Primitive(WireType::Double)),
         "bool" => Ok(TypeExpr::Primitive(WireType::Bool)),
+        "complex" => Ok(TypeExpr::Complex),
         "bytes" => Ok(TypeExpr::List(Box::new(TypeExpr::Primitive(WireType::Int)))),
+        "ipaddress" => Ok(TypeExpr::IpAddress(
+            obj.getattr("cls")?.cast_into::<PyType>()?.unbind(),
+        )),
+        "path" => Ok(TypeExpr::Path(
+            obj.getattr("cls")?.cast_into::<PyType>()?.unbind(),
+        )),
         "any" => Ok(TypeExpr::Any),
         "none" => Ok(TypeExpr::NoneType),
         "tarsdict" => Ok(TypeExpr::TarsDict),
@@ -392,7 +844,15 @@ fn parse_type_info(obj: &Bound<'_, PyAny>) -> PyResult<TypeExpr> {
         "set" => {
             let inner_any = obj.getattr("item_type")?;
             let inner = parse_type_info(&inner_any)?;
-            Ok(TypeExpr::Set(Box::new(inner)))
+            let frozen = match obj.getattr("frozen") {
+                Ok(v) => v.is_truthy()?,
+                Err(_) => false,
+            };
+            if frozen {
+                Ok(TypeExpr::FrozenSet(Box::new(inner)))
+            } else {
+                Ok(TypeExpr::Set(Box::new(inner)))
+            }
         }
         "list" => {
             let inner_any = obj.getattr("item_type")?;
@@ -427,7 +887,24 @@ fn parse_type_info(obj: &Bound<'_, PyAny>) -> PyResult<TypeExpr> {
             let value_any = obj.getattr("value_type")?;
             let key = parse_type_info(&key_any)?;
             let value = parse_type_info(&value_any)?;
-            Ok(TypeExpr::Map(Box::new(key), Box::new(value)))
+            let factory = obj.getattr("default_factory")?;
+            let cls_any = obj.getattr("cls")?;
+            if !cls_any.is_none() {
+                let cls = cls_any.cast::<PyType>()?;
+                Ok(TypeExpr::DictSubclass(
+                    Box::new(key),
+                    Box::new(value),
+                    cls.clone().unbind(),
+                ))
+            } else if factory.is_none() {
+                Ok(TypeExpr::Map(Box::new(key), Box::new(value)))
+            } else {
+                Ok(TypeExpr::DefaultDict(
+                    Box::new(key),
+                    Box::new(value),
+                    factory.unbind(),
+                ))
+            }
         }
         "optional" => {
             let inner_any = obj.getattr("inner_type")?;
@@ -441,6 +918,28 @@ fn parse_type_info(obj: &Bound<'_, PyAny>) -> PyResult<TypeExpr> {
             let inner = parse_type_info(&inner_any)?;
             Ok(TypeExpr::Enum(cls.clone().unbind(), Box::new(inner)))
         }
+        "flag" => {
+            let cls_any = obj.getattr("cls")?;
+            let cls = cls_any.cast::<PyType>()?;
+            Ok(TypeExpr::Flag(cls.clone().unbind()))
+        }
+        "subclass" => {
+            let cls_any = obj.getattr("cls")?;
+            let cls = cls_any.cast::<PyType>()?;
+            let inner_any = obj.getattr("value_type")?;
+            let inner = parse_type_info(&inner_any)?;
+            Ok(TypeExpr::Subclass(cls.clone().unbind(), Box::new(inner)))
+        }
+        "literal" => {
+            let values_any = obj.getattr("values")?;
+            let values = values_any.cast::<PyTuple>()?;
+            let inner_any = obj.getattr("value_type")?;
+            let inner = parse_type_info(&inner_any)?;
+            Ok(TypeExpr::Literal(
+                values.iter().map(|v| v.unbind()).collect(),
+                Box::new(inner),
+            ))
+        }
         "union" => {
             let variants_any = obj.getattr("variants")?;
             let variants = variants_any.cast::<PyTuple>()?;
@@ -474,18 +973,35 @@ fn parse_constraints(
     let lt: Option<f64> = obj.getattr("lt")?.extract()?;
     let ge: Option<f64> = obj.getattr("ge")?.extract()?;
     let le: Option<f64> = obj.getattr("le")?.extract()?;
+    let multiple_of: Option<f64> = obj
+        .getattr("multiple_of")
+        .ok()
+        .and_then(|v| v.extract().ok())
+        .flatten();
     let min_len: Option<usize> = obj.getattr("min_len")?.extract()?;
     let max_len: Option<usize> = obj.getattr("max_len")?.extract()?;
+    let fixed_len: Option<usize> = obj
+        .getattr("fixed_len")
+        .ok()
+        .and_then(|v| v.extract().ok())
+        .flatten();
     let pattern_str: Option<String> = obj.getattr("pattern")?.extract()?;
+    let choices = match obj.getattr("choices") {
+        Ok(v) if !v.is_none() => Some(Choices::from_py(&v)?),
+        _ => None,
+    };
 
     if !has_any_constraints(
         gt.is_some(),
         lt.is_some(),
         ge.is_some(),
         le.is_some(),
+        multiple_of.is_some(),
         min_len.is_some(),
         max_len.is_some(),
+        fixed_len.is_some(),
         pattern_str.is_some(),
+        choices.is_some(),
     ) {
         return Ok(None);
     }
@@ -510,21 +1026,28 @@ fn parse_constraints(
         lt,
         ge,
         le,
+        multiple_of,
         min_len,
         max_len,
+        fixed_len,
         pattern,
+        choices,
     })))
 }
 
 #[inline]
+#[allow(clippy::too_many_arguments)]
 fn has_any_constraints(
     gt: bool,
     lt: bool,
     ge: bool,
     le: bool,
+    multiple_of: bool,
     min_len: bool,
     max_len: bool,
+    fixed_len: bool,
     pattern: bool,
+    choices: bool,
 ) -> bool {
-    gt || lt || ge || le || min_len || max_len || pattern
+    gt || lt || ge || le || multiple_of || min_len || max_len || fixed_len || pattern || choices
 }
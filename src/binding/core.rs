@@ -1,12 +1,15 @@
 use parking_lot::RwLock;
 use pyo3::gc::{PyTraverseError, PyVisit};
 use pyo3::prelude::*;
-use pyo3::types::{PyAny, PyDict, PyString, PyType};
+use pyo3::types::{PyAny, PyBool, PyDict, PyFrozenSet, PyList, PyString, PyType};
 use rustc_hash::FxHashMap;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Weak};
 
+use crate::codec::consts::TarsType;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum WireType {
     Int,
@@ -26,33 +29,237 @@ pub enum TypeExpr {
     Struct(Py<PyType>),
     TarsDict,
     Bytes,
-    TypedDict,
+    /// `bytearray`, 线上与 `Bytes` 相同 (SimpleList), 解码为可变的 `bytearray`.
+    ByteArray,
+    /// `complex`, 编码为 Struct: tag 0 为实部, tag 1 为虚部 (均为 double).
+    Complex,
+    /// `ipaddress.IPv4Address` / `IPv6Address`, 以 `.packed` 字节写为 SimpleList,
+    /// 解码时按字节长度 (4 或 16) 还原对应的类.
+    IpAddress(Py<PyType>),
+    /// `pathlib.PurePath` 及其子类, 以 `str(path)` 写为 String, 解码时按声明的类还原.
+    Path(Py<PyType>),
+    /// `int`/`str`/`float`/`bytes` 的子类: 按底层类型编码, 解码时以 `cls(value)` 构造子类实例.
+    Subclass(Py<PyType>, Box<TypeExpr>),
+    /// `TypedDict`, 按 `dict[str, Any]` 编解码; 附带 `(字段名, 别名)` 列表 (`Meta(alias=...)`),
+    /// 线上 Map 的键为字段名, Python 侧 dict 的键为别名.
+    TypedDict(Vec<(String, String)>),
     NamedTuple(Py<PyType>, Vec<TypeExpr>),
     Dataclass(Py<PyType>),
     Any,
     NoneType,
     Set(Box<TypeExpr>),
+    /// `frozenset[T]`, 线上编码与 `Set` 相同, 解码为 `frozenset`.
+    FrozenSet(Box<TypeExpr>),
     Enum(Py<PyType>, Box<TypeExpr>),
+    /// 按成员名称编码为字符串的枚举 (`enum_mode="name"`).
+    EnumName(Py<PyType>),
+    /// `enum.Flag` 子类 (含 `IntFlag`): 按整数值编码, 解码时以 `cls(value)` 组合成员.
+    Flag(Py<PyType>),
+    /// `Literal[...]`: 按底层类型编解码, 取值须为列出的成员之一 (值与类型均相同).
+    Literal(Vec<Py<PyAny>>, Box<TypeExpr>),
     Union(Vec<TypeExpr>, UnionCache),
     List(Box<TypeExpr>),
     Tuple(Vec<TypeExpr>),
     VarTuple(Box<TypeExpr>),
     Map(Box<TypeExpr>, Box<TypeExpr>),
+    /// `collections.defaultdict[K, V]`, 解码时以 `default_factory` 构造.
+    DefaultDict(Box<TypeExpr>, Box<TypeExpr>, Py<PyAny>),
+    /// 用户定义的 `dict` 子类, 解码时以 `cls(items)` 构造以保留子类.
+    DictSubclass(Box<TypeExpr>, Box<TypeExpr>, Py<PyType>),
     Optional(Box<TypeExpr>),
 }
 
+/// 以逗号连接 Literal 取值的 repr, 用于类型描述与错误信息.
+pub(crate) fn literal_values_repr(py: Python<'_>, values: &[Py<PyAny>]) -> String {
+    values
+        .iter()
+        .map(|v| v.bind(py).repr().map(|r| r.to_string()).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 impl TypeExpr {
     pub fn is_optional(&self) -> bool {
         matches!(self, TypeExpr::Optional(_))
     }
 
+    /// 返回 `Enum` 或 `Optional[Enum]` 对应的枚举类.
+    pub fn enum_class(&self) -> Option<&Py<PyType>> {
+        match self {
+            TypeExpr::Enum(cls, _) | TypeExpr::EnumName(cls) | TypeExpr::Flag(cls) => Some(cls),
+            TypeExpr::Optional(inner) => inner.enum_class(),
+            _ => None,
+        }
+    }
+
+    /// 是否为 `str` 或 `Optional[str]`.
+    pub fn is_str(&self) -> bool {
+        match self {
+            TypeExpr::Primitive(WireType::String) => true,
+            TypeExpr::Optional(inner) => inner.is_str(),
+            _ => false,
+        }
+    }
+
+    /// 是否为 `int` 或 `Optional[int]`.
+    pub fn is_int(&self) -> bool {
+        match self {
+            TypeExpr::Primitive(WireType::Int | WireType::Long) => true,
+            TypeExpr::Optional(inner) => inner.is_int(),
+            _ => false,
+        }
+    }
+
+    /// 是否为 `Any` 或 `Optional[Any]`.
+    pub fn is_any(&self) -> bool {
+        match self {
+            TypeExpr::Any => true,
+            TypeExpr::Optional(inner) => inner.is_any(),
+            _ => false,
+        }
+    }
+
+    /// 是否为 `bytes` 或 `Optional[bytes]`.
+    pub fn is_bytes(&self) -> bool {
+        match self {
+            TypeExpr::Bytes => true,
+            TypeExpr::Optional(inner) => inner.is_bytes(),
+            _ => false,
+        }
+    }
+
+    /// 是否为扁平的标量类型 (基础类型、bytes 或其 Optional 形式).
+    pub fn is_flat_scalar(&self) -> bool {
+        match self {
+            TypeExpr::Primitive(_) | TypeExpr::Bytes => true,
+            TypeExpr::Optional(inner)
+            | TypeExpr::Literal(_, inner)
+            | TypeExpr::Subclass(_, inner) => inner.is_flat_scalar(),
+            _ => false,
+        }
+    }
+
+    /// 以 Python 标注风格描述类型, 用于错误信息 (如 `Union[int, str]`).
+    pub fn annotation_repr(&self, py: Python<'_>) -> String {
+        let class_name = |cls: &Py<PyType>| {
+            cls.bind(py)
+                .name()
+                .map(|n| n.to_string())
+                .unwrap_or_else(|_| "?".to_string())
+        };
+        let join = |items: &[TypeExpr]| {
+            items
+                .iter()
+                .map(|item| item.annotation_repr(py))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match self {
+            TypeExpr::Primitive(WireType::Bool) => "bool".to_string(),
+            TypeExpr::Primitive(WireType::Float | WireType::Double) => "float".to_string(),
+            TypeExpr::Primitive(WireType::String) => "str".to_string(),
+            TypeExpr::Primitive(_) => "int".to_string(),
+            TypeExpr::Struct(cls)
+            | TypeExpr::NamedTuple(cls, _)
+            | TypeExpr::Dataclass(cls)
+            | TypeExpr::Enum(cls, _)
+            | TypeExpr::EnumName(cls)
+            | TypeExpr::Flag(cls)
+            | TypeExpr::IpAddress(cls)
+            | TypeExpr::Path(cls)
+            | TypeExpr::Subclass(cls, _) => class_name(cls),
+            TypeExpr::TarsDict => "TarsDict".to_string(),
+            TypeExpr::Bytes => "bytes".to_string(),
+            TypeExpr::ByteArray => "bytearray".to_string(),
+            TypeExpr::Complex => "complex".to_string(),
+            TypeExpr::TypedDict(_) => "TypedDict".to_string(),
+            TypeExpr::Any => "Any".to_string(),
+            TypeExpr::NoneType => "None".to_string(),
+            TypeExpr::Set(inner) => format!("set[{}]", inner.annotation_repr(py)),
+            TypeExpr::FrozenSet(inner) => format!("frozenset[{}]", inner.annotation_repr(py)),
+            TypeExpr::Literal(values, _) => format!("Literal[{}]", literal_values_repr(py, values)),
+            TypeExpr::Union(items, _) => format!("Union[{}]", join(items)),
+            TypeExpr::List(inner) => format!("list[{}]", inner.annotation_repr(py)),
+            TypeExpr::Tuple(items) => format!("tuple[{}]", join(items)),
+            TypeExpr::VarTuple(inner) => format!("tuple[{}, ...]", inner.annotation_repr(py)),
+            TypeExpr::Map(k, v) => {
+                format!("dict[{}, {}]", k.annotation_repr(py), v.annotation_repr(py))
+            }
+            TypeExpr::DefaultDict(k, v, _) => format!(
+                "defaultdict[{}, {}]",
+                k.annotation_repr(py),
+                v.annotation_repr(py)
+            ),
+            TypeExpr::DictSubclass(k, v, cls) => format!(
+                "{}[{}, {}]",
+                cls.bind(py)
+                    .name()
+                    .map(|n| n.to_string())
+                    .unwrap_or_default(),
+                k.annotation_repr(py),
+                v.annotation_repr(py)
+            ),
+            TypeExpr::Optional(inner) => format!("Optional[{}]", inner.annotation_repr(py)),
+        }
+    }
+
+    /// 类型树中是否包含枚举.
+    pub fn contains_enum(&self) -> bool {
+        match self {
+            TypeExpr::Enum(_, _) | TypeExpr::EnumName(_) | TypeExpr::Flag(_) => true,
+            TypeExpr::NamedTuple(_, items) | TypeExpr::Union(items, _) | TypeExpr::Tuple(items) => {
+                items.iter().any(TypeExpr::contains_enum)
+            }
+            TypeExpr::Set(inner)
+            | TypeExpr::FrozenSet(inner)
+            | TypeExpr::List(inner)
+            | TypeExpr::VarTuple(inner)
+            | TypeExpr::Optional(inner) => inner.contains_enum(),
+            TypeExpr::Map(k, v)
+            | TypeExpr::DefaultDict(k, v, _)
+            | TypeExpr::DictSubclass(k, v, _) => k.contains_enum() || v.contains_enum(),
+            _ => false,
+        }
+    }
+
+    /// 将类型树中的枚举改为按成员名称编码.
+    ///
+    /// 嵌套 Struct 的字段由其自身配置决定, 不受影响; Flag 的组合值没有对应的成员名,
+    /// 始终按值编码.
+    pub fn into_enum_names(self) -> TypeExpr {
+        let boxed = |inner: Box<TypeExpr>| Box::new(inner.into_enum_names());
+        let many = |items: Vec<TypeExpr>| {
+            items
+                .into_iter()
+                .map(TypeExpr::into_enum_names)
+                .collect::<Vec<_>>()
+        };
+        match self {
+            TypeExpr::Enum(cls, _) => TypeExpr::EnumName(cls),
+            TypeExpr::NamedTuple(cls, items) => TypeExpr::NamedTuple(cls, many(items)),
+            TypeExpr::Union(items, _) => TypeExpr::Union(many(items), UnionCache::default()),
+            TypeExpr::Tuple(items) => TypeExpr::Tuple(many(items)),
+            TypeExpr::Set(inner) => TypeExpr::Set(boxed(inner)),
+            TypeExpr::FrozenSet(inner) => TypeExpr::FrozenSet(boxed(inner)),
+            TypeExpr::List(inner) => TypeExpr::List(boxed(inner)),
+            TypeExpr::VarTuple(inner) => TypeExpr::VarTuple(boxed(inner)),
+            TypeExpr::Optional(inner) => TypeExpr::Optional(boxed(inner)),
+            TypeExpr::Map(k, v) => TypeExpr::Map(boxed(k), boxed(v)),
+            TypeExpr::DefaultDict(k, v, factory) => {
+                TypeExpr::DefaultDict(boxed(k), boxed(v), factory)
+            }
+            TypeExpr::DictSubclass(k, v, cls) => TypeExpr::DictSubclass(boxed(k), boxed(v), cls),
+            other => other,
+        }
+    }
+
     pub fn traverse(&self, visit: &PyVisit<'_>) -> Result<(), PyTraverseError> {
         match self {
             TypeExpr::Primitive(_) => Ok(()),
             TypeExpr::Struct(cls) => visit.call(cls),
             TypeExpr::TarsDict => Ok(()),
-            TypeExpr::Bytes => Ok(()),
-            TypeExpr::TypedDict => Ok(()),
+            TypeExpr::Bytes | TypeExpr::ByteArray | TypeExpr::Complex => Ok(()),
+            TypeExpr::TypedDict(_) => Ok(()),
             TypeExpr::NamedTuple(cls, items) => {
                 visit.call(cls)?;
                 for item in items {
@@ -61,13 +268,25 @@ impl TypeExpr {
                 Ok(())
             }
             TypeExpr::Dataclass(cls) => visit.call(cls),
+            TypeExpr::IpAddress(cls) | TypeExpr::Path(cls) => visit.call(cls),
+            TypeExpr::Subclass(cls, inner) => {
+                visit.call(cls)?;
+                inner.traverse(visit)
+            }
             TypeExpr::Any => Ok(()),
             TypeExpr::NoneType => Ok(()),
-            TypeExpr::Set(inner) => inner.traverse(visit),
+            TypeExpr::Set(inner) | TypeExpr::FrozenSet(inner) => inner.traverse(visit),
+            TypeExpr::Literal(values, inner) => {
+                for value in values {
+                    visit.call(value)?;
+                }
+                inner.traverse(visit)
+            }
             TypeExpr::Enum(cls, inner) => {
                 visit.call(cls)?;
                 inner.traverse(visit)
             }
+            TypeExpr::EnumName(cls) | TypeExpr::Flag(cls) => visit.call(cls),
             TypeExpr::Union(items, _) => {
                 for item in items {
                     item.traverse(visit)?;
@@ -86,6 +305,16 @@ impl TypeExpr {
                 k.traverse(visit)?;
                 v.traverse(visit)
             }
+            TypeExpr::DefaultDict(k, v, factory) => {
+                visit.call(factory)?;
+                k.traverse(visit)?;
+                v.traverse(visit)
+            }
+            TypeExpr::DictSubclass(k, v, cls) => {
+                visit.call(cls)?;
+                k.traverse(visit)?;
+                v.traverse(visit)
+            }
             TypeExpr::Optional(inner) => inner.traverse(visit),
         }
     }
@@ -138,10 +367,280 @@ pub struct Constraints {
     pub lt: Option<f64>,
     pub ge: Option<f64>,
     pub le: Option<f64>,
+    /// 数值必须为该步长的整数倍 (`Meta(multiple_of=...)`).
+    pub multiple_of: Option<f64>,
     pub min_len: Option<usize>,
     pub max_len: Option<usize>,
+    /// bytes 的精确长度 (`Meta(fixed_len=N)`).
+    pub fixed_len: Option<usize>,
     /// Python 正则对象 (re.Pattern).
     pub pattern: Option<Py<PyAny>>,
+    /// 允许的取值集合 (`Meta(choices=[...])`).
+    pub choices: Option<Choices>,
+}
+
+/// `Meta(choices=[...])` 的取值集合, 仅支持全 int 或全 str.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Choices {
+    Int(Vec<i64>),
+    Str(Vec<String>),
+}
+
+impl Choices {
+    /// 从 Python 序列解析取值集合, 元素必须全为 int (不含 bool) 或全为 str, 且不能为空.
+    pub fn from_py(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if value.is_instance_of::<PyString>() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "Meta choices must be a list of int or a list of str",
+            ));
+        }
+        if value.len().is_ok_and(|len| len == 0) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Meta choices must not be empty",
+            ));
+        }
+        if let Ok(iter) = value.try_iter() {
+            for item in iter {
+                if item?.is_instance_of::<PyBool>() {
+                    return Err(pyo3::exceptions::PyTypeError::new_err(
+                        "Meta choices must not contain bool values",
+                    ));
+                }
+            }
+        }
+        if let Ok(items) = value.extract::<Vec<i64>>() {
+            return Ok(Choices::Int(items));
+        }
+        if let Ok(items) = value.extract::<Vec<String>>() {
+            return Ok(Choices::Str(items));
+        }
+        Err(pyo3::exceptions::PyTypeError::new_err(
+            "Meta choices must be a list of int or a list of str",
+        ))
+    }
+
+    /// 值是否属于该集合; 类型不匹配 (含 bool 之于 int 集合) 时视为不属于.
+    pub fn contains(&self, value: &Bound<'_, PyAny>) -> bool {
+        match self {
+            Choices::Int(items) => {
+                !value.is_instance_of::<PyBool>()
+                    && value.extract::<i64>().is_ok_and(|v| items.contains(&v))
+            }
+            Choices::Str(items) => value
+                .cast::<PyString>()
+                .ok()
+                .and_then(|s| s.to_str().ok().map(|s| items.iter().any(|i| i == s)))
+                .unwrap_or(false),
+        }
+    }
+
+    /// 用于错误信息的 Python 风格列表表示.
+    pub fn describe(&self) -> String {
+        match self {
+            Choices::Int(items) => format!("{:?}", items),
+            Choices::Str(items) => {
+                let parts: Vec<String> = items.iter().map(|s| format!("'{}'", s)).collect();
+                format!("[{}]", parts.join(", "))
+            }
+        }
+    }
+
+    /// 转换为 Python list.
+    pub fn to_list<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        match self {
+            Choices::Int(items) => PyList::new(py, items),
+            Choices::Str(items) => PyList::new(py, items),
+        }
+    }
+}
+
+/// 未传入 `max_bytes` 时 `Meta(compress=...)` 字段解压后的默认长度上限 (64 MiB).
+pub const DEFAULT_MAX_DECOMPRESSED: usize = 64 * 1024 * 1024;
+
+/// bytes 字段的压缩算法 (`Meta(compress=...)`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    Zlib,
+    Gzip,
+}
+
+impl Compression {
+    /// 解析 `Meta(compress=...)` 的取值.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "zlib" => Some(Compression::Zlib),
+            "gzip" => Some(Compression::Gzip),
+            _ => None,
+        }
+    }
+
+    fn module(self) -> &'static str {
+        match self {
+            Compression::Zlib => "zlib",
+            Compression::Gzip => "gzip",
+        }
+    }
+
+    /// 使用标准库对应模块压缩数据.
+    pub fn compress<'py>(
+        self,
+        py: Python<'py>,
+        data: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        py.import(self.module())?.call_method1("compress", (data,))
+    }
+
+    /// 解压时 zlib 的 `wbits`: zlib 头部或 gzip 头部.
+    fn wbits(self) -> i32 {
+        match self {
+            Compression::Zlib => 15,
+            Compression::Gzip => 16 + 15,
+        }
+    }
+
+    /// 增量解压数据, 输出超过 `limit` 字节时抛出 ValueError, 防止压缩炸弹耗尽内存.
+    pub fn decompress<'py>(
+        self,
+        py: Python<'py>,
+        data: &Bound<'py, PyAny>,
+        limit: usize,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let decompressor = py
+            .import("zlib")?
+            .call_method1("decompressobj", (self.wbits(),))?;
+        let out = decompressor.call_method1("decompress", (data, limit.saturating_add(1)))?;
+        if out.len()? > limit {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "decompressed size exceeds {limit} bytes"
+            )));
+        }
+        if !decompressor.getattr("eof")?.is_truthy()? {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "incomplete compressed data",
+            ));
+        }
+        Ok(out)
+    }
+}
+
+/// 数值字段的字节序覆盖 (`Meta(endian=...)`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Endian {
+    Big,
+    Little,
+}
+
+impl Endian {
+    /// 解析 `Meta(endian=...)` 的取值.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "big" => Some(Endian::Big),
+            "little" => Some(Endian::Little),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Endian::Big => "big",
+            Endian::Little => "little",
+        }
+    }
+}
+
+/// int 字段的固定线上宽度 (`Meta(wire=...)`), 覆盖按数值大小选择宽度的默认行为.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WireInt {
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+}
+
+impl WireInt {
+    /// 解析 `Meta(wire=...)` 的取值.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "int8" => Some(WireInt::Int8),
+            "int16" => Some(WireInt::Int16),
+            "int32" => Some(WireInt::Int32),
+            "int64" => Some(WireInt::Int64),
+            "uint8" => Some(WireInt::UInt8),
+            "uint16" => Some(WireInt::UInt16),
+            "uint32" => Some(WireInt::UInt32),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WireInt::Int8 => "int8",
+            WireInt::Int16 => "int16",
+            WireInt::Int32 => "int32",
+            WireInt::Int64 => "int64",
+            WireInt::UInt8 => "uint8",
+            WireInt::UInt16 => "uint16",
+            WireInt::UInt32 => "uint32",
+        }
+    }
+
+    /// 取值的闭区间 `(min, max)`.
+    pub fn bounds(self) -> (i64, i64) {
+        match self {
+            WireInt::Int8 => (i8::MIN as i64, i8::MAX as i64),
+            WireInt::Int16 => (i16::MIN as i64, i16::MAX as i64),
+            WireInt::Int32 => (i32::MIN as i64, i32::MAX as i64),
+            WireInt::Int64 => (i64::MIN, i64::MAX),
+            WireInt::UInt8 => (0, u8::MAX as i64),
+            WireInt::UInt16 => (0, u16::MAX as i64),
+            WireInt::UInt32 => (0, u32::MAX as i64),
+        }
+    }
+
+    /// 写出时使用的 Tars 整数类型, 无符号类型与同宽度有符号类型共用.
+    pub fn tars_type(self) -> TarsType {
+        match self {
+            WireInt::Int8 | WireInt::UInt8 => TarsType::Int1,
+            WireInt::Int16 | WireInt::UInt16 => TarsType::Int2,
+            WireInt::Int32 | WireInt::UInt32 => TarsType::Int4,
+            WireInt::Int64 => TarsType::Int8,
+        }
+    }
+
+    /// 将按有符号读出的值还原为字段取值.
+    ///
+    /// 无符号类型在线上类型与声明宽度一致时按补码重新解释 (如 Int2 的 -1 还原为 65535);
+    /// 其他宽度 (如 ZeroTag 或更窄的整数) 原样返回, 由调用方做范围校验.
+    pub fn from_wire(self, type_id: TarsType, raw: i64) -> i64 {
+        if raw >= 0 || type_id != self.tars_type() {
+            return raw;
+        }
+        match self {
+            WireInt::UInt8 => raw & 0xff,
+            WireInt::UInt16 => raw & 0xffff,
+            WireInt::UInt32 => raw & 0xffff_ffff,
+            _ => raw,
+        }
+    }
+
+    /// 校验取值是否落在该宽度的范围内.
+    pub fn check_range(self, field_name: &str, value: i64) -> Result<(), String> {
+        let (min, max) = self.bounds();
+        if value < min || value > max {
+            return Err(format!(
+                "Field '{}' value {} out of range for wire '{}' ({}..={})",
+                field_name,
+                value,
+                self.name(),
+                min,
+                max
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -157,6 +656,32 @@ pub struct FieldDef {
     pub is_required: bool,
     pub init: bool,
     pub wrap_simplelist: bool,
+    /// 解码时额外接受的旧 tag (`field(alias_tags=...)`), 编码始终使用 `tag`.
+    pub alias_tags: Vec<u8>,
+    /// 数值 payload 的字节序 (`Meta(endian=...)`); None 沿用编解码调用的 `little_endian`.
+    pub endian: Option<Endian>,
+    /// 是否接受枚举原始值并转换为成员 (`Meta(coerce_enum=True)`).
+    pub coerce_enum: bool,
+    /// bytes payload 的压缩算法 (`Meta(compress=...)`).
+    pub compress: Option<Compression>,
+    /// str 字段是否接受 bytes 并按 UTF-8 解码 (`Meta(accept_bytes=True)`).
+    pub accept_bytes: bool,
+    /// int 字段的固定线上宽度 (`Meta(wire=...)`).
+    pub wire: Option<WireInt>,
+    /// bytes 字段的分块大小 (`Meta(chunk_size=...)`).
+    pub chunk_size: Option<usize>,
+    /// str 字段按原始 bytes 解码, 不做 UTF-8 校验 (`Meta(as_bytes=True)`).
+    pub as_bytes: bool,
+    /// Any 字段中的 bool 以标记结构体编码, 解码时还原为 bool (`Meta(any_preserve_bool=True)`).
+    pub any_preserve_bool: bool,
+    /// Python 侧 dict 表示使用的公开键名 (`Meta(alias=...)`), 不影响按 tag 的线上编码.
+    pub alias: Option<String>,
+    /// str 字段编码前执行的 Unicode 规范化形式 (`Meta(normalize=...)`).
+    pub normalize: Option<String>,
+    /// 编码前转换字段值的回调 (`Meta(encoder=...)`).
+    pub encoder: Option<Py<PyAny>>,
+    /// 解码后转换字段值的回调 (`Meta(decoder=...)`).
+    pub decoder: Option<Py<PyAny>>,
     pub constraints: Option<Box<Constraints>>,
 }
 
@@ -169,6 +694,10 @@ pub struct StructMetaData {
 #[derive(Debug)]
 pub struct StructDef {
     pub class_ptr: usize,
+    /// 编译目标类的强引用, 用于识别沿继承链读到或模块重载后残留的 Schema.
+    ///
+    /// 与 `TypeExpr::Struct` 一样由 `Schema::__traverse__` 访问, 类与 Schema 的循环引用可被 GC 回收.
+    pub class_ref: Py<PyType>,
     pub name: String,
     pub fields_sorted: Vec<FieldDef>,
     pub tag_lookup_vec: Vec<Option<usize>>,
@@ -178,23 +707,78 @@ pub struct StructDef {
     pub forbid_unknown_tags: bool,
     pub eq: bool,
     pub omit_defaults: bool,
+    /// 编码时跳过值为 None 的字段 (默认 True); 为 False 时报错, 除非被 `omit_defaults` 省略.
+    pub omit_none: bool,
     pub repr_omit_defaults: bool,
     pub kw_only: bool,
     pub dict: bool,
     pub weakref: bool,
+    pub emit_empty_marker: bool,
+    /// 解码后存在未消费的字节时报错 (默认 True).
+    pub forbid_extra_bytes: bool,
+    /// 枚举字段默认按成员名称编码 (`enum_mode="name"`).
+    pub enum_by_name: bool,
+    /// `str()` 展示的字段下标 (`str_fields=[...]`), None 表示沿用 `__repr__`.
+    pub str_fields: Option<Vec<usize>>,
+    /// 所有字段均为扁平标量 (无嵌套 Struct、容器、Union 或特殊类型).
+    pub flat: bool,
+    /// 目标类为 `pydantic.BaseModel` 子类: 解码时收集字段后经 `cls(**kwargs)` 构造.
+    pub is_pydantic: bool,
+    /// 类属性 `__tarsio_version__` 声明的 Schema 版本号, 未声明时为 None.
+    pub version: Option<i64>,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl StructDef {
+    /// 判断该 Schema 是否由 `cls` 本身编译而来.
+    ///
+    /// `__tarsio_schema__` 会沿继承链被子类读到, 模块重载后也会留下指向旧类的 Schema.
+    /// Schema 持有类的强引用, 旧类不会被释放, 因此比较对象身份即可, 无需升级弱引用.
+    #[inline]
+    pub fn is_compiled_for(&self, cls: &Bound<'_, PyType>) -> bool {
+        self.class_ref.is(cls)
+    }
+
+    /// 还原编译该 Schema 时使用的配置.
+    pub fn schema_config(&self) -> SchemaConfig {
+        SchemaConfig {
+            frozen: self.frozen,
+            order: self.order,
+            forbid_unknown_tags: self.forbid_unknown_tags,
+            eq: self.eq,
+            omit_defaults: self.omit_defaults,
+            omit_none: self.omit_none,
+            repr_omit_defaults: self.repr_omit_defaults,
+            kw_only: self.kw_only,
+            dict: self.dict,
+            weakref: self.weakref,
+            emit_empty_marker: self.emit_empty_marker,
+            forbid_extra_bytes: self.forbid_extra_bytes,
+            enum_by_name: self.enum_by_name,
+            str_fields: self.str_fields.as_ref().map(|idxs| {
+                idxs.iter()
+                    .map(|&i| self.fields_sorted[i].name.clone())
+                    .collect()
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct SchemaConfig {
     pub frozen: bool,
     pub order: bool,
     pub forbid_unknown_tags: bool,
     pub eq: bool,
     pub omit_defaults: bool,
+    pub omit_none: bool,
     pub repr_omit_defaults: bool,
     pub kw_only: bool,
     pub dict: bool,
     pub weakref: bool,
+    pub emit_empty_marker: bool,
+    pub forbid_extra_bytes: bool,
+    pub enum_by_name: bool,
+    pub str_fields: Option<Vec<String>>,
 }
 
 #[pyclass(module = "tarsio._core")]
@@ -212,10 +796,20 @@ pub struct StructConfig {
     #[pyo3(get)]
     pub omit_defaults: bool,
     #[pyo3(get)]
+    pub omit_none: bool,
+    #[pyo3(get)]
     pub weakref: bool,
     #[pyo3(get)]
     pub dict: bool,
     #[pyo3(get)]
+    pub emit_empty_marker: bool,
+    #[pyo3(get)]
+    pub forbid_extra_bytes: bool,
+    #[pyo3(get)]
+    pub enum_mode: &'static str,
+    #[pyo3(get)]
+    pub str_fields: Option<Vec<String>>,
+    #[pyo3(get)]
     pub rename: Option<Py<PyAny>>,
 }
 
@@ -228,8 +822,13 @@ impl StructConfig {
             kw_only: config.kw_only,
             repr_omit_defaults: config.repr_omit_defaults,
             omit_defaults: config.omit_defaults,
+            omit_none: config.omit_none,
             weakref: config.weakref,
             dict: config.dict,
+            emit_empty_marker: config.emit_empty_marker,
+            forbid_extra_bytes: config.forbid_extra_bytes,
+            enum_mode: if config.enum_by_name { "name" } else { "value" },
+            str_fields: config.str_fields.clone(),
             rename: None,
         }
     }
@@ -246,18 +845,66 @@ pub struct Meta {
     pub ge: Option<f64>,
     #[pyo3(get, set)]
     pub le: Option<f64>,
+    /// 数值字段必须为该值的整数倍, 须大于 0.
+    #[pyo3(get, set)]
+    pub multiple_of: Option<f64>,
     #[pyo3(get, set)]
     pub min_len: Option<usize>,
     #[pyo3(get, set)]
     pub max_len: Option<usize>,
     #[pyo3(get, set)]
     pub pattern: Option<String>,
+    /// 数值字段 payload 的字节序覆盖 ("big" 或 "little"), None 表示协议默认的大端.
+    #[pyo3(get)]
+    pub endian: Option<String>,
+    /// 编码/构造时是否把枚举成员的原始值转换为成员 (`enum_cls(value)`).
+    #[pyo3(get)]
+    pub coerce_enum: bool,
+    /// 枚举字段的编码方式覆盖 ("value" 或 "name"), None 表示沿用 Struct 的 `enum_mode`.
+    #[pyo3(get)]
+    pub enum_mode: Option<String>,
+    /// bytes 字段的压缩算法 ("zlib" 或 "gzip"), None 表示不压缩.
+    #[pyo3(get)]
+    pub compress: Option<String>,
+    /// str 字段在构造/编码时是否接受 bytes 并按 UTF-8 解码.
+    #[pyo3(get)]
+    pub accept_bytes: bool,
+    /// bytes 字段的精确长度, 编码与解码时长度不等于该值即报错.
+    #[pyo3(get, set)]
+    pub fixed_len: Option<usize>,
+    /// int/str 字段允许的取值集合, 构造、编码与解码时不在集合内即报错.
+    pub choices: Option<Choices>,
+    /// int 字段的固定线上宽度 ("int8"/"int16"/"int32"/"int64"/"uint8"/"uint16"/"uint32"),
+    /// None 表示按数值大小自动选择.
+    #[pyo3(get)]
+    pub wire: Option<String>,
+    /// bytes 字段的分块大小, 设置后编码为由多个 SimpleList 组成的 List, 解码时拼接还原.
+    #[pyo3(get)]
+    pub chunk_size: Option<usize>,
+    /// str 字段解码为原始 bytes (不做 UTF-8 校验), 构造与编码时同时接受 str 与 bytes.
+    #[pyo3(get)]
+    pub as_bytes: bool,
+    /// Any 字段的 bool 值编码为 tarsio 专有的标记结构体, 解码时还原为 bool 而非 int.
+    #[pyo3(get)]
+    pub any_preserve_bool: bool,
+    /// Python 侧 dict 表示使用的公开键名, 线上仍按 tag (TypedDict 成员按字段名) 编码.
+    #[pyo3(get)]
+    pub alias: Option<String>,
+    /// str 字段编码前的 Unicode 规范化形式 ("NFC"/"NFD"/"NFKC"/"NFKD"), None 表示原样写出.
+    #[pyo3(get)]
+    pub normalize: Option<String>,
+    /// 编码前以 `encoder(value)` 转换字段值, 返回值须符合字段标注的类型.
+    #[pyo3(get)]
+    pub encoder: Option<Py<PyAny>>,
+    /// 解码后以 `decoder(value)` 转换按标注类型读出的值.
+    #[pyo3(get)]
+    pub decoder: Option<Py<PyAny>>,
 }
 
 #[pymethods]
 impl Meta {
     #[new]
-    #[pyo3(signature=(gt=None, lt=None, ge=None, le=None, min_len=None, max_len=None, pattern=None))]
+    #[pyo3(signature=(gt=None, lt=None, ge=None, le=None, min_len=None, max_len=None, pattern=None, endian=None, coerce_enum=false, enum_mode=None, compress=None, accept_bytes=false, fixed_len=None, choices=None, wire=None, multiple_of=None, chunk_size=None, as_bytes=false, any_preserve_bool=false, alias=None, normalize=None, encoder=None, decoder=None))]
     #[allow(clippy::too_many_arguments)]
     fn new(
         gt: Option<f64>,
@@ -267,8 +914,88 @@ impl Meta {
         min_len: Option<usize>,
         max_len: Option<usize>,
         pattern: Option<String>,
-    ) -> Self {
-        Self {
+        endian: Option<String>,
+        coerce_enum: bool,
+        enum_mode: Option<String>,
+        compress: Option<String>,
+        accept_bytes: bool,
+        fixed_len: Option<usize>,
+        choices: Option<&Bound<'_, PyAny>>,
+        wire: Option<String>,
+        multiple_of: Option<f64>,
+        chunk_size: Option<usize>,
+        as_bytes: bool,
+        any_preserve_bool: bool,
+        alias: Option<String>,
+        normalize: Option<String>,
+        encoder: Option<&Bound<'_, PyAny>>,
+        decoder: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        let choices = choices.map(Choices::from_py).transpose()?;
+        if let Some(e) = endian.as_deref()
+            && Endian::from_name(e).is_none()
+        {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Meta endian must be 'big' or 'little', got '{e}'"
+            )));
+        }
+        if let Some(m) = enum_mode.as_deref()
+            && m != "value"
+            && m != "name"
+        {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Meta enum_mode must be 'value' or 'name', got '{m}'"
+            )));
+        }
+        if let Some(c) = compress.as_deref()
+            && Compression::from_name(c).is_none()
+        {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Meta compress must be 'zlib' or 'gzip', got '{c}'"
+            )));
+        }
+        if let Some(m) = multiple_of
+            && !(m.is_finite() && m > 0.0)
+        {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Meta multiple_of must be a positive number, got {m}"
+            )));
+        }
+        if let Some(w) = wire.as_deref()
+            && WireInt::from_name(w).is_none()
+        {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Meta wire must be one of 'int8', 'int16', 'int32', 'int64', \
+                 'uint8', 'uint16', 'uint32', got '{w}'"
+            )));
+        }
+        if chunk_size == Some(0) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Meta chunk_size must be a positive integer",
+            ));
+        }
+        if alias.as_deref() == Some("") {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Meta alias must be a non-empty string",
+            ));
+        }
+        if let Some(n) = normalize.as_deref()
+            && !matches!(n, "NFC" | "NFD" | "NFKC" | "NFKD")
+        {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Meta normalize must be one of 'NFC', 'NFD', 'NFKC', 'NFKD', got '{n}'"
+            )));
+        }
+        for (name, hook) in [("encoder", encoder), ("decoder", decoder)] {
+            if let Some(hook) = hook
+                && !hook.is_callable()
+            {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Meta {name} must be callable"
+                )));
+            }
+        }
+        Ok(Self {
             gt,
             lt,
             ge,
@@ -276,7 +1003,55 @@ impl Meta {
             min_len,
             max_len,
             pattern,
-        }
+            endian,
+            coerce_enum,
+            enum_mode,
+            compress,
+            accept_bytes,
+            fixed_len,
+            choices,
+            wire,
+            multiple_of,
+            chunk_size,
+            as_bytes,
+            any_preserve_bool,
+            alias,
+            normalize,
+            encoder: encoder.map(|h| h.clone().unbind()),
+            decoder: decoder.map(|h| h.clone().unbind()),
+        })
+    }
+
+    /// 允许的取值集合, 未设置时为 None.
+    #[getter]
+    fn choices<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyList>>> {
+        self.choices.as_ref().map(|c| c.to_list(py)).transpose()
+    }
+}
+
+/// 显式字段 Tag 标记.
+///
+/// 用于 `Annotated[T, Tag(n)]`, 与裸整数 Tag 等价, 但不会与其他整数型元数据混淆.
+#[pyclass(module = "tarsio._core", frozen)]
+pub struct Tag {
+    #[pyo3(get)]
+    pub value: u8,
+}
+
+#[pymethods]
+impl Tag {
+    #[new]
+    fn new(value: i64) -> PyResult<Self> {
+        let value = u8::try_from(value).map_err(|_| {
+            pyo3::exceptions::PyTypeError::new_err(format!(
+                "Tag must be in range 0..=255, got {value}"
+            ))
+        })?;
+        Ok(Self { value })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Tag({})", self.value)
     }
 }
 
@@ -299,6 +1074,7 @@ pub struct FieldSpec {
     pub default_value: Option<Py<PyAny>>,
     pub default_factory: Option<Py<PyAny>>,
     pub wrap_simplelist: bool,
+    pub alias_tags: Vec<u8>,
 }
 
 /// 获取 `NODEFAULT` 单例.
@@ -329,6 +1105,7 @@ pub fn field(py: Python<'_>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Py<
     let mut default_value: Option<Py<PyAny>> = None;
     let mut default_factory: Option<Py<PyAny>> = None;
     let mut wrap_simplelist = false;
+    let mut alias_tags: Vec<u8> = Vec::new();
 
     if let Some(k) = kwargs {
         for (key, value) in k.iter() {
@@ -366,6 +1143,23 @@ pub fn field(py: Python<'_>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Py<
                         )
                     })?;
                 }
+                "alias_tags" => {
+                    let invalid = || {
+                        pyo3::exceptions::PyTypeError::new_err(
+                            "field() 'alias_tags' must be a sequence of integers in range 0..=255",
+                        )
+                    };
+                    if value.is_instance_of::<pyo3::types::PyString>() {
+                        return Err(invalid());
+                    }
+                    for item in value.try_iter().map_err(|_| invalid())? {
+                        let int_tag = item?.extract::<i64>().map_err(|_| invalid())?;
+                        if !(0..=255).contains(&int_tag) {
+                            return Err(invalid());
+                        }
+                        alias_tags.push(int_tag as u8);
+                    }
+                }
                 _ => {
                     return Err(pyo3::exceptions::PyTypeError::new_err(format!(
                         "field() got an unexpected keyword argument '{}'",
@@ -399,12 +1193,20 @@ pub fn field(py: Python<'_>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Py<
             default_value,
             default_factory,
             wrap_simplelist,
+            alias_tags,
         },
     )
 }
 
 #[pyclass(subclass, weakref, module = "tarsio._core", name = "_StructBase")]
-pub struct Struct;
+pub struct Struct {
+    /// 实例级只读标记 (如 `decode(..., freeze=True)`), 与类级 `frozen` 配置叠加生效.
+    ///
+    /// 实例经 `PyType_GenericAlloc` 零初始化分配, 因此默认为 false.
+    pub(crate) frozen_instance: AtomicBool,
+    /// `decode(..., track_presence=True)` 记录的线上出现字段名集合, 未跟踪时为 None.
+    pub(crate) present: Option<Py<PyFrozenSet>>,
+}
 
 #[pyclass(
     subclass,
@@ -416,6 +1218,9 @@ pub struct TarsDict;
 
 pub const SCHEMA_ATTR: &str = "__tarsio_schema__";
 
+/// 延迟编译标记的类属性名, 只在类自身的 `__dict__` 中查找, 不沿继承链读取.
+pub const PENDING_SCHEMA_ATTR: &str = "__tarsio_pending_schema__";
+
 thread_local! {
     // 线程内 schema 缓存,用于减少高频 getattr 开销。
     // 使用 Weak 引用，避免循环引用导致的内存泄漏。
@@ -427,9 +1232,18 @@ pub struct Schema {
     pub def: Arc<StructDef>,
 }
 
+/// 定义类时注解引用了尚未定义的名称 (如互相引用的 Struct), Schema 推迟到首次使用时编译.
+///
+/// 保存类定义时解析出的配置, 编译成功后从类上移除.
+#[pyclass(module = "tarsio._core", name = "PendingSchema", frozen)]
+pub struct PendingSchema {
+    pub config: SchemaConfig,
+}
+
 #[pymethods]
 impl Schema {
     fn __traverse__(&self, visit: PyVisit<'_>) -> Result<(), PyTraverseError> {
+        visit.call(&self.def.class_ref)?;
         for field in &self.def.fields_sorted {
             visit.call(&field.name_py)?;
             if let Some(v) = &field.default_value {
@@ -438,6 +1252,9 @@ impl Schema {
             if let Some(v) = &field.default_factory {
                 visit.call(v)?;
             }
+            for hook in [&field.encoder, &field.decoder].into_iter().flatten() {
+                visit.call(hook)?;
+            }
             if let Some(constraints) = &field.constraints
                 && let Some(pattern) = &constraints.pattern
             {
@@ -30,8 +30,11 @@ impl fmt::Display for PathItem {
 pub struct DeError {
     pub msg: String,
     pub path: Vec<PathItem>,
-    pub cause: Option<PyErr>,
+    /// 原始 Python 异常; 装箱以控制 `DeResult` 的大小.
+    pub cause: Option<Box<PyErr>>,
     pub passthrough: bool,
+    /// 出错时读取器在输入中的字节偏移, 由最内层能访问读取器的调用方记录.
+    pub offset: Option<u64>,
 }
 
 impl DeError {
@@ -41,6 +44,7 @@ impl DeError {
             path: Vec::new(),
             cause: None,
             passthrough: false,
+            offset: None,
         }
     }
 
@@ -48,8 +52,9 @@ impl DeError {
         Self {
             msg: err.to_string(),
             path: Vec::new(),
-            cause: Some(err),
+            cause: Some(Box::new(err)),
             passthrough: false,
+            offset: None,
         }
     }
 
@@ -57,8 +62,9 @@ impl DeError {
         Self {
             msg: err.to_string(),
             path: Vec::new(),
-            cause: Some(err),
+            cause: Some(Box::new(err)),
             passthrough: true,
+            offset: None,
         }
     }
 
@@ -67,6 +73,12 @@ impl DeError {
         self
     }
 
+    /// 记录出错位置的字节偏移; 已记录时保留更早 (更内层) 的值.
+    pub fn at_offset(mut self, offset: u64) -> Self {
+        self.offset.get_or_insert(offset);
+        self
+    }
+
     pub fn to_pyerr(mut self, py: Python<'_>) -> PyErr {
         if self.passthrough
             && let Some(cause) = &self.cause
@@ -95,11 +107,15 @@ impl DeError {
             let _ = write!(&mut path_str, "{}", item);
         }
 
-        let msg = format!("Error at {}: {}", path_str, self.msg);
+        let mut msg = format!("Error at {}: {}", path_str, self.msg);
+        if let Some(offset) = self.offset {
+            use std::fmt::Write;
+            let _ = write!(&mut msg, " at byte offset {}", offset);
+        }
 
         if let Some(cause) = self.cause {
             let new_err = ValidationError::new_err(msg);
-            new_err.set_cause(py, Some(cause));
+            new_err.set_cause(py, Some(*cause));
             new_err
         } else {
             ValidationError::new_err(msg)
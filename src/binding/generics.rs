@@ -133,6 +133,7 @@ pub(crate) fn handle_class_getitem<'py>(
     kwargs.set_item("forbid_unknown_tags", false)?;
     kwargs.set_item("eq", struct_cfg.getattr("eq")?)?;
     kwargs.set_item("omit_defaults", struct_cfg.getattr("omit_defaults")?)?;
+    kwargs.set_item("omit_none", struct_cfg.getattr("omit_none")?)?;
     kwargs.set_item(
         "repr_omit_defaults",
         struct_cfg.getattr("repr_omit_defaults")?,
@@ -140,6 +141,16 @@ pub(crate) fn handle_class_getitem<'py>(
     kwargs.set_item("kw_only", struct_cfg.getattr("kw_only")?)?;
     kwargs.set_item("dict", struct_cfg.getattr("dict")?)?;
     kwargs.set_item("weakref", struct_cfg.getattr("weakref")?)?;
+    kwargs.set_item(
+        "emit_empty_marker",
+        struct_cfg.getattr("emit_empty_marker")?,
+    )?;
+    kwargs.set_item(
+        "forbid_extra_bytes",
+        struct_cfg.getattr("forbid_extra_bytes")?,
+    )?;
+    kwargs.set_item("enum_mode", struct_cfg.getattr("enum_mode")?)?;
+    kwargs.set_item("str_fields", struct_cfg.getattr("str_fields")?)?;
 
     let mcls = cls.get_type();
     let new_cls_any = mcls.call((name, bases, namespace), Some(&kwargs))?;
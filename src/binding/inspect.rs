@@ -1,12 +1,19 @@
 use pyo3::prelude::*;
 use pyo3::pyclass_init::PyClassInitializer;
-use pyo3::types::{PyAny, PyTuple, PyType};
+use pyo3::types::{
+    PyAny, PyComplex, PyDict, PyFrozenSet, PyList, PySet, PyString, PyTuple, PyType,
+};
 use std::collections::HashSet;
 
-use crate::binding::core::nodefault_singleton;
+use crate::binding::codec::ser::encoded_struct_size;
+use crate::binding::core::{
+    Choices, Constraints, FieldDef, StructDef, TypeExpr, WireInt, WireType, nodefault_singleton,
+};
 use crate::binding::parse::{
     ConstraintsIR, FieldInfoIR, TypeInfoIR, introspect_struct_fields, introspect_type_info_ir,
 };
+use crate::binding::schema::{Struct, ensure_schema_for_class};
+use crate::binding::utils::{check_depth, with_stdlib_cache};
 
 /// 字段约束信息.
 ///
@@ -60,6 +67,10 @@ fn constraint_le(constraints: &Option<ConstraintsIR>) -> Option<f64> {
     constraints.as_ref().and_then(|c| c.le)
 }
 
+fn constraint_multiple_of(constraints: &Option<ConstraintsIR>) -> Option<f64> {
+    constraints.as_ref().and_then(|c| c.multiple_of)
+}
+
 fn constraint_min_length(constraints: &Option<ConstraintsIR>) -> Option<usize> {
     constraints.as_ref().and_then(|c| c.min_len)
 }
@@ -68,10 +79,25 @@ fn constraint_max_length(constraints: &Option<ConstraintsIR>) -> Option<usize> {
     constraints.as_ref().and_then(|c| c.max_len)
 }
 
+fn constraint_fixed_length(constraints: &Option<ConstraintsIR>) -> Option<usize> {
+    constraints.as_ref().and_then(|c| c.fixed_len)
+}
+
 fn constraint_pattern(constraints: &Option<ConstraintsIR>) -> Option<String> {
     constraints.as_ref().and_then(|c| c.pattern.clone())
 }
 
+fn constraint_choices<'py>(
+    py: Python<'py>,
+    constraints: &Option<ConstraintsIR>,
+) -> PyResult<Option<Bound<'py, PyList>>> {
+    constraints
+        .as_ref()
+        .and_then(|c| c.choices.as_ref())
+        .map(|c| c.to_list(py))
+        .transpose()
+}
+
 /// 整数类型（JCE int 家族的抽象视图）.
 ///
 /// Attributes:
@@ -107,6 +133,16 @@ impl IntType {
     fn le(&self) -> Option<f64> {
         constraint_le(&self.constraints)
     }
+
+    #[getter]
+    fn multiple_of(&self) -> Option<f64> {
+        constraint_multiple_of(&self.constraints)
+    }
+
+    #[getter]
+    fn choices<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyList>>> {
+        constraint_choices(py, &self.constraints)
+    }
 }
 
 /// 字符串类型.
@@ -139,6 +175,11 @@ impl StrType {
     fn pattern(&self) -> Option<String> {
         constraint_pattern(&self.constraints)
     }
+
+    #[getter]
+    fn choices<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyList>>> {
+        constraint_choices(py, &self.constraints)
+    }
 }
 
 /// 浮点类型（运行时对应 double 语义）.
@@ -176,6 +217,11 @@ impl FloatType {
     fn le(&self) -> Option<f64> {
         constraint_le(&self.constraints)
     }
+
+    #[getter]
+    fn multiple_of(&self) -> Option<f64> {
+        constraint_multiple_of(&self.constraints)
+    }
 }
 
 /// 布尔类型（在 JCE 编码层面通常以 int 表达）.
@@ -197,9 +243,12 @@ impl BoolType {
 ///
 /// Attributes:
 ///     constraints: 字段约束。
+///     mutable: 是否声明为 `bytearray`（解码得到可变缓冲区）。
 #[pyclass(module = "tarsio._core.inspect", extends = BasicTypeBase)]
 pub struct BytesType {
     constraints: Option<ConstraintsIR>,
+    #[pyo3(get)]
+    mutable: bool,
 }
 
 #[pymethods]
@@ -218,6 +267,71 @@ impl BytesType {
     fn max_length(&self) -> Option<usize> {
         constraint_max_length(&self.constraints)
     }
+
+    #[getter]
+    fn fixed_length(&self) -> Option<usize> {
+        constraint_fixed_length(&self.constraints)
+    }
+}
+
+/// IP 地址类型（以 `.packed` 字节按 SimpleList 编码）.
+///
+/// Attributes:
+///     cls: 声明的地址类（`IPv4Address` 或 `IPv6Address`）。
+///     version: 地址版本（4 或 6）。
+#[pyclass(module = "tarsio._core.inspect", extends = BasicTypeBase)]
+pub struct IpAddressType {
+    #[pyo3(get)]
+    pub cls: Py<PyType>,
+}
+
+#[pymethods]
+impl IpAddressType {
+    #[getter]
+    fn kind(&self) -> &'static str {
+        "ipaddress"
+    }
+
+    #[getter]
+    fn version(&self, py: Python<'_>) -> PyResult<u8> {
+        with_stdlib_cache(py, |cache| {
+            Ok(if self.cls.bind(py).is(cache.ipv6_type.bind(py)) {
+                6
+            } else {
+                4
+            })
+        })
+    }
+}
+
+/// 路径类型（以 `str(path)` 按 String 编码）.
+///
+/// Attributes:
+///     cls: 声明的路径类（`pathlib.PurePath` 及其子类）。
+#[pyclass(module = "tarsio._core.inspect", extends = BasicTypeBase)]
+pub struct PathType {
+    #[pyo3(get)]
+    pub cls: Py<PyType>,
+}
+
+#[pymethods]
+impl PathType {
+    #[getter]
+    fn kind(&self) -> &'static str {
+        "path"
+    }
+}
+
+/// 复数类型（编码为 tag 0 实部、tag 1 虚部的 Struct）.
+#[pyclass(module = "tarsio._core.inspect", extends = BasicTypeBase)]
+pub struct ComplexType {}
+
+#[pymethods]
+impl ComplexType {
+    #[getter]
+    fn kind(&self) -> &'static str {
+        "complex"
+    }
 }
 
 /// 动态类型（运行时根据值推断编码）.
@@ -272,6 +386,68 @@ impl EnumType {
     }
 }
 
+/// Flag 类型：`enum.Flag` 子类（含 `IntFlag`）.
+///
+/// Attributes:
+///     cls: Flag 类型。
+#[pyclass(module = "tarsio._core.inspect", extends = CompoundTypeBase)]
+pub struct FlagType {
+    #[pyo3(get)]
+    pub cls: Py<PyType>,
+}
+
+#[pymethods]
+impl FlagType {
+    #[getter]
+    fn kind(&self) -> &'static str {
+        "flag"
+    }
+}
+
+/// 标量子类类型：`int`/`str`/`float`/`bytes` 的直接子类（如 `class Port(int)`）.
+///
+/// Attributes:
+///     cls: 子类。
+///     value_type: 底层类型的内省结果。
+#[pyclass(module = "tarsio._core.inspect", extends = CompoundTypeBase)]
+pub struct SubclassType {
+    #[pyo3(get)]
+    pub cls: Py<PyType>,
+    #[pyo3(get)]
+    pub value_type: Py<PyAny>,
+}
+
+#[pymethods]
+impl SubclassType {
+    #[getter]
+    fn kind(&self) -> &'static str {
+        "subclass"
+    }
+}
+
+/// Literal 类型：`Literal[v1, v2, ...]`.
+///
+/// 编解码按取值的底层类型进行，混合类型的取值对应 Union。
+///
+/// Attributes:
+///     values: 允许的取值（不含 None）。
+///     value_type: 取值底层类型的内省结果。
+#[pyclass(module = "tarsio._core.inspect", extends = CompoundTypeBase)]
+pub struct LiteralType {
+    #[pyo3(get)]
+    pub values: Py<PyTuple>,
+    #[pyo3(get)]
+    pub value_type: Py<PyAny>,
+}
+
+#[pymethods]
+impl LiteralType {
+    #[getter]
+    fn kind(&self) -> &'static str {
+        "literal"
+    }
+}
+
 /// Union 类型（非 Optional 形式）。
 ///
 /// Attributes:
@@ -381,11 +557,13 @@ impl VarTupleType {
     }
 }
 
-/// 映射类型：`dict[K, V]`.
+/// 映射类型：`dict[K, V]` / `defaultdict[K, V]`.
 ///
 /// Attributes:
 ///     key_type: 键类型。
 ///     value_type: 值类型。
+///     default_factory: `defaultdict` 的工厂函数，普通 dict 为 None。
+///     cls: 用户定义的 `dict` 子类，普通 dict 为 None。
 ///     constraints: 字段约束。
 #[pyclass(module = "tarsio._core.inspect", extends = CompoundTypeBase)]
 pub struct MapType {
@@ -393,6 +571,10 @@ pub struct MapType {
     pub key_type: Py<PyAny>,
     #[pyo3(get)]
     pub value_type: Py<PyAny>,
+    #[pyo3(get)]
+    pub default_factory: Option<Py<PyAny>>,
+    #[pyo3(get)]
+    pub cls: Option<Py<PyType>>,
     constraints: Option<ConstraintsIR>,
 }
 
@@ -418,11 +600,14 @@ impl MapType {
 ///
 /// Attributes:
 ///     item_type: 元素类型。
+///     frozen: 是否为 `frozenset[T]`（解码为 frozenset）。
 ///     constraints: 字段约束。
 #[pyclass(module = "tarsio._core.inspect", extends = CompoundTypeBase)]
 pub struct SetType {
     #[pyo3(get)]
     pub item_type: Py<PyAny>,
+    #[pyo3(get)]
+    pub frozen: bool,
     constraints: Option<ConstraintsIR>,
 }
 
@@ -672,6 +857,942 @@ pub fn struct_info(py: Python<'_>, cls: &Bound<'_, PyType>) -> PyResult<Option<S
     }))
 }
 
+/// 判断 Struct 是否为扁平结构.
+///
+/// 扁平结构只包含基础类型字段 (int/float/str/bool/bytes 及其 Optional 形式),
+/// 不含嵌套 Struct、容器、Union 或其他特殊类型, 且字段未设置 endian/compress/encoder
+/// 等改变编解码行为的 `Meta` 选项或取值约束 (`alias` 除外), 编解码开销最低.
+///
+/// Args:
+///     cls: 需要判断的 `tarsio.Struct` 子类。
+///
+/// Returns:
+///     编译期计算的扁平标记。
+///
+/// Raises:
+///     TypeError: cls 不是可编译 Schema 的类型时抛出。
+#[pyfunction]
+pub fn is_flat(py: Python<'_>, cls: &Bound<'_, PyType>) -> PyResult<bool> {
+    Ok(ensure_schema_for_class(py, cls)?.flat)
+}
+
+/// 计算 Struct 实例编码后的字节数, 不实际生成输出.
+///
+/// Tars 的编码长度由取值唯一确定, 因此结果是精确值, 与 `len(encode(obj))` 相同,
+/// 可用于预分配缓冲区或上报报文大小指标. 字节序不影响长度.
+///
+/// Args:
+///     obj: Struct 实例。
+///
+/// Returns:
+///     编码后的字节数。
+///
+/// Raises:
+///     TypeError: obj 不是可编码的 Struct 实例时抛出。
+///     ValueError: 缺少必填字段、类型不匹配或递归深度超过限制时抛出。
+#[pyfunction]
+pub fn wire_size_estimate(py: Python<'_>, obj: &Bound<'_, PyAny>) -> PyResult<usize> {
+    encoded_struct_size(py, obj)
+}
+
+/// 比较两个 Struct 版本的 Schema, 生成迁移报告.
+///
+/// 字段按名称配对, 嵌套 Struct (含 Optional、list/tuple/set 元素与 dict 值中的 Struct)
+/// 递归比较, 嵌套字段的 `path` 以 `.` 连接, 容器元素以 `[]` 标记 (如 `"items[].name"`).
+///
+/// Args:
+///     old: 旧版本 Struct 类。
+///     new: 新版本 Struct 类。
+///
+/// Returns:
+///     dict, 键为变更类别, 值为变更列表:
+///     `added`/`removed` 项含 `path`、`tag`、`type`;
+///     `tag_changed` 项含 `path`、`old_tag`、`new_tag`;
+///     `type_changed` 项含 `path`、`old_type`、`new_type`;
+///     `optionality_changed` 项含 `path`、`old_optional`、`new_optional`;
+///     `version_changed` 项含 `path` (顶层为空串)、`old_version`、`new_version`.
+///
+/// Raises:
+///     TypeError: old 或 new 不是可编译 Schema 的类型时抛出。
+#[pyfunction]
+pub fn diff_schema<'py>(
+    py: Python<'py>,
+    old: &Bound<'py, PyType>,
+    new: &Bound<'py, PyType>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let old_def = ensure_schema_for_class(py, old)?;
+    let new_def = ensure_schema_for_class(py, new)?;
+    let mut diff = SchemaDiff {
+        added: PyList::empty(py),
+        removed: PyList::empty(py),
+        tag_changed: PyList::empty(py),
+        type_changed: PyList::empty(py),
+        optionality_changed: PyList::empty(py),
+        version_changed: PyList::empty(py),
+        visited: HashSet::new(),
+    };
+    diff.visited
+        .insert((old.as_ptr() as usize, new.as_ptr() as usize));
+    diff.compare_structs(py, &old_def, &new_def, "")?;
+
+    let report = PyDict::new(py);
+    report.set_item("added", diff.added)?;
+    report.set_item("removed", diff.removed)?;
+    report.set_item("tag_changed", diff.tag_changed)?;
+    report.set_item("type_changed", diff.type_changed)?;
+    report.set_item("optionality_changed", diff.optionality_changed)?;
+    report.set_item("version_changed", diff.version_changed)?;
+    Ok(report)
+}
+
+/// 计算 Struct Schema 的稳定指纹.
+///
+/// 指纹覆盖类名、`__tarsio_version__`、各字段的 tag/名称/类型/可选性,
+/// 并递归包含嵌套 Struct; 任意一项变化都会改变结果. 同一 Schema 在不同进程中结果相同,
+/// 可在滚动发布时比对两端的 Schema 是否一致.
+///
+/// Args:
+///     cls: 需要计算指纹的 Struct 类。
+///
+/// Returns:
+///     16 位十六进制字符串 (64 位 FNV-1a)。
+///
+/// Raises:
+///     TypeError: cls 不是可编译 Schema 的类型时抛出。
+#[pyfunction]
+pub fn schema_hash(py: Python<'_>, cls: &Bound<'_, PyType>) -> PyResult<String> {
+    let mut text = String::new();
+    let mut seen = HashSet::new();
+    schema_fingerprint(py, cls, &mut seen, &mut text)?;
+    // FNV-1a: 结果不依赖进程级随机种子, 跨进程稳定.
+    let hash = text.bytes().fold(0xcbf2_9ce4_8422_2325u64, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    Ok(format!("{hash:016x}"))
+}
+
+/// 以规范文本描述 Struct 及其嵌套 Struct, 供 `schema_hash` 计算指纹.
+fn schema_fingerprint(
+    py: Python<'_>,
+    cls: &Bound<'_, PyType>,
+    seen: &mut HashSet<usize>,
+    out: &mut String,
+) -> PyResult<()> {
+    if !seen.insert(cls.as_ptr() as usize) {
+        return Ok(());
+    }
+    let def = ensure_schema_for_class(py, cls)?;
+    out.push_str(&def.name);
+    match def.version {
+        Some(v) => out.push_str(&format!("@{v}{{")),
+        None => out.push('{'),
+    }
+    let mut nested = Vec::new();
+    for field in &def.fields_sorted {
+        out.push_str(&format!(
+            "{}:{}:{}:{};",
+            field.tag,
+            field.name,
+            field.ty.annotation_repr(py),
+            if field.is_optional { "o" } else { "r" }
+        ));
+        collect_struct_classes(&field.ty, &mut nested);
+    }
+    out.push('}');
+    for nested_cls in nested {
+        schema_fingerprint(py, nested_cls.bind(py), seen, out)?;
+    }
+    Ok(())
+}
+
+/// 收集类型表达式中引用的所有 Struct 类.
+fn collect_struct_classes<'a>(ty: &'a TypeExpr, out: &mut Vec<&'a Py<PyType>>) {
+    match ty {
+        TypeExpr::Struct(cls) => out.push(cls),
+        TypeExpr::Optional(inner)
+        | TypeExpr::List(inner)
+        | TypeExpr::VarTuple(inner)
+        | TypeExpr::Set(inner)
+        | TypeExpr::FrozenSet(inner)
+        | TypeExpr::Enum(_, inner) => collect_struct_classes(inner, out),
+        TypeExpr::Map(k, v) | TypeExpr::DefaultDict(k, v, _) | TypeExpr::DictSubclass(k, v, _) => {
+            collect_struct_classes(k, out);
+            collect_struct_classes(v, out);
+        }
+        TypeExpr::Tuple(items) | TypeExpr::NamedTuple(_, items) | TypeExpr::Union(items, _) => {
+            for item in items {
+                collect_struct_classes(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 将 Struct 的 Schema 导出为 Tars IDL 文本.
+///
+/// 嵌套 Struct 递归收集, 按依赖顺序 (被引用者在前) 各自输出一个 `struct` 定义,
+/// `cls` 自身位于最后. 必填字段输出 `require`, 其余输出 `optional`.
+/// 整数值的 Enum 输出为 `enum` 定义并按名称引用 (别名成员不重复输出),
+/// 其他 Enum 按其成员值类型输出.
+///
+/// Args:
+///     cls: 需要导出的 Struct 类。
+///
+/// Returns:
+///     IDL 文本, 定义之间以空行分隔。
+///
+/// Raises:
+///     TypeError: cls 不是可编译 Schema 的类型, 或字段类型无法用 Tars IDL 表达
+///         (如 Any、Union、TarsDict、异构 tuple) 时抛出。
+#[pyfunction]
+pub fn to_tars_idl(py: Python<'_>, cls: &Bound<'_, PyType>) -> PyResult<String> {
+    let mut emitter = IdlEmitter {
+        seen: HashSet::new(),
+        defs: Vec::new(),
+    };
+    emitter.emit_struct(py, cls)?;
+    Ok(emitter.defs.join("\n"))
+}
+
+/// `to_tars_idl` 的累积状态.
+struct IdlEmitter {
+    /// 已开始输出的类指针 (Struct 与 Enum), 递归结构只引用类名而不重复展开.
+    seen: HashSet<usize>,
+    defs: Vec<String>,
+}
+
+impl IdlEmitter {
+    fn emit_struct(&mut self, py: Python<'_>, cls: &Bound<'_, PyType>) -> PyResult<()> {
+        if !self.seen.insert(cls.as_ptr() as usize) {
+            return Ok(());
+        }
+        let def = ensure_schema_for_class(py, cls)?;
+        let mut body = String::new();
+        for field in &def.fields_sorted {
+            let ty = self.field_type(py, field)?;
+            let kind = if field.is_required {
+                "require"
+            } else {
+                "optional"
+            };
+            body.push_str(&format!(
+                "    {} {} {} {};\n",
+                field.tag, kind, ty, field.name
+            ));
+        }
+        self.defs
+            .push(format!("struct {} {{\n{}}};\n", def.name, body));
+        Ok(())
+    }
+
+    /// 输出 `complex` 对应的辅助结构体 (每次导出至多一次).
+    fn emit_complex(&mut self, py: Python<'_>) {
+        if self
+            .seen
+            .insert(py.get_type::<PyComplex>().as_ptr() as usize)
+        {
+            self.defs.push(
+                "struct Complex {\n    0 require double real;\n    1 require double imag;\n};\n"
+                    .to_string(),
+            );
+        }
+    }
+
+    fn emit_enum(&mut self, cls: &Bound<'_, PyType>) -> PyResult<()> {
+        if !self.seen.insert(cls.as_ptr() as usize) {
+            return Ok(());
+        }
+        // 迭代 Enum 类只产出规范成员, 别名不会重复出现.
+        let mut members = Vec::new();
+        for member in cls.try_iter()? {
+            let member = member?;
+            let name = member.getattr(pyo3::intern!(cls.py(), "name"))?;
+            let value: i64 = member
+                .getattr(pyo3::intern!(cls.py(), "value"))?
+                .extract()?;
+            members.push(format!("    {} = {}", name, value));
+        }
+        self.defs.push(format!(
+            "enum {} {{\n{}\n}};\n",
+            cls.name()?,
+            members.join(",\n")
+        ));
+        Ok(())
+    }
+
+    fn field_type(&mut self, py: Python<'_>, field: &FieldDef) -> PyResult<String> {
+        // 包装为 SimpleList 或压缩后的字段在线上就是 bytes.
+        if field.wrap_simplelist || field.compress.is_some() {
+            return Ok("vector<byte>".to_string());
+        }
+        if field.chunk_size.is_some() {
+            return Ok("vector<vector<byte>>".to_string());
+        }
+        if let Some(wire) = field.wire {
+            return Ok(match wire {
+                WireInt::Int8 => "byte",
+                WireInt::Int16 => "short",
+                WireInt::Int32 => "int",
+                WireInt::Int64 => "long",
+                WireInt::UInt8 => "unsigned byte",
+                WireInt::UInt16 => "unsigned short",
+                WireInt::UInt32 => "unsigned int",
+            }
+            .to_string());
+        }
+        self.type_name(py, &field.ty).map_err(|e| {
+            pyo3::exceptions::PyTypeError::new_err(format!(
+                "Field '{}': {}",
+                field.name,
+                e.value(py)
+            ))
+        })
+    }
+
+    fn type_name(&mut self, py: Python<'_>, ty: &TypeExpr) -> PyResult<String> {
+        Ok(match ty {
+            TypeExpr::Primitive(WireType::Int) => "int".to_string(),
+            TypeExpr::Primitive(WireType::Long) | TypeExpr::Flag(_) => "long".to_string(),
+            TypeExpr::Primitive(WireType::Bool) => "bool".to_string(),
+            TypeExpr::Primitive(WireType::Float) => "float".to_string(),
+            TypeExpr::Primitive(WireType::Double) => "double".to_string(),
+            TypeExpr::Primitive(WireType::String) | TypeExpr::EnumName(_) | TypeExpr::Path(_) => {
+                "string".to_string()
+            }
+            TypeExpr::Bytes | TypeExpr::ByteArray | TypeExpr::IpAddress(_) => {
+                "vector<byte>".to_string()
+            }
+            TypeExpr::Enum(cls, inner)
+                if matches!(**inner, TypeExpr::Primitive(WireType::Int | WireType::Long)) =>
+            {
+                let cls = cls.bind(py);
+                self.emit_enum(cls)?;
+                cls.name()?.to_string()
+            }
+            TypeExpr::Enum(_, inner)
+            | TypeExpr::Literal(_, inner)
+            | TypeExpr::Subclass(_, inner)
+            | TypeExpr::Optional(inner) => self.type_name(py, inner)?,
+            TypeExpr::List(inner)
+            | TypeExpr::VarTuple(inner)
+            | TypeExpr::Set(inner)
+            | TypeExpr::FrozenSet(inner) => {
+                format!("vector<{}>", self.type_name(py, inner)?)
+            }
+            TypeExpr::Tuple(items)
+                if items
+                    .windows(2)
+                    .all(|w| w[0].annotation_repr(py) == w[1].annotation_repr(py))
+                    && !items.is_empty() =>
+            {
+                format!("vector<{}>", self.type_name(py, &items[0])?)
+            }
+            TypeExpr::Map(k, v)
+            | TypeExpr::DefaultDict(k, v, _)
+            | TypeExpr::DictSubclass(k, v, _) => {
+                format!(
+                    "map<{}, {}>",
+                    self.type_name(py, k)?,
+                    self.type_name(py, v)?
+                )
+            }
+            TypeExpr::Struct(cls) => {
+                let cls = cls.bind(py);
+                self.emit_struct(py, cls)?;
+                cls.name()?.to_string()
+            }
+            TypeExpr::Complex => {
+                self.emit_complex(py);
+                "Complex".to_string()
+            }
+            other => {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "{} cannot be expressed in Tars IDL",
+                    other.annotation_repr(py)
+                )));
+            }
+        })
+    }
+}
+
+/// JSON Schema 方言标识 (draft 2020-12).
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// 将 Struct 的 Schema 导出为 JSON Schema (draft 2020-12) dict.
+///
+/// 描述的是 `to_builtins` 输出的 dict 形式: 属性以字段名 (或 `Meta(alias=...)`) 为键,
+/// 必填字段列入 `required`. 嵌套 Struct 收集到 `$defs` 并以 `$ref` 引用,
+/// `cls` 自身的递归引用写为 `{"$ref": "#"}`. `Meta` 的数值与长度约束映射为
+/// `exclusiveMinimum`/`minimum`/`minLength`/`minItems` 等关键字, Enum 与 Literal
+/// 输出取值的 `enum` 数组, bytes 输出 base64 编码的字符串.
+///
+/// Args:
+///     cls: 需要导出的 Struct 类。
+///
+/// Returns:
+///     可直接交给 `json.dumps` 的 JSON Schema dict。
+///
+/// Raises:
+///     TypeError: cls 不是可编译 Schema 的类型时抛出。
+#[pyfunction]
+pub fn json_schema<'py>(py: Python<'py>, cls: &Bound<'py, PyType>) -> PyResult<Bound<'py, PyDict>> {
+    let mut emitter = JsonSchemaEmitter {
+        root: cls.as_ptr() as usize,
+        seen: HashSet::new(),
+        defs: PyDict::new(py),
+    };
+    let schema = emitter.struct_schema(py, cls)?;
+    let out = PyDict::new(py);
+    out.set_item("$schema", JSON_SCHEMA_DIALECT)?;
+    for (k, v) in schema.iter() {
+        out.set_item(k, v)?;
+    }
+    if !emitter.defs.is_empty() {
+        out.set_item("$defs", emitter.defs)?;
+    }
+    Ok(out)
+}
+
+/// `json_schema` 的累积状态.
+struct JsonSchemaEmitter<'py> {
+    /// 顶层类指针, 其递归引用指向文档根.
+    root: usize,
+    /// 已收集到 `$defs` 的类指针.
+    seen: HashSet<usize>,
+    defs: Bound<'py, PyDict>,
+}
+
+impl<'py> JsonSchemaEmitter<'py> {
+    fn struct_schema(
+        &mut self,
+        py: Python<'py>,
+        cls: &Bound<'py, PyType>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let def = ensure_schema_for_class(py, cls)?;
+        let properties = PyDict::new(py);
+        let required = PyList::empty(py);
+        for field in &def.fields_sorted {
+            let key = field.alias.as_deref().unwrap_or(&field.name);
+            let schema = self.type_schema(py, &field.ty, field.constraints.as_deref())?;
+            properties.set_item(key, schema)?;
+            if field.is_required {
+                required.append(key)?;
+            }
+        }
+        let schema = PyDict::new(py);
+        schema.set_item("title", &def.name)?;
+        schema.set_item("type", "object")?;
+        schema.set_item("properties", properties)?;
+        if !required.is_empty() {
+            schema.set_item("required", required)?;
+        }
+        Ok(schema)
+    }
+
+    fn struct_ref(
+        &mut self,
+        py: Python<'py>,
+        cls: &Bound<'py, PyType>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let ptr = cls.as_ptr() as usize;
+        let reference = PyDict::new(py);
+        if ptr == self.root {
+            reference.set_item("$ref", "#")?;
+            return Ok(reference);
+        }
+        let name = cls.name()?.to_string();
+        if self.seen.insert(ptr) {
+            let schema = self.struct_schema(py, cls)?;
+            self.defs.set_item(&name, schema)?;
+        }
+        reference.set_item("$ref", format!("#/$defs/{}", name))?;
+        Ok(reference)
+    }
+
+    fn type_schema(
+        &mut self,
+        py: Python<'py>,
+        ty: &TypeExpr,
+        constraints: Option<&Constraints>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let schema = PyDict::new(py);
+        match ty {
+            TypeExpr::Primitive(WireType::Int | WireType::Long) => {
+                schema.set_item("type", "integer")?;
+            }
+            TypeExpr::Primitive(WireType::Bool) => schema.set_item("type", "boolean")?,
+            TypeExpr::Primitive(WireType::Float | WireType::Double) => {
+                schema.set_item("type", "number")?;
+            }
+            TypeExpr::Primitive(WireType::String) | TypeExpr::Path(_) => {
+                schema.set_item("type", "string")?;
+            }
+            TypeExpr::Primitive(_) | TypeExpr::Any => {}
+            TypeExpr::Bytes | TypeExpr::ByteArray => {
+                schema.set_item("type", "string")?;
+                schema.set_item("contentEncoding", "base64")?;
+            }
+            TypeExpr::Complex => {
+                let number = PyDict::new(py);
+                number.set_item("type", "number")?;
+                let properties = PyDict::new(py);
+                properties.set_item("real", &number)?;
+                properties.set_item("imag", &number)?;
+                schema.set_item("type", "object")?;
+                schema.set_item("properties", properties)?;
+                schema.set_item("required", PyList::new(py, ["real", "imag"])?)?;
+            }
+            TypeExpr::IpAddress(cls) => {
+                let is_v4 = with_stdlib_cache(py, |cache| {
+                    cls.bind(py).is_subclass(cache.ipv4_type.bind(py))
+                })?;
+                schema.set_item("type", "string")?;
+                schema.set_item("format", if is_v4 { "ipv4" } else { "ipv6" })?;
+            }
+            TypeExpr::Subclass(_, inner) => return self.type_schema(py, inner, constraints),
+            TypeExpr::Enum(cls, _) => {
+                let values = PyList::empty(py);
+                for member in cls.bind(py).try_iter()? {
+                    values.append(member?.getattr(pyo3::intern!(py, "value"))?)?;
+                }
+                schema.set_item("title", cls.bind(py).name()?)?;
+                schema.set_item("enum", values)?;
+            }
+            TypeExpr::EnumName(cls) => {
+                let names = PyList::empty(py);
+                for member in cls.bind(py).try_iter()? {
+                    names.append(member?.getattr(pyo3::intern!(py, "name"))?)?;
+                }
+                schema.set_item("title", cls.bind(py).name()?)?;
+                schema.set_item("enum", names)?;
+            }
+            TypeExpr::Flag(cls) => {
+                // 按位组合的取值不在成员列表内, 只能约束为非负整数.
+                schema.set_item("type", "integer")?;
+                schema.set_item("title", cls.bind(py).name()?)?;
+                schema.set_item("minimum", 0)?;
+            }
+            TypeExpr::Literal(values, _) => {
+                schema.set_item("enum", PyList::new(py, values.iter().map(|v| v.bind(py)))?)?;
+            }
+            TypeExpr::Struct(cls) => return self.struct_ref(py, cls.bind(py)),
+            TypeExpr::TarsDict | TypeExpr::TypedDict(_) | TypeExpr::Dataclass(_) => {
+                schema.set_item("type", "object")?;
+            }
+            TypeExpr::NoneType => schema.set_item("type", "null")?,
+            TypeExpr::List(inner) | TypeExpr::VarTuple(inner) => {
+                schema.set_item("type", "array")?;
+                schema.set_item("items", self.type_schema(py, inner, None)?)?;
+            }
+            TypeExpr::Set(inner) | TypeExpr::FrozenSet(inner) => {
+                schema.set_item("type", "array")?;
+                schema.set_item("items", self.type_schema(py, inner, None)?)?;
+                schema.set_item("uniqueItems", true)?;
+            }
+            TypeExpr::Tuple(items) | TypeExpr::NamedTuple(_, items) => {
+                let prefix = PyList::empty(py);
+                for item in items {
+                    prefix.append(self.type_schema(py, item, None)?)?;
+                }
+                schema.set_item("type", "array")?;
+                schema.set_item("prefixItems", prefix)?;
+                schema.set_item("minItems", items.len())?;
+                schema.set_item("maxItems", items.len())?;
+            }
+            TypeExpr::Map(_, v)
+            | TypeExpr::DefaultDict(_, v, _)
+            | TypeExpr::DictSubclass(_, v, _) => {
+                schema.set_item("type", "object")?;
+                schema.set_item("additionalProperties", self.type_schema(py, v, None)?)?;
+            }
+            TypeExpr::Optional(inner) => {
+                let any_of = PyList::empty(py);
+                any_of.append(self.type_schema(py, inner, constraints)?)?;
+                any_of.append(self.type_schema(py, &TypeExpr::NoneType, None)?)?;
+                schema.set_item("anyOf", any_of)?;
+                return Ok(schema);
+            }
+            TypeExpr::Union(variants, _) => {
+                let any_of = PyList::empty(py);
+                for variant in variants {
+                    any_of.append(self.type_schema(py, variant, None)?)?;
+                }
+                schema.set_item("anyOf", any_of)?;
+            }
+        }
+        if let Some(c) = constraints {
+            apply_json_constraints(py, &schema, ty, c)?;
+        }
+        Ok(schema)
+    }
+}
+
+/// 将字段级 `Meta` 约束写入对应类型的 JSON Schema 关键字.
+fn apply_json_constraints(
+    py: Python<'_>,
+    schema: &Bound<'_, PyDict>,
+    ty: &TypeExpr,
+    c: &Constraints,
+) -> PyResult<()> {
+    let numeric = [
+        ("exclusiveMinimum", c.gt),
+        ("minimum", c.ge),
+        ("exclusiveMaximum", c.lt),
+        ("maximum", c.le),
+        ("multipleOf", c.multiple_of),
+    ];
+    for (key, value) in numeric {
+        if let Some(v) = value {
+            // 整数字段的边界以 int 输出, 便于与 `"type": "integer"` 一起阅读.
+            if ty.is_int() && v.fract() == 0.0 {
+                schema.set_item(key, v as i64)?;
+            } else {
+                schema.set_item(key, v)?;
+            }
+        }
+    }
+    let (min_key, max_key) = match ty {
+        TypeExpr::Primitive(WireType::String) => ("minLength", "maxLength"),
+        TypeExpr::List(_) | TypeExpr::VarTuple(_) | TypeExpr::Set(_) | TypeExpr::FrozenSet(_) => {
+            ("minItems", "maxItems")
+        }
+        TypeExpr::Map(..) | TypeExpr::DefaultDict(..) | TypeExpr::DictSubclass(..) => {
+            ("minProperties", "maxProperties")
+        }
+        _ => ("", ""),
+    };
+    if !min_key.is_empty() {
+        if let Some(n) = c.min_len {
+            schema.set_item(min_key, n)?;
+        }
+        if let Some(n) = c.max_len {
+            schema.set_item(max_key, n)?;
+        }
+    }
+    if let Some(pattern) = &c.pattern {
+        schema.set_item(
+            "pattern",
+            pattern.bind(py).getattr(pyo3::intern!(py, "pattern"))?,
+        )?;
+    }
+    match &c.choices {
+        Some(Choices::Int(values)) => schema.set_item("enum", values)?,
+        Some(Choices::Str(values)) => schema.set_item("enum", values)?,
+        None => {}
+    }
+    Ok(())
+}
+
+/// 将 Struct 实例递归转换为由内置类型组成的 dict.
+///
+/// 嵌套 Struct 转为以字段名为键的 dict, list/tuple/set 转为 list, dict (含 TarsDict)
+/// 转为 dict (键保持原样), 其余值 (基础类型、bytes、Enum 等) 原样保留.
+/// 所有已赋值的字段都会输出, 包括值为 None 或等于默认值的字段; 未赋值的字段跳过.
+/// 声明了 `Meta(alias=...)` 的字段以别名为键.
+///
+/// Args:
+///     obj: Struct 实例。
+///
+/// Returns:
+///     字段名到转换后值的 dict, 顺序按 tag。
+///
+/// Raises:
+///     TypeError: obj 不是 Struct 实例时抛出。
+///     ValueError: 嵌套层数超过 `MAX_DEPTH` (如存在循环引用) 时抛出。
+#[pyfunction]
+pub fn to_builtins<'py>(obj: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    ensure_struct_instance(obj)?;
+    builtins_value(obj, false, 0)
+}
+
+/// 将 Struct 实例按 tag 顺序转换为字段值组成的 tuple.
+///
+/// 与 [`to_builtins`] 的转换规则相同, 但 Struct (含嵌套) 转为 tuple 而非 dict;
+/// 未赋值的字段以 None 占位, 使各位置始终与 tag 顺序对应.
+///
+/// Args:
+///     obj: Struct 实例。
+///
+/// Returns:
+///     按 tag 顺序排列的字段值 tuple。
+///
+/// Raises:
+///     TypeError: obj 不是 Struct 实例时抛出。
+///     ValueError: 嵌套层数超过 `MAX_DEPTH` (如存在循环引用) 时抛出。
+#[pyfunction]
+pub fn astuple<'py>(obj: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+    ensure_struct_instance(obj)?;
+    builtins_value(obj, true, 0)
+}
+
+fn ensure_struct_instance(obj: &Bound<'_, PyAny>) -> PyResult<()> {
+    if obj.is_instance_of::<Struct>() {
+        return Ok(());
+    }
+    Err(pyo3::exceptions::PyTypeError::new_err(format!(
+        "expected a Struct instance, got {}",
+        obj.get_type().name()?
+    )))
+}
+
+/// `to_builtins`/`astuple` 的递归实现, `as_tuple` 决定 Struct 的输出形式.
+fn builtins_value<'py>(
+    val: &Bound<'py, PyAny>,
+    as_tuple: bool,
+    depth: usize,
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = val.py();
+    if val.is_instance_of::<Struct>() {
+        check_depth(depth)?;
+        let def = ensure_schema_for_class(py, &val.get_type())?;
+        let mut items = Vec::with_capacity(def.fields_sorted.len());
+        for field in &def.fields_sorted {
+            let name = field.name_py.bind(py);
+            // 未赋值的字段 (如经 `__new__` 创建后尚未初始化): dict 中省略,
+            // tuple 中以 None 占位, 保持各位置与 tag 顺序对应.
+            let item = match val.getattr(name) {
+                Ok(field_val) => builtins_value(&field_val, as_tuple, depth + 1)?,
+                Err(err) if err.is_instance_of::<pyo3::exceptions::PyAttributeError>(py) => {
+                    if !as_tuple {
+                        continue;
+                    }
+                    py.None().into_bound(py)
+                }
+                Err(err) => return Err(err),
+            };
+            let key = match field.alias.as_deref() {
+                Some(alias) => PyString::new(py, alias),
+                None => name.clone(),
+            };
+            items.push((key, item));
+        }
+        if as_tuple {
+            return Ok(PyTuple::new(py, items.into_iter().map(|(_, v)| v))?.into_any());
+        }
+        let dict = PyDict::new(py);
+        for (name, item) in items {
+            dict.set_item(name, item)?;
+        }
+        return Ok(dict.into_any());
+    }
+    if let Ok(dict) = val.cast::<PyDict>() {
+        check_depth(depth)?;
+        let out = PyDict::new(py);
+        for (k, v) in dict.iter() {
+            out.set_item(k, builtins_value(&v, as_tuple, depth + 1)?)?;
+        }
+        return Ok(out.into_any());
+    }
+    if val.is_instance_of::<PyList>()
+        || val.is_instance_of::<PyTuple>()
+        || val.is_instance_of::<PySet>()
+        || val.is_instance_of::<PyFrozenSet>()
+    {
+        check_depth(depth)?;
+        let out = PyList::empty(py);
+        for item in val.try_iter()? {
+            out.append(builtins_value(&item?, as_tuple, depth + 1)?)?;
+        }
+        return Ok(out.into_any());
+    }
+    Ok(val.clone())
+}
+
+/// `diff_schema` 的累积状态.
+struct SchemaDiff<'py> {
+    added: Bound<'py, PyList>,
+    removed: Bound<'py, PyList>,
+    tag_changed: Bound<'py, PyList>,
+    type_changed: Bound<'py, PyList>,
+    optionality_changed: Bound<'py, PyList>,
+    version_changed: Bound<'py, PyList>,
+    /// 当前递归路径上的 (旧类, 新类) 指针对, 防止递归结构无限展开.
+    visited: HashSet<(usize, usize)>,
+}
+
+impl<'py> SchemaDiff<'py> {
+    fn compare_structs(
+        &mut self,
+        py: Python<'py>,
+        old: &StructDef,
+        new: &StructDef,
+        prefix: &str,
+    ) -> PyResult<()> {
+        let path_of = |name: &str| format!("{prefix}{name}");
+
+        if old.version != new.version {
+            self.version_changed.append(change_entry(
+                py,
+                prefix.trim_end_matches('.'),
+                &[
+                    ("old_version", old.version.into_pyobject(py)?.into_any()),
+                    ("new_version", new.version.into_pyobject(py)?.into_any()),
+                ],
+            )?)?;
+        }
+
+        for old_field in &old.fields_sorted {
+            let path = path_of(&old_field.name);
+            let Some(&new_idx) = new.meta.name_to_index.get(&old_field.name) else {
+                self.removed.append(change_entry(
+                    py,
+                    &path,
+                    &[
+                        ("tag", old_field.tag.into_pyobject(py)?.into_any()),
+                        (
+                            "type",
+                            old_field
+                                .ty
+                                .annotation_repr(py)
+                                .into_pyobject(py)?
+                                .into_any(),
+                        ),
+                    ],
+                )?)?;
+                continue;
+            };
+            let new_field = &new.fields_sorted[new_idx];
+            if old_field.tag != new_field.tag {
+                self.tag_changed.append(change_entry(
+                    py,
+                    &path,
+                    &[
+                        ("old_tag", old_field.tag.into_pyobject(py)?.into_any()),
+                        ("new_tag", new_field.tag.into_pyobject(py)?.into_any()),
+                    ],
+                )?)?;
+            }
+            if old_field.is_optional != new_field.is_optional {
+                self.optionality_changed.append(change_entry(
+                    py,
+                    &path,
+                    &[
+                        (
+                            "old_optional",
+                            old_field
+                                .is_optional
+                                .into_pyobject(py)?
+                                .to_owned()
+                                .into_any(),
+                        ),
+                        (
+                            "new_optional",
+                            new_field
+                                .is_optional
+                                .into_pyobject(py)?
+                                .to_owned()
+                                .into_any(),
+                        ),
+                    ],
+                )?)?;
+            }
+            self.compare_types(
+                py,
+                strip_optional(&old_field.ty),
+                strip_optional(&new_field.ty),
+                &path,
+            )?;
+        }
+
+        for new_field in &new.fields_sorted {
+            if old.meta.name_to_index.contains_key(&new_field.name) {
+                continue;
+            }
+            self.added.append(change_entry(
+                py,
+                &path_of(&new_field.name),
+                &[
+                    ("tag", new_field.tag.into_pyobject(py)?.into_any()),
+                    (
+                        "type",
+                        new_field
+                            .ty
+                            .annotation_repr(py)
+                            .into_pyobject(py)?
+                            .into_any(),
+                    ),
+                ],
+            )?)?;
+        }
+        Ok(())
+    }
+
+    fn compare_types(
+        &mut self,
+        py: Python<'py>,
+        old: &TypeExpr,
+        new: &TypeExpr,
+        path: &str,
+    ) -> PyResult<()> {
+        match (old, new) {
+            (TypeExpr::Struct(old_cls), TypeExpr::Struct(new_cls)) => {
+                let key = (old_cls.as_ptr() as usize, new_cls.as_ptr() as usize);
+                if !self.visited.insert(key) {
+                    return Ok(());
+                }
+                let old_def = ensure_schema_for_class(py, old_cls.bind(py))?;
+                let new_def = ensure_schema_for_class(py, new_cls.bind(py))?;
+                let res = self.compare_structs(py, &old_def, &new_def, &format!("{path}."));
+                self.visited.remove(&key);
+                res
+            }
+            (TypeExpr::Optional(a), TypeExpr::Optional(b))
+            | (TypeExpr::List(a), TypeExpr::List(b))
+            | (TypeExpr::VarTuple(a), TypeExpr::VarTuple(b))
+            | (TypeExpr::Set(a), TypeExpr::Set(b))
+            | (TypeExpr::FrozenSet(a), TypeExpr::FrozenSet(b)) => {
+                let path = if matches!(old, TypeExpr::Optional(_)) {
+                    path.to_string()
+                } else {
+                    format!("{path}[]")
+                };
+                self.compare_types(py, a, b, &path)
+            }
+            (TypeExpr::Map(ka, va), TypeExpr::Map(kb, vb))
+                if ka.annotation_repr(py) == kb.annotation_repr(py) =>
+            {
+                self.compare_types(py, va, vb, &format!("{path}[]"))
+            }
+            _ => {
+                let old_repr = old.annotation_repr(py);
+                let new_repr = new.annotation_repr(py);
+                if old_repr != new_repr {
+                    self.type_changed.append(change_entry(
+                        py,
+                        path,
+                        &[
+                            ("old_type", old_repr.into_pyobject(py)?.into_any()),
+                            ("new_type", new_repr.into_pyobject(py)?.into_any()),
+                        ],
+                    )?)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// 去掉字段级 Optional 包装, 可选性单独以 `optionality_changed` 报告.
+fn strip_optional(ty: &TypeExpr) -> &TypeExpr {
+    match ty {
+        TypeExpr::Optional(inner) => inner,
+        other => other,
+    }
+}
+
+fn change_entry<'py>(
+    py: Python<'py>,
+    path: &str,
+    items: &[(&str, Bound<'py, PyAny>)],
+) -> PyResult<Bound<'py, PyDict>> {
+    let entry = PyDict::new(py);
+    entry.set_item("path", path)?;
+    for (key, value) in items {
+        entry.set_item(*key, value)?;
+    }
+    Ok(entry)
+}
+
 /// 构建类型内省对象.
 ///
 /// Args:
@@ -715,11 +1836,39 @@ fn build_type_info(
                 .add_subclass(BoolType {}),
         )?
         .into_any()),
-        TypeInfoIR::Bytes => Ok(Py::new(
+        TypeInfoIR::Complex => Ok(Py::new(
+            py,
+            PyClassInitializer::from(TypeBase)
+                .add_subclass(BasicTypeBase)
+                .add_subclass(ComplexType {}),
+        )?
+        .into_any()),
+        TypeInfoIR::Bytes | TypeInfoIR::ByteArray => Ok(Py::new(
+            py,
+            PyClassInitializer::from(TypeBase)
+                .add_subclass(BasicTypeBase)
+                .add_subclass(BytesType {
+                    constraints,
+                    mutable: matches!(typ, TypeInfoIR::ByteArray),
+                }),
+        )?
+        .into_any()),
+        TypeInfoIR::IpAddress(cls) => Ok(Py::new(
             py,
             PyClassInitializer::from(TypeBase)
                 .add_subclass(BasicTypeBase)
-                .add_subclass(BytesType { constraints }),
+                .add_subclass(IpAddressType {
+                    cls: cls.clone_ref(py),
+                }),
+        )?
+        .into_any()),
+        TypeInfoIR::Path(cls) => Ok(Py::new(
+            py,
+            PyClassInitializer::from(TypeBase)
+                .add_subclass(BasicTypeBase)
+                .add_subclass(PathType {
+                    cls: cls.clone_ref(py),
+                }),
         )?
         .into_any()),
         TypeInfoIR::Any => Ok(Py::new(
@@ -736,7 +1885,7 @@ fn build_type_info(
                 .add_subclass(NoneType {}),
         )?
         .into_any()),
-        TypeInfoIR::TypedDict => Ok(Py::new(
+        TypeInfoIR::TypedDict(_) => Ok(Py::new(
             py,
             PyClassInitializer::from(TypeBase)
                 .add_subclass(CompoundTypeBase)
@@ -782,6 +1931,41 @@ fn build_type_info(
             )?
             .into_any())
         }
+        TypeInfoIR::Flag(cls) => Ok(Py::new(
+            py,
+            PyClassInitializer::from(TypeBase)
+                .add_subclass(CompoundTypeBase)
+                .add_subclass(FlagType {
+                    cls: cls.clone_ref(py),
+                }),
+        )?
+        .into_any()),
+        TypeInfoIR::Subclass(cls, inner) => {
+            let value_type = build_type_info(py, inner, constraints, build_ctx)?;
+            Ok(Py::new(
+                py,
+                PyClassInitializer::from(TypeBase)
+                    .add_subclass(CompoundTypeBase)
+                    .add_subclass(SubclassType {
+                        cls: cls.clone_ref(py),
+                        value_type,
+                    }),
+            )?
+            .into_any())
+        }
+        TypeInfoIR::Literal(values, inner) => {
+            let value_type = build_type_info(py, inner, None, build_ctx)?;
+            Ok(Py::new(
+                py,
+                PyClassInitializer::from(TypeBase)
+                    .add_subclass(CompoundTypeBase)
+                    .add_subclass(LiteralType {
+                        values: PyTuple::new(py, values.iter().map(|v| v.bind(py)))?.unbind(),
+                        value_type,
+                    }),
+            )?
+            .into_any())
+        }
         TypeInfoIR::Union(variants) => {
             let mut items = Vec::with_capacity(variants.len());
             for item in variants {
@@ -851,12 +2035,48 @@ fn build_type_info(
                     .add_subclass(MapType {
                         key_type,
                         value_type,
+                        default_factory: None,
+                        cls: None,
+                        constraints,
+                    }),
+            )?
+            .into_any())
+        }
+        TypeInfoIR::DefaultDict(k, v, factory) => {
+            let key_type = build_type_info(py, k, None, build_ctx)?;
+            let value_type = build_type_info(py, v, None, build_ctx)?;
+            Ok(Py::new(
+                py,
+                PyClassInitializer::from(TypeBase)
+                    .add_subclass(CompoundTypeBase)
+                    .add_subclass(MapType {
+                        key_type,
+                        value_type,
+                        default_factory: Some(factory.clone_ref(py)),
+                        cls: None,
+                        constraints,
+                    }),
+            )?
+            .into_any())
+        }
+        TypeInfoIR::DictSubclass(k, v, cls) => {
+            let key_type = build_type_info(py, k, None, build_ctx)?;
+            let value_type = build_type_info(py, v, None, build_ctx)?;
+            Ok(Py::new(
+                py,
+                PyClassInitializer::from(TypeBase)
+                    .add_subclass(CompoundTypeBase)
+                    .add_subclass(MapType {
+                        key_type,
+                        value_type,
+                        default_factory: None,
+                        cls: Some(cls.clone_ref(py)),
                         constraints,
                     }),
             )?
             .into_any())
         }
-        TypeInfoIR::Set(inner) => {
+        TypeInfoIR::Set(inner) | TypeInfoIR::FrozenSet(inner) => {
             let item_type = build_type_info(py, inner, None, build_ctx)?;
             Ok(Py::new(
                 py,
@@ -864,6 +2084,7 @@ fn build_type_info(
                     .add_subclass(CompoundTypeBase)
                     .add_subclass(SetType {
                         item_type,
+                        frozen: matches!(typ, TypeInfoIR::FrozenSet(_)),
                         constraints,
                     }),
             )?
@@ -5,7 +5,9 @@ use pyo3::types::{PyAny, PyDict, PyString, PyTuple};
 use smallvec::SmallVec;
 
 use crate::binding::ir::{FieldDef, StructDef};
-use crate::binding::validation::validate_type_and_constraints;
+use crate::binding::validation::{
+    coerce_bytes_field, coerce_enum_field, validate_raw_str_bytes, validate_type_and_constraints,
+};
 
 fn set_field_value(
     self_obj: &Bound<'_, PyAny>,
@@ -34,11 +36,27 @@ fn missing_required_argument_error(field: &FieldDef) -> PyErr {
     ))
 }
 
-pub(crate) fn run_post_init(self_obj: &Bound<'_, PyAny>) -> PyResult<()> {
+/// 调用实例的 `__post_init__` (若存在).
+///
+/// 传入 `context` 时以关键字参数 `context` 调用, 用于 `decode(..., context=...)`;
+/// 构造路径不传入, 保持 `__post_init__(self)` 的无参约定.
+pub(crate) fn run_post_init(
+    self_obj: &Bound<'_, PyAny>,
+    context: Option<&Bound<'_, PyAny>>,
+) -> PyResult<()> {
     let py = self_obj.py();
     match self_obj.getattr("__post_init__") {
         Ok(post_init) => {
-            post_init.call0()?;
+            match context {
+                Some(ctx) => {
+                    let kwargs = PyDict::new(py);
+                    kwargs.set_item(pyo3::intern!(py, "context"), ctx)?;
+                    post_init.call((), Some(&kwargs))?;
+                }
+                None => {
+                    post_init.call0()?;
+                }
+            }
             Ok(())
         }
         Err(err) => {
@@ -94,8 +112,11 @@ pub fn construct_instance(
     let no_kwargs = kwargs.is_none_or(|k| k.is_empty());
     if no_kwargs && num_positional == num_fields {
         for (idx, field) in def.fields_sorted.iter().enumerate() {
-            let val = args.get_item(idx)?;
-            if !(field.is_optional && val.is_none()) {
+            let val = coerce_bytes_field(field, coerce_enum_field(field, args.get_item(idx)?)?)?;
+            let validated = (field.is_optional && val.is_none())
+                || field.encoder.is_some()
+                || validate_raw_str_bytes(field, &val)?;
+            if !validated {
                 validate_type_and_constraints(
                     py,
                     &val,
@@ -106,7 +127,7 @@ pub fn construct_instance(
             }
             set_field_value(self_obj, field, &val)?;
         }
-        run_post_init(self_obj)?;
+        run_post_init(self_obj, None)?;
         return Ok(());
     }
 
@@ -191,7 +212,11 @@ pub fn construct_instance(
             }
         };
 
-        if !(field.is_optional && val_to_set.is_none()) {
+        let val_to_set = coerce_bytes_field(field, coerce_enum_field(field, val_to_set)?)?;
+        let validated = (field.is_optional && val_to_set.is_none())
+            || field.encoder.is_some()
+            || validate_raw_str_bytes(field, &val_to_set)?;
+        if !validated {
             validate_type_and_constraints(
                 py,
                 &val_to_set,
@@ -203,6 +228,6 @@ pub fn construct_instance(
         set_field_value(self_obj, field, &val_to_set)?;
     }
 
-    run_post_init(self_obj)?;
+    run_post_init(self_obj, None)?;
     Ok(())
 }
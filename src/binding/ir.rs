@@ -4,5 +4,6 @@
 //! `TypeInfoIR` 仍作为前端适配输入存在，编译后统一落到这里的类型。
 
 pub use crate::binding::core::{
-    Constraints, FieldDef, StructDef, StructMetaData, TypeExpr, UnionCache, WireType,
+    Choices, Compression, Constraints, Endian, FieldDef, StructDef, StructMetaData, TypeExpr,
+    UnionCache, WireInt, WireType,
 };
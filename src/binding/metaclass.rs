@@ -1,8 +1,9 @@
+use pyo3::exceptions::PyNameError;
 use pyo3::prelude::*;
 use pyo3::types::{PyAny, PyDict, PyModule, PyTuple, PyType};
 
 use crate::binding::compiler::compile_schema_from_class;
-use crate::binding::schema::SchemaConfig;
+use crate::binding::schema::{PENDING_SCHEMA_ATTR, PendingSchema, SchemaConfig, StructDef};
 
 #[pyfunction]
 #[pyo3(signature = (mcls, name, bases, namespace, **kwargs))]
@@ -19,10 +20,15 @@ pub fn _tarsio_structmeta_new<'py>(
     let mut forbid_unknown_tags = false;
     let mut eq = true;
     let mut omit_defaults = false;
+    let mut omit_none = true;
     let mut repr_omit_defaults = false;
     let mut kw_only = false;
     let mut dict = false;
     let mut weakref = false;
+    let mut emit_empty_marker = false;
+    let mut forbid_extra_bytes = true;
+    let mut enum_by_name = false;
+    let mut str_fields: Option<Vec<String>> = None;
 
     if let Some(k) = kwargs {
         if let Some(v) = k.get_item("frozen")? {
@@ -45,6 +51,10 @@ pub fn _tarsio_structmeta_new<'py>(
             omit_defaults = v.extract::<bool>()?;
             k.del_item("omit_defaults")?;
         }
+        if let Some(v) = k.get_item("omit_none")? {
+            omit_none = v.extract::<bool>()?;
+            k.del_item("omit_none")?;
+        }
         if let Some(v) = k.get_item("repr_omit_defaults")? {
             repr_omit_defaults = v.extract::<bool>()?;
             k.del_item("repr_omit_defaults")?;
@@ -61,6 +71,37 @@ pub fn _tarsio_structmeta_new<'py>(
             weakref = v.extract::<bool>()?;
             k.del_item("weakref")?;
         }
+        if let Some(v) = k.get_item("emit_empty_marker")? {
+            emit_empty_marker = v.extract::<bool>()?;
+            k.del_item("emit_empty_marker")?;
+        }
+        if let Some(v) = k.get_item("forbid_extra_bytes")? {
+            forbid_extra_bytes = v.extract::<bool>()?;
+            k.del_item("forbid_extra_bytes")?;
+        }
+        if let Some(v) = k.get_item("enum_mode")? {
+            enum_by_name = match v.extract::<String>()?.as_str() {
+                "value" => false,
+                "name" => true,
+                other => {
+                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "enum_mode must be 'value' or 'name', got '{other}'"
+                    )));
+                }
+            };
+            k.del_item("enum_mode")?;
+        }
+        if let Some(v) = k.get_item("str_fields")? {
+            if !v.is_none() {
+                if v.is_instance_of::<pyo3::types::PyString>() {
+                    return Err(pyo3::exceptions::PyTypeError::new_err(
+                        "str_fields must be a sequence of field names, not str",
+                    ));
+                }
+                str_fields = Some(v.extract::<Vec<String>>()?);
+            }
+            k.del_item("str_fields")?;
+        }
     }
 
     let mut field_names: Vec<String> = Vec::new();
@@ -109,25 +150,75 @@ pub fn _tarsio_structmeta_new<'py>(
     let new_cls_any = type_obj.call_method("__new__", (mcls, name, bases, namespace), None)?;
     let new_cls = new_cls_any.cast::<PyType>()?.clone();
 
-    let _ = compile_schema_from_class(
-        py,
-        &new_cls,
-        SchemaConfig {
-            frozen,
-            order,
-            forbid_unknown_tags,
-            eq,
-            omit_defaults,
-            repr_omit_defaults,
-            kw_only,
-            dict,
-            weakref,
-        },
-    )?;
+    let config = SchemaConfig {
+        frozen,
+        order,
+        forbid_unknown_tags,
+        eq,
+        omit_defaults,
+        omit_none,
+        repr_omit_defaults,
+        kw_only,
+        dict,
+        weakref,
+        emit_empty_marker,
+        forbid_extra_bytes,
+        enum_by_name,
+        str_fields,
+    };
+    let def = match compile_schema_from_class(py, &new_cls, config.clone()) {
+        Ok(def) => def,
+        // 注解引用了尚未定义的名称 (如互相引用的 Struct), 推迟到首次使用时编译.
+        Err(err) if err.is_instance_of::<PyNameError>(py) => {
+            new_cls.setattr(PENDING_SCHEMA_ATTR, Py::new(py, PendingSchema { config })?)?;
+            return Ok(new_cls.into_any());
+        }
+        Err(err) => return Err(err),
+    };
+    restore_non_field_defaults(&new_cls, def.as_deref())?;
 
     Ok(new_cls.into_any())
 }
 
+/// 将未进入 schema 的注解名 (如 Final 类常量) 从 `__tarsio_defaults__` 还原为普通类属性.
+pub(crate) fn restore_non_field_defaults(
+    cls: &Bound<'_, PyType>,
+    def: Option<&StructDef>,
+) -> PyResult<()> {
+    let Some(defaults_any) = own_class_attr(cls, "__tarsio_defaults__")? else {
+        return Ok(());
+    };
+    let Ok(defaults) = defaults_any.cast::<PyDict>() else {
+        return Ok(());
+    };
+    let names = defaults
+        .keys()
+        .iter()
+        .map(|k| k.extract::<String>())
+        .collect::<PyResult<Vec<_>>>()?;
+    for name in names {
+        let is_field = def.is_some_and(|d| d.meta.name_to_index.contains_key(name.as_str()));
+        if is_field {
+            continue;
+        }
+        if let Some(v) = defaults.get_item(name.as_str())? {
+            cls.setattr(name.as_str(), v)?;
+            defaults.del_item(name.as_str())?;
+        }
+    }
+    Ok(())
+}
+
+/// 读取类自身 `__dict__` 中的属性, 不沿继承链查找.
+pub(crate) fn own_class_attr<'py>(
+    cls: &Bound<'py, PyType>,
+    name: &str,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    cls.getattr(pyo3::intern!(cls.py(), "__dict__"))?
+        .call_method1(pyo3::intern!(cls.py(), "get"), (name,))
+        .map(|v| (!v.is_none()).then_some(v))
+}
+
 pub fn add_struct_meta(m: &Bound<'_, PyModule>) -> PyResult<()> {
     let py = m.py();
     let builtins = py.import("builtins")?;
@@ -2,8 +2,9 @@ use pyo3::intern;
 use pyo3::prelude::*;
 use pyo3::types::{PyAny, PyDict, PyModule, PyString, PyTuple, PyType};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
-use crate::binding::core::{FieldSpec, Meta, Struct, TarsDict, is_nodefault};
+use crate::binding::core::{Choices, FieldSpec, Meta, Struct, Tag, TarsDict, is_nodefault};
 
 #[derive(Debug, Clone)]
 pub struct ConstraintsIR {
@@ -11,9 +12,26 @@ pub struct ConstraintsIR {
     pub lt: Option<f64>,
     pub ge: Option<f64>,
     pub le: Option<f64>,
+    pub multiple_of: Option<f64>,
     pub min_len: Option<usize>,
     pub max_len: Option<usize>,
     pub pattern: Option<String>,
+    pub endian: Option<String>,
+    pub coerce_enum: bool,
+    /// `Meta(enum_mode=...)` 覆盖: Some(true) 按名称, Some(false) 按值, None 沿用 Struct 配置.
+    pub enum_by_name: Option<bool>,
+    pub compress: Option<String>,
+    pub accept_bytes: bool,
+    pub fixed_len: Option<usize>,
+    pub choices: Option<Choices>,
+    pub wire: Option<String>,
+    pub chunk_size: Option<usize>,
+    pub as_bytes: bool,
+    pub any_preserve_bool: bool,
+    pub alias: Option<String>,
+    pub normalize: Option<String>,
+    pub encoder: Option<Arc<Py<PyAny>>>,
+    pub decoder: Option<Arc<Py<PyAny>>>,
 }
 
 #[derive(Debug)]
@@ -23,18 +41,38 @@ pub enum TypeInfoIR {
     Float,
     Bool,
     Bytes,
+    /// `bytearray`.
+    ByteArray,
+    /// `complex`.
+    Complex,
+    /// `ipaddress.IPv4Address` / `IPv6Address`.
+    IpAddress(Py<PyType>),
+    /// `pathlib.PurePath` 及其子类.
+    Path(Py<PyType>),
+    /// `int`/`str`/`float`/`bytes` 的直接子类及其底层类型.
+    Subclass(Py<PyType>, Box<TypeInfoIR>),
     Any,
     NoneType,
-    TypedDict,
+    /// `TypedDict` 及其成员的 `(字段名, 别名)` 列表.
+    TypedDict(Vec<(String, String)>),
     NamedTuple(Py<PyType>, Vec<TypeInfoIR>),
     Dataclass(Py<PyType>),
     Set(Box<TypeInfoIR>),
+    FrozenSet(Box<TypeInfoIR>),
     Enum(Py<PyType>, Box<TypeInfoIR>),
+    /// `enum.Flag` 子类 (含 `IntFlag`), 取值可为多个成员的按位组合.
+    Flag(Py<PyType>),
+    /// `Literal[...]` 的取值 (不含 None) 与其底层类型 (单一基础类型或 Union).
+    Literal(Vec<Py<PyAny>>, Box<TypeInfoIR>),
     Union(Vec<TypeInfoIR>),
     List(Box<TypeInfoIR>),
     Tuple(Vec<TypeInfoIR>),
     VarTuple(Box<TypeInfoIR>),
     Map(Box<TypeInfoIR>, Box<TypeInfoIR>),
+    /// `collections.defaultdict[K, V]`, 附带由值类型推导的 `default_factory`.
+    DefaultDict(Box<TypeInfoIR>, Box<TypeInfoIR>, Py<PyAny>),
+    /// 用户定义的 `dict` 子类, 附带子类本身.
+    DictSubclass(Box<TypeInfoIR>, Box<TypeInfoIR>, Py<PyType>),
     Optional(Box<TypeInfoIR>),
     Struct(Py<PyType>),
     TarsDict,
@@ -52,6 +90,7 @@ pub struct FieldInfoIR {
     pub is_required: bool,
     pub init: bool,
     pub wrap_simplelist: bool,
+    pub alias_tags: Vec<u8>,
     pub constraints: Option<ConstraintsIR>,
 }
 
@@ -62,6 +101,7 @@ struct DefaultSpecIR {
     default_value: Option<Py<PyAny>>,
     default_factory: Option<Py<PyAny>>,
     wrap_simplelist: bool,
+    alias_tags: Vec<u8>,
 }
 
 struct IntrospectionContext<'py> {
@@ -88,11 +128,16 @@ struct IntrospectionContext<'py> {
     builtin_bool: Bound<'py, PyAny>,
     builtin_bytes: Bound<'py, PyAny>,
     builtin_bytearray: Bound<'py, PyAny>,
+    builtin_complex: Bound<'py, PyAny>,
     builtin_list: Bound<'py, PyAny>,
     builtin_tuple: Bound<'py, PyAny>,
     builtin_dict: Bound<'py, PyAny>,
     builtin_set: Bound<'py, PyAny>,
     builtin_frozenset: Bound<'py, PyAny>,
+    ipv4_address: Bound<'py, PyAny>,
+    ipv6_address: Bound<'py, PyAny>,
+    pure_path: Bound<'py, PyAny>,
+    defaultdict_cls: Bound<'py, PyAny>,
     collection_cls: Bound<'py, PyAny>,
     sequence_cls: Bound<'py, PyAny>,
     mutable_sequence_cls: Bound<'py, PyAny>,
@@ -102,12 +147,15 @@ struct IntrospectionContext<'py> {
     mutable_mapping_cls: Bound<'py, PyAny>,
     union_type: Option<Bound<'py, PyAny>>,
     enum_base: Bound<'py, PyAny>,
+    flag_base: Bound<'py, PyAny>,
+    pydantic_base_model: Option<Bound<'py, PyAny>>,
 }
 
 impl<'py> IntrospectionContext<'py> {
     fn new(py: Python<'py>) -> PyResult<Self> {
         let typing = py.import("typing")?;
         let builtins = py.import("builtins")?;
+        let collections = py.import("collections")?;
         let collections_abc = py.import("collections.abc")?;
         let types_mod = py.import("types")?;
         let enum_mod = py.import("enum")?;
@@ -126,6 +174,13 @@ impl<'py> IntrospectionContext<'py> {
             .and_then(|m| m.getattr("is_typeddict").ok())
             .or_else(|| typing.getattr("is_typeddict").ok());
         let dataclasses_is_dataclass = dataclasses.and_then(|m| m.getattr("is_dataclass").ok());
+        // 仅在 pydantic 已被导入时识别其模型, 避免为未使用 pydantic 的进程引入导入开销.
+        let pydantic_base_model = py
+            .import("sys")?
+            .getattr("modules")?
+            .get_item("pydantic")
+            .ok()
+            .and_then(|m| m.getattr("BaseModel").ok());
 
         let final_cls = typing.getattr("Final").ok();
         let type_alias = typing.getattr("TypeAlias").ok();
@@ -159,11 +214,17 @@ impl<'py> IntrospectionContext<'py> {
         let builtin_bool = builtins.getattr("bool")?;
         let builtin_bytes = builtins.getattr("bytes")?;
         let builtin_bytearray = builtins.getattr("bytearray")?;
+        let builtin_complex = builtins.getattr("complex")?;
         let builtin_list = builtins.getattr("list")?;
         let builtin_tuple = builtins.getattr("tuple")?;
         let builtin_dict = builtins.getattr("dict")?;
         let builtin_set = builtins.getattr("set")?;
         let builtin_frozenset = builtins.getattr("frozenset")?;
+        let defaultdict_cls = collections.getattr("defaultdict")?;
+        let ipaddress = py.import("ipaddress")?;
+        let ipv4_address = ipaddress.getattr("IPv4Address")?;
+        let ipv6_address = ipaddress.getattr("IPv6Address")?;
+        let pure_path = py.import("pathlib")?.getattr("PurePath")?;
 
         let collection_cls = collections_abc.getattr("Collection")?;
         let sequence_cls = collections_abc.getattr("Sequence")?;
@@ -175,6 +236,7 @@ impl<'py> IntrospectionContext<'py> {
 
         let union_type = types_mod.getattr("UnionType").ok();
         let enum_base = enum_mod.getattr("Enum")?;
+        let flag_base = enum_mod.getattr("Flag")?;
         Ok(Self {
             typing,
             builtins,
@@ -199,11 +261,16 @@ impl<'py> IntrospectionContext<'py> {
             builtin_bool,
             builtin_bytes,
             builtin_bytearray,
+            builtin_complex,
             builtin_list,
             builtin_tuple,
             builtin_dict,
             builtin_set,
             builtin_frozenset,
+            ipv4_address,
+            ipv6_address,
+            pure_path,
+            defaultdict_cls,
             collection_cls,
             sequence_cls,
             mutable_sequence_cls,
@@ -213,6 +280,8 @@ impl<'py> IntrospectionContext<'py> {
             mutable_mapping_cls,
             union_type,
             enum_base,
+            flag_base,
+            pydantic_base_model,
         })
     }
 }
@@ -222,6 +291,9 @@ pub fn introspect_struct_fields<'py>(
     cls: &Bound<'py, PyType>,
 ) -> PyResult<Option<Vec<FieldInfoIR>>> {
     let ctx = IntrospectionContext::new(py)?;
+    if is_pydantic_model_with_ctx(cls, &ctx)? {
+        return introspect_pydantic_fields(py, cls, &ctx);
+    }
     if !detect_struct_kind_with_ctx(py, cls, &ctx)? {
         return Ok(None);
     }
@@ -229,6 +301,204 @@ pub fn introspect_struct_fields<'py>(
     introspect_tars_struct_fields(py, cls, &ctx)
 }
 
+/// 判断 cls 是否为 `pydantic.BaseModel` 子类.
+pub fn is_pydantic_model<'py>(py: Python<'py>, cls: &Bound<'py, PyType>) -> PyResult<bool> {
+    let ctx = IntrospectionContext::new(py)?;
+    is_pydantic_model_with_ctx(cls, &ctx)
+}
+
+fn is_pydantic_model_with_ctx<'py>(
+    cls: &Bound<'py, PyType>,
+    ctx: &IntrospectionContext<'py>,
+) -> PyResult<bool> {
+    match ctx.pydantic_base_model.as_ref() {
+        Some(base) => Ok(!cls.is(base) && cls.is_subclass(base)?),
+        None => Ok(false),
+    }
+}
+
+/// 从 pydantic 模型的 `model_fields` 提取字段.
+///
+/// tag 取自字段元数据中的整数 (如 `Annotated[int, 1]`), 否则按定义顺序自动分配;
+/// `Meta` 与 pydantic 的 `Field(gt=..., min_length=..., pattern=...)` 约束都会映射为线级约束.
+fn introspect_pydantic_fields<'py>(
+    py: Python<'py>,
+    cls: &Bound<'py, PyType>,
+    ctx: &IntrospectionContext<'py>,
+) -> PyResult<Option<Vec<FieldInfoIR>>> {
+    let typevar_map = build_typevar_map(py, cls, ctx)?;
+    let model_fields_any = cls.getattr(intern!(py, "model_fields"))?;
+    let model_fields = model_fields_any.cast::<PyDict>()?;
+    let undefined = py.import("pydantic_core")?.getattr("PydanticUndefined")?;
+
+    let mut pending: Vec<PendingField> = Vec::with_capacity(model_fields.len());
+    for (name_obj, info) in model_fields.iter() {
+        let name: String = name_obj.extract()?;
+        let annotation = info.getattr(intern!(py, "annotation"))?;
+        let metadata_any = info.getattr(intern!(py, "metadata"))?;
+        let mut args: Vec<Bound<'py, PyAny>> = vec![annotation];
+        for item in metadata_any.try_iter()? {
+            args.push(item?);
+        }
+        let args = PyTuple::new(py, args)?;
+        let (resolved_type, explicit_tag, meta_constraints) =
+            parse_annotated_payload(name.as_str(), &args)?;
+        let init_alias = pydantic_init_alias(cls, &name, &info)?;
+        let constraints = merge_pydantic_constraints(meta_constraints, &args, init_alias)?;
+
+        let default = info.getattr(intern!(py, "default"))?;
+        let factory = info.getattr(intern!(py, "default_factory"))?;
+        let has_value_default = !default.is(&undefined);
+        let default_factory = (!factory.is_none()).then(|| factory.unbind());
+        let has_default = has_value_default || default_factory.is_some();
+
+        let (typ, is_optional) = translate_type_info_ir(py, &resolved_type, &typevar_map, ctx)?;
+        pending.push(PendingField {
+            name,
+            explicit_tag,
+            typ,
+            default_value: has_value_default.then(|| default.unbind()),
+            default_factory,
+            has_default,
+            is_optional,
+            is_required: !is_optional && !has_default,
+            wrap_simplelist: false,
+            alias_tags: Vec::new(),
+            constraints,
+        });
+    }
+
+    if pending.is_empty() {
+        return Ok(None);
+    }
+    assign_field_tags(cls, pending).map(Some)
+}
+
+/// 解析 pydantic 字段构造时使用的关键字名 (`Field(alias=...)`/`validation_alias`).
+///
+/// 返回 None 表示按字段名传参. 非字符串的 `validation_alias` (`AliasPath`/`AliasChoices`)
+/// 无法直接作为关键字, 仅在模型允许按字段名传参时接受, 否则抛出 TypeError.
+fn pydantic_init_alias<'py>(
+    cls: &Bound<'py, PyType>,
+    name: &str,
+    info: &Bound<'py, PyAny>,
+) -> PyResult<Option<String>> {
+    let py = cls.py();
+    let validation_alias = info.getattr(intern!(py, "validation_alias"))?;
+    if validation_alias.is_none() {
+        return Ok(None);
+    }
+    let config = cls.getattr(intern!(py, "model_config"))?;
+    let config_flag = |key: &str, default: bool| -> PyResult<bool> {
+        match config.cast::<PyDict>()?.get_item(key)? {
+            Some(v) => v.is_truthy(),
+            None => Ok(default),
+        }
+    };
+    if !config_flag("validate_by_alias", true)? {
+        return Ok(None);
+    }
+    if let Ok(alias) = validation_alias.extract::<String>() {
+        return Ok(Some(alias));
+    }
+    if config_flag("validate_by_name", false)? || config_flag("populate_by_name", false)? {
+        return Ok(None);
+    }
+    Err(pyo3::exceptions::PyTypeError::new_err(format!(
+        "pydantic field '{}' of '{}' uses a non-string validation_alias; \
+         use a plain string alias or enable validate_by_name",
+        name,
+        cls.name()?
+    )))
+}
+
+/// 将 pydantic/annotated_types 约束对象 (`Gt`, `MinLen`, `pattern` 等) 合并进 `Meta` 约束.
+///
+/// `Meta` 中显式给出的约束优先; pydantic 的构造别名 `init_alias` 始终覆盖 `Meta(alias=...)`,
+/// 解码时以它作为 `cls(**kwargs)` 的关键字.
+fn merge_pydantic_constraints<'py>(
+    base: Option<ConstraintsIR>,
+    metadata: &Bound<'py, PyTuple>,
+    init_alias: Option<String>,
+) -> PyResult<Option<ConstraintsIR>> {
+    let mut out = base.clone().unwrap_or(ConstraintsIR {
+        gt: None,
+        lt: None,
+        ge: None,
+        le: None,
+        multiple_of: None,
+        min_len: None,
+        max_len: None,
+        pattern: None,
+        endian: None,
+        coerce_enum: false,
+        enum_by_name: None,
+        compress: None,
+        accept_bytes: false,
+        fixed_len: None,
+        choices: None,
+        wire: None,
+        chunk_size: None,
+        as_bytes: false,
+        any_preserve_bool: false,
+        alias: None,
+        normalize: None,
+        encoder: None,
+        decoder: None,
+    });
+    let mut found = base.is_some();
+    if let Some(alias) = init_alias {
+        found = true;
+        out.alias = Some(alias);
+    }
+
+    fn attr<'py, T: for<'a> FromPyObject<'a, 'py>>(
+        item: &Bound<'py, PyAny>,
+        name: &str,
+    ) -> Option<T> {
+        item.getattr(name)
+            .ok()
+            .filter(|v| !v.is_none())
+            .and_then(|v| v.extract::<T>().ok())
+    }
+
+    for item in metadata.iter().skip(1) {
+        if item.extract::<PyRef<'py, Meta>>().is_ok()
+            || item.is_instance_of::<Tag>()
+            || item.extract::<i64>().is_ok()
+        {
+            continue;
+        }
+        let slots: [(&mut Option<f64>, &str); 5] = [
+            (&mut out.gt, "gt"),
+            (&mut out.lt, "lt"),
+            (&mut out.ge, "ge"),
+            (&mut out.le, "le"),
+            (&mut out.multiple_of, "multiple_of"),
+        ];
+        for (slot, name) in slots {
+            if let Some(v) = attr::<f64>(&item, name) {
+                found = true;
+                slot.get_or_insert(v);
+            }
+        }
+        if let Some(v) = attr::<usize>(&item, "min_length") {
+            found = true;
+            out.min_len.get_or_insert(v);
+        }
+        if let Some(v) = attr::<usize>(&item, "max_length") {
+            found = true;
+            out.max_len.get_or_insert(v);
+        }
+        if let Some(v) = attr::<String>(&item, "pattern") {
+            found = true;
+            out.pattern.get_or_insert(v);
+        }
+    }
+
+    Ok(found.then_some(out))
+}
+
 pub fn introspect_type_info_ir<'py>(
     py: Python<'py>,
     tp: &Bound<'py, PyAny>,
@@ -429,19 +699,6 @@ fn introspect_tars_struct_fields<'py>(
         return Ok(None);
     }
 
-    struct PendingField {
-        name: String,
-        explicit_tag: Option<u8>,
-        typ: TypeInfoIR,
-        default_value: Option<Py<PyAny>>,
-        default_factory: Option<Py<PyAny>>,
-        has_default: bool,
-        is_optional: bool,
-        is_required: bool,
-        wrap_simplelist: bool,
-        constraints: Option<ConstraintsIR>,
-    }
-
     let mut pending: Vec<PendingField> = Vec::new();
     for (name_obj, type_hint) in hints.iter() {
         let name: String = name_obj.extract()?;
@@ -449,6 +706,7 @@ fn introspect_tars_struct_fields<'py>(
             continue;
         }
 
+        let (type_hint, outer_final) = strip_final(&type_hint, ctx)?;
         let origin = ctx.typing.call_method1("get_origin", (&type_hint,))?;
         let (resolved_type, annotated_tag, constraints) =
             if !origin.is_none() && origin.is(&ctx.annotated) {
@@ -468,6 +726,12 @@ fn introspect_tars_struct_fields<'py>(
         }
         let explicit_tag = default_spec.explicit_tag.or(annotated_tag);
 
+        // 未显式指定 tag 的 Final 注解视为类常量, 不参与序列化;
+        // 显式指定 tag 时 (如 `Annotated[Final[int], 1]`) 才是 Final 类型的字段.
+        if explicit_tag.is_none() && (outer_final || strip_final(&resolved_type, ctx)?.1) {
+            continue;
+        }
+
         let (typ, is_optional) = translate_type_info_ir(py, &resolved_type, &typevar_map, ctx)?;
         let is_required = !is_optional && !default_spec.has_default;
 
@@ -481,6 +745,7 @@ fn introspect_tars_struct_fields<'py>(
             is_optional,
             is_required,
             wrap_simplelist: default_spec.wrap_simplelist,
+            alias_tags: default_spec.alias_tags,
             constraints,
         });
     }
@@ -488,6 +753,29 @@ fn introspect_tars_struct_fields<'py>(
     if pending.is_empty() {
         return Ok(None);
     }
+    assign_field_tags(cls, pending).map(Some)
+}
+
+/// 尚未分配 tag 的字段.
+struct PendingField {
+    name: String,
+    explicit_tag: Option<u8>,
+    typ: TypeInfoIR,
+    default_value: Option<Py<PyAny>>,
+    default_factory: Option<Py<PyAny>>,
+    has_default: bool,
+    is_optional: bool,
+    is_required: bool,
+    wrap_simplelist: bool,
+    alias_tags: Vec<u8>,
+    constraints: Option<ConstraintsIR>,
+}
+
+/// 为字段分配 tag: 显式 tag 原样使用, 其余按定义顺序从上一个 tag 之后递增分配.
+fn assign_field_tags(
+    cls: &Bound<'_, PyType>,
+    pending: Vec<PendingField>,
+) -> PyResult<Vec<FieldInfoIR>> {
     if pending.len() > 256 {
         return Err(pyo3::exceptions::PyTypeError::new_err(
             "Too many fields to auto-assign tags (max 256)",
@@ -502,8 +790,12 @@ fn introspect_tars_struct_fields<'py>(
         let tag = if let Some(tag) = field.explicit_tag {
             if let Some(existing) = tags_seen[tag as usize].as_ref() {
                 return Err(pyo3::exceptions::PyTypeError::new_err(format!(
-                    "Duplicate tag {} in '{}' and '{}'",
-                    tag, existing, field.name
+                    "Duplicate tag {} declared in {}.{} and {}.{}",
+                    tag,
+                    field_origin_class(cls, existing)?,
+                    existing,
+                    field_origin_class(cls, field.name.as_str())?,
+                    field.name
                 )));
             }
             if tag >= next_auto_tag {
@@ -549,12 +841,73 @@ fn introspect_tars_struct_fields<'py>(
             is_required: field.is_required,
             init: true,
             wrap_simplelist: field.wrap_simplelist,
+            alias_tags: field.alias_tags,
             constraints: field.constraints,
         });
     }
 
     fields.sort_by_key(|f| f.tag);
-    Ok(Some(fields))
+    Ok(fields)
+}
+
+/// 沿 MRO 查找声明该字段的类, 返回其类名.
+///
+/// 从基类向子类逐个经 `typing.get_type_hints` 解析注解, 字段首次出现或注解被重新声明的
+/// 最后一个类视为声明者; 找不到时 (如字段来自动态注入的注解) 回退为 `cls` 自身的类名.
+fn field_origin_class(cls: &Bound<'_, PyType>, name: &str) -> PyResult<String> {
+    let py = cls.py();
+    let ctx = IntrospectionContext::new(py)?;
+    let mro_any = cls.getattr(intern!(py, "__mro__"))?;
+    let mro = mro_any.cast::<PyTuple>()?;
+    let mut origin = None;
+    let mut previous: Option<Bound<'_, PyAny>> = None;
+    for klass in mro.iter().rev() {
+        let Ok(klass_type) = klass.cast::<PyType>() else {
+            continue;
+        };
+        let hint = get_type_hints_with_fallback(py, klass_type, &ctx)
+            .ok()
+            .and_then(|hints| hints.get_item(name).ok().flatten());
+        if let Some(hint) = &hint
+            && !previous
+                .as_ref()
+                .is_some_and(|prev| prev.eq(hint).unwrap_or(false))
+        {
+            origin = Some(klass.clone());
+        }
+        previous = hint;
+    }
+    match origin {
+        Some(klass) => klass.getattr(intern!(py, "__name__"))?.extract(),
+        None => cls.name()?.extract(),
+    }
+}
+
+/// 剥离最外层的 `Final` / `Final[T]`, 返回内层类型及是否为 Final.
+///
+/// 裸 `Final` 没有内层类型, 按 `Any` 处理.
+fn strip_final<'py>(
+    tp: &Bound<'py, PyAny>,
+    ctx: &IntrospectionContext<'py>,
+) -> PyResult<(Bound<'py, PyAny>, bool)> {
+    let Some(final_cls) = ctx.final_cls.as_ref() else {
+        return Ok((tp.clone(), false));
+    };
+    if tp.is(final_cls) {
+        return Ok((ctx.any_type.clone(), true));
+    }
+    let origin = ctx.typing.call_method1("get_origin", (tp,))?;
+    if origin.is_none() || !origin.is(final_cls) {
+        return Ok((tp.clone(), false));
+    }
+    let args_any = ctx.typing.call_method1("get_args", (tp,))?;
+    let args = args_any.cast::<PyTuple>()?;
+    if args.is_empty() {
+        return Err(pyo3::exceptions::PyTypeError::new_err(
+            "Final requires an inner type",
+        ));
+    }
+    Ok((args.get_item(0)?, true))
 }
 
 fn parse_annotated_args_loose<'py>(
@@ -573,6 +926,17 @@ fn parse_annotated_payload<'py>(
     let mut found_meta: Option<PyRef<'py, Meta>> = None;
 
     for item in args.iter().skip(1) {
+        if let Ok(tag) = item.cast::<Tag>() {
+            if found_int_tag.is_some() {
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Multiple integer tags are not allowed for field '{}'",
+                    field_name
+                )));
+            }
+            found_int_tag = Some(tag.get().value);
+            continue;
+        }
+
         if let Ok(int_tag) = item.extract::<i64>() {
             if !(0..=255).contains(&int_tag) {
                 return Err(pyo3::exceptions::PyTypeError::new_err(format!(
@@ -607,9 +971,31 @@ fn parse_annotated_payload<'py>(
             lt: meta.lt,
             ge: meta.ge,
             le: meta.le,
+            multiple_of: meta.multiple_of,
             min_len: meta.min_len,
             max_len: meta.max_len,
             pattern: meta.pattern.clone(),
+            endian: meta.endian.clone(),
+            coerce_enum: meta.coerce_enum,
+            enum_by_name: meta.enum_mode.as_deref().map(|m| m == "name"),
+            compress: meta.compress.clone(),
+            accept_bytes: meta.accept_bytes,
+            fixed_len: meta.fixed_len,
+            choices: meta.choices.clone(),
+            wire: meta.wire.clone(),
+            chunk_size: meta.chunk_size,
+            as_bytes: meta.as_bytes,
+            any_preserve_bool: meta.any_preserve_bool,
+            alias: meta.alias.clone(),
+            normalize: meta.normalize.clone(),
+            encoder: meta
+                .encoder
+                .as_ref()
+                .map(|h| Arc::new(h.clone_ref(args.py()))),
+            decoder: meta
+                .decoder
+                .as_ref()
+                .map(|h| Arc::new(h.clone_ref(args.py()))),
         };
         return Ok((real_type, found_int_tag, Some(constraints)));
     }
@@ -617,6 +1003,39 @@ fn parse_annotated_payload<'py>(
     Ok((real_type, found_int_tag, None))
 }
 
+/// 由 `defaultdict[K, V]` 的值类型推导 `default_factory`.
+///
+/// 取值类型的运行时类 (如 `list[int]` -> `list`); 值类型不是类 (Union、Any 等) 时
+/// 无法推导, 抛出 TypeError.
+fn defaultdict_factory<'py>(
+    value_tp: &Bound<'py, PyAny>,
+    ctx: &IntrospectionContext<'py>,
+) -> PyResult<Py<PyAny>> {
+    let mut tp = value_tp.clone();
+    loop {
+        let origin = ctx.typing.call_method1("get_origin", (&tp,))?;
+        if origin.is(&ctx.annotated) {
+            tp = tp.getattr("__origin__")?;
+            continue;
+        }
+        let is_union =
+            origin.is(&ctx.union_origin) || ctx.union_type.as_ref().is_some_and(|u| origin.is(u));
+        let candidate = if origin.is_none() { tp } else { origin };
+        if !is_union
+            && candidate.is_instance_of::<PyType>()
+            && !candidate.is(&ctx.none_type)
+            && !candidate.is(&ctx.any_type)
+        {
+            return Ok(candidate.unbind());
+        }
+        let repr: String = value_tp.repr()?.extract()?;
+        return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "Cannot derive defaultdict default_factory from value type: {}",
+            repr
+        )));
+    }
+}
+
 fn translate_type_info_ir<'py>(
     py: Python<'py>,
     tp: &Bound<'py, PyAny>,
@@ -753,6 +1172,7 @@ fn translate_type_info_ir<'py>(
             }
 
             let mut variants = Vec::new();
+            let mut values = Vec::new();
             let mut seen = HashSet::new();
             let mut has_none = false;
 
@@ -761,6 +1181,7 @@ fn translate_type_info_ir<'py>(
                     has_none = true;
                     continue;
                 }
+                values.push(val.clone().unbind());
                 let val_type = val.get_type();
                 let (typ, _opt) = translate_type_info_ir(py, val_type.as_any(), typevar_map, ctx)?;
                 let key = format!("{:?}", typ);
@@ -775,10 +1196,15 @@ fn translate_type_info_ir<'py>(
             if has_none {
                 forced_optional = true;
             }
-            if variants.len() == 1 {
-                return Ok((variants.remove(0), forced_optional));
-            }
-            return Ok((TypeInfoIR::Union(variants), forced_optional));
+            let inner = if variants.len() == 1 {
+                variants.remove(0)
+            } else {
+                TypeInfoIR::Union(variants)
+            };
+            return Ok((
+                TypeInfoIR::Literal(values, Box::new(inner)),
+                forced_optional,
+            ));
         }
 
         let is_union =
@@ -827,6 +1253,7 @@ fn translate_type_info_ir<'py>(
             || origin.is(&ctx.builtin_set)
             || origin.is(&ctx.builtin_frozenset)
         {
+            let frozen = origin.is(&ctx.builtin_frozenset);
             let args_any = ctx.typing.call_method1("get_args", (&resolved,))?;
             let args = args_any.cast::<PyTuple>()?;
             if args.is_empty() {
@@ -837,7 +1264,12 @@ fn translate_type_info_ir<'py>(
                 )));
             }
             let (inner, _opt) = translate_type_info_ir(py, &args.get_item(0)?, typevar_map, ctx)?;
-            return Ok((TypeInfoIR::Set(Box::new(inner)), forced_optional));
+            let ir = if frozen {
+                TypeInfoIR::FrozenSet(Box::new(inner))
+            } else {
+                TypeInfoIR::Set(Box::new(inner))
+            };
+            return Ok((ir, forced_optional));
         }
 
         if origin.is(&ctx.mapping_cls) || origin.is(&ctx.mutable_mapping_cls) {
@@ -891,6 +1323,26 @@ fn translate_type_info_ir<'py>(
             return Ok((TypeInfoIR::List(Box::new(inner)), forced_optional));
         }
 
+        if origin.is(&ctx.defaultdict_cls) {
+            let args_any = ctx.typing.call_method1("get_args", (&resolved,))?;
+            let args = args_any.cast::<PyTuple>()?;
+            if args.len() < 2 {
+                let repr: String = resolved.repr()?.extract()?;
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Unsupported Tars type: {}",
+                    repr
+                )));
+            }
+            let value_any = args.get_item(1)?;
+            let factory = defaultdict_factory(&value_any, ctx)?;
+            let (k, _opt_k) = translate_type_info_ir(py, &args.get_item(0)?, typevar_map, ctx)?;
+            let (v, _opt_v) = translate_type_info_ir(py, &value_any, typevar_map, ctx)?;
+            return Ok((
+                TypeInfoIR::DefaultDict(Box::new(k), Box::new(v), factory),
+                forced_optional,
+            ));
+        }
+
         if origin.is(&ctx.builtin_dict) {
             let args_any = ctx.typing.call_method1("get_args", (&resolved,))?;
             let args = args_any.cast::<PyTuple>()?;
@@ -906,6 +1358,26 @@ fn translate_type_info_ir<'py>(
             return Ok((TypeInfoIR::Map(Box::new(k), Box::new(v)), forced_optional));
         }
 
+        if let Ok(origin_type) = origin.clone().cast_into::<PyType>()
+            && is_dict_subclass(&origin_type, ctx)?
+        {
+            let args_any = ctx.typing.call_method1("get_args", (&resolved,))?;
+            let args = args_any.cast::<PyTuple>()?;
+            if args.len() != 2 {
+                let repr: String = resolved.repr()?.extract()?;
+                return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "Unsupported Tars type: {}",
+                    repr
+                )));
+            }
+            let (k, _opt_k) = translate_type_info_ir(py, &args.get_item(0)?, typevar_map, ctx)?;
+            let (v, _opt_v) = translate_type_info_ir(py, &args.get_item(1)?, typevar_map, ctx)?;
+            return Ok((
+                TypeInfoIR::DictSubclass(Box::new(k), Box::new(v), origin_type.unbind()),
+                forced_optional,
+            ));
+        }
+
         break;
     }
 
@@ -932,8 +1404,21 @@ fn translate_type_info_ir<'py>(
     if resolved.is(&ctx.builtin_bytes) {
         return Ok((TypeInfoIR::Bytes, forced_optional));
     }
+    if resolved.is(&ctx.builtin_bytearray) {
+        return Ok((TypeInfoIR::ByteArray, forced_optional));
+    }
+    if resolved.is(&ctx.builtin_complex) {
+        return Ok((TypeInfoIR::Complex, forced_optional));
+    }
+    if resolved.is(&ctx.ipv4_address) || resolved.is(&ctx.ipv6_address) {
+        let cls = resolved.cast_into::<PyType>()?;
+        return Ok((TypeInfoIR::IpAddress(cls.unbind()), forced_optional));
+    }
 
     if let Ok(resolved_type) = resolved.clone().cast_into::<PyType>() {
+        if resolved_type.is_subclass(&ctx.pure_path)? {
+            return Ok((TypeInfoIR::Path(resolved_type.unbind()), forced_optional));
+        }
         if is_namedtuple_type(&resolved_type, ctx)? {
             let items = build_namedtuple_items(py, &resolved_type, typevar_map, ctx)?;
             return Ok((
@@ -942,7 +1427,8 @@ fn translate_type_info_ir<'py>(
             ));
         }
         if is_typeddict_type(&resolved_type, ctx)? {
-            return Ok((TypeInfoIR::TypedDict, forced_optional));
+            let aliases = typeddict_aliases(py, &resolved_type, ctx)?;
+            return Ok((TypeInfoIR::TypedDict(aliases), forced_optional));
         }
         if is_dataclass_type(&resolved_type, ctx)? {
             return Ok((
@@ -952,6 +1438,12 @@ fn translate_type_info_ir<'py>(
         }
     }
 
+    if let Ok(resolved_type) = resolved.clone().cast_into::<PyType>()
+        && is_subclass(&resolved_type, &ctx.flag_base, ctx)?
+    {
+        return Ok((TypeInfoIR::Flag(resolved_type.unbind()), forced_optional));
+    }
+
     if let Ok(resolved_type) = resolved.clone().cast_into::<PyType>()
         && is_subclass(&resolved_type, &ctx.enum_base, ctx)?
     {
@@ -988,7 +1480,8 @@ fn translate_type_info_ir<'py>(
     }
 
     if let Ok(resolved_type) = resolved.clone().cast_into::<PyType>()
-        && resolved_type.is_subclass_of::<Struct>()?
+        && (resolved_type.is_subclass_of::<Struct>()?
+            || is_pydantic_model_with_ctx(&resolved_type, ctx)?)
     {
         return Ok((TypeInfoIR::Struct(resolved_type.unbind()), forced_optional));
     }
@@ -999,6 +1492,31 @@ fn translate_type_info_ir<'py>(
         return Ok((TypeInfoIR::TarsDict, forced_optional));
     }
 
+    if let Ok(resolved_type) = resolved.clone().cast_into::<PyType>()
+        && let Some(base) = scalar_base_info(&resolved_type, ctx)?
+    {
+        return Ok((
+            TypeInfoIR::Subclass(resolved_type.unbind(), Box::new(base)),
+            forced_optional,
+        ));
+    }
+
+    if let Ok(resolved_type) = resolved.clone().cast_into::<PyType>()
+        && is_dict_subclass(&resolved_type, ctx)?
+    {
+        let (k, v) = match dict_base_args(&resolved_type, ctx)? {
+            Some((k_any, v_any)) => (
+                translate_type_info_ir(py, &k_any, typevar_map, ctx)?.0,
+                translate_type_info_ir(py, &v_any, typevar_map, ctx)?.0,
+            ),
+            None => (TypeInfoIR::Any, TypeInfoIR::Any),
+        };
+        return Ok((
+            TypeInfoIR::DictSubclass(Box::new(k), Box::new(v), resolved_type.unbind()),
+            forced_optional,
+        ));
+    }
+
     let repr: String = resolved.repr()?.extract()?;
     Err(pyo3::exceptions::PyTypeError::new_err(format!(
         "Unsupported Tars type: {}",
@@ -1006,6 +1524,63 @@ fn translate_type_info_ir<'py>(
     )))
 }
 
+/// 用户定义的 `int`/`str`/`float`/`bytes` 子类 (如 `class Port(int)`) 对应的底层类型.
+///
+/// 枚举等已单独处理的类型在此之前匹配, 不会进入该分支.
+fn scalar_base_info<'py>(
+    cls: &Bound<'py, PyType>,
+    ctx: &IntrospectionContext<'py>,
+) -> PyResult<Option<TypeInfoIR>> {
+    let bases = [
+        (&ctx.builtin_int, TypeInfoIR::Int),
+        (&ctx.builtin_str, TypeInfoIR::Str),
+        (&ctx.builtin_float, TypeInfoIR::Float),
+        (&ctx.builtin_bytes, TypeInfoIR::Bytes),
+    ];
+    for (base, info) in bases {
+        if cls.is_subclass(base)? {
+            return Ok(Some(info));
+        }
+    }
+    Ok(None)
+}
+
+/// 是否为用户定义的 `dict` 子类 (不含 dict、defaultdict 与 TarsDict 本身).
+fn is_dict_subclass<'py>(
+    cls: &Bound<'py, PyType>,
+    ctx: &IntrospectionContext<'py>,
+) -> PyResult<bool> {
+    if cls.is(&ctx.defaultdict_cls) || cls.is_subclass_of::<TarsDict>()? {
+        return Ok(false);
+    }
+    is_subclass(cls, &ctx.builtin_dict, ctx)
+}
+
+/// 从 `class D(dict[K, V])` 的 `__orig_bases__` 中取出键值类型; 未参数化时返回 None.
+fn dict_base_args<'py>(
+    cls: &Bound<'py, PyType>,
+    ctx: &IntrospectionContext<'py>,
+) -> PyResult<Option<(Bound<'py, PyAny>, Bound<'py, PyAny>)>> {
+    let Ok(bases_any) = cls.getattr("__orig_bases__") else {
+        return Ok(None);
+    };
+    let Ok(bases) = bases_any.cast::<PyTuple>() else {
+        return Ok(None);
+    };
+    for base in bases.iter() {
+        let origin = ctx.typing.call_method1("get_origin", (&base,))?;
+        if !origin.is(&ctx.builtin_dict) {
+            continue;
+        }
+        let args_any = ctx.typing.call_method1("get_args", (&base,))?;
+        let args = args_any.cast::<PyTuple>()?;
+        if args.len() == 2 {
+            return Ok(Some((args.get_item(0)?, args.get_item(1)?)));
+        }
+    }
+    Ok(None)
+}
+
 fn is_typeddict_type<'py>(
     cls: &Bound<'py, PyType>,
     ctx: &IntrospectionContext<'py>,
@@ -1021,6 +1596,50 @@ fn is_typeddict_type<'py>(
     Ok(has_total && has_annotations && has_keys)
 }
 
+/// 收集 TypedDict 成员上 `Meta(alias=...)` 声明的别名.
+///
+/// 成员注解可能被 `Required`/`NotRequired` 包裹, 逐层剥离后在 `Annotated` 元数据中查找 Meta.
+fn typeddict_aliases<'py>(
+    py: Python<'py>,
+    cls: &Bound<'py, PyType>,
+    ctx: &IntrospectionContext<'py>,
+) -> PyResult<Vec<(String, String)>> {
+    let hints = get_type_hints_with_fallback(py, cls, ctx)?;
+    let mut aliases = Vec::new();
+    for (name, ann) in hints.iter() {
+        let mut current = ann;
+        let alias = loop {
+            let origin = ctx.typing.call_method1("get_origin", (&current,))?;
+            if origin.is_none() {
+                break None;
+            }
+            let args_any = ctx.typing.call_method1("get_args", (&current,))?;
+            let args = args_any.cast::<PyTuple>()?;
+            if args.is_empty() {
+                break None;
+            }
+            if origin.is(&ctx.annotated) {
+                let meta_alias = args
+                    .iter()
+                    .skip(1)
+                    .find_map(|item| item.extract::<PyRef<'py, Meta>>().ok()?.alias.clone());
+                if meta_alias.is_some() {
+                    break meta_alias;
+                }
+            } else if !ctx.required_cls.as_ref().is_some_and(|c| origin.is(c))
+                && !ctx.not_required_cls.as_ref().is_some_and(|c| origin.is(c))
+            {
+                break None;
+            }
+            current = args.get_item(0)?;
+        };
+        if let Some(alias) = alias {
+            aliases.push((name.extract::<String>()?, alias));
+        }
+    }
+    Ok(aliases)
+}
+
 fn is_namedtuple_type<'py>(
     cls: &Bound<'py, PyType>,
     ctx: &IntrospectionContext<'py>,
@@ -1043,16 +1662,15 @@ fn build_namedtuple_items<'py>(
 ) -> PyResult<Vec<TypeInfoIR>> {
     let fields_any = cls.getattr("_fields")?;
     let fields = fields_any.cast::<PyTuple>()?;
-    let annotations_any = cls.getattr("__annotations__").ok();
-    let annotations = annotations_any.and_then(|a| a.cast::<PyDict>().ok().cloned());
+    // 通过 get_type_hints 沿 MRO 合并注解, 子类未重新声明注解时仍可取到基类类型.
+    // `_fields` 中缺少注解的条目按 Any 处理, 保证编码/解码的元素个数始终等于 `len(_fields)`,
+    // 与 `cls(*values)` 重建所需的位置参数个数一致.
+    let hints = get_type_hints_with_fallback(py, cls, ctx)?;
 
     let mut items = Vec::with_capacity(fields.len());
     for name_any in fields.iter() {
         let name: String = name_any.extract()?;
-        if let Some(ann) = annotations
-            .as_ref()
-            .and_then(|a| a.get_item(name.as_str()).ok().flatten())
-        {
+        if let Some(ann) = hints.get_item(name.as_str())? {
             let (inner, _opt) = translate_type_info_ir(py, &ann, typevar_map, ctx)?;
             items.push(inner);
         } else {
@@ -1146,6 +1764,7 @@ fn lookup_default_value<'py>(
         default_value: None,
         default_factory: None,
         wrap_simplelist: false,
+        alias_tags: Vec::new(),
     })
 }
 
@@ -1178,6 +1797,7 @@ fn normalize_default_spec<'py>(
                 default_value: None,
                 default_factory: None,
                 wrap_simplelist: spec.wrap_simplelist,
+                alias_tags: spec.alias_tags.clone(),
             });
         }
 
@@ -1186,6 +1806,7 @@ fn normalize_default_spec<'py>(
                 normalize_default_value(py, default_value.bind(py), field_name, ctx)?;
             normalized.explicit_tag = spec.tag;
             normalized.wrap_simplelist = spec.wrap_simplelist;
+            normalized.alias_tags = spec.alias_tags.clone();
             return Ok(normalized);
         }
 
@@ -1196,6 +1817,7 @@ fn normalize_default_spec<'py>(
                 default_value: None,
                 default_factory: Some(default_factory.clone_ref(py)),
                 wrap_simplelist: spec.wrap_simplelist,
+                alias_tags: spec.alias_tags.clone(),
             });
         }
     }
@@ -1207,6 +1829,7 @@ fn normalize_default_spec<'py>(
             default_value: None,
             default_factory: None,
             wrap_simplelist: false,
+            alias_tags: Vec::new(),
         });
     }
 
@@ -1241,6 +1864,7 @@ fn normalize_default_value<'py>(
                 default_value: None,
                 default_factory: Some(default_factory),
                 wrap_simplelist: false,
+                alias_tags: Vec::new(),
             });
         }
 
@@ -1256,5 +1880,6 @@ fn normalize_default_value<'py>(
         default_value: Some(default_value.clone().unbind()),
         default_factory: None,
         wrap_simplelist: false,
+        alias_tags: Vec::new(),
     })
 }
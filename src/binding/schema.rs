@@ -1,35 +1,80 @@
 use pyo3::ffi;
+use pyo3::intern;
 use pyo3::prelude::*;
 use pyo3::pyclass::CompareOp;
-use pyo3::types::{PyAny, PyDict, PyTuple, PyType};
+use pyo3::types::{PyAny, PyBool, PyDict, PyFrozenSet, PyModule, PyTuple, PyType};
 use smallvec::SmallVec;
 use std::fmt::Write;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
+use crate::binding::codec::coverage::SchemaCoverage;
 use crate::binding::compiler::compile_schema_from_class;
 pub use crate::binding::core::*;
 use crate::binding::generics::handle_class_getitem;
 use crate::binding::instantiate::construct_instance;
-use crate::binding::parse::detect_struct_kind;
+use crate::binding::metaclass::{own_class_attr, restore_non_field_defaults};
+use crate::binding::parse::{detect_struct_kind, is_pydantic_model};
 
 pub(crate) fn schema_from_class(
     py: Python<'_>,
     cls: &Bound<'_, PyType>,
 ) -> PyResult<Option<Arc<StructDef>>> {
+    let mut stale_config = None;
     if let Ok(schema_attr) = cls.getattr(SCHEMA_ATTR)
         && let Ok(schema) = schema_attr.extract::<Py<Schema>>()
     {
-        return Ok(Some(schema.borrow(py).def.clone()));
+        let def = schema.borrow(py).def.clone();
+        if def.is_compiled_for(cls) {
+            return Ok(Some(def));
+        }
+        stale_config = Some(def.schema_config());
     }
 
     let cls_key = cls.as_ptr() as usize;
-    let cached =
-        SCHEMA_CACHE.with(|cache| cache.borrow().get(&cls_key).and_then(|weak| weak.upgrade()));
+    let cached = SCHEMA_CACHE.with(|cache| {
+        cache
+            .borrow()
+            .get(&cls_key)
+            .and_then(|weak| weak.upgrade())
+            .filter(|def| def.is_compiled_for(cls))
+    });
 
     if cached.is_some() {
         return Ok(cached);
     }
 
+    // 类定义时注解引用了尚未定义的名称, 此时再解析一次; 仍无法解析时保留标记以便重试.
+    if let Some(pending) = own_class_attr(cls, PENDING_SCHEMA_ATTR)?
+        && let Ok(pending) = pending.cast::<PendingSchema>()
+    {
+        let config = pending.get().config.clone();
+        let def = compile_schema_from_class(py, cls, config).map_err(|err| {
+            if !err.is_instance_of::<pyo3::exceptions::PyNameError>(py) {
+                return err;
+            }
+            let name = cls
+                .name()
+                .map(|n| n.to_string())
+                .unwrap_or_else(|_| "Unknown".to_string());
+            let wrapped = pyo3::exceptions::PyNameError::new_err(format!(
+                "Cannot compile schema for '{}': unresolved forward reference ({})",
+                name,
+                err.value(py)
+            ));
+            wrapped.set_cause(py, Some(err));
+            wrapped
+        })?;
+        cls.delattr(PENDING_SCHEMA_ATTR)?;
+        restore_non_field_defaults(cls, def.as_deref())?;
+        return Ok(def);
+    }
+
+    // Schema 属于其他类对象 (继承而来, 或模块重载后残留的旧类), 沿用其配置重新编译.
+    if let Some(config) = stale_config {
+        return compile_schema_from_class(py, cls, config);
+    }
+
     Ok(None)
 }
 
@@ -46,17 +91,22 @@ pub fn ensure_schema_for_class(
         return Ok(def);
     }
 
-    if detect_struct_kind(py, cls)? {
+    if detect_struct_kind(py, cls)? || is_pydantic_model(py, cls)? {
         let default_config = SchemaConfig {
             frozen: false,
             order: false,
             forbid_unknown_tags: false,
             eq: true,
             omit_defaults: false,
+            omit_none: true,
             repr_omit_defaults: false,
             kw_only: false,
             dict: false,
             weakref: false,
+            emit_empty_marker: false,
+            forbid_extra_bytes: true,
+            enum_by_name: false,
+            str_fields: None,
         };
 
         if let Some(def) = compile_schema_from_class(py, cls, default_config)? {
@@ -74,6 +124,49 @@ pub fn ensure_schema_for_class(
     )))
 }
 
+/// 预编译模块中定义的所有 Struct 与 pydantic 模型的 Schema.
+///
+/// 用于在启动阶段集中暴露 Schema 错误, 而不是等到首次编解码时才失败.
+/// 仅处理 `__module__` 与该模块同名的类, 从其他模块导入的类会被跳过.
+///
+/// Args:
+///     module: 目标模块.
+///
+/// Returns:
+///     已编译的类列表, 顺序同模块命名空间中的定义顺序.
+///
+/// Raises:
+///     TypeError: 某个类的 Schema 无效; 异常类型沿用原始错误, 消息以类名开头.
+#[pyfunction]
+pub fn compile_module<'py>(
+    py: Python<'py>,
+    module: &Bound<'py, PyModule>,
+) -> PyResult<Vec<Bound<'py, PyType>>> {
+    let module_name = module.name()?;
+    let mut compiled = Vec::new();
+    for (_, value) in module.dict().iter() {
+        let Ok(cls) = value.cast_into::<PyType>() else {
+            continue;
+        };
+        let defined_here = cls
+            .getattr(intern!(py, "__module__"))
+            .and_then(|m| m.eq(&module_name))
+            .unwrap_or(false);
+        if !defined_here || !(detect_struct_kind(py, &cls)? || is_pydantic_model(py, &cls)?) {
+            continue;
+        }
+        if let Err(err) = ensure_schema_for_class(py, &cls) {
+            let qualname = cls.qualname()?;
+            let wrapped =
+                PyErr::from_type(err.get_type(py), format!("{}: {}", qualname, err.value(py)));
+            wrapped.set_cause(py, Some(err));
+            return Err(wrapped);
+        }
+        compiled.push(cls);
+    }
+    Ok(compiled)
+}
+
 #[pymethods]
 impl TarsDict {
     #[new]
@@ -111,14 +204,28 @@ impl Struct {
     #[new]
     #[pyo3(signature = (*_args, **_kwargs))]
     fn new(_args: &Bound<'_, PyTuple>, _kwargs: Option<&Bound<'_, PyDict>>) -> Self {
-        Struct
+        Struct {
+            frozen_instance: AtomicBool::new(false),
+            present: None,
+        }
     }
 
-    fn __traverse__(&self, _visit: pyo3::PyVisit<'_>) -> Result<(), pyo3::PyTraverseError> {
+    fn __traverse__(&self, visit: pyo3::PyVisit<'_>) -> Result<(), pyo3::PyTraverseError> {
+        if let Some(present) = &self.present {
+            visit.call(present)?;
+        }
         Ok(())
     }
 
-    fn __clear__(&mut self) {}
+    fn __clear__(&mut self) {
+        self.present = None;
+    }
+
+    /// 解码时 `track_presence=True` 记录的线上出现字段名集合; 未跟踪时为 None.
+    #[getter(__tarsio_present__)]
+    fn tarsio_present(&self, py: Python<'_>) -> Option<Py<PyFrozenSet>> {
+        self.present.as_ref().map(|present| present.clone_ref(py))
+    }
 
     #[pyo3(signature = (*args, **kwargs))]
     fn __init__(
@@ -151,13 +258,32 @@ impl Struct {
     ///     ValueError: 缺少必填字段、类型不匹配、或递归深度超过限制.
     fn encode(slf: &Bound<'_, Struct>) -> PyResult<Py<pyo3::types::PyBytes>> {
         let py = slf.py();
-        crate::binding::codec::ser::encode_object_to_pybytes(py, slf.as_any())
+        crate::binding::codec::ser::encode_object_to_pybytes(py, slf.as_any(), false, None, None)
+    }
+
+    /// 支持 `bytes(obj)`, 结果与 `encode()` 相同.
+    fn __bytes__(slf: &Bound<'_, Struct>) -> PyResult<Py<pyo3::types::PyBytes>> {
+        Self::encode(slf)
     }
 
     /// 将 Tars 二进制数据解码为当前类的实例.
     ///
     /// Args:
     ///     data: 待解码的 bytes.
+    ///     freeze: 为 True 时返回的实例拒绝属性赋值.
+    ///     track_presence: 为 True 时在 `__tarsio_present__` 记录线上出现的字段名.
+    ///     coverage: 可选的 `SchemaCoverage`, 累计本次解码中出现的字段与未知 Tag.
+    ///     on_unknown: 可选回调, 任意深度的 Struct 每遇到一个未知 Tag 时以
+    ///         `(tag, type_code, offset)` 调用; 返回值被忽略, 抛出的异常原样传播.
+    ///     max_bytes: 输入长度上限, 解析前检查, 同时限制压缩字段解压后的长度;
+    ///         None 表示不限制输入长度.
+    ///     float_as_decimal: 为 True 时 float/double 字段解码为 `decimal.Decimal`.
+    ///     widen_float: 为 True 时单精度 Float 按最短十进制表示转换为 Python float.
+    ///     allow_trailing: 为 True 时忽略解码结束后剩余的字节.
+    ///     accept_wrapped_structs: 为 True 时 Struct 字段也接受 SimpleList 包装的嵌套序列化.
+    ///     strict: 为 True 时要求线上类型与字段声明精确一致 (double 字段拒绝 Float).
+    ///     context: 非 None 时以关键字参数 `context` 传给字段 decoder 钩子与 `__post_init__`.
+    ///     max_depth: 嵌套深度上限; None 表示默认上限.
     ///
     /// Returns:
     ///     解码得到的实例.
@@ -165,10 +291,93 @@ impl Struct {
     /// Raises:
     ///     TypeError: 目标类未注册 Schema.
     ///     ValueError: 数据格式不正确、缺少必填字段、或递归深度超过限制.
+    ///     ValidationError: 输入长度超过 max_bytes.
     #[classmethod]
-    fn decode<'py>(cls: &Bound<'py, PyType>, data: &[u8]) -> PyResult<Bound<'py, PyAny>> {
+    #[pyo3(signature = (
+        data,
+        *,
+        freeze=false,
+        track_presence=false,
+        coverage=None,
+        on_unknown=None,
+        max_bytes=None,
+        float_as_decimal=false,
+        widen_float=false,
+        allow_trailing=false,
+        accept_wrapped_structs=false,
+        strict=false,
+        context=None,
+        max_depth=None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn decode<'py>(
+        cls: &Bound<'py, PyType>,
+        data: &[u8],
+        freeze: bool,
+        track_presence: bool,
+        coverage: Option<&Bound<'py, SchemaCoverage>>,
+        on_unknown: Option<&Bound<'py, PyAny>>,
+        max_bytes: Option<usize>,
+        float_as_decimal: bool,
+        widen_float: bool,
+        allow_trailing: bool,
+        accept_wrapped_structs: bool,
+        strict: bool,
+        context: Option<Py<PyAny>>,
+        max_depth: Option<usize>,
+    ) -> PyResult<Bound<'py, PyAny>> {
         let py = cls.py();
-        crate::binding::codec::de::decode_object(py, cls, data)
+        let _context = crate::binding::validation::HookContextGuard::set(context);
+        let _depth = crate::binding::utils::DepthLimitGuard::set(max_depth)?;
+        let obj = crate::binding::codec::de::decode_object(
+            py,
+            cls,
+            data,
+            0,
+            track_presence,
+            coverage,
+            on_unknown,
+            max_bytes,
+            crate::binding::codec::de::DecodeMode {
+                as_decimal: float_as_decimal,
+                widen_f32: widen_float,
+                accept_wrapped_structs,
+                strict,
+                max_decompressed: max_bytes,
+            },
+            false,
+            allow_trailing,
+            None,
+        )?;
+        if freeze {
+            crate::binding::codec::de::freeze_instance(&obj)?;
+        }
+        Ok(obj)
+    }
+
+    /// 将 Tars 二进制数据解码并就地覆盖当前实例的字段.
+    ///
+    /// 与 `decode` 的 tag 查找、默认值填充、尾随字节检查及 `__post_init__` 行为一致,
+    /// 但不分配新对象, 适合在热循环中复用实例池.
+    ///
+    /// Args:
+    ///     data: 待解码的 bytes.
+    ///
+    /// Raises:
+    ///     AttributeError: 实例或其类为 frozen.
+    ///     ValueError: 数据格式不正确、缺少必填字段、或递归深度超过限制.
+    ///         失败时实例可能已被部分覆盖.
+    fn decode_into(slf: &Bound<'_, Struct>, data: &[u8]) -> PyResult<()> {
+        let py = slf.py();
+        let cls = slf.get_type();
+        let frozen_instance = slf.borrow().frozen_instance.load(Ordering::Relaxed);
+        if frozen_instance || schema_from_class(py, &cls)?.is_some_and(|def| def.frozen) {
+            return Err(pyo3::exceptions::PyAttributeError::new_err(format!(
+                "can't set attributes of frozen instance '{}'",
+                cls.name()?
+            )));
+        }
+        crate::binding::codec::de::decode_into_instance(py, slf, data)
     }
 
     #[classmethod]
@@ -180,61 +389,82 @@ impl Struct {
     }
 
     fn __copy__(slf: &Bound<'_, Struct>) -> PyResult<Py<PyAny>> {
+        copy_struct(slf, None)
+    }
+
+    /// 深拷贝: 字段值按 `copy.deepcopy(value, memo)` 递归复制.
+    fn __deepcopy__(slf: &Bound<'_, Struct>, memo: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        copy_struct(slf, Some(memo))
+    }
+
+    /// 返回 pickle 使用的状态: `(属性 dict, 实例级只读标记)`.
+    ///
+    /// 属性 dict 包含已赋值的字段与实例 `__dict__` 中的额外属性, 未赋值的字段不包含在内.
+    fn __getstate__<'py>(slf: &Bound<'py, Struct>) -> PyResult<Bound<'py, PyTuple>> {
         let py = slf.py();
         let cls = slf.get_type();
         let def = schema_from_class(py, &cls)?.ok_or_else(|| {
-            pyo3::exceptions::PyTypeError::new_err("Schema not found during copy")
+            pyo3::exceptions::PyTypeError::new_err("Schema not found during pickling")
         })?;
-
-        // SAFETY:
-        // 1. `cls` 是有效的 Python 类型对象，来自 `slf.get_type()`。
-        // 2. `PyType_GenericAlloc` 返回新引用；空指针时立即通过 `PyErr::fetch` 返回错误。
-        // 3. `Bound::from_owned_ptr` 正确接管该新引用所有权。
-        let instance = unsafe {
-            let type_ptr = cls.as_ptr() as *mut ffi::PyTypeObject;
-            let obj_ptr = ffi::PyType_GenericAlloc(type_ptr, 0);
-            if obj_ptr.is_null() {
-                return Err(PyErr::fetch(py));
-            }
-            Bound::from_owned_ptr(py, obj_ptr)
-        };
-
+        let attrs = PyDict::new(py);
+        if let Ok(extras) = slf.getattr(pyo3::intern!(py, "__dict__"))
+            && let Ok(extras) = extras.cast::<PyDict>()
+        {
+            attrs.update(extras.as_mapping())?;
+        }
         for field in &def.fields_sorted {
-            let val = match slf.getattr(field.name_py.bind(py)) {
-                Ok(v) => v,
-                Err(_) => {
-                    if let Some(default_value) = field.default_value.as_ref() {
-                        default_value.bind(py).clone()
-                    } else if let Some(factory) = field.default_factory.as_ref() {
-                        factory.bind(py).call0()?
-                    } else if field.is_optional {
-                        py.None().into_bound(py)
-                    } else if field.is_required {
-                        return Err(pyo3::exceptions::PyTypeError::new_err(format!(
-                            "Missing required field '{}' during copy",
-                            field.name
-                        )));
-                    } else {
-                        continue;
-                    }
-                }
-            };
+            let name_py = field.name_py.bind(py);
+            if let Ok(val) = slf.getattr(name_py) {
+                attrs.set_item(name_py, val)?;
+            }
+        }
+        let frozen_instance = slf.borrow().frozen_instance.load(Ordering::Relaxed);
+        PyTuple::new(
+            py,
+            [
+                attrs.into_any(),
+                PyBool::new(py, frozen_instance).to_owned().into_any(),
+            ],
+        )
+    }
 
+    /// 从 `__getstate__` 的结果恢复属性与实例级只读标记.
+    ///
+    /// 直接写入属性, 绕过 frozen 检查与 `__post_init__`, 以便 frozen 实例也能反序列化.
+    fn __setstate__(slf: &Bound<'_, Struct>, state: (Bound<'_, PyDict>, bool)) -> PyResult<()> {
+        let py = slf.py();
+        let (attrs, frozen_instance) = state;
+        for (name, val) in attrs.iter() {
             // SAFETY:
-            // 1. `instance` 与 `name_py` 均为当前 GIL 下的有效 Python 对象。
-            // 2. `val` 在调用期间保持存活，`PyObject_GenericSetAttr` 仅借用引用。
+            // 1. `slf`、`name` 与 `val` 均为当前 GIL 下的有效 Python 对象。
+            // 2. `PyObject_GenericSetAttr` 仅借用引用, 调用期间三者保持存活。
             // 3. 返回非 0 表示 Python 异常已设置，立即 `PyErr::fetch` 传播。
             unsafe {
-                let name_py = field.name_py.bind(py);
-                let res =
-                    ffi::PyObject_GenericSetAttr(instance.as_ptr(), name_py.as_ptr(), val.as_ptr());
+                let res = ffi::PyObject_GenericSetAttr(slf.as_ptr(), name.as_ptr(), val.as_ptr());
                 if res != 0 {
                     return Err(PyErr::fetch(py));
                 }
             }
         }
+        slf.borrow()
+            .frozen_instance
+            .store(frozen_instance, Ordering::Relaxed);
+        Ok(())
+    }
 
-        Ok(instance.unbind())
+    /// 支持 pickle: 以 `cls.__new__(cls)` 创建空实例, 再通过 `__setstate__` 恢复属性与只读标记.
+    fn __reduce__<'py>(slf: &Bound<'py, Struct>) -> PyResult<Bound<'py, PyTuple>> {
+        let cls = slf.get_type();
+        let new = cls.getattr(pyo3::intern!(slf.py(), "__new__"))?;
+        let state = Self::__getstate__(slf)?;
+        PyTuple::new(
+            slf.py(),
+            [
+                new.into_any(),
+                PyTuple::new(slf.py(), [&cls])?.into_any(),
+                state.into_any(),
+            ],
+        )
     }
 
     #[pyo3(signature = (**changes))]
@@ -293,9 +523,32 @@ impl Struct {
 
         let empty_args = PyTuple::empty(py);
         construct_instance(&def, instance.as_any(), &empty_args, Some(&kwargs))?;
+        copy_frozen_instance(slf, &instance)?;
         Ok(instance.unbind())
     }
 
+    /// 返回替换指定字段后的新实例, 与 `dataclasses.replace` 对应.
+    ///
+    /// 未替换字段沿用原实例值, 新实例按构造流程校验并调用 `__post_init__`;
+    /// frozen 实例同样适用, `decode(..., freeze=True)` 的实例级只读标记会保留.
+    ///
+    /// Args:
+    ///     **changes: 需要替换的字段名和值.
+    ///
+    /// Returns:
+    ///     同类的新实例.
+    ///
+    /// Raises:
+    ///     TypeError: 包含未知字段名.
+    ///     ValidationError: 替换值不满足类型或约束.
+    #[pyo3(signature = (**changes))]
+    fn replace(
+        slf: &Bound<'_, Struct>,
+        changes: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        Self::__replace__(slf, changes)
+    }
+
     fn __repr__(slf: &Bound<'_, Struct>) -> PyResult<String> {
         let py = slf.py();
         let cls = slf.get_type();
@@ -338,7 +591,32 @@ impl Struct {
         Ok(result)
     }
 
-    fn __rich_repr__(slf: &Bound<'_, Struct>) -> PyResult<Vec<(String, Py<PyAny>)>> {
+    fn __str__(slf: &Bound<'_, Struct>) -> PyResult<String> {
+        let py = slf.py();
+        let cls = slf.get_type();
+        let def = match schema_from_class(py, &cls)? {
+            Some(d) => d,
+            None => return Ok(slf.repr()?.to_str()?.to_owned()),
+        };
+        // 未配置 str_fields 时走 repr(), 以便子类覆盖的 __repr__ 生效.
+        let Some(str_fields) = def.str_fields.as_ref() else {
+            return Ok(slf.repr()?.to_str()?.to_owned());
+        };
+
+        let class_name = cls.name()?.extract::<String>()?;
+        let mut parts = Vec::with_capacity(str_fields.len());
+        for &idx in str_fields {
+            let field = &def.fields_sorted[idx];
+            let val = match slf.getattr(field.name_py.bind(py)) {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+            parts.push(format!("{}={}", field.name, val.repr()?.to_str()?));
+        }
+        Ok(format!("{}({})", class_name, parts.join(", ")))
+    }
+
+    fn __rich_repr__(slf: &Bound<'_, Struct>) -> PyResult<Vec<Py<PyTuple>>> {
         let py = slf.py();
         let cls = slf.get_type();
         let def = match schema_from_class(py, &cls)? {
@@ -359,7 +637,13 @@ impl Struct {
             {
                 continue;
             }
-            items.push((field.name.clone(), val.unbind()));
+            // Optional 字段为 None 时附带默认值 None, rich 据此省略该项.
+            let item = if field.is_optional && val.is_none() {
+                PyTuple::new(py, [field.name_py.bind(py).as_any(), &val, &val])?
+            } else {
+                PyTuple::new(py, [field.name_py.bind(py).as_any(), &val])?
+            };
+            items.push(item.unbind());
         }
         Ok(items)
     }
@@ -476,9 +760,8 @@ impl Struct {
         value: Bound<'_, PyAny>,
     ) -> PyResult<()> {
         let cls = slf.get_type();
-        if let Some(def) = schema_from_class(slf.py(), &cls)?
-            && def.frozen
-        {
+        let frozen_instance = slf.borrow().frozen_instance.load(Ordering::Relaxed);
+        if frozen_instance || schema_from_class(slf.py(), &cls)?.is_some_and(|def| def.frozen) {
             return Err(pyo3::exceptions::PyAttributeError::new_err(format!(
                 "can't set attributes of frozen instance '{}'",
                 cls.name()?
@@ -498,3 +781,87 @@ impl Struct {
         Ok(())
     }
 }
+
+/// 按 `__copy__`/`__deepcopy__` 的语义复制实例.
+///
+/// 新实例通过 `PyType_GenericAlloc` 分配并以 `PyObject_GenericSetAttr` 逐字段赋值,
+/// 不经过 `__init__` 与 `__setattr__`, 因此 frozen 实例同样可以复制; 实例级只读标记随之复制.
+/// 传入 `memo` 时按 `copy.deepcopy` 递归复制字段值, 并先登记新实例以处理循环引用.
+fn copy_struct(slf: &Bound<'_, Struct>, memo: Option<&Bound<'_, PyAny>>) -> PyResult<Py<PyAny>> {
+    let py = slf.py();
+    let cls = slf.get_type();
+    let def = schema_from_class(py, &cls)?
+        .ok_or_else(|| pyo3::exceptions::PyTypeError::new_err("Schema not found during copy"))?;
+
+    // SAFETY:
+    // 1. `cls` 是有效的 Python 类型对象，来自 `slf.get_type()`。
+    // 2. `PyType_GenericAlloc` 返回新引用；空指针时立即通过 `PyErr::fetch` 返回错误。
+    // 3. `Bound::from_owned_ptr` 正确接管该新引用所有权。
+    let instance = unsafe {
+        let type_ptr = cls.as_ptr() as *mut ffi::PyTypeObject;
+        let obj_ptr = ffi::PyType_GenericAlloc(type_ptr, 0);
+        if obj_ptr.is_null() {
+            return Err(PyErr::fetch(py));
+        }
+        Bound::from_owned_ptr(py, obj_ptr)
+    };
+    let deepcopy = match memo {
+        Some(memo) => {
+            memo.set_item(slf.as_ptr() as usize, &instance)?;
+            Some((py.import("copy")?.getattr("deepcopy")?, memo))
+        }
+        None => None,
+    };
+
+    for field in &def.fields_sorted {
+        let val = match slf.getattr(field.name_py.bind(py)) {
+            Ok(v) => v,
+            Err(_) => {
+                if let Some(default_value) = field.default_value.as_ref() {
+                    default_value.bind(py).clone()
+                } else if let Some(factory) = field.default_factory.as_ref() {
+                    factory.bind(py).call0()?
+                } else if field.is_optional {
+                    py.None().into_bound(py)
+                } else if field.is_required {
+                    return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                        "Missing required field '{}' during copy",
+                        field.name
+                    )));
+                } else {
+                    continue;
+                }
+            }
+        };
+        let val = match &deepcopy {
+            Some((deepcopy, memo)) => deepcopy.call1((val, *memo))?,
+            None => val,
+        };
+
+        // SAFETY:
+        // 1. `instance` 与 `name_py` 均为当前 GIL 下的有效 Python 对象。
+        // 2. `val` 在调用期间保持存活，`PyObject_GenericSetAttr` 仅借用引用。
+        // 3. 返回非 0 表示 Python 异常已设置，立即 `PyErr::fetch` 传播。
+        unsafe {
+            let name_py = field.name_py.bind(py);
+            let res =
+                ffi::PyObject_GenericSetAttr(instance.as_ptr(), name_py.as_ptr(), val.as_ptr());
+            if res != 0 {
+                return Err(PyErr::fetch(py));
+            }
+        }
+    }
+
+    copy_frozen_instance(slf, &instance)?;
+    Ok(instance.unbind())
+}
+
+/// 将实例级只读标记 (`decode(..., freeze=True)`) 从原实例复制到新分配的实例.
+fn copy_frozen_instance(src: &Bound<'_, Struct>, dst: &Bound<'_, PyAny>) -> PyResult<()> {
+    let frozen_instance = src.borrow().frozen_instance.load(Ordering::Relaxed);
+    dst.cast::<Struct>()?
+        .borrow()
+        .frozen_instance
+        .store(frozen_instance, Ordering::Relaxed);
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::ffi;
@@ -12,6 +12,11 @@ thread_local! {
 pub(crate) struct StdlibCache {
     pub(crate) enum_type: Py<PyAny>,
     pub(crate) builtin_bytes: Py<PyAny>,
+    pub(crate) decimal_type: Py<PyAny>,
+    pub(crate) ipv4_type: Py<PyAny>,
+    pub(crate) ipv6_type: Py<PyAny>,
+    pub(crate) pure_path_type: Py<PyAny>,
+    pub(crate) unicode_normalize: Py<PyAny>,
 }
 
 pub(crate) fn with_stdlib_cache<F, R>(py: Python<'_>, f: F) -> PyResult<R>
@@ -25,10 +30,21 @@ where
             let enum_type = enum_mod.getattr("Enum")?.unbind();
             let builtins = py.import("builtins")?;
             let builtin_bytes = builtins.getattr("bytes")?.unbind();
+            let decimal_type = py.import("decimal")?.getattr("Decimal")?.unbind();
+            let ipaddress = py.import("ipaddress")?;
+            let ipv4_type = ipaddress.getattr("IPv4Address")?.unbind();
+            let ipv6_type = ipaddress.getattr("IPv6Address")?.unbind();
+            let pure_path_type = py.import("pathlib")?.getattr("PurePath")?.unbind();
+            let unicode_normalize = py.import("unicodedata")?.getattr("normalize")?.unbind();
 
             *cache_opt = Some(StdlibCache {
                 enum_type,
                 builtin_bytes,
+                decimal_type,
+                ipv4_type,
+                ipv6_type,
+                pure_path_type,
+                unicode_normalize,
             });
         }
         f(cache_opt.as_ref().unwrap())
@@ -80,6 +96,12 @@ pub(crate) fn dataclass_fields<'py>(
 }
 
 pub const MAX_DEPTH: usize = 48;
+// Hard ceiling for a caller supplied `max_depth`. Recursion runs on the native
+// stack, so larger limits would let hostile nesting overflow it and crash.
+pub const MAX_DEPTH_CEILING: usize = 256;
+// Max field reads per typed struct decode. Distinct tags are bounded by 256, so
+// anything beyond this means a payload is repeating tags to amplify decode work.
+pub const MAX_FIELDS_READ: usize = 4096;
 // Capacity threshold (1MB). If buffer exceeds this, we shrink it back.
 pub const BUFFER_SHRINK_THRESHOLD: usize = 1024 * 1024;
 // Default initial capacity (128 bytes).
@@ -105,12 +127,54 @@ pub(crate) fn class_from_type<'py>(py: Python<'py>, cls: &Py<PyType>) -> Bound<'
     cls.bind(py).clone()
 }
 
+thread_local! {
+    // 当前编解码调用显式传入的 `max_depth`, 由 `DepthLimitGuard` 在入口设置.
+    static DEPTH_LIMIT: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// 在作用域内设置嵌套深度上限, 离开时恢复旧值.
+///
+/// None 表示使用默认的 `MAX_DEPTH`, 钩子中发起的嵌套编解码因此不会继承外层的上限.
+/// 超过 `MAX_DEPTH_CEILING` 的取值会被拒绝, 避免深层嵌套耗尽原生栈.
+pub(crate) struct DepthLimitGuard(Option<usize>);
+
+impl DepthLimitGuard {
+    pub(crate) fn set(max_depth: Option<usize>) -> PyResult<Self> {
+        if max_depth == Some(0) {
+            return Err(PyValueError::new_err(
+                "max_depth must be a positive integer",
+            ));
+        }
+        if let Some(limit) = max_depth.filter(|&limit| limit > MAX_DEPTH_CEILING) {
+            return Err(PyValueError::new_err(format!(
+                "max_depth must not exceed {MAX_DEPTH_CEILING}, got {limit}"
+            )));
+        }
+        Ok(Self(DEPTH_LIMIT.with(|cell| cell.replace(max_depth))))
+    }
+}
+
+/// 当前编解码调用显式传入的 `max_depth`; 未传入时为 None.
+#[inline]
+pub(crate) fn max_depth_override() -> Option<usize> {
+    DEPTH_LIMIT.with(Cell::get)
+}
+
+impl Drop for DepthLimitGuard {
+    fn drop(&mut self) {
+        DEPTH_LIMIT.with(|cell| cell.set(self.0));
+    }
+}
+
 #[inline]
 pub fn check_depth(depth: usize) -> PyResult<()> {
-    if depth >= MAX_DEPTH {
+    let limit = max_depth_override()
+        .unwrap_or(MAX_DEPTH)
+        .min(MAX_DEPTH_CEILING);
+    if depth >= limit {
         return Err(PyValueError::new_err(format!(
             "Recursion depth exceeded (max={}, observed={})",
-            MAX_DEPTH, depth
+            limit, depth
         )));
     }
     Ok(())
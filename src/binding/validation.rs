@@ -1,9 +1,41 @@
 use crate::ValidationError;
-use crate::binding::ir::{Constraints, TypeExpr, WireType};
+use crate::binding::ir::{Constraints, FieldDef, TypeExpr, WireType};
 use crate::binding::schema::TarsDict;
-use crate::binding::utils::{class_from_type, dataclass_fields, is_buffer_like};
+use crate::binding::utils::{class_from_type, dataclass_fields, is_buffer_like, with_stdlib_cache};
 use pyo3::prelude::*;
-use pyo3::types::{PyAny, PyBytes, PyDict, PyFloat, PyFrozenSet, PySequence, PySet, PyString};
+use pyo3::types::{
+    PyAny, PyBool, PyBytes, PyComplex, PyDict, PyFloat, PyFrozenSet, PyInt, PyMapping, PySequence,
+    PySet, PyString,
+};
+use std::cell::RefCell;
+
+thread_local! {
+    // 当前编解码调用传入的 `context`, 由 `HookContextGuard` 在入口设置.
+    static HOOK_CONTEXT: RefCell<Option<Py<PyAny>>> = const { RefCell::new(None) };
+}
+
+/// 在作用域内设置钩子上下文, 离开时恢复旧值.
+///
+/// 钩子或 `__post_init__` 中发起的嵌套编解码各自设置上下文, 互不影响.
+pub(crate) struct HookContextGuard(Option<Py<PyAny>>);
+
+impl HookContextGuard {
+    pub(crate) fn set(context: Option<Py<PyAny>>) -> Self {
+        Self(HOOK_CONTEXT.with(|cell| cell.replace(context)))
+    }
+}
+
+impl Drop for HookContextGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        HOOK_CONTEXT.with(|cell| *cell.borrow_mut() = previous);
+    }
+}
+
+/// 返回当前编解码调用的 `context`; 未传入时为 None.
+pub(crate) fn hook_context(py: Python<'_>) -> Option<Bound<'_, PyAny>> {
+    HOOK_CONTEXT.with(|cell| cell.borrow().as_ref().map(|ctx| ctx.bind(py).clone()))
+}
 
 #[inline]
 fn field_prefix(field_name: Option<&str>) -> String {
@@ -15,27 +47,27 @@ fn field_prefix(field_name: Option<&str>) -> String {
 
 #[inline]
 fn has_numeric_constraints(c: &Constraints) -> bool {
-    c.gt.is_some() || c.ge.is_some() || c.lt.is_some() || c.le.is_some()
+    c.gt.is_some() || c.ge.is_some() || c.lt.is_some() || c.le.is_some() || c.multiple_of.is_some()
 }
 
 #[inline]
 fn has_length_constraints(c: &Constraints) -> bool {
-    c.min_len.is_some() || c.max_len.is_some()
+    c.min_len.is_some() || c.max_len.is_some() || c.fixed_len.is_some()
 }
 
-pub(crate) fn validate_numeric_constraints_raw(
+/// 校验 gt/ge/lt/le, `shown` 为报错中展示的原始值 (整数不经 f64 舍入).
+fn validate_numeric_bounds(
     value: f64,
+    shown: &dyn std::fmt::Display,
     constraints: &Constraints,
-    field_name: Option<&str>,
+    label: &str,
 ) -> PyResult<()> {
-    let label = field_prefix(field_name);
-
     if let Some(gt) = constraints.gt
         && value.partial_cmp(&gt) != Some(std::cmp::Ordering::Greater)
     {
         return Err(ValidationError::new_err(format!(
             "{} must be > {}, got {}",
-            label, gt, value
+            label, gt, shown
         )));
     }
     if let Some(ge) = constraints.ge
@@ -46,7 +78,7 @@ pub(crate) fn validate_numeric_constraints_raw(
     {
         return Err(ValidationError::new_err(format!(
             "{} must be >= {}, got {}",
-            label, ge, value
+            label, ge, shown
         )));
     }
     if let Some(lt) = constraints.lt
@@ -54,7 +86,7 @@ pub(crate) fn validate_numeric_constraints_raw(
     {
         return Err(ValidationError::new_err(format!(
             "{} must be < {}, got {}",
-            label, lt, value
+            label, lt, shown
         )));
     }
     if let Some(le) = constraints.le
@@ -65,13 +97,59 @@ pub(crate) fn validate_numeric_constraints_raw(
     {
         return Err(ValidationError::new_err(format!(
             "{} must be <= {}, got {}",
-            label, le, value
+            label, le, shown
         )));
     }
+    Ok(())
+}
+
+pub(crate) fn validate_numeric_constraints_raw(
+    value: f64,
+    constraints: &Constraints,
+    field_name: Option<&str>,
+) -> PyResult<()> {
+    let label = field_prefix(field_name);
+    validate_numeric_bounds(value, &value, constraints, &label)?;
+
+    if let Some(step) = constraints.multiple_of {
+        // 以商与最近整数的距离判断, 容忍浮点步长 (如 0.1) 的舍入误差.
+        let quotient = value / step;
+        if !quotient.is_finite() || (quotient - quotient.round()).abs() > 1e-9 {
+            return Err(ValidationError::new_err(format!(
+                "{} must be a multiple of {}, got {}",
+                label, step, value
+            )));
+        }
+    }
 
     Ok(())
 }
 
+/// 整数版本的数值约束校验.
+///
+/// 步长为整数时 multiple_of 按精确取模判断, 避免超过 2**53 的值经 f64 舍入后误判.
+pub(crate) fn validate_int_constraints_raw(
+    value: i128,
+    constraints: &Constraints,
+    field_name: Option<&str>,
+) -> PyResult<()> {
+    let Some(step) = constraints
+        .multiple_of
+        .filter(|step| step.fract() == 0.0 && *step < 1e38)
+    else {
+        return validate_numeric_constraints_raw(value as f64, constraints, field_name);
+    };
+    let label = field_prefix(field_name);
+    validate_numeric_bounds(value as f64, &value, constraints, &label)?;
+    if value % (step as i128) != 0 {
+        return Err(ValidationError::new_err(format!(
+            "{} must be a multiple of {}, got {}",
+            label, step, value
+        )));
+    }
+    Ok(())
+}
+
 pub(crate) fn validate_length_constraints_raw(
     len: usize,
     constraints: &Constraints,
@@ -95,6 +173,14 @@ pub(crate) fn validate_length_constraints_raw(
             label, max_len, len
         )));
     }
+    if let Some(fixed_len) = constraints.fixed_len
+        && len != fixed_len
+    {
+        return Err(ValidationError::new_err(format!(
+            "{} must be exactly {} bytes, got {}",
+            label, fixed_len, len
+        )));
+    }
 
     Ok(())
 }
@@ -107,13 +193,19 @@ pub(crate) fn validate_constraints_on_value(
     let label = field_prefix(field_name);
 
     if has_numeric_constraints(constraints) {
-        let numeric: f64 = value.extract().map_err(|_| {
-            ValidationError::new_err(format!(
-                "{} must be a number to apply numeric constraints",
-                label
-            ))
-        })?;
-        validate_numeric_constraints_raw(numeric, constraints, field_name)?;
+        if value.is_instance_of::<PyInt>()
+            && let Ok(int) = value.extract::<i128>()
+        {
+            validate_int_constraints_raw(int, constraints, field_name)?;
+        } else {
+            let numeric: f64 = value.extract().map_err(|_| {
+                ValidationError::new_err(format!(
+                    "{} must be a number to apply numeric constraints",
+                    label
+                ))
+            })?;
+            validate_numeric_constraints_raw(numeric, constraints, field_name)?;
+        }
     }
 
     if has_length_constraints(constraints) {
@@ -126,6 +218,17 @@ pub(crate) fn validate_constraints_on_value(
         validate_length_constraints_raw(len, constraints, field_name)?;
     }
 
+    if let Some(choices) = constraints.choices.as_ref()
+        && !choices.contains(value)
+    {
+        return Err(ValidationError::new_err(format!(
+            "{} must be one of {}, got {}",
+            label,
+            choices.describe(),
+            value.repr().map(|r| r.to_string()).unwrap_or_default()
+        )));
+    }
+
     if let Some(pattern_py) = constraints.pattern.as_ref() {
         let py = value.py();
         let pattern = pattern_py.bind(py);
@@ -141,6 +244,20 @@ pub(crate) fn validate_constraints_on_value(
     Ok(())
 }
 
+/// 判断值是否为 `Literal` 成员之一.
+///
+/// 值与类型都须相同, 避免 `True`/`1`、`1`/`1.0` 这类相等但类型不同的值误匹配.
+pub(crate) fn literal_contains(values: &[Py<PyAny>], value: &Bound<'_, PyAny>) -> PyResult<bool> {
+    let value_type = value.get_type();
+    for member in values {
+        let member = member.bind(value.py());
+        if member.get_type().is(&value_type) && member.eq(value)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 pub(crate) fn value_matches_type<'py>(
     py: Python<'py>,
     typ: &TypeExpr,
@@ -163,8 +280,17 @@ pub(crate) fn value_matches_type<'py>(
             WireType::String => Ok(value.is_instance_of::<PyString>()),
             _ => Ok(false),
         },
-        TypeExpr::Bytes => Ok(is_buffer_like(value)),
-        TypeExpr::Enum(enum_cls, _) => Ok(value.is_instance(enum_cls.bind(py).as_any())?),
+        TypeExpr::Bytes | TypeExpr::ByteArray => Ok(is_buffer_like(value)),
+        TypeExpr::Complex => Ok(value.is_instance_of::<PyComplex>()
+            || value.is_instance_of::<PyFloat>()
+            || (value.is_instance_of::<PyInt>() && !value.is_instance_of::<PyBool>())),
+        TypeExpr::Enum(enum_cls, _)
+        | TypeExpr::EnumName(enum_cls)
+        | TypeExpr::Flag(enum_cls)
+        | TypeExpr::IpAddress(enum_cls)
+        | TypeExpr::Path(enum_cls)
+        | TypeExpr::Subclass(enum_cls, _) => Ok(value.is_instance(enum_cls.bind(py).as_any())?),
+        TypeExpr::Literal(values, _) => literal_contains(values, value),
         TypeExpr::Struct(cls_obj) => {
             let cls = class_from_type(py, cls_obj);
             Ok(value.is_instance(cls.as_any())?)
@@ -208,13 +334,15 @@ pub(crate) fn value_matches_type<'py>(
             }
             Ok(true)
         }
-        TypeExpr::Set(_) => {
+        TypeExpr::Set(_) | TypeExpr::FrozenSet(_) => {
             Ok(value.is_instance_of::<PySet>() || value.is_instance_of::<PyFrozenSet>())
         }
-        TypeExpr::Map(_, _) => {
-            Ok(value.is_instance_of::<PyDict>() || dataclass_fields(value)?.is_some())
+        TypeExpr::Map(_, _) | TypeExpr::DefaultDict(_, _, _) | TypeExpr::DictSubclass(_, _, _) => {
+            Ok(value.is_instance_of::<PyDict>()
+                || dataclass_fields(value)?.is_some()
+                || value.is_instance_of::<PyMapping>())
         }
-        TypeExpr::TypedDict => {
+        TypeExpr::TypedDict(_) => {
             Ok(value.is_instance_of::<PyDict>() || dataclass_fields(value)?.is_some())
         }
         TypeExpr::Optional(inner) => {
@@ -235,6 +363,155 @@ pub(crate) fn value_matches_type<'py>(
     }
 }
 
+/// `coerce_enum=True` 字段: 将枚举成员的原始值转换为成员 (`enum_cls(value)`).
+///
+/// 未开启该选项、值为 None 或已是成员时原样返回.
+pub(crate) fn coerce_enum_field<'py>(
+    field: &FieldDef,
+    value: Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    if !field.coerce_enum || value.is_none() {
+        return Ok(value);
+    }
+    let Some(enum_cls) = field.ty.enum_class() else {
+        return Ok(value);
+    };
+    let enum_cls = enum_cls.bind(value.py());
+    if value.is_instance(enum_cls)? {
+        return Ok(value);
+    }
+    enum_cls.call1((&value,)).map_err(|_| {
+        ValidationError::new_err(format!(
+            "Field '{}' value {} is not a valid {} member",
+            field.name,
+            value.repr().map(|r| r.to_string()).unwrap_or_default(),
+            enum_cls.name().map(|n| n.to_string()).unwrap_or_default()
+        ))
+    })
+}
+
+/// `accept_bytes=True` 字段: 将 bytes 值按 UTF-8 解码为 str.
+///
+/// 未开启该选项或值不是 bytes 时原样返回.
+pub(crate) fn coerce_bytes_field<'py>(
+    field: &FieldDef,
+    value: Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    if !field.accept_bytes {
+        return Ok(value);
+    }
+    let Ok(bytes) = value.cast::<PyBytes>() else {
+        return Ok(value);
+    };
+    let text = std::str::from_utf8(bytes.as_bytes()).map_err(|e| {
+        ValidationError::new_err(format!(
+            "Field '{}' bytes value is not valid UTF-8: {}",
+            field.name, e
+        ))
+    })?;
+    Ok(PyString::new(value.py(), text).into_any())
+}
+
+/// `normalize=...` 字段: 编码前按 `unicodedata.normalize` 规范化 str 值.
+///
+/// 未开启该选项或值不是 str 时原样返回.
+pub(crate) fn normalize_str_field<'py>(
+    field: &FieldDef,
+    value: Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let Some(form) = field.normalize.as_deref() else {
+        return Ok(value);
+    };
+    if !value.is_instance_of::<PyString>() {
+        return Ok(value);
+    }
+    let py = value.py();
+    with_stdlib_cache(py, |cache| {
+        cache.unicode_normalize.bind(py).call1((form, &value))
+    })
+}
+
+/// 调用字段的 `Meta(encoder=...)` / `Meta(decoder=...)` 钩子.
+///
+/// 编解码时传入了 `context` 则以关键字参数 `context` 一并传给钩子.
+/// 钩子抛出的任何异常都转换为带字段名的 `ValueError`, 原异常保存在 `__cause__` 中.
+pub(crate) fn call_field_hook<'py>(
+    field: &FieldDef,
+    kind: &str,
+    hook: &Py<PyAny>,
+    value: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = value.py();
+    let result = match hook_context(py) {
+        Some(ctx) => {
+            let kwargs = PyDict::new(py);
+            kwargs.set_item(pyo3::intern!(py, "context"), ctx)?;
+            hook.bind(py).call((value,), Some(&kwargs))
+        }
+        None => hook.bind(py).call1((value,)),
+    };
+    result.map_err(|err| {
+        let wrapped = pyo3::exceptions::PyValueError::new_err(format!(
+            "Field '{}' {} failed: {}",
+            field.name,
+            kind,
+            err.value(py)
+        ));
+        wrapped.set_cause(py, Some(err));
+        wrapped
+    })
+}
+
+/// `as_bytes=True` 字段: 校验原始 bytes 值, 只检查按字节计的长度约束.
+///
+/// 返回 `true` 表示已按该规则校验, 调用方应跳过常规的 str 类型校验.
+pub(crate) fn validate_raw_str_bytes(field: &FieldDef, value: &Bound<'_, PyAny>) -> PyResult<bool> {
+    if !field.as_bytes {
+        return Ok(false);
+    }
+    let Ok(bytes) = value.cast::<PyBytes>() else {
+        return Ok(false);
+    };
+    if let Some(c) = field.constraints.as_deref()
+        && has_length_constraints(c)
+    {
+        validate_length_constraints_raw(bytes.as_bytes().len(), c, Some(field.name.as_str()))?;
+    }
+    Ok(true)
+}
+
+/// 编码前校验 `Meta(fixed_len=N)`, 拦截构造后被改写为错误长度的 bytes 字段.
+pub(crate) fn check_fixed_len(field: &FieldDef, value: &Bound<'_, PyAny>) -> PyResult<()> {
+    let Some(c) = field.constraints.as_deref() else {
+        return Ok(());
+    };
+    if c.fixed_len.is_none() {
+        return Ok(());
+    }
+    let len = value.len()?;
+    validate_length_constraints_raw(len, c, Some(field.name.as_str()))
+}
+
+/// 编码前校验 `Meta(choices=[...])`, 拦截构造后被改写为集合外取值的字段.
+pub(crate) fn check_choices(field: &FieldDef, value: &Bound<'_, PyAny>) -> PyResult<()> {
+    let Some(choices) = field
+        .constraints
+        .as_deref()
+        .and_then(|c| c.choices.as_ref())
+    else {
+        return Ok(());
+    };
+    if choices.contains(value) {
+        return Ok(());
+    }
+    Err(ValidationError::new_err(format!(
+        "Field '{}' must be one of {}, got {}",
+        field.name,
+        choices.describe(),
+        value.repr().map(|r| r.to_string()).unwrap_or_default()
+    )))
+}
+
 pub(crate) fn validate_type_and_constraints(
     py: Python<'_>,
     value: &Bound<'_, PyAny>,
@@ -2,14 +2,25 @@ use crate::codec::consts::TarsType;
 use crate::codec::error::{Error, Result};
 use std::convert::TryFrom;
 
+/// `skip_field` 默认允许的最大嵌套深度.
+pub const DEFAULT_MAX_SKIP_DEPTH: usize = 100;
+
+/// `with_max_depth` 可设置的最大嵌套深度, 更大的值会被截断以免递归耗尽栈空间.
+pub const MAX_SKIP_DEPTH_CEILING: usize = 256;
+
 /// Tars 数据流读取器.
 ///
 /// 直接基于字节切片 (`&[u8]`) 实现。
-/// 内部维护了解码深度 (`depth`),以防止恶意的深度嵌套攻击.
+/// 跳过未知字段时限制嵌套深度 (`max_depth`),以防止恶意的深度嵌套攻击.
+/// 多字节数值默认按大端解释, 可通过 [`with_little_endian`](Self::with_little_endian)
+/// 切换为小端, 以兼容部分旧客户端的 JCE 报文.
 pub struct TarsReader<'a> {
     data: &'a [u8],
     pos: usize,
-    depth: usize,
+    /// `skip_field` 允许的最大嵌套深度.
+    max_depth: usize,
+    /// 整数、浮点数与 String4 长度是否按小端解释 (默认大端).
+    little_endian: bool,
 }
 
 impl<'a> TarsReader<'a> {
@@ -23,10 +34,42 @@ impl<'a> TarsReader<'a> {
         Self {
             data: bytes,
             pos: 0,
-            depth: 0,
+            max_depth: DEFAULT_MAX_SKIP_DEPTH,
+            little_endian: false,
         }
     }
 
+    /// 设置 `skip_field` 允许的最大嵌套深度 (默认 [`DEFAULT_MAX_SKIP_DEPTH`]).
+    ///
+    /// 更小的值可更早拒绝恶意的深度嵌套, 更大的值可跳过合法但层级较深的未知字段.
+    /// 超过 [`MAX_SKIP_DEPTH_CEILING`] 的值按该上限处理.
+    #[inline]
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth.min(MAX_SKIP_DEPTH_CEILING);
+        self
+    }
+
+    /// 切换多字节数值的字节序.
+    ///
+    /// 开启后 Int2/Int4/Int8、Float/Double 以及 String4 的长度前缀均按小端读取.
+    #[inline]
+    pub fn with_little_endian(mut self, enabled: bool) -> Self {
+        self.little_endian = enabled;
+        self
+    }
+
+    /// 原地切换字节序, 用于按字段覆盖字节序后恢复外层设置.
+    #[inline]
+    pub fn set_little_endian(&mut self, enabled: bool) {
+        self.little_endian = enabled;
+    }
+
+    /// 当前是否为小端模式, 用于为嵌套 payload 创建同字节序的读取器.
+    #[inline]
+    pub fn is_little_endian(&self) -> bool {
+        self.little_endian
+    }
+
     /// 获取当前偏移量.
     #[inline]
     pub fn position(&self) -> u64 {
@@ -141,7 +184,11 @@ impl<'a> TarsReader<'a> {
                     }
                     e
                 })?;
-                let v = i16::from_be_bytes(bytes);
+                let v = if self.little_endian {
+                    i16::from_le_bytes(bytes)
+                } else {
+                    i16::from_be_bytes(bytes)
+                };
                 Ok(v as i64)
             }
             TarsType::Int4 => {
@@ -152,7 +199,11 @@ impl<'a> TarsReader<'a> {
                     }
                     e
                 })?;
-                let v = i32::from_be_bytes(bytes);
+                let v = if self.little_endian {
+                    i32::from_le_bytes(bytes)
+                } else {
+                    i32::from_be_bytes(bytes)
+                };
                 Ok(v as i64)
             }
             TarsType::Int8 => {
@@ -163,7 +214,11 @@ impl<'a> TarsReader<'a> {
                     }
                     e
                 })?;
-                let v = i64::from_be_bytes(bytes);
+                let v = if self.little_endian {
+                    i64::from_le_bytes(bytes)
+                } else {
+                    i64::from_be_bytes(bytes)
+                };
                 Ok(v)
             }
             _ => Err(Error::new(
@@ -199,7 +254,11 @@ impl<'a> TarsReader<'a> {
                     }
                     e
                 })?;
-                let v = u16::from_be_bytes(bytes);
+                let v = if self.little_endian {
+                    u16::from_le_bytes(bytes)
+                } else {
+                    u16::from_be_bytes(bytes)
+                };
                 Ok(v as u64)
             }
             TarsType::Int4 => {
@@ -210,7 +269,11 @@ impl<'a> TarsReader<'a> {
                     }
                     e
                 })?;
-                let v = u32::from_be_bytes(bytes);
+                let v = if self.little_endian {
+                    u32::from_le_bytes(bytes)
+                } else {
+                    u32::from_be_bytes(bytes)
+                };
                 Ok(v as u64)
             }
             TarsType::Int8 => {
@@ -221,7 +284,11 @@ impl<'a> TarsReader<'a> {
                     }
                     e
                 })?;
-                let v = u64::from_be_bytes(bytes);
+                let v = if self.little_endian {
+                    u64::from_le_bytes(bytes)
+                } else {
+                    u64::from_be_bytes(bytes)
+                };
                 Ok(v)
             }
             _ => Err(Error::new(
@@ -231,79 +298,10 @@ impl<'a> TarsReader<'a> {
         }
     }
 
-    /// 跳过指定的 Tars 类型值.
+    /// 跳过指定的 Tars 类型值 (头部已读取).
     fn skip_element(&mut self, type_id: TarsType) -> Result<()> {
-        match type_id {
-            TarsType::Int1 => self.skip(1),
-            TarsType::Int2 => self.skip(2),
-            TarsType::Int4 => self.skip(4),
-            TarsType::Int8 => self.skip(8),
-            TarsType::Float => self.skip(4),
-            TarsType::Double => self.skip(8),
-            TarsType::String1 => {
-                let len = self.read_u8()? as usize;
-                self.skip(len)
-            }
-            TarsType::String4 => {
-                let bytes = self.read_array::<4>()?;
-                let len = u32::from_be_bytes(bytes) as usize;
-                self.skip(len)
-            }
-            TarsType::StructBegin => {
-                loop {
-                    let (_, t) = self.read_head()?;
-                    if t == TarsType::StructEnd {
-                        break;
-                    }
-                    self.skip_recursive(t)?;
-                }
-                Ok(())
-            }
-            TarsType::StructEnd => Ok(()),
-            TarsType::ZeroTag => Ok(()),
-            TarsType::SimpleList => {
-                let t = self.read_u8()?; // 内部类型(byte)
-                if t != 0 {
-                    return Err(Error::new(
-                        self.pos,
-                        format!("SimpleList must contain Byte (0), got {}", t),
-                    ));
-                }
-                let size = self.read_size()?;
-                self.skip(size as usize)
-            }
-            TarsType::Map => {
-                let size = self.read_size()?;
-                for _ in 0..size * 2 {
-                    let (_, t) = self.read_head()?;
-                    self.skip_recursive(t)?;
-                }
-                Ok(())
-            }
-            TarsType::List => {
-                let size = self.read_size()?;
-                for _ in 0..size {
-                    let (_, t) = self.read_head()?;
-                    self.skip_recursive(t)?;
-                }
-                Ok(())
-            }
-        }
-    }
-
-    #[inline]
-    fn skip_recursive(&mut self, type_id: TarsType) -> Result<()> {
-        match type_id {
-            TarsType::StructBegin | TarsType::List | TarsType::Map => self.skip_field(type_id),
-            _ => self.skip_element(type_id),
-        }
-    }
-
-    #[inline]
-    fn skip(&mut self, len: usize) -> Result<()> {
-        self.ensure_available(len)?;
-        self.pos += len;
-        Ok(())
+        let max_depth = self.max_depth;
+        skip_value(self, type_id, 0, max_depth)
     }
 
     /// 读取单精度浮点数.
@@ -320,7 +318,11 @@ impl<'a> TarsReader<'a> {
                     }
                     e
                 })?;
-                let v = f32::from_be_bytes(bytes);
+                let v = if self.little_endian {
+                    f32::from_le_bytes(bytes)
+                } else {
+                    f32::from_be_bytes(bytes)
+                };
                 Ok(v)
             }
             _ => Err(Error::new(
@@ -345,7 +347,11 @@ impl<'a> TarsReader<'a> {
                     }
                     e
                 })?;
-                let v = f64::from_be_bytes(bytes);
+                let v = if self.little_endian {
+                    f64::from_le_bytes(bytes)
+                } else {
+                    f64::from_be_bytes(bytes)
+                };
                 Ok(v)
             }
             _ => Err(Error::new(
@@ -383,7 +389,11 @@ impl<'a> TarsReader<'a> {
                     }
                     e
                 })?;
-                u32::from_be_bytes(bytes) as usize
+                (if self.little_endian {
+                    u32::from_le_bytes(bytes)
+                } else {
+                    u32::from_be_bytes(bytes)
+                }) as usize
             }
             _ => {
                 return Err(Error::new(
@@ -399,17 +409,7 @@ impl<'a> TarsReader<'a> {
 
     /// 跳过当前字段.
     pub fn skip_field(&mut self, type_id: TarsType) -> Result<()> {
-        if self.depth > 100 {
-            return Err(Error::new(
-                self.pos,
-                "Max recursion depth exceeded in skip_field",
-            ));
-        }
-
-        self.depth += 1;
-        let res = self.skip_element(type_id); // Reused skip_element logic
-        self.depth -= 1;
-        res
+        self.skip_element(type_id)
     }
 
     pub fn read_simplelist_bytes(&mut self) -> Result<&'a [u8]> {
@@ -450,13 +450,198 @@ impl<'a> TarsReader<'a> {
     }
 
     /// 读取 Tars 容器的大小(List/Map/SimpleList 长度).
+    ///
+    /// 超出 i32 范围的值 (如 Int8 写出的超大长度) 直接报错, 不做截断;
+    /// 负数原样返回, 由调用方按上下文报错.
     #[inline]
     pub fn read_size(&mut self) -> Result<i32> {
         let (_, t) = self.read_head()?;
-        self.read_int(t).map(|v| v as i32)
+        let start = self.pos;
+        let v = self.read_int(t)?;
+        i32::try_from(v).map_err(|_| Error::new(start, format!("Size {} out of range", v)))
+    }
+}
+
+/// 按 Tars 布局跳过值时使用的字节来源.
+///
+/// [`TarsReader`] 在切片上前移游标; 流式解码从文件对象按需读取恰好所需的字节.
+/// 两者共用 [`skip_value`] 的跳过逻辑.
+pub trait ByteSource {
+    /// 消费恰好 `n` 个字节, 不足时返回 `Error::BufferOverflow`.
+    fn take(&mut self, n: usize) -> Result<&[u8]>;
+
+    /// 当前偏移量, 用于错误信息.
+    fn offset(&self) -> usize;
+
+    /// 多字节数值是否按小端解释.
+    fn is_little_endian(&self) -> bool;
+}
+
+impl ByteSource for TarsReader<'_> {
+    #[inline]
+    fn take(&mut self, n: usize) -> Result<&[u8]> {
+        self.read_bytes(n)
+    }
+
+    #[inline]
+    fn offset(&self) -> usize {
+        self.pos
+    }
+
+    #[inline]
+    fn is_little_endian(&self) -> bool {
+        self.little_endian
     }
 }
 
+#[inline]
+fn take_array<const N: usize>(src: &mut impl ByteSource) -> Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(src.take(N)?);
+    Ok(bytes)
+}
+
+/// 从字节来源读取字段头部, 与 [`TarsReader::read_head`] 的布局一致.
+pub fn read_head_from(src: &mut impl ByteSource) -> Result<(u8, TarsType)> {
+    let start = src.offset();
+    let b = src.take(1)?[0];
+    let tag = match b >> 4 {
+        15 => src.take(1)?[0],
+        tag => tag,
+    };
+    let type_id =
+        TarsType::try_from(b & 0x0F).map_err(|id| Error::invalid_type(start, id, "0..=13"))?;
+    Ok((tag, type_id))
+}
+
+/// 从字节来源读取容器长度, 负数或超出 i32 范围时报错.
+pub fn read_size_from(src: &mut impl ByteSource) -> Result<usize> {
+    let (_, type_id) = read_head_from(src)?;
+    let start = src.offset();
+    let le = src.is_little_endian();
+    let v = match type_id {
+        TarsType::ZeroTag => 0,
+        TarsType::Int1 => src.take(1)?[0] as i8 as i64,
+        TarsType::Int2 => {
+            let b = take_array::<2>(src)?;
+            (if le {
+                i16::from_le_bytes(b)
+            } else {
+                i16::from_be_bytes(b)
+            }) as i64
+        }
+        TarsType::Int4 => {
+            let b = take_array::<4>(src)?;
+            (if le {
+                i32::from_le_bytes(b)
+            } else {
+                i32::from_be_bytes(b)
+            }) as i64
+        }
+        TarsType::Int8 => {
+            let b = take_array::<8>(src)?;
+            if le {
+                i64::from_le_bytes(b)
+            } else {
+                i64::from_be_bytes(b)
+            }
+        }
+        other => {
+            return Err(Error::new(
+                start,
+                format!("Cannot read size from type {:?}", other),
+            ));
+        }
+    };
+    match i32::try_from(v) {
+        Ok(size) if size >= 0 => Ok(size as usize),
+        _ => Err(Error::new(start, format!("Invalid size {}", v))),
+    }
+}
+
+/// 跳过一个类型为 `type_id` 的值 (头部已读取).
+///
+/// `depth` 为当前嵌套层数, 进入 Struct/List/Map 时超过 `max_depth` 即报错.
+pub fn skip_value(
+    src: &mut impl ByteSource,
+    type_id: TarsType,
+    depth: usize,
+    max_depth: usize,
+) -> Result<()> {
+    if matches!(
+        type_id,
+        TarsType::StructBegin | TarsType::List | TarsType::Map
+    ) && depth > max_depth
+    {
+        return Err(Error::new(
+            src.offset(),
+            "Max recursion depth exceeded in skip_field",
+        ));
+    }
+    match type_id {
+        TarsType::ZeroTag | TarsType::StructEnd => {}
+        TarsType::Int1 => {
+            src.take(1)?;
+        }
+        TarsType::Int2 => {
+            src.take(2)?;
+        }
+        TarsType::Int4 | TarsType::Float => {
+            src.take(4)?;
+        }
+        TarsType::Int8 | TarsType::Double => {
+            src.take(8)?;
+        }
+        TarsType::String1 => {
+            let len = src.take(1)?[0] as usize;
+            src.take(len)?;
+        }
+        TarsType::String4 => {
+            let b = take_array::<4>(src)?;
+            let len = if src.is_little_endian() {
+                u32::from_le_bytes(b)
+            } else {
+                u32::from_be_bytes(b)
+            } as usize;
+            src.take(len)?;
+        }
+        TarsType::StructBegin => loop {
+            let (_, t) = read_head_from(src)?;
+            if t == TarsType::StructEnd {
+                break;
+            }
+            skip_value(src, t, depth + 1, max_depth)?;
+        },
+        TarsType::SimpleList => {
+            let start = src.offset();
+            let inner = src.take(1)?[0];
+            if inner != 0 {
+                return Err(Error::new(
+                    start,
+                    format!("SimpleList must contain Byte (0), got {}", inner),
+                ));
+            }
+            let len = read_size_from(src)?;
+            src.take(len)?;
+        }
+        TarsType::List => {
+            for _ in 0..read_size_from(src)? {
+                let (_, t) = read_head_from(src)?;
+                skip_value(src, t, depth + 1, max_depth)?;
+            }
+        }
+        TarsType::Map => {
+            for _ in 0..read_size_from(src)? {
+                for _ in 0..2 {
+                    let (_, t) = read_head_from(src)?;
+                    skip_value(src, t, depth + 1, max_depth)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -599,6 +784,25 @@ mod tests {
         assert_eq!(reader.read_double(t2).unwrap(), 2.5f64);
     }
 
+    #[test]
+    fn test_little_endian_reader_round_trips_with_writer() {
+        let long = "x".repeat(300);
+        let mut writer = TarsWriter::new().with_little_endian(true);
+        writer.write_int(0, -70000);
+        writer.write_float(1, 1.5);
+        writer.write_string(2, &long);
+        let data = writer.get_buffer().to_vec();
+
+        let mut reader = TarsReader::new(&data).with_little_endian(true);
+        let (_, t) = reader.read_head().unwrap();
+        assert_eq!(reader.read_int(t).unwrap(), -70000);
+        let (_, t) = reader.read_head().unwrap();
+        assert_eq!(reader.read_float(t).unwrap(), 1.5);
+        let (_, t) = reader.read_head().unwrap();
+        assert_eq!(reader.read_string(t).unwrap(), long.as_bytes());
+        assert!(reader.is_end());
+    }
+
     #[test]
     fn test_read_float_with_zero_tag_returns_zero() {
         let mut w = TarsWriter::new();
@@ -737,6 +941,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sizes_out_of_range_are_rejected_instead_of_truncated() {
+        let mut w = TarsWriter::new();
+        w.write_tag(0, TarsType::List);
+        w.write_int(0, 1 << 40);
+        let mut reader = TarsReader::new(w.get_buffer());
+        let (_tag, t) = reader.read_head().unwrap();
+        assert!(reader.skip_field(t).is_err());
+        let mut reader = TarsReader::new(&w.get_buffer()[1..]);
+        assert!(reader.read_size().is_err());
+
+        let mut w = TarsWriter::new();
+        w.write_tag(0, TarsType::List);
+        w.write_int(0, -1);
+        let mut reader = TarsReader::new(w.get_buffer());
+        let (_tag, t) = reader.read_head().unwrap();
+        let err = reader.skip_field(t).unwrap_err();
+        assert!(err.to_string().contains("Invalid size -1"));
+    }
+
+    #[test]
+    fn test_skip_field_respects_configured_max_depth() {
+        let mut w = TarsWriter::new();
+
+        for _ in 0..5 {
+            w.write_tag(0, TarsType::List);
+            w.write_int(0, 1);
+        }
+        w.write_tag(0, TarsType::ZeroTag);
+
+        let mut reader = TarsReader::new(w.get_buffer()).with_max_depth(2);
+        let (_tag, t) = reader.read_head().unwrap();
+        assert!(reader.skip_field(t).is_err());
+
+        let mut reader = TarsReader::new(w.get_buffer()).with_max_depth(8);
+        let (_tag, t) = reader.read_head().unwrap();
+        reader.skip_field(t).unwrap();
+        assert!(reader.is_end());
+    }
+
+    #[test]
+    fn test_skip_field_clamps_oversized_max_depth() {
+        let depth = MAX_SKIP_DEPTH_CEILING * 64;
+        let mut data = vec![0x0a; depth];
+        data.extend(std::iter::repeat_n(0x0b, depth));
+
+        let mut reader = TarsReader::new(&data).with_max_depth(usize::MAX);
+        let (_tag, t) = reader.read_head().unwrap();
+        assert!(reader.skip_field(t).is_err());
+    }
+
     #[test]
     fn test_skip_field_with_simple_list_validates_subtype_is_byte() {
         let mut w = TarsWriter::new();
@@ -819,6 +1074,21 @@ mod tests {
             let _ = reader.read_head();
             let _ = reader.read_int(TarsType::Int4);
         }
+
+        #[test]
+        fn test_struct_walk_with_random_input_is_panic_free_and_advances(data in proptest::collection::vec(any::<u8>(), 0..256)) {
+            // 模拟 decode 跳过未知字段的整段遍历: 任意输入都必须以 Ok 或 Err 结束且游标单调前进.
+            let mut reader = TarsReader::new(&data);
+            while !reader.is_end() {
+                let before = reader.position();
+                let Ok((_, type_id)) = reader.read_head() else { break };
+                if reader.skip_field(type_id).is_err() {
+                    break;
+                }
+                prop_assert!(reader.position() > before);
+                prop_assert!(reader.position() <= data.len() as u64);
+            }
+        }
     }
 
     #[test]
@@ -1,6 +1,8 @@
 use crate::codec::consts::TarsType;
+use crate::codec::error::{Error, Result};
 
 use bytes::BufMut;
+use bytes::buf::UninitSlice;
 
 /// Tars 数据流编码器(写入器).
 ///
@@ -8,6 +10,12 @@ use bytes::BufMut;
 /// 支持内存缓冲区 (`Vec<u8>`) 以及任何实现了 `bytes::BufMut` 的类型.
 pub struct TarsWriter<B = Vec<u8>> {
     buffer: B,
+    /// 是否校验 `begin_struct`/`end_struct` 的配对 (默认关闭).
+    validate: bool,
+    /// 当前尚未闭合的 Struct 层数.
+    depth: usize,
+    /// 整数、浮点数与 String4 长度是否按小端写出 (默认大端).
+    little_endian: bool,
 }
 
 impl Default for TarsWriter<Vec<u8>> {
@@ -19,15 +27,22 @@ impl Default for TarsWriter<Vec<u8>> {
 impl TarsWriter<Vec<u8>> {
     /// 创建一个新的 TarsWriter.
     pub fn new() -> Self {
-        Self {
-            buffer: Vec::with_capacity(128),
-        }
+        Self::with_buffer(Vec::with_capacity(128))
     }
 
     pub fn into_inner(self) -> Vec<u8> {
         self.buffer
     }
 
+    /// 校验 Struct 标记配对后取出字节流.
+    ///
+    /// Returns:
+    ///     编码后的字节流; 校验模式下存在未闭合的 Struct 时返回错误.
+    pub fn try_into_inner(self) -> Result<Vec<u8>> {
+        self.check_balanced()?;
+        Ok(self.buffer)
+    }
+
     pub fn reserve(&mut self, additional: usize) {
         self.buffer.reserve(additional);
     }
@@ -35,13 +50,76 @@ impl TarsWriter<Vec<u8>> {
     /// 重置写入器(针对 Vec 的特化实现).
     pub fn clear(&mut self) {
         self.buffer.clear();
+        self.depth = 0;
     }
 }
 
 impl<B: BufMut> TarsWriter<B> {
     /// 使用指定的缓冲区创建 TarsWriter.
     pub fn with_buffer(buffer: B) -> Self {
-        Self { buffer }
+        Self {
+            buffer,
+            validate: false,
+            depth: 0,
+            little_endian: false,
+        }
+    }
+
+    /// 开启或关闭 Struct 标记配对校验.
+    ///
+    /// 仅影响 [`begin_struct`](Self::begin_struct)/[`end_struct`](Self::end_struct),
+    /// 内部编码路径直接写头部, 不受该开关影响.
+    pub fn with_validation(mut self, enabled: bool) -> Self {
+        self.validate = enabled;
+        self
+    }
+
+    /// 切换多字节数值的字节序.
+    ///
+    /// 开启后 Int2/Int4/Int8、Float/Double 以及 String4 的长度前缀均按小端写出,
+    /// 用于兼容部分旧客户端的 JCE 报文.
+    pub fn with_little_endian(mut self, enabled: bool) -> Self {
+        self.little_endian = enabled;
+        self
+    }
+
+    /// 原地切换字节序, 用于按字段覆盖字节序后恢复外层设置.
+    #[inline]
+    pub fn set_little_endian(&mut self, enabled: bool) {
+        self.little_endian = enabled;
+    }
+
+    /// 当前是否为小端模式, 用于为嵌套 payload 创建同字节序的写入器.
+    #[inline]
+    pub fn is_little_endian(&self) -> bool {
+        self.little_endian
+    }
+
+    #[inline]
+    fn put_i16(&mut self, v: i16) {
+        if self.little_endian {
+            self.buffer.put_i16_le(v);
+        } else {
+            self.buffer.put_i16(v);
+        }
+    }
+
+    #[inline]
+    fn put_i32(&mut self, v: i32) {
+        if self.little_endian {
+            self.buffer.put_i32_le(v);
+        } else {
+            self.buffer.put_i32(v);
+        }
+    }
+
+    #[inline]
+    fn put_i64(&mut self, v: i64) {
+        if self.little_endian {
+            self.buffer.put_i64_le(v);
+        } else {
+            self.buffer.put_i64(v);
+        }
     }
 
     /// 获取编码后的字节流.
@@ -71,6 +149,13 @@ impl<B: BufMut> TarsWriter<B> {
         }
     }
 
+    /// 写入 StructBegin 头部并进入一层 Struct.
+    #[inline]
+    pub fn begin_struct(&mut self, tag: u8) {
+        self.write_tag(tag, TarsType::StructBegin);
+        self.depth += 1;
+    }
+
     /// 写入整数(自动选择最小宽度).
     ///
     /// 根据数值大小自动选择 Int1、Int2、Int4、Int8 或 ZeroTag 类型.
@@ -84,13 +169,39 @@ impl<B: BufMut> TarsWriter<B> {
             self.buffer.put_u8(value as u8);
         } else if value >= i16::MIN as i64 && value <= i16::MAX as i64 {
             self.write_tag(tag, TarsType::Int2);
-            self.buffer.put_i16(value as i16);
+            self.put_i16(value as i16);
         } else if value >= i32::MIN as i64 && value <= i32::MAX as i64 {
             self.write_tag(tag, TarsType::Int4);
-            self.buffer.put_i32(value as i32);
+            self.put_i32(value as i32);
         } else {
             self.write_tag(tag, TarsType::Int8);
-            self.buffer.put_i64(value);
+            self.put_i64(value);
+        }
+    }
+
+    /// 以指定的整数类型写入, 不做宽度压缩.
+    ///
+    /// `type_id` 须为 Int1/Int2/Int4/Int8 之一 (其他取值按 Int8 处理), 值按该宽度截断为补码,
+    /// 用于与固定宽度的外部结构体互通 (如 uint16 写为 Int2).
+    #[inline]
+    pub fn write_int_fixed(&mut self, tag: u8, value: i64, type_id: TarsType) {
+        match type_id {
+            TarsType::Int1 => {
+                self.write_tag(tag, TarsType::Int1);
+                self.buffer.put_u8(value as u8);
+            }
+            TarsType::Int2 => {
+                self.write_tag(tag, TarsType::Int2);
+                self.put_i16(value as i16);
+            }
+            TarsType::Int4 => {
+                self.write_tag(tag, TarsType::Int4);
+                self.put_i32(value as i32);
+            }
+            _ => {
+                self.write_tag(tag, TarsType::Int8);
+                self.put_i64(value);
+            }
         }
     }
 
@@ -102,7 +213,11 @@ impl<B: BufMut> TarsWriter<B> {
             return;
         }
         self.write_tag(tag, TarsType::Float);
-        self.buffer.put_f32(value);
+        if self.little_endian {
+            self.buffer.put_f32_le(value);
+        } else {
+            self.buffer.put_f32(value);
+        }
     }
 
     /// 写入双精度浮点数.
@@ -113,20 +228,33 @@ impl<B: BufMut> TarsWriter<B> {
             return;
         }
         self.write_tag(tag, TarsType::Double);
-        self.buffer.put_f64(value);
+        if self.little_endian {
+            self.buffer.put_f64_le(value);
+        } else {
+            self.buffer.put_f64(value);
+        }
     }
 
     /// 写入字符串.
     #[inline]
     pub fn write_string(&mut self, tag: u8, value: &str) {
-        let bytes = value.as_bytes();
+        self.write_string_bytes(tag, value.as_bytes());
+    }
+
+    /// 以 String1/String4 写入原始字节, 不要求内容为合法 UTF-8.
+    #[inline]
+    pub fn write_string_bytes(&mut self, tag: u8, bytes: &[u8]) {
         let len = bytes.len();
         if len <= 255 {
             self.write_tag(tag, TarsType::String1);
             self.buffer.put_u8(len as u8);
         } else {
             self.write_tag(tag, TarsType::String4);
-            self.buffer.put_u32(len as u32);
+            if self.little_endian {
+                self.buffer.put_u32_le(len as u32);
+            } else {
+                self.buffer.put_u32(len as u32);
+            }
         }
         self.buffer.put_slice(bytes);
     }
@@ -143,6 +271,107 @@ impl<B: BufMut> TarsWriter<B> {
     }
 }
 
+impl<B: BufMut + AsRef<[u8]>> TarsWriter<B> {
+    /// 写入 StructEnd 头部并退出一层 Struct.
+    ///
+    /// Returns:
+    ///     校验模式下没有匹配的 `begin_struct` 时返回错误, 且不写入任何字节.
+    #[inline]
+    pub fn end_struct(&mut self) -> Result<()> {
+        if self.depth == 0 {
+            if self.validate {
+                return Err(Error::new(
+                    self.buffer.as_ref().len(),
+                    "end_struct without matching begin_struct",
+                ));
+            }
+        } else {
+            self.depth -= 1;
+        }
+        self.write_tag(0, TarsType::StructEnd);
+        Ok(())
+    }
+
+    /// 检查所有 `begin_struct` 是否均已闭合.
+    ///
+    /// 非校验模式下始终返回 `Ok`.
+    pub fn check_balanced(&self) -> Result<()> {
+        if self.validate && self.depth != 0 {
+            return Err(Error::new(
+                self.buffer.as_ref().len(),
+                format!("{} unclosed struct(s) at end of output", self.depth),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 只统计写入字节数、不保存内容的缓冲区.
+///
+/// 与 [`TarsWriter::with_buffer`] 配合使用, 可在不分配输出缓冲区的情况下
+/// 得到与实际编码完全一致的字节长度.
+#[derive(Debug, Default)]
+pub struct SizeCounter {
+    len: usize,
+    scratch: [u8; 16],
+}
+
+impl SizeCounter {
+    /// 创建一个计数为 0 的计数器.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 已写入的字节数.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// 是否尚未写入任何字节.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+// SAFETY: `chunk_mut` 始终返回内部的暂存区, `advance_mut` 只累加计数,
+// 不会暴露未初始化的内存; 计数上限为 `usize::MAX`.
+unsafe impl BufMut for SizeCounter {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        usize::MAX - self.len
+    }
+
+    #[inline]
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        self.len += cnt;
+    }
+
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        UninitSlice::new(&mut self.scratch)
+    }
+
+    #[inline]
+    fn put_slice(&mut self, src: &[u8]) {
+        self.len += src.len();
+    }
+
+    #[inline]
+    fn put_bytes(&mut self, _val: u8, cnt: usize) {
+        self.len += cnt;
+    }
+}
+
+impl TarsWriter<SizeCounter> {
+    /// 已写出的字节数.
+    #[inline]
+    pub fn written_len(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,6 +411,33 @@ mod tests {
         assert_eq!(writer.get_buffer(), b"\x01\x01\x00"); // 标签 0,Int2,值 256(0x0100)
     }
 
+    /// 验证固定宽度写入不做 ZeroTag/宽度压缩, 超出有符号范围的值按补码截断.
+    #[test]
+    fn test_write_int_fixed_keeps_declared_width() {
+        let mut writer = TarsWriter::new();
+        writer.write_int_fixed(0, 0, TarsType::Int2);
+        assert_eq!(writer.get_buffer(), b"\x01\x00\x00"); // 标签 0,Int2,值 0
+
+        writer.clear();
+        writer.write_int_fixed(0, 65535, TarsType::Int2);
+        assert_eq!(writer.get_buffer(), b"\x01\xff\xff"); // uint16 最大值
+
+        writer.clear();
+        writer.write_int_fixed(1, 1, TarsType::Int4);
+        assert_eq!(writer.get_buffer(), b"\x12\x00\x00\x00\x01");
+    }
+
+    #[test]
+    fn test_little_endian_writer_reverses_multibyte_payloads() {
+        let mut writer = TarsWriter::new().with_little_endian(true);
+        writer.write_int(0, 0x1234);
+        writer.write_double(1, 1.0);
+        assert_eq!(
+            writer.get_buffer(),
+            b"\x01\x34\x12\x15\x00\x00\x00\x00\x00\x00\xf0\x3f"
+        );
+    }
+
     /// 验证字符串的编码布局,包含 Tag、类型标记、长度及内容.
     #[test]
     fn test_write_string_with_short_value_produces_string1_type() {
@@ -205,4 +461,58 @@ mod tests {
         writer.write_int(15, 1);
         assert_eq!(writer.get_buffer(), b"\xf0\x0f\x01"); // 标签 15,Int1,值 1
     }
+
+    /// 验证校验模式下配对的 Struct 标记可正常取出字节流.
+    #[test]
+    fn test_validation_with_balanced_structs_succeeds() {
+        let mut writer = TarsWriter::new().with_validation(true);
+        writer.begin_struct(0);
+        writer.begin_struct(1);
+        writer.write_int(0, 1);
+        writer.end_struct().unwrap();
+        writer.end_struct().unwrap();
+        assert_eq!(
+            writer.try_into_inner().unwrap(),
+            b"\x0a\x1a\x00\x01\x0b\x0b"
+        );
+    }
+
+    /// 验证校验模式下未闭合或多余的 Struct 标记会报错.
+    #[test]
+    fn test_validation_with_unbalanced_structs_returns_error() {
+        let mut writer = TarsWriter::new().with_validation(true);
+        writer.begin_struct(0);
+        assert!(writer.check_balanced().is_err());
+        assert!(writer.try_into_inner().is_err());
+
+        let mut writer = TarsWriter::new().with_validation(true);
+        let err = writer.end_struct().unwrap_err();
+        assert_eq!(
+            err,
+            Error::new(0, "end_struct without matching begin_struct")
+        );
+        assert!(writer.get_buffer().is_empty());
+
+        let mut writer = TarsWriter::new();
+        writer.begin_struct(0);
+        writer.end_struct().unwrap();
+        writer.end_struct().unwrap();
+        assert!(writer.try_into_inner().is_ok());
+    }
+
+    /// 验证 SizeCounter 统计的长度与实际写入 Vec 的字节数一致.
+    #[test]
+    fn test_size_counter_matches_vec_output_length() {
+        let mut writer = TarsWriter::new();
+        let mut counter = TarsWriter::with_buffer(SizeCounter::new());
+        for w in [0i64, 1, 300, 70_000, i64::MAX] {
+            writer.write_int(20, w);
+            counter.write_int(20, w);
+        }
+        writer.write_string(1, &"x".repeat(300));
+        counter.write_string(1, &"x".repeat(300));
+        writer.write_bytes(2, b"abc");
+        counter.write_bytes(2, b"abc");
+        assert_eq!(counter.written_len(), writer.get_buffer().len());
+    }
 }
@@ -11,6 +11,7 @@ fn init_core_types(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<binding::core::Struct>()?;
     m.add_class::<binding::core::StructConfig>()?;
     m.add_class::<binding::core::Meta>()?;
+    m.add_class::<binding::core::Tag>()?;
     m.add_class::<binding::core::NoDefaultType>()?;
     m.add_class::<binding::core::FieldSpec>()?;
     m.add_class::<binding::core::TarsDict>()?;
@@ -22,11 +23,27 @@ fn init_core_types(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
 fn init_core_functions(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(binding::codec::ser::encode, m)?)?;
+    m.add_function(wrap_pyfunction!(binding::codec::ser::encode_into, m)?)?;
+    m.add_function(wrap_pyfunction!(binding::codec::ser::encode_many, m)?)?;
     m.add_function(wrap_pyfunction!(binding::codec::de::decode, m)?)?;
+    m.add_function(wrap_pyfunction!(binding::codec::de::decode_checked, m)?)?;
+    m.add_function(wrap_pyfunction!(binding::codec::de::decode_value, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        binding::codec::de::decode_concatenated,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(binding::codec::de::decode_stream, m)?)?;
+    m.add_function(wrap_pyfunction!(binding::codec::de::decode_many, m)?)?;
     m.add_function(wrap_pyfunction!(binding::codec::raw::encode_raw, m)?)?;
     m.add_function(wrap_pyfunction!(binding::codec::raw::decode_raw, m)?)?;
     m.add_function(wrap_pyfunction!(binding::codec::raw::probe_struct, m)?)?;
+    m.add_function(wrap_pyfunction!(binding::codec::probe::probe_schema, m)?)?;
+    m.add_function(wrap_pyfunction!(binding::codec::probe::detect_prefix, m)?)?;
+    m.add_function(wrap_pyfunction!(binding::codec::probe::guess_layout, m)?)?;
     m.add_function(wrap_pyfunction!(binding::core::field, m)?)?;
+    m.add_function(wrap_pyfunction!(binding::schema::compile_module, m)?)?;
+    m.add_class::<binding::codec::coverage::SchemaCoverage>()?;
+    m.add_class::<binding::codec::de::ConcatenatedDecoder>()?;
     m.add_class::<binding::codec::trace::TraceNode>()?;
     m.add_function(wrap_pyfunction!(binding::codec::trace::decode_trace, m)?)?;
     Ok(())
@@ -53,9 +70,15 @@ fn init_inspect_submodule(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<(
     inspect_mod.add_class::<binding::inspect::FloatType>()?;
     inspect_mod.add_class::<binding::inspect::BoolType>()?;
     inspect_mod.add_class::<binding::inspect::BytesType>()?;
+    inspect_mod.add_class::<binding::inspect::IpAddressType>()?;
+    inspect_mod.add_class::<binding::inspect::PathType>()?;
+    inspect_mod.add_class::<binding::inspect::ComplexType>()?;
+    inspect_mod.add_class::<binding::inspect::SubclassType>()?;
     inspect_mod.add_class::<binding::inspect::AnyType>()?;
     inspect_mod.add_class::<binding::inspect::NoneType>()?;
     inspect_mod.add_class::<binding::inspect::EnumType>()?;
+    inspect_mod.add_class::<binding::inspect::FlagType>()?;
+    inspect_mod.add_class::<binding::inspect::LiteralType>()?;
     inspect_mod.add_class::<binding::inspect::UnionType>()?;
     inspect_mod.add_class::<binding::inspect::ListType>()?;
     inspect_mod.add_class::<binding::inspect::TupleType>()?;
@@ -69,10 +92,36 @@ fn init_inspect_submodule(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<(
     inspect_mod.add("FieldInfo", inspect_mod.getattr("Field")?)?;
     inspect_mod.add_class::<binding::inspect::StructInfo>()?;
     inspect_mod.add_function(wrap_pyfunction!(binding::inspect::type_info, &inspect_mod)?)?;
+    inspect_mod.add_function(wrap_pyfunction!(binding::inspect::is_flat, &inspect_mod)?)?;
+    inspect_mod.add_function(wrap_pyfunction!(
+        binding::inspect::diff_schema,
+        &inspect_mod
+    )?)?;
+    inspect_mod.add_function(wrap_pyfunction!(
+        binding::inspect::to_tars_idl,
+        &inspect_mod
+    )?)?;
+    inspect_mod.add_function(wrap_pyfunction!(
+        binding::inspect::json_schema,
+        &inspect_mod
+    )?)?;
+    inspect_mod.add_function(wrap_pyfunction!(
+        binding::inspect::to_builtins,
+        &inspect_mod
+    )?)?;
+    inspect_mod.add_function(wrap_pyfunction!(binding::inspect::astuple, &inspect_mod)?)?;
+    inspect_mod.add_function(wrap_pyfunction!(
+        binding::inspect::schema_hash,
+        &inspect_mod
+    )?)?;
     inspect_mod.add_function(wrap_pyfunction!(
         binding::inspect::struct_info,
         &inspect_mod
     )?)?;
+    inspect_mod.add_function(wrap_pyfunction!(
+        binding::inspect::wire_size_estimate,
+        &inspect_mod
+    )?)?;
 
     m.add("inspect", inspect_mod.as_any())?;
 